@@ -0,0 +1,93 @@
+//! Live agent credential, swapped in place when the gateway asks for
+//! rotation so heartbeat cadence never has to pause for a config reload.
+
+use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credential {
+    pub secret: String,
+    #[serde(default)]
+    pub tls_cert_pem: String,
+    #[serde(default)]
+    pub tls_key_pem: String,
+    #[serde(default)]
+    pub expires_unix_ms: u64,
+}
+
+impl Credential {
+    pub fn bootstrap() -> Self {
+        Self {
+            secret: "bootstrap-placeholder".into(),
+            tls_cert_pem: String::new(),
+            tls_key_pem: String::new(),
+            expires_unix_ms: 0,
+        }
+    }
+}
+
+/// Holds the credential currently in use for registration/heartbeats and
+/// mirrors it to disk so a restart after rotation doesn't fall back to the
+/// bootstrap secret.
+pub struct CredentialCell {
+    live: ArcSwap<Credential>,
+    path: PathBuf,
+}
+
+impl CredentialCell {
+    /// Load a persisted credential from `path`, or fall back to the
+    /// well-known bootstrap secret when none exists yet.
+    pub fn load_or_bootstrap(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let credential = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_else(Credential::bootstrap);
+        Self {
+            live: ArcSwap::from_pointee(credential),
+            path,
+        }
+    }
+
+    pub fn current(&self) -> Arc<Credential> {
+        self.live.load_full()
+    }
+
+    /// Atomically install `credential` as the one used by subsequent calls,
+    /// then persist it to disk with owner-only permissions.
+    pub fn rotate(&self, credential: Credential) -> Result<()> {
+        self.live.store(Arc::new(credential));
+        self.persist()
+    }
+
+    fn persist(&self) -> Result<()> {
+        let credential = self.live.load();
+        let bytes = serde_json::to_vec_pretty(&*credential)?;
+        write_restricted(&self.path, &bytes)
+            .with_context(|| format!("persisting agent credential to {}", self.path.display()))
+    }
+}
+
+#[cfg(unix)]
+fn write_restricted(path: &Path, bytes: &[u8]) -> Result<()> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(bytes)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_restricted(path: &Path, bytes: &[u8]) -> Result<()> {
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
@@ -1,34 +1,108 @@
+mod credential;
+
 use anyhow::Result;
 use cncore::{config, init_tracing};
-use cnproto::{agent_control_client::AgentControlClient, HeartbeatRequest, RegisterAgentRequest};
+use cnproto::{
+    agent_control_client::AgentControlClient, HeartbeatRequest, RegisterAgentRequest,
+    RotateCredentialsRequest,
+};
+use credential::{Credential, CredentialCell};
 use sysinfo::{CpuExt, System, SystemExt};
-use tonic::transport::Channel;
-use tracing::{error, info};
+use tonic::{transport::Channel, Code};
+use tracing::{error, info, warn};
 use uuid::Uuid;
+use x25519_dalek::{EphemeralSecret, PublicKey};
+
+/// Where the live credential is mirrored to disk so restarts after a
+/// rotation don't fall back to the bootstrap secret.
+fn credential_path() -> String {
+    std::env::var("CASS_AGENT_CREDENTIAL_PATH").unwrap_or_else(|_| "agent-credential.json".into())
+}
+
+async fn connect(grpc_addr: &str) -> Channel {
+    loop {
+        match Channel::from_shared(grpc_addr.to_string())
+            .expect("valid grpc endpoint")
+            .connect()
+            .await
+        {
+            Ok(ch) => break ch,
+            Err(e) => {
+                error!(error=%e, "waiting for grpc server");
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+/// Call `RotateCredentials` and apply the result to `cell`. A rejected
+/// rotation (auth failure) clears the cell back to the bootstrap secret so
+/// the next register call re-bootstraps from scratch; any other error is
+/// treated as transient and the caller should retry with the old credential.
+async fn rotate_credentials(
+    client: &mut AgentControlClient<Channel>,
+    cell: &CredentialCell,
+    assigned_id: &str,
+) -> Result<(), Code> {
+    let current = cell.current();
+    let req = RotateCredentialsRequest {
+        assigned_id: assigned_id.to_string(),
+        current_secret: current.secret.clone(),
+    };
+    match client.rotate_credentials(req).await {
+        Ok(resp) => {
+            let resp = resp.into_inner();
+            if !resp.ok {
+                return Err(Code::Unauthenticated);
+            }
+            let rotated = Credential {
+                secret: resp.new_secret,
+                tls_cert_pem: resp.tls_cert_pem,
+                tls_key_pem: resp.tls_key_pem,
+                expires_unix_ms: resp.expires_unix_ms,
+            };
+            if let Err(e) = cell.rotate(rotated) {
+                error!(error=%e, "failed to persist rotated credential");
+            }
+            info!("rotate.creds.applied", "credential rotation succeeded");
+            Ok(())
+        }
+        Err(status) => {
+            if matches!(
+                status.code(),
+                Code::Unauthenticated | Code::PermissionDenied
+            ) {
+                warn!("rotate.creds.rejected", "credential rejected; re-bootstrapping");
+                let _ = cell.rotate(Credential::bootstrap());
+            } else {
+                warn!(error=%status, "rotate.creds.transient");
+            }
+            Err(status.code())
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
     init_tracing();
     info!("agent.start", config=?config(), "Starting CassandraNet Agent prototype");
     // Connect to gateway gRPC (assumes default http bind +1 port for grpc as implemented)
-    let http_addr = &config().http.bind_addr;
+    let http_addr = config().http.bind_addr.clone();
     let mut parts = http_addr.split(':').collect::<Vec<_>>();
     let port: u16 = parts.pop().unwrap_or("0").parse().unwrap_or(8080);
     let host = parts.join(":");
     let grpc_addr = format!("http://{}:{}", host, port + 1);
-    let channel = loop {
-        match Channel::from_shared(grpc_addr.clone())?.connect().await {
-            Ok(ch) => break ch,
-            Err(e) => {
-                error!(error=%e, "waiting for grpc server");
-                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-            }
-        }
-    };
+    let channel = connect(&grpc_addr).await;
     let mut client = AgentControlClient::new(channel);
+    let credential_cell = CredentialCell::load_or_bootstrap(credential_path());
     let mut sys = System::new_all();
     sys.refresh_all();
     let node_id = Uuid::new_v4().to_string();
+    // Generated fresh per enrollment attempt so an operator can run the SAS
+    // handshake below instead of the gateway trusting `node_id`/`hostname`
+    // outright.
+    let agent_secret = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+    let agent_public = PublicKey::from(&agent_secret);
     let req = RegisterAgentRequest {
         node_id: node_id.clone(),
         hostname: sys.host_name().unwrap_or_else(|| "unknown".into()),
@@ -36,11 +110,25 @@ async fn main() -> Result<()> {
         arch: std::env::consts::ARCH.into(),
         cpu_cores: sys.cpus().len() as u32,
         memory_bytes: sys.total_memory() * 1024,
-        secret: "bootstrap-placeholder".into(),
+        secret: credential_cell.current().secret.clone(),
+        x25519_public_key: agent_public.as_bytes().to_vec(),
+        zone: std::env::var("CASS_AGENT_ZONE").unwrap_or_default(),
+        ..Default::default()
     };
     let resp = client.register_agent(req).await?.into_inner();
     info!(assigned_id=%resp.assigned_id, interval=resp.heartbeat_interval_seconds, "agent registered");
+    if resp.verification_required {
+        // Display-only: the operator compares this against the dashboard's
+        // copy (`GET /admin/agents/{id}/enrollment`) and calls the verify
+        // endpoint before heartbeats are accepted past the grace window.
+        info!(
+            "agent.awaiting_verification",
+            sas_code = %resp.sas_code,
+            "compare this code with the gateway dashboard, then have an operator confirm it"
+        );
+    }
     let assigned = resp.assigned_id;
+    let mut session_token = resp.session_token;
     let interval = std::time::Duration::from_secs(resp.heartbeat_interval_seconds as u64);
     loop {
         tokio::select! {
@@ -55,9 +143,28 @@ async fn main() -> Result<()> {
                     network_rx_bytes: 0,
                     network_tx_bytes: 0,
                     timestamp_unix_ms: (std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()) as u64,
+                    session_token: session_token.clone(),
                 };
                 match client.heartbeat(hb).await {
-                    Ok(r) => { let r = r.into_inner(); if r.rotate_credentials { info!("rotate.creds", "server requested credential rotation"); } }
+                    Ok(r) => {
+                        let r = r.into_inner();
+                        if r.rotate_credentials {
+                            if !r.new_session_token.is_empty() {
+                                // The gateway rotates the session JWT itself when it's
+                                // near expiry; swap it in rather than treating this as
+                                // the legacy bootstrap-secret rotation below.
+                                info!("session.rotate", "refreshed expiring session token");
+                                session_token = r.new_session_token;
+                            } else {
+                                info!("rotate.creds", "server requested credential rotation");
+                                if rotate_credentials(&mut client, &credential_cell, &assigned).await.is_err() {
+                                    // Transient failures keep using the old credential and
+                                    // retry on the next rotation signal; rejections have
+                                    // already reset the cell to the bootstrap secret above.
+                                }
+                            }
+                        }
+                    }
                     Err(e) => error!(error=%e, "heartbeat failed"),
                 }
             }
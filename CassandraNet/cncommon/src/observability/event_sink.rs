@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// An agent-fleet or task-scheduling state change worth telling the outside
+/// world about. Serialized as JSON (tagged by `type`) before it ever reaches
+/// a sink, so a subscriber on the other end of Redis/MQTT doesn't need this
+/// crate to decode it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum FleetEvent {
+    AgentRegistered {
+        agent_id: String,
+        tenant_id: Option<String>,
+        hostname: String,
+        timestamp: DateTime<Utc>,
+    },
+    AgentHeartbeat {
+        agent_id: String,
+        cpu_percent: f64,
+        memory_used_bytes: u64,
+        timestamp: DateTime<Utc>,
+    },
+    AgentOffline {
+        agent_id: String,
+        timestamp: DateTime<Utc>,
+    },
+    TaskScheduled {
+        task_id: String,
+        tenant_id: String,
+        kind: String,
+        assigned_agent_ids: Vec<String>,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// Fans a [`FleetEvent`] out to wherever it's configured to go. Implementors
+/// must not block the caller on a slow or unreachable downstream (a
+/// dashboard being offline shouldn't stall `register_agent`) — publish best
+/// effort and log the failure instead of propagating it.
+pub trait EventSink: Send + Sync {
+    fn publish(&self, event: &FleetEvent);
+}
+
+/// Drops every event. The default so the core scheduling/registration path
+/// stays allocation-free when no downstream bus is configured.
+#[derive(Clone, Default)]
+pub struct NoopEventSink;
+
+impl EventSink for NoopEventSink {
+    fn publish(&self, _event: &FleetEvent) {}
+}
+
+/// Collects events in memory for tests and local development.
+#[derive(Clone, Default)]
+pub struct InMemoryEventSink {
+    events: Arc<RwLock<Vec<FleetEvent>>>,
+}
+
+impl InMemoryEventSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> Vec<FleetEvent> {
+        self.events.read().expect("event sink lock poisoned").clone()
+    }
+}
+
+impl EventSink for InMemoryEventSink {
+    fn publish(&self, event: &FleetEvent) {
+        self.events
+            .write()
+            .expect("event sink lock poisoned")
+            .push(event.clone());
+    }
+}
+
+/// Publishes each event as a JSON string via `PUBLISH` on a Redis pub/sub
+/// channel. Connections are opened per-publish rather than pooled, matching
+/// the throwaway-connection style the rest of this crate uses for optional
+/// backends rather than introducing a new pooling dependency.
+#[cfg(feature = "redis")]
+pub struct RedisEventSink {
+    client: redis::Client,
+    channel: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisEventSink {
+    pub fn new(redis_url: &str, channel: impl Into<String>) -> redis::RedisResult<Self> {
+        Ok(Self {
+            client: redis::Client::open(redis_url)?,
+            channel: channel.into(),
+        })
+    }
+}
+
+#[cfg(feature = "redis")]
+impl EventSink for RedisEventSink {
+    fn publish(&self, event: &FleetEvent) {
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::error!(error = %err, "event_sink.redis.encode_failed");
+                return;
+            }
+        };
+        match self.client.get_connection() {
+            Ok(mut conn) => {
+                let result: redis::RedisResult<i64> =
+                    redis::cmd("PUBLISH").arg(&self.channel).arg(payload).query(&mut conn);
+                if let Err(err) = result {
+                    tracing::error!(error = %err, "event_sink.redis.publish_failed");
+                }
+            }
+            Err(err) => tracing::error!(error = %err, "event_sink.redis.connect_failed"),
+        }
+    }
+}
+
+/// Publishes each event as a JSON string to an MQTT topic with
+/// `QoS::AtLeastOnce`. Holds a live `rumqttc` client/connection pair; the
+/// connection's event loop must be driven elsewhere (e.g. a background task
+/// started alongside this sink) for publishes to actually leave the process.
+#[cfg(feature = "mqtt")]
+pub struct MqttEventSink {
+    client: rumqttc::Client,
+    topic: String,
+}
+
+#[cfg(feature = "mqtt")]
+impl MqttEventSink {
+    pub fn new(client: rumqttc::Client, topic: impl Into<String>) -> Self {
+        Self {
+            client,
+            topic: topic.into(),
+        }
+    }
+}
+
+#[cfg(feature = "mqtt")]
+impl EventSink for MqttEventSink {
+    fn publish(&self, event: &FleetEvent) {
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::error!(error = %err, "event_sink.mqtt.encode_failed");
+                return;
+            }
+        };
+        if let Err(err) =
+            self.client
+                .publish(&self.topic, rumqttc::QoS::AtLeastOnce, false, payload)
+        {
+            tracing::error!(error = %err, "event_sink.mqtt.publish_failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_sink_collects_published_events() {
+        let sink = InMemoryEventSink::new();
+        sink.publish(&FleetEvent::AgentOffline {
+            agent_id: "agent-1".into(),
+            timestamp: Utc::now(),
+        });
+        let events = sink.snapshot();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], FleetEvent::AgentOffline { .. }));
+    }
+
+    #[test]
+    fn noop_sink_drops_events() {
+        let sink = NoopEventSink;
+        sink.publish(&FleetEvent::AgentOffline {
+            agent_id: "agent-1".into(),
+            timestamp: Utc::now(),
+        });
+    }
+}
@@ -1,8 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Capacity of the broadcast channel backing [`LogPipeline::subscribe`].
+/// Slow subscribers (e.g. a stalled `GrpcLogSink` stream) fall behind rather
+/// than block `emit`; once they're more than this many events behind,
+/// `tokio::sync::broadcast` drops the oldest unread events out from under
+/// them and their next `recv` resolves to `RecvError::Lagged`.
+const LOG_SUBSCRIPTION_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
     Trace,
     Debug,
@@ -60,9 +68,52 @@ pub trait LogSink: Send + Sync {
     fn on_event(&self, event: &LogEvent);
 }
 
-#[derive(Clone, Default)]
+/// Tenant/level/component filter applied by [`LogPipeline::subscribe`]
+/// consumers (e.g. `GrpcLogSink`'s tailing RPC) so a subscriber only sees
+/// the slice of the live feed it asked for.
+#[derive(Debug, Clone, Default)]
+pub struct LogSubscriptionFilter {
+    /// Only forward events at or above this severity. `None` forwards all.
+    pub min_level: Option<LogLevel>,
+    pub tenant_id: Option<String>,
+    pub component: Option<String>,
+}
+
+impl LogSubscriptionFilter {
+    pub fn matches(&self, event: &LogEvent) -> bool {
+        if let Some(min_level) = &self.min_level {
+            if event.level < *min_level {
+                return false;
+            }
+        }
+        if let Some(tenant_id) = &self.tenant_id {
+            if event.tenant_id.as_deref() != Some(tenant_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(component) = &self.component {
+            if event.component.as_deref() != Some(component.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
 pub struct LogPipeline {
     sinks: Arc<RwLock<Vec<Arc<dyn LogSink>>>>,
+    live: broadcast::Sender<LogEvent>,
+}
+
+impl Default for LogPipeline {
+    fn default() -> Self {
+        let (live, _) = broadcast::channel(LOG_SUBSCRIPTION_CAPACITY);
+        Self {
+            sinks: Arc::new(RwLock::new(Vec::new())),
+            live,
+        }
+    }
 }
 
 impl LogPipeline {
@@ -77,6 +128,14 @@ impl LogPipeline {
             .push(sink);
     }
 
+    /// Subscribes to the live event feed. Events are dropped, oldest first,
+    /// once a subscriber falls more than [`LOG_SUBSCRIPTION_CAPACITY`] events
+    /// behind rather than applying backpressure to `emit`; `recv` surfaces
+    /// that as `RecvError::Lagged` so the subscriber knows it missed events.
+    pub fn subscribe(&self) -> broadcast::Receiver<LogEvent> {
+        self.live.subscribe()
+    }
+
     pub fn emit(&self, event: LogEvent) {
         let sinks = self
             .sinks
@@ -86,6 +145,9 @@ impl LogPipeline {
         for sink in sinks {
             sink.on_event(&event);
         }
+        // No receivers is the common case outside of an active gRPC tail;
+        // `send` erroring just means nobody's listening right now.
+        let _ = self.live.send(event);
     }
 }
 
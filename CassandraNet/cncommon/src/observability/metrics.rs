@@ -127,6 +127,167 @@ impl InMemoryMetricsRegistry {
             .expect("metrics read lock poisoned")
             .clone()
     }
+
+    /// Renders everything currently recorded as Prometheus text exposition
+    /// format (one `# HELP`/`# TYPE` pair per metric name, then its series).
+    /// Counters sum every point recorded under a given label set (each
+    /// `increment_counter` call is treated as a delta); gauges report the
+    /// most recently recorded value per label set; histograms are bucketed
+    /// into [`HISTOGRAM_BUCKETS_MS`] with the standard `_bucket`/`_sum`/
+    /// `_count` series, since every `observe_histogram` caller in this repo
+    /// records a millisecond-scale duration.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let snapshot = self.snapshot_all();
+        let mut names: Vec<&String> = snapshot.keys().collect();
+        names.sort();
+        for name in names {
+            let points = &snapshot[name];
+            let Some(first) = points.first() else {
+                continue;
+            };
+            let metric_name = sanitize_metric_name(name);
+            out.push_str(&format!(
+                "# HELP {metric_name} {metric_name} recorded via InMemoryMetricsRegistry.\n"
+            ));
+            out.push_str(&format!(
+                "# TYPE {metric_name} {}\n",
+                prometheus_type(&first.kind)
+            ));
+            match first.kind {
+                MetricKind::Counter => render_counter(&mut out, &metric_name, points),
+                MetricKind::Gauge => render_gauge(&mut out, &metric_name, points),
+                MetricKind::Histogram => render_histogram(&mut out, &metric_name, points),
+            }
+        }
+        out
+    }
+}
+
+/// Upper bounds (milliseconds) for `render_prometheus`'s histogram buckets,
+/// plus an implicit trailing `+Inf`.
+const HISTOGRAM_BUCKETS_MS: &[f64] = &[
+    5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+];
+
+fn prometheus_type(kind: &MetricKind) -> &'static str {
+    match kind {
+        MetricKind::Counter => "counter",
+        MetricKind::Gauge => "gauge",
+        MetricKind::Histogram => "histogram",
+    }
+}
+
+/// Prometheus metric names must match `[a-zA-Z_:][a-zA-Z0-9_:]*`; every name
+/// recorded by this repo already does, but this guards against whatever a
+/// future caller passes to `increment_counter`/`set_gauge`/`observe_histogram`.
+fn sanitize_metric_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// Labels as a sorted `(key, value)` list, used as a `BTreeMap` key so
+/// distinct label sets become distinct series in deterministic order.
+fn label_key(labels: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = labels
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+fn format_labels(pairs: &[(String, String)]) -> String {
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let body = pairs
+        .iter()
+        .map(|(k, v)| format!("{k}=\"{}\"", escape_label_value(v)))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{{body}}}")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn render_counter(out: &mut String, name: &str, points: &[MetricPoint]) {
+    let mut sums: std::collections::BTreeMap<Vec<(String, String)>, f64> =
+        std::collections::BTreeMap::new();
+    for point in points {
+        *sums.entry(label_key(&point.labels)).or_insert(0.0) += point.value;
+    }
+    for (labels, value) in sums {
+        out.push_str(&format!("{name}{} {value}\n", format_labels(&labels)));
+    }
+}
+
+fn render_gauge(out: &mut String, name: &str, points: &[MetricPoint]) {
+    let mut latest: std::collections::BTreeMap<Vec<(String, String)>, &MetricPoint> =
+        std::collections::BTreeMap::new();
+    for point in points {
+        latest
+            .entry(label_key(&point.labels))
+            .and_modify(|existing| {
+                if point.timestamp > existing.timestamp {
+                    *existing = point;
+                }
+            })
+            .or_insert(point);
+    }
+    for (labels, point) in latest {
+        out.push_str(&format!("{name}{} {}\n", format_labels(&labels), point.value));
+    }
+}
+
+fn render_histogram(out: &mut String, name: &str, points: &[MetricPoint]) {
+    let mut groups: std::collections::BTreeMap<Vec<(String, String)>, Vec<f64>> =
+        std::collections::BTreeMap::new();
+    for point in points {
+        groups
+            .entry(label_key(&point.labels))
+            .or_default()
+            .push(point.value);
+    }
+    for (labels, values) in groups {
+        for &bound in HISTOGRAM_BUCKETS_MS {
+            let count = values.iter().filter(|v| **v <= bound).count();
+            let mut bucket_labels = labels.clone();
+            bucket_labels.push(("le".to_string(), bound.to_string()));
+            out.push_str(&format!(
+                "{name}_bucket{} {count}\n",
+                format_labels(&bucket_labels)
+            ));
+        }
+        let mut inf_labels = labels.clone();
+        inf_labels.push(("le".to_string(), "+Inf".to_string()));
+        out.push_str(&format!(
+            "{name}_bucket{} {}\n",
+            format_labels(&inf_labels),
+            values.len()
+        ));
+        let sum: f64 = values.iter().sum();
+        out.push_str(&format!("{name}_sum{} {sum}\n", format_labels(&labels)));
+        out.push_str(&format!(
+            "{name}_count{} {}\n",
+            format_labels(&labels),
+            values.len()
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +312,27 @@ mod tests {
         assert_eq!(snapshot.len(), 1);
         assert_eq!(snapshot[0].kind, MetricKind::Histogram);
     }
+
+    #[test]
+    fn render_prometheus_sums_counters_per_label_set() {
+        let registry = InMemoryMetricsRegistry::new();
+        let mut labels = HashMap::new();
+        labels.insert("tenant_id".to_string(), "t1".to_string());
+        registry.increment_counter("gateway_requests_total", 1.0, Some(labels.clone()));
+        registry.increment_counter("gateway_requests_total", 1.0, Some(labels));
+        let rendered = registry.render_prometheus();
+        assert!(rendered.contains("# TYPE gateway_requests_total counter"));
+        assert!(rendered.contains("gateway_requests_total{tenant_id=\"t1\"} 2"));
+    }
+
+    #[test]
+    fn render_prometheus_emits_histogram_buckets_sum_and_count() {
+        let registry = InMemoryMetricsRegistry::new();
+        registry.observe_histogram("upload_latency", 42.0, None);
+        let rendered = registry.render_prometheus();
+        assert!(rendered.contains("upload_latency_bucket{le=\"50\"} 1"));
+        assert!(rendered.contains("upload_latency_bucket{le=\"+Inf\"} 1"));
+        assert!(rendered.contains("upload_latency_sum 42"));
+        assert!(rendered.contains("upload_latency_count 1"));
+    }
 }
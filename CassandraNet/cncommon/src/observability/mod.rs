@@ -1,5 +1,13 @@
+pub mod event_sink;
 pub mod logging;
 pub mod metrics;
 
-pub use logging::{InMemoryLogSink, LogEvent, LogLevel, LogPipeline, LogSink};
+pub use event_sink::{EventSink, FleetEvent, InMemoryEventSink, NoopEventSink};
+#[cfg(feature = "mqtt")]
+pub use event_sink::MqttEventSink;
+#[cfg(feature = "redis")]
+pub use event_sink::RedisEventSink;
+pub use logging::{
+    InMemoryLogSink, LogEvent, LogLevel, LogPipeline, LogSink, LogSubscriptionFilter,
+};
 pub use metrics::{InMemoryMetricsRegistry, MetricKind, MetricPoint};
@@ -16,6 +16,10 @@ pub mod messaging {
     include!(concat!(env!("OUT_DIR"), "/cassandra.messaging.v1.rs"));
 }
 
+pub mod observability {
+    include!(concat!(env!("OUT_DIR"), "/cassandra.observability.v1.rs"));
+}
+
 pub use agent::*;
 pub use messaging::*;
 pub use orchestration::*;
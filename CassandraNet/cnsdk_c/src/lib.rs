@@ -74,6 +74,10 @@ fn map_error(err: PlatformError) -> i32 {
         PlatformError::Unauthorized | PlatformError::Forbidden => ERR_UNAUTHORIZED,
         PlatformError::NotFound(_) => ERR_INVALID,
         PlatformError::Conflict(_) => ERR_INVALID,
+        PlatformError::Locked(_) => ERR_INVALID,
+        PlatformError::AudienceNotAllowed => ERR_UNAUTHORIZED,
+        PlatformError::IssuerNotTrusted => ERR_UNAUTHORIZED,
+        PlatformError::Validation(_) => ERR_INVALID,
         PlatformError::Internal(_) => ERR_INTERNAL,
     }
 }
@@ -115,11 +119,11 @@ pub extern "C" fn cass_authenticate(api_key: *const c_char) -> i32 {
 
 #[no_mangle]
 pub extern "C" fn cass_send_metric(name: *const c_char, value: f64) -> i32 {
-    if name.is_null() {
-        return ERR_INVALID;
-    }
-    let _name = unsafe { CStr::from_ptr(name) };
-    let _value = value;
+    let name = match from_c_str(name) {
+        Some(n) => n,
+        None => return ERR_INVALID,
+    };
+    platform().metrics().set_gauge(name, value, None);
     0
 }
 
@@ -244,12 +248,26 @@ pub extern "C" fn cass_issue_agent_token(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn cass_verify_agent_token(token: *const c_char) -> i32 {
+    let token = match from_c_str(token) {
+        Some(t) => t,
+        None => return ERR_INVALID,
+    };
+    match platform().auth().validate_token(&token) {
+        Ok(_) => 0,
+        Err(err) => map_error(err),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn cass_schedule_task(
     tenant_id: *const cass_uuid,
     kind: *const c_char,
     payload_json: *const c_char,
+    replicas: u32,
     out_task_id: *mut cass_uuid,
+    out_agent_ids_json: *mut *mut c_char,
 ) -> i32 {
     let tenant = match uuid_from_c(tenant_id) {
         Some(id) => id,
@@ -274,12 +292,22 @@ pub extern "C" fn cass_schedule_task(
         tenant_id: tenant,
         kind,
         payload,
+        replicas,
     };
     match platform().orchestration().schedule_task(request) {
         Ok(task) => {
             unsafe {
                 *out_task_id = uuid_to_c(task.id);
             }
+            // `out_agent_ids_json` is optional: a caller that doesn't care
+            // which agents placement picked (or never wired an
+            // `AgentCandidateSource`, so the list is always empty) can pass
+            // a null pointer and skip the allocation entirely.
+            if !out_agent_ids_json.is_null() {
+                let encoded = serde_json::to_string(&task.assigned_agent_ids)
+                    .unwrap_or_else(|_| "[]".to_string());
+                return set_c_string(out_agent_ids_json, encoded);
+            }
             0
         }
         Err(err) => map_error(err),
@@ -339,7 +367,9 @@ mod tests {
                 &tenant_id,
                 task_kind.as_ptr(),
                 payload.as_ptr(),
-                &mut task_id
+                1,
+                &mut task_id,
+                ptr::null_mut(),
             ),
             0
         );
@@ -0,0 +1,333 @@
+//! Layered, hot-reloadable application configuration.
+//!
+//! Precedence, lowest to highest: built-in defaults → an optional TOML/YAML
+//! file named by `CASS_CONFIG` → `CASS__`-prefixed environment overrides
+//! (e.g. `CASS__HTTP__BIND_ADDR`). The merged result lives behind an
+//! [`ArcSwap`] rather than the one-shot `Lazy<AppConfig>` this replaces, so
+//! [`reload`] can atomically swap in a freshly-merged `AppConfig` without a
+//! restart; [`subscribe_config`] lets a subsystem watch for that swap and
+//! react (e.g. re-reading `agent_liveness.scan_interval_seconds` on its next
+//! tick) instead of only ever seeing the config as it was at startup.
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppConfig {
+    pub service_name: String,
+    pub log_level: Option<String>,
+    pub http: HttpConfig,
+    #[cfg(feature = "db")]
+    pub database: DatabaseConfig,
+    pub events: EventsConfig,
+    pub agent_session: AgentSessionConfig,
+    pub agent_liveness: AgentLivenessConfig,
+    pub sigv4: Sigv4Config,
+    #[cfg(feature = "otel")]
+    pub otel: OtelConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HttpConfig {
+    pub bind_addr: String,
+}
+
+/// Selects the outbound `EventSink` fleet events (agent lifecycle, task
+/// scheduling) fan out to. `backend` is one of `"none"`, `"redis"`, or
+/// `"mqtt"`; a backend whose feature isn't compiled in falls back to
+/// `NoopEventSink` rather than failing startup.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventsConfig {
+    pub backend: String,
+    pub redis_url: Option<String>,
+    pub redis_channel: Option<String>,
+    pub mqtt_broker: Option<String>,
+    pub mqtt_topic: Option<String>,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            backend: "none".into(),
+            redis_url: None,
+            redis_channel: Some("cassandra.fleet".into()),
+            mqtt_broker: None,
+            mqtt_topic: Some("cassandra/fleet".into()),
+        }
+    }
+}
+
+/// Controls the lifetime of agent session JWTs minted by `RegisterAgent` and
+/// the window before expiry in which `Heartbeat` proactively rotates them.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AgentSessionConfig {
+    pub token_ttl_seconds: i64,
+    pub rotation_window_seconds: i64,
+}
+
+impl Default for AgentSessionConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl_seconds: 3600,
+            rotation_window_seconds: 300,
+        }
+    }
+}
+
+/// Controls the gateway's background sweep that marks an agent `"offline"`
+/// once it's missed too many heartbeats. `tranquility` is the same 0 (no
+/// throttle) .. 10 (heaviest) scale `OrchestrationEngine`'s per-kind task
+/// throttle uses: it caps how many agents a single tick re-evaluates so
+/// scanning a large fleet doesn't turn into a scan storm.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AgentLivenessConfig {
+    pub scan_interval_seconds: u64,
+    pub missed_heartbeat_threshold: u32,
+    pub tranquility: u8,
+}
+
+impl Default for AgentLivenessConfig {
+    fn default() -> Self {
+        Self {
+            scan_interval_seconds: 15,
+            missed_heartbeat_threshold: 3,
+            tranquility: 0,
+        }
+    }
+}
+
+impl AgentLivenessConfig {
+    /// Agents re-evaluated per tick: unthrottled at `tranquility == 0`,
+    /// shrinking toward a 16-agent floor as `tranquility` climbs toward 10.
+    pub fn max_agents_per_tick(&self) -> usize {
+        if self.tranquility == 0 {
+            usize::MAX
+        } else {
+            (512 / (self.tranquility.min(10) as usize + 1)).max(16)
+        }
+    }
+}
+
+/// Controls how strict the SigV4 request validator (`gateway::auth::sigv4`)
+/// is about `X-Amz-Date` drifting from wall-clock time, same purpose as
+/// AWS's own presigned-URL/header skew tolerance.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Sigv4Config {
+    pub clock_skew_seconds: i64,
+}
+
+impl Default for Sigv4Config {
+    fn default() -> Self {
+        Self {
+            clock_skew_seconds: 900,
+        }
+    }
+}
+
+/// Drives the `otel` module's OTLP traces/metrics/logs pipeline. `endpoint`
+/// unset disables export entirely, same convention as the rest of this
+/// subsystem used to follow via `CASS_OTLP_ENDPOINT` before it moved under
+/// `AppConfig`. `protocol` is `"grpc"` (OTLP/gRPC, the default) or `"http"`
+/// (OTLP/HTTP+protobuf). `resource_attributes` are merged onto the
+/// `service.name` resource attached to every exported span, metric, and log
+/// record.
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtelConfig {
+    pub endpoint: Option<String>,
+    pub protocol: String,
+    pub sample_ratio: f64,
+    #[serde(default)]
+    pub resource_attributes: std::collections::HashMap<String, String>,
+}
+
+#[cfg(feature = "otel")]
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            protocol: "grpc".into(),
+            sample_ratio: 1.0,
+            resource_attributes: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            service_name: "cassandra-gateway".into(),
+            log_level: Some("info".into()),
+            http: HttpConfig {
+                bind_addr: "127.0.0.1:8080".into(),
+            },
+            #[cfg(feature = "db")]
+            database: DatabaseConfig::default(),
+            events: EventsConfig::default(),
+            agent_session: AgentSessionConfig::default(),
+            agent_liveness: AgentLivenessConfig::default(),
+            sigv4: Sigv4Config::default(),
+            #[cfg(feature = "otel")]
+            otel: OtelConfig::default(),
+        }
+    }
+}
+
+#[cfg(feature = "db")]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+}
+
+#[cfg(feature = "db")]
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "postgres://localhost:5432/cassandra".into(),
+            max_connections: 5,
+        }
+    }
+}
+
+/// Name of the env var naming an optional TOML/YAML/JSON config file to
+/// layer between built-in defaults and `CASS__`-prefixed env overrides.
+/// Format is inferred from the file's extension.
+const CONFIG_FILE_ENV: &str = "CASS_CONFIG";
+
+/// How often [`spawn_config_file_watcher`] polls `CASS_CONFIG`'s mtime.
+/// There's no filesystem-event crate in this dependency set, so this trades
+/// a small, bounded reload latency for not adding one.
+const FILE_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+fn build_layered_config() -> Result<AppConfig> {
+    #[allow(unused_mut)]
+    let mut builder = config::Config::builder()
+        .set_default("service_name", "cassandra-gateway")?
+        .set_default("http.bind_addr", "127.0.0.1:8080")?
+        .set_default("events.backend", "none")?
+        .set_default("events.redis_channel", "cassandra.fleet")?
+        .set_default("events.mqtt_topic", "cassandra/fleet")?
+        .set_default("agent_session.token_ttl_seconds", 3600)?
+        .set_default("agent_session.rotation_window_seconds", 300)?
+        .set_default("agent_liveness.scan_interval_seconds", 15)?
+        .set_default("agent_liveness.missed_heartbeat_threshold", 3)?
+        .set_default("agent_liveness.tranquility", 0)?
+        .set_default("sigv4.clock_skew_seconds", 900)?;
+    #[cfg(feature = "db")]
+    {
+        builder = builder
+            .set_default("database.url", "postgres://localhost:5432/cassandra")?
+            .set_default("database.max_connections", 5)?;
+    }
+    #[cfg(feature = "otel")]
+    {
+        builder = builder
+            .set_default("otel.protocol", "grpc")?
+            .set_default("otel.sample_ratio", 1.0)?;
+    }
+    if let Some(path) = std::env::var_os(CONFIG_FILE_ENV) {
+        builder = builder.add_source(config::File::from(PathBuf::from(path)));
+    }
+    let c = builder
+        .add_source(config::Environment::with_prefix("CASS").separator("__"))
+        .build()?;
+    let cfg: AppConfig = c.try_deserialize()?;
+    Ok(cfg)
+}
+
+/// Holds the live, merged `AppConfig` and fans out a `tracing` event plus a
+/// [`watch`] notification every time [`reload`](ConfigProvider::reload)
+/// swaps in a new one.
+struct ConfigProvider {
+    live: ArcSwap<AppConfig>,
+    reload_tx: watch::Sender<Arc<AppConfig>>,
+}
+
+impl ConfigProvider {
+    fn load() -> Self {
+        let initial = Arc::new(build_layered_config().unwrap_or_default());
+        let (reload_tx, _) = watch::channel(initial.clone());
+        Self {
+            live: ArcSwap::new(initial),
+            reload_tx,
+        }
+    }
+
+    fn current(&self) -> Arc<AppConfig> {
+        self.live.load_full()
+    }
+
+    fn subscribe(&self) -> watch::Receiver<Arc<AppConfig>> {
+        self.reload_tx.subscribe()
+    }
+
+    /// Re-merges defaults/file/env and swaps the result in, regardless of
+    /// whether anything actually changed; callers that only want to react
+    /// to genuine changes can compare the old and new `Arc` via
+    /// `subscribe_config`'s receiver against their own last-seen value.
+    fn reload(&self) -> Result<()> {
+        let next = Arc::new(build_layered_config()?);
+        self.live.store(next.clone());
+        tracing::info!(service_name = %next.service_name, log_level = ?next.log_level, "config.reloaded");
+        // No active subscribers is not an error; it just means nothing is
+        // watching for this particular reload.
+        let _ = self.reload_tx.send(next);
+        Ok(())
+    }
+}
+
+static GLOBAL_CONFIG: Lazy<ConfigProvider> = Lazy::new(ConfigProvider::load);
+
+pub fn config() -> Arc<AppConfig> {
+    GLOBAL_CONFIG.current()
+}
+
+/// Subscribes to config reloads; the receiver's current value is always the
+/// most recently loaded `AppConfig`, so a subsystem can read it once up
+/// front and then `changed().await` in its own loop rather than polling
+/// [`config`].
+pub fn subscribe_config() -> watch::Receiver<Arc<AppConfig>> {
+    GLOBAL_CONFIG.subscribe()
+}
+
+/// Forces an immediate re-merge of defaults/file/env, same as
+/// [`spawn_config_file_watcher`]'s poll loop does on a detected change.
+pub fn reload_config() -> Result<()> {
+    GLOBAL_CONFIG.reload()
+}
+
+/// Polls `CASS_CONFIG`'s mtime every [`FILE_WATCH_INTERVAL`] and calls
+/// [`reload_config`] when it changes, so operators can retune
+/// `agent_liveness`/`log_level`/etc. by editing the file in place. A no-op
+/// if `CASS_CONFIG` isn't set, since there's then no file to watch.
+pub fn spawn_config_file_watcher() {
+    let Some(path) = std::env::var_os(CONFIG_FILE_ENV).map(PathBuf::from) else {
+        return;
+    };
+    tokio::spawn(async move {
+        let mut last_modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let mut ticker = tokio::time::interval(FILE_WATCH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let modified = match std::fs::metadata(&path).and_then(|m| m.modified()) {
+                Ok(modified) => modified,
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), error = %err, "config.watch_stat_failed");
+                    continue;
+                }
+            };
+            if Some(modified) == last_modified {
+                continue;
+            }
+            last_modified = Some(modified);
+            if let Err(err) = reload_config() {
+                tracing::warn!(path = %path.display(), error = %err, "config.reload_failed");
+            }
+        }
+    });
+}
@@ -1,93 +1,209 @@
 //! Core foundational utilities: configuration, tracing init, shutdown signals.
 use anyhow::Result;
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 pub mod build_info;
 pub use build_info::{build_info, BuildInfo};
+pub mod config;
+pub use config::{
+    config, reload_config, spawn_config_file_watcher, subscribe_config, AgentLivenessConfig,
+    AgentSessionConfig, AppConfig, EventsConfig, HttpConfig, Sigv4Config,
+};
+#[cfg(feature = "db")]
+pub use config::DatabaseConfig;
+#[cfg(feature = "otel")]
+pub use config::OtelConfig;
 pub mod platform;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct AppConfig {
-    pub service_name: String,
-    pub log_level: Option<String>,
-    pub http: HttpConfig,
-    #[cfg(feature = "db")]
-    pub database: DatabaseConfig,
-}
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct HttpConfig {
-    pub bind_addr: String,
-}
-
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            service_name: "cassandra-gateway".into(),
-            log_level: Some("info".into()),
-            http: HttpConfig {
-                bind_addr: "127.0.0.1:8080".into(),
-            },
-            #[cfg(feature = "db")]
-            database: DatabaseConfig::default(),
+pub fn init_tracing() {
+    static START: Lazy<()> = Lazy::new(|| {
+        let cfg = config();
+        let level = cfg.log_level.clone().unwrap_or_else(|| "info".into());
+        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+        let registry = tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt::layer());
+        #[cfg(feature = "otel")]
+        {
+            let trace_layer = otel::otlp_trace_layer(&cfg);
+            let log_layer = otel::otlp_log_layer(&cfg);
+            otel::init_metrics(&cfg);
+            registry.with(trace_layer).with(log_layer).init();
         }
-    }
+        #[cfg(not(feature = "otel"))]
+        {
+            registry.init();
+        }
+    });
+    Lazy::force(&START);
 }
 
-#[cfg(feature = "db")]
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct DatabaseConfig {
-    pub url: String,
-    pub max_connections: u32,
-}
+/// OTLP traces, metrics, and logs export, enabled via the `otel` feature and
+/// configured through `AppConfig::otel` (so a collector endpoint change is a
+/// config change, not a redeploy).
+#[cfg(feature = "otel")]
+pub mod otel {
+    use crate::{AppConfig, OtelConfig};
+    use once_cell::sync::OnceCell;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::trace::Sampler;
+    use opentelemetry_sdk::Resource;
+    use tracing_subscriber::Layer;
 
-#[cfg(feature = "db")]
-impl Default for DatabaseConfig {
-    fn default() -> Self {
-        Self {
-            url: "postgres://localhost:5432/cassandra".into(),
-            max_connections: 5,
-        }
-    }
-}
+    static TRACER_PROVIDER: OnceCell<opentelemetry_sdk::trace::TracerProvider> = OnceCell::new();
+    static METER_PROVIDER: OnceCell<opentelemetry_sdk::metrics::SdkMeterProvider> =
+        OnceCell::new();
+    static LOGGER_PROVIDER: OnceCell<opentelemetry_sdk::logs::LoggerProvider> = OnceCell::new();
 
-static GLOBAL_CONFIG: Lazy<AppConfig> = Lazy::new(|| load_config().unwrap_or_default());
+    fn resource(cfg: &OtelConfig, service_name: &str) -> Resource {
+        let mut attributes = vec![opentelemetry::KeyValue::new(
+            "service.name",
+            service_name.to_string(),
+        )];
+        attributes.extend(
+            cfg.resource_attributes
+                .iter()
+                .map(|(k, v)| opentelemetry::KeyValue::new(k.clone(), v.clone())),
+        );
+        Resource::new(attributes)
+    }
 
-pub fn config() -> &'static AppConfig {
-    &GLOBAL_CONFIG
-}
+    /// `cfg.otel.endpoint` unset disables export entirely. `protocol` selects
+    /// between OTLP/gRPC (`"grpc"`, the default) and OTLP/HTTP+protobuf
+    /// (`"http"`); anything else is treated as `"grpc"`.
+    pub fn otlp_trace_layer<S>(
+        cfg: &AppConfig,
+    ) -> Option<Box<dyn Layer<S> + Send + Sync + 'static>>
+    where
+        S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    {
+        let endpoint = cfg.otel.endpoint.clone()?;
+        let sample_ratio = cfg.otel.sample_ratio.clamp(0.0, 1.0);
+        let pipeline = opentelemetry_otlp::new_pipeline().tracing().with_trace_config(
+            opentelemetry_sdk::trace::config()
+                .with_sampler(Sampler::TraceIdRatioBased(sample_ratio))
+                .with_resource(resource(&cfg.otel, &cfg.service_name)),
+        );
+        let provider = if cfg.otel.protocol == "http" {
+            pipeline
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .http()
+                        .with_endpoint(endpoint),
+                )
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .ok()?
+        } else {
+            pipeline
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .ok()?
+        };
+        let tracer = provider.tracer(cfg.service_name.clone());
+        let _ = TRACER_PROVIDER.set(provider);
+        Some(Box::new(tracing_opentelemetry::layer().with_tracer(tracer)))
+    }
 
-fn load_config() -> Result<AppConfig> {
-    #[allow(unused_mut)]
-    let mut builder = config::Config::builder()
-        .set_default("service_name", "cassandra-gateway")?
-        .set_default("http.bind_addr", "127.0.0.1:8080")?;
-    #[cfg(feature = "db")]
+    /// Bridges `tracing` log-style events into OTLP log records via the same
+    /// endpoint as [`otlp_trace_layer`].
+    pub fn otlp_log_layer<S>(cfg: &AppConfig) -> Option<Box<dyn Layer<S> + Send + Sync + 'static>>
+    where
+        S: tracing::Subscriber,
+        for<'a> S: tracing_subscriber::registry::LookupSpan<'a>,
     {
-        builder = builder
-            .set_default("database.url", "postgres://localhost:5432/cassandra")?
-            .set_default("database.max_connections", 5)?;
+        let endpoint = cfg.otel.endpoint.clone()?;
+        let pipeline = opentelemetry_otlp::new_pipeline()
+            .logging()
+            .with_resource(resource(&cfg.otel, &cfg.service_name));
+        let provider = if cfg.otel.protocol == "http" {
+            pipeline
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .http()
+                        .with_endpoint(endpoint),
+                )
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .ok()?
+        } else {
+            pipeline
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .ok()?
+        };
+        let bridge = opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge::new(
+            &provider,
+        );
+        let _ = LOGGER_PROVIDER.set(provider);
+        Some(Box::new(bridge))
     }
-    let c = builder
-        .add_source(config::Environment::with_prefix("CASS").separator("__"))
-        .build()?;
-    let cfg: AppConfig = c.try_deserialize()?;
-    Ok(cfg)
-}
 
-pub fn init_tracing() {
-    static START: Lazy<()> = Lazy::new(|| {
-        let cfg = config();
-        let level = cfg.log_level.clone().unwrap_or_else(|| "info".into());
-        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
-        tracing_subscriber::registry()
-            .with(filter)
-            .with(fmt::layer())
-            .init();
-    });
-    Lazy::force(&START);
+    /// Installs the global OTLP meter provider counters and histograms read
+    /// through `opentelemetry::global::meter(...)` report to; a no-op if
+    /// `cfg.otel.endpoint` is unset.
+    pub fn init_metrics(cfg: &AppConfig) {
+        let Some(endpoint) = cfg.otel.endpoint.clone() else {
+            return;
+        };
+        let exporter_result = if cfg.otel.protocol == "http" {
+            opentelemetry_otlp::new_pipeline()
+                .metrics(opentelemetry_sdk::runtime::Tokio)
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .http()
+                        .with_endpoint(endpoint),
+                )
+                .with_resource(resource(&cfg.otel, &cfg.service_name))
+                .build()
+        } else {
+            opentelemetry_otlp::new_pipeline()
+                .metrics(opentelemetry_sdk::runtime::Tokio)
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .with_resource(resource(&cfg.otel, &cfg.service_name))
+                .build()
+        };
+        let Ok(provider) = exporter_result else {
+            tracing::warn!("otel.init_metrics_failed");
+            return;
+        };
+        opentelemetry::global::set_meter_provider(provider.clone());
+        let _ = METER_PROVIDER.set(provider);
+    }
+
+    /// Flushes and shuts down every provider installed by this module.
+    /// Called from [`crate::shutdown_signal`] so buffered spans, metrics, and
+    /// log records aren't dropped on exit.
+    pub fn shutdown() {
+        if let Some(provider) = TRACER_PROVIDER.get() {
+            if let Err(err) = provider.shutdown() {
+                tracing::warn!(error = %err, "otel.tracer_shutdown_failed");
+            }
+        }
+        if let Some(provider) = METER_PROVIDER.get() {
+            if let Err(err) = provider.shutdown() {
+                tracing::warn!(error = %err, "otel.meter_shutdown_failed");
+            }
+        }
+        if let Some(provider) = LOGGER_PROVIDER.get() {
+            if let Err(err) = provider.shutdown() {
+                tracing::warn!(error = %err, "otel.logger_shutdown_failed");
+            }
+        }
+    }
 }
 
 pub async fn shutdown_signal() {
@@ -105,6 +221,8 @@ pub async fn shutdown_signal() {
         let _ = tokio::signal::ctrl_c().await;
     }
     tracing::info!("shutdown signal received");
+    #[cfg(feature = "otel")]
+    otel::shutdown();
 }
 
 // Database pool singleton (sqlx) behind feature flag
@@ -118,10 +236,10 @@ pub async fn db() -> Result<&'static sqlx::Pool<sqlx::Postgres>> {
     if let Some(p) = DB.get() {
         return Ok(p);
     }
-    let cfg = &config().database;
+    let cfg = config();
     let pool = sqlx::postgres::PgPoolOptions::new()
-        .max_connections(cfg.max_connections)
-        .connect(&cfg.url)
+        .max_connections(cfg.database.max_connections)
+        .connect(&cfg.database.url)
         .await?;
     let _ = DB.set(pool);
     Ok(DB.get().unwrap())
@@ -133,3 +251,81 @@ pub async fn run_migrations() -> Result<()> {
     sqlx::migrate!("./migrations").run(pool).await?;
     Ok(())
 }
+
+/// One migration file's applied/pending state, as reported by
+/// [`migration_status`].
+#[cfg(feature = "db")]
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+/// Lists every migration under `./migrations` alongside whether it's already
+/// been applied to the connected database, for the gateway's `migrate
+/// status` subcommand.
+#[cfg(feature = "db")]
+pub async fn migration_status() -> Result<Vec<MigrationStatus>> {
+    use sqlx::migrate::Migrate;
+
+    let pool = db().await?;
+    let migrator = sqlx::migrate!("./migrations");
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let applied: std::collections::HashSet<i64> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+    Ok(migrator
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: m.description.to_string(),
+            applied: applied.contains(&m.version),
+        })
+        .collect())
+}
+
+/// Number of migrations under `./migrations` that haven't been applied yet,
+/// so a caller (e.g. the gateway's `migrate status` subcommand, or a
+/// readiness probe) can verify the database is current before serving
+/// traffic without printing the full per-migration listing.
+#[cfg(feature = "db")]
+pub async fn pending_migration_count() -> Result<usize> {
+    Ok(migration_status()
+        .await?
+        .iter()
+        .filter(|m| !m.applied)
+        .count())
+}
+
+/// Reverts the `steps` most recently applied migrations (newest first).
+/// Returns the versions that were reverted; reverting more steps than are
+/// applied just walks back to an empty schema rather than erroring.
+#[cfg(feature = "db")]
+pub async fn migrate_down(steps: usize) -> Result<Vec<i64>> {
+    use sqlx::migrate::Migrate;
+
+    let pool = db().await?;
+    let migrator = sqlx::migrate!("./migrations");
+    let mut conn = pool.acquire().await?;
+    let mut applied = conn.list_applied_migrations().await?;
+    applied.sort_by_key(|m| m.version);
+    applied.reverse();
+    let reverted: Vec<i64> = applied.iter().take(steps).map(|m| m.version).collect();
+    let target = applied.get(steps).map(|m| m.version).unwrap_or(0);
+    migrator.undo(pool, target).await?;
+    Ok(reverted)
+}
+
+/// Reverts the single most recently applied migration, then reapplies it.
+/// Useful for iterating on a migration file against a live database without
+/// hand-rolling matching up/down SQL.
+#[cfg(feature = "db")]
+pub async fn migrate_redo() -> Result<()> {
+    migrate_down(1).await?;
+    run_migrations().await
+}
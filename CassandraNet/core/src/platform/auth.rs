@@ -1,6 +1,6 @@
 use super::error::{PlatformError, PlatformResult};
 use super::models::*;
-use super::persistence::{ApiKeyStore, TenantStore};
+use super::persistence::{ApiKeyStore, AuditStore, TenantStore};
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{Duration, Utc};
 use hmac::{Hmac, Mac};
@@ -20,6 +20,7 @@ type HmacSha256 = Hmac<Sha256>;
 pub struct AuthService {
     tenants: Arc<dyn TenantStore>,
     api_keys: Arc<dyn ApiKeyStore>,
+    audit: Arc<dyn AuditStore>,
     secret: Arc<Vec<u8>>,
     default_ttl: Duration,
     default_refresh_ttl: Duration,
@@ -31,11 +32,13 @@ impl AuthService {
     pub fn new(
         tenants: Arc<dyn TenantStore>,
         api_keys: Arc<dyn ApiKeyStore>,
+        audit: Arc<dyn AuditStore>,
         secret: impl Into<Vec<u8>>,
     ) -> Self {
         Self {
             tenants,
             api_keys,
+            audit,
             secret: Arc::new(secret.into()),
             default_ttl: Duration::minutes(60),
             default_refresh_ttl: Duration::hours(12),
@@ -74,7 +77,7 @@ impl AuthService {
         self.create_api_key(tenant_id, label.into(), scopes, None)
     }
 
-    pub fn rotate_api_key(&self, id: ApiKeyId) -> PlatformResult<ApiKey> {
+    pub fn rotate_api_key(&self, id: ApiKeyId, actor: &AuthContext) -> PlatformResult<ApiKey> {
         let mut existing = self
             .api_keys
             .get_api_key(id)?
@@ -92,28 +95,82 @@ impl AuthService {
         existing.deleted_at = Some(Utc::now());
         existing.rotated_to = Some(new_key.id);
         self.api_keys.update_api_key(existing)?;
+        self.record_audit(
+            actor,
+            "apikey.rotate",
+            AuditArea::Auth,
+            AuditCategory::Modify,
+            id.to_string(),
+            Some(serde_json::json!({ "rotated_to": new_key.id })),
+        )?;
         Ok(new_key)
     }
 
-    pub fn soft_delete_api_key(&self, id: ApiKeyId) -> PlatformResult<()> {
+    pub fn soft_delete_api_key(&self, id: ApiKeyId, actor: &AuthContext) -> PlatformResult<()> {
         if let Some(mut record) = self.api_keys.get_api_key(id)? {
             record.deleted_at = Some(Utc::now());
             record.revoked = true;
-            self.api_keys.update_api_key(record)
+            self.api_keys.update_api_key(record)?;
+            self.record_audit(
+                actor,
+                "apikey.delete",
+                AuditArea::Auth,
+                AuditCategory::Remove,
+                id.to_string(),
+                None,
+            )
         } else {
             Err(PlatformError::NotFound("api_key"))
         }
     }
 
-    pub fn revoke_api_key(&self, id: ApiKeyId) -> PlatformResult<()> {
+    pub fn revoke_api_key(&self, id: ApiKeyId, actor: &AuthContext) -> PlatformResult<()> {
         if let Some(mut record) = self.api_keys.get_api_key(id)? {
             record.revoked = true;
-            self.api_keys.update_api_key(record)
+            self.api_keys.update_api_key(record)?;
+            self.record_audit(
+                actor,
+                "apikey.revoke",
+                AuditArea::Auth,
+                AuditCategory::Remove,
+                id.to_string(),
+                None,
+            )
         } else {
             Err(PlatformError::NotFound("api_key"))
         }
     }
 
+    /// Revokes and soft-deletes `id` with no caller `AuthContext`, for
+    /// cleanup a service performs on its own behalf rather than on a
+    /// principal's — mirrors the system-actor audit events
+    /// `ProvisioningService::sweep_inactive_agents` writes directly. Used by
+    /// `ProvisioningService` to retire a key minted by a caller that went on
+    /// to lose an idempotent-create race, so the loser's key never lives on
+    /// as an unreferenced, usable credential. No-ops if `id` is already
+    /// gone.
+    pub(crate) fn revoke_api_key_system(&self, id: ApiKeyId) -> PlatformResult<()> {
+        let Some(mut record) = self.api_keys.get_api_key(id)? else {
+            return Ok(());
+        };
+        record.revoked = true;
+        record.deleted_at = Some(Utc::now());
+        let tenant_id = record.tenant_id;
+        self.api_keys.update_api_key(record)?;
+        self.audit.record_event(AuditEvent {
+            id: Uuid::new_v4(),
+            tenant_id,
+            action_id: "apikey.revoke".to_string(),
+            area: AuditArea::Auth,
+            category: AuditCategory::Remove,
+            actor_id: Uuid::nil(),
+            actor_type: PrincipalType::Service,
+            target_id: id.to_string(),
+            timestamp: Utc::now(),
+            diff: Some(serde_json::json!({ "reason": "idempotency_claim_lost" })),
+        })
+    }
+
     pub fn authenticate_api_key(&self, token: &str) -> PlatformResult<AuthContext> {
         let (prefix, secret) = parse_api_key(token)?;
         let mut record = self
@@ -179,13 +236,16 @@ impl AuthService {
         let claims = verify_jwt(refresh_token, &self.secret)?;
         self.ensure_claims_valid(&claims, TokenUse::Refresh)?;
         let context = AuthContext::from(claims);
+        self.ensure_audience_and_issuer_allowed(&context)?;
         self.issue_token_from_context(context, None)
     }
 
     pub fn validate_token(&self, token: &str) -> PlatformResult<AuthContext> {
         let claims = verify_jwt(token, &self.secret)?;
         self.ensure_claims_valid(&claims, TokenUse::Access)?;
-        Ok(AuthContext::from(claims))
+        let context = AuthContext::from(claims);
+        self.ensure_audience_and_issuer_allowed(&context)?;
+        Ok(context)
     }
 
     pub fn list_keys(&self, tenant_id: TenantId) -> PlatformResult<Vec<ApiKeyRecord>> {
@@ -282,6 +342,29 @@ impl AuthService {
             .map(|tenant| tenant.settings))
     }
 
+    fn record_audit(
+        &self,
+        actor: &AuthContext,
+        action_id: &str,
+        area: AuditArea,
+        category: AuditCategory,
+        target_id: String,
+        diff: Option<serde_json::Value>,
+    ) -> PlatformResult<()> {
+        self.audit.record_event(AuditEvent {
+            id: Uuid::new_v4(),
+            tenant_id: actor.tenant_id,
+            action_id: action_id.to_string(),
+            area,
+            category,
+            actor_id: actor.principal_id,
+            actor_type: actor.principal_type.clone(),
+            target_id,
+            timestamp: Utc::now(),
+            diff,
+        })
+    }
+
     fn issue_refresh_token(&self, context: &AuthContext) -> PlatformResult<Option<String>> {
         let refresh_ttl = self.resolve_refresh_ttl(context.tenant_id)?;
         if refresh_ttl <= Duration::zero() {
@@ -316,6 +399,39 @@ impl AuthService {
         }
         Ok(())
     }
+
+    /// After signature/expiry checks pass, rejects a token whose
+    /// audience/issuer isn't present in its tenant's [`TenantSettings`]
+    /// allowlists. An empty allowlist accepts any value, preserving
+    /// today's behavior for tenants that haven't opted in — this is the
+    /// precondition for safely accepting externally-issued (federated)
+    /// tokens rather than only gateway-minted HS256 ones.
+    fn ensure_audience_and_issuer_allowed(&self, context: &AuthContext) -> PlatformResult<()> {
+        let Some(settings) = self.tenant_settings(context.tenant_id)? else {
+            return Ok(());
+        };
+        if !settings.allowed_audiences.is_empty() {
+            let allowed = context
+                .audience
+                .as_deref()
+                .map(|aud| settings.allowed_audiences.iter().any(|a| a == aud))
+                .unwrap_or(false);
+            if !allowed {
+                return Err(PlatformError::AudienceNotAllowed);
+            }
+        }
+        if !settings.trusted_issuers.is_empty() {
+            let trusted = context
+                .issuer
+                .as_deref()
+                .map(|iss| settings.trusted_issuers.iter().any(|i| i == iss))
+                .unwrap_or(false);
+            if !trusted {
+                return Err(PlatformError::IssuerNotTrusted);
+            }
+        }
+        Ok(())
+    }
 }
 
 fn hash_secret(secret: &str) -> String {
@@ -460,15 +576,16 @@ fn verify_jwt(token: &str, secret: &[u8]) -> PlatformResult<TokenClaims> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::platform::persistence::{ApiKeyStore, InMemoryPersistence, TenantStore};
+    use crate::platform::persistence::{ApiKeyStore, AuditStore, InMemoryPersistence, TenantStore};
 
     #[test]
     fn api_key_issue_and_authenticate() {
         let storage = Arc::new(InMemoryPersistence::new());
         let tenant_store: Arc<dyn TenantStore> = storage.clone();
         let api_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
         let secret = b"secret".to_vec();
-        let service = AuthService::new(tenant_store.clone(), api_store, secret);
+        let service = AuthService::new(tenant_store.clone(), api_store, audit_store, secret);
         let tenant_id = Uuid::new_v4();
         tenant_store
             .insert_tenant(Tenant {
@@ -494,8 +611,9 @@ mod tests {
         let storage = Arc::new(InMemoryPersistence::new());
         let tenant_store: Arc<dyn TenantStore> = storage.clone();
         let api_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
         let secret = b"another-secret".to_vec();
-        let service = AuthService::new(tenant_store.clone(), api_store, secret)
+        let service = AuthService::new(tenant_store.clone(), api_store, audit_store, secret)
             .with_default_audience("cncore");
         let tenant_id = Uuid::new_v4();
         tenant_store
@@ -536,8 +654,14 @@ mod tests {
         let storage = Arc::new(InMemoryPersistence::new());
         let tenant_store: Arc<dyn TenantStore> = storage.clone();
         let api_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
         let secret = b"rotate-secret".to_vec();
-        let service = AuthService::new(tenant_store.clone(), api_store.clone(), secret);
+        let service = AuthService::new(
+            tenant_store.clone(),
+            api_store.clone(),
+            audit_store.clone(),
+            secret,
+        );
         let tenant_id = Uuid::new_v4();
         tenant_store
             .insert_tenant(Tenant {
@@ -550,13 +674,121 @@ mod tests {
         let key = service
             .issue_api_key(tenant_id, "primary", vec![Scope::ApiKeyManage])
             .unwrap();
-        let rotated = service.rotate_api_key(key.id).unwrap();
+        let actor = AuthContext {
+            principal_id: Uuid::new_v4(),
+            principal_type: PrincipalType::Tenant,
+            tenant_id,
+            scopes: vec![Scope::ApiKeyManage],
+            issued_at: Utc::now(),
+            expires_at: Utc::now(),
+            audience: None,
+            issuer: None,
+            session: None,
+        };
+        let rotated = service.rotate_api_key(key.id, &actor).unwrap();
         assert_eq!(rotated.rotation_parent, Some(key.id));
         let original = api_store.get_api_key(key.id).unwrap().unwrap();
         assert!(original.revoked);
         assert_eq!(original.rotated_to, Some(rotated.id));
-        service.soft_delete_api_key(rotated.id).unwrap();
+        service.soft_delete_api_key(rotated.id, &actor).unwrap();
         let rotated_record = api_store.get_api_key(rotated.id).unwrap().unwrap();
         assert!(rotated_record.deleted_at.is_some());
+
+        let events = audit_store
+            .list_events(&AuditQuery {
+                tenant_id,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().any(|e| e.action_id == "apikey.rotate"));
+        assert!(events.iter().any(|e| e.action_id == "apikey.delete"));
+        assert!(events.iter().all(|e| e.actor_id == actor.principal_id));
+    }
+
+    fn service_with_tenant(settings: TenantSettings) -> (AuthService, TenantId) {
+        let storage = Arc::new(InMemoryPersistence::new());
+        let tenant_store: Arc<dyn TenantStore> = storage.clone();
+        let api_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
+        let service = AuthService::new(
+            tenant_store.clone(),
+            api_store,
+            audit_store,
+            b"allowlist-secret".to_vec(),
+        )
+        .with_default_audience("cncore");
+        let tenant_id = Uuid::new_v4();
+        tenant_store
+            .insert_tenant(Tenant {
+                id: tenant_id,
+                name: "Allowlisted".into(),
+                created_at: Utc::now(),
+                settings,
+            })
+            .unwrap();
+        (service, tenant_id)
+    }
+
+    fn context_for(tenant_id: TenantId) -> AuthContext {
+        AuthContext {
+            principal_id: Uuid::new_v4(),
+            principal_type: PrincipalType::Tenant,
+            tenant_id,
+            scopes: vec![Scope::Admin],
+            issued_at: Utc::now(),
+            expires_at: Utc::now(),
+            audience: Some("cncore".into()),
+            issuer: Some("cassantranet".into()),
+            session: None,
+        }
+    }
+
+    #[test]
+    fn validate_token_accepts_any_audience_and_issuer_with_empty_allowlists() {
+        let (service, tenant_id) = service_with_tenant(TenantSettings::default());
+        let token = service
+            .issue_token_from_context(context_for(tenant_id), None)
+            .unwrap();
+        assert!(service.validate_token(&token.token).is_ok());
+    }
+
+    #[test]
+    fn validate_token_rejects_an_audience_outside_the_tenant_allowlist() {
+        let (service, tenant_id) = service_with_tenant(TenantSettings {
+            allowed_audiences: vec!["other-audience".into()],
+            ..Default::default()
+        });
+        let token = service
+            .issue_token_from_context(context_for(tenant_id), None)
+            .unwrap();
+        let err = service.validate_token(&token.token).unwrap_err();
+        assert!(matches!(err, PlatformError::AudienceNotAllowed));
+    }
+
+    #[test]
+    fn validate_token_rejects_an_issuer_the_tenant_does_not_trust() {
+        let (service, tenant_id) = service_with_tenant(TenantSettings {
+            trusted_issuers: vec!["some-other-issuer".into()],
+            ..Default::default()
+        });
+        let token = service
+            .issue_token_from_context(context_for(tenant_id), None)
+            .unwrap();
+        let err = service.validate_token(&token.token).unwrap_err();
+        assert!(matches!(err, PlatformError::IssuerNotTrusted));
+    }
+
+    #[test]
+    fn validate_token_accepts_an_audience_and_issuer_present_in_the_allowlists() {
+        let (service, tenant_id) = service_with_tenant(TenantSettings {
+            allowed_audiences: vec!["cncore".into()],
+            trusted_issuers: vec!["cassantranet".into()],
+            ..Default::default()
+        });
+        let token = service
+            .issue_token_from_context(context_for(tenant_id), None)
+            .unwrap();
+        assert!(service.validate_token(&token.token).is_ok());
     }
 }
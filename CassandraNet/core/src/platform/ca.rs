@@ -0,0 +1,273 @@
+//! A minimal certificate authority for minting per-agent mTLS client
+//! certificates at provisioning time. Each tenant gets its own intermediate
+//! CA, persisted through `CertificateStore` and loaded (or, on first use,
+//! generated and persisted) once per process — every agent certificate it
+//! signs carries the agent id in the subject CN and the tenant/project ids
+//! as SAN URIs, so the gateway can derive an `AuthContext` with
+//! `principal_type = Agent` straight off the peer certificate instead of
+//! doing a separate token lookup. Persisting the CA (rather than
+//! regenerating it lazily per-process) is what lets a certificate signed by
+//! one replica validate against another, and survive a restart without
+//! invalidating every certificate it already signed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyUsagePurpose, SanType,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::error::{PlatformError, PlatformResult};
+use super::models::{
+    AgentCertificateBundle, AgentCertificateRecord, AgentId, ProjectId, TenantCaRecord, TenantId,
+};
+use super::persistence::CertificateStore;
+
+/// How long a freshly minted agent certificate is valid for before it must
+/// be rotated. Short-lived by design — the mTLS identity is meant to be
+/// cheap to rotate, not a long-lived credential like an API key.
+const AGENT_CERT_TTL_HOURS: i64 = 72;
+
+struct TenantCa {
+    certificate: Certificate,
+}
+
+/// Issues and rotates per-agent mTLS client certificates, signed by a
+/// per-tenant intermediate CA that is generated lazily on first use and
+/// cached for the lifetime of the process.
+#[derive(Clone)]
+pub struct CertificateAuthority {
+    certificates: Arc<dyn CertificateStore>,
+    tenant_cas: Arc<RwLock<HashMap<TenantId, Arc<TenantCa>>>>,
+}
+
+impl CertificateAuthority {
+    pub fn new(certificates: Arc<dyn CertificateStore>) -> Self {
+        Self {
+            certificates,
+            tenant_cas: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Mints a fresh agent certificate with no rotation lineage.
+    pub fn issue_agent_certificate(
+        &self,
+        tenant_id: TenantId,
+        project_id: ProjectId,
+        agent_id: AgentId,
+    ) -> PlatformResult<AgentCertificateBundle> {
+        self.issue(tenant_id, project_id, agent_id, None)
+    }
+
+    /// Rotates `existing_id`, linking the new certificate back to it via
+    /// `rotated_from`/`rotated_to`, mirroring `AuthService::rotate_api_key`.
+    pub fn rotate_agent_certificate(&self, existing_id: Uuid) -> PlatformResult<AgentCertificateBundle> {
+        let mut existing = self
+            .certificates
+            .get_certificate(existing_id)?
+            .ok_or(PlatformError::NotFound("agent_certificate"))?;
+        if existing.revoked {
+            return Err(PlatformError::InvalidInput("certificate inactive"));
+        }
+        let bundle = self.issue(
+            existing.tenant_id,
+            existing.project_id,
+            existing.agent_id,
+            Some(existing.id),
+        )?;
+        existing.revoked = true;
+        existing.rotated_to = Some(bundle.record.id);
+        self.certificates.update_certificate(existing)?;
+        Ok(bundle)
+    }
+
+    fn issue(
+        &self,
+        tenant_id: TenantId,
+        project_id: ProjectId,
+        agent_id: AgentId,
+        rotated_from: Option<Uuid>,
+    ) -> PlatformResult<AgentCertificateBundle> {
+        let ca = self.tenant_ca(tenant_id)?;
+
+        let mut params = CertificateParams::new(vec![]);
+        let mut dn = DistinguishedName::new();
+        dn.push(DnType::CommonName, agent_id.to_string());
+        params.distinguished_name = dn;
+        params.subject_alt_names = vec![
+            SanType::URI(format!("urn:cassandra:tenant:{tenant_id}")),
+            SanType::URI(format!("urn:cassandra:project:{project_id}")),
+        ];
+        params.is_ca = IsCa::NoCa;
+        params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let now = Utc::now();
+        let expires_at = now + Duration::hours(AGENT_CERT_TTL_HOURS);
+        params.not_before = to_offset_datetime(now);
+        params.not_after = to_offset_datetime(expires_at);
+        let serial = Uuid::new_v4();
+        params.serial_number = Some(serial.as_u128().into());
+
+        let leaf = Certificate::from_params(params)
+            .map_err(|_| PlatformError::Internal("certificate generation failed"))?;
+        let certificate_pem = leaf
+            .serialize_pem_with_signer(&ca.certificate)
+            .map_err(|_| PlatformError::Internal("certificate signing failed"))?;
+        let chain_pem = ca
+            .certificate
+            .serialize_pem()
+            .map_err(|_| PlatformError::Internal("certificate chain encoding failed"))?;
+        let private_key_pem = leaf.serialize_private_key_pem();
+
+        let record = AgentCertificateRecord {
+            id: Uuid::new_v4(),
+            tenant_id,
+            project_id,
+            agent_id,
+            serial: serial.to_string(),
+            issued_at: now,
+            expires_at,
+            revoked: false,
+            rotated_from,
+            rotated_to: None,
+        };
+        self.certificates.insert_certificate(record.clone())?;
+
+        Ok(AgentCertificateBundle {
+            record,
+            certificate_pem,
+            chain_pem,
+            private_key_pem,
+        })
+    }
+
+    /// Returns the tenant's intermediate CA: from the in-process cache if
+    /// another call already loaded it, else from `CertificateStore`, else by
+    /// generating and persisting a fresh one. The private key never leaves
+    /// this process except through `CertificateStore`.
+    fn tenant_ca(&self, tenant_id: TenantId) -> PlatformResult<Arc<TenantCa>> {
+        if let Some(ca) = self.tenant_cas.read().get(&tenant_id) {
+            return Ok(ca.clone());
+        }
+        if let Some(record) = self.certificates.get_tenant_ca(tenant_id)? {
+            let ca = Arc::new(decode_tenant_ca(&record)?);
+            self.tenant_cas.write().insert(tenant_id, ca.clone());
+            return Ok(ca);
+        }
+
+        let mut params = CertificateParams::new(vec![]);
+        let mut dn = DistinguishedName::new();
+        dn.push(DnType::CommonName, format!("cassandra-tenant-ca-{tenant_id}"));
+        params.distinguished_name = dn;
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let certificate = Certificate::from_params(params)
+            .map_err(|_| PlatformError::Internal("tenant CA generation failed"))?;
+        let record = TenantCaRecord {
+            tenant_id,
+            certificate_pem: certificate
+                .serialize_pem()
+                .map_err(|_| PlatformError::Internal("tenant CA encoding failed"))?,
+            private_key_pem: certificate.serialize_private_key_pem(),
+            created_at: Utc::now(),
+        };
+        match self.certificates.insert_tenant_ca(record) {
+            Ok(()) => {
+                let ca = Arc::new(TenantCa { certificate });
+                self.tenant_cas.write().insert(tenant_id, ca.clone());
+                Ok(ca)
+            }
+            // Another process won the race to create this tenant's CA
+            // first; sign with what it persisted instead of our own, so the
+            // two processes don't diverge on which CA is authoritative.
+            Err(PlatformError::Conflict(_)) => {
+                let record = self
+                    .certificates
+                    .get_tenant_ca(tenant_id)?
+                    .ok_or(PlatformError::Internal("tenant CA vanished after conflict"))?;
+                let ca = Arc::new(decode_tenant_ca(&record)?);
+                self.tenant_cas.write().insert(tenant_id, ca.clone());
+                Ok(ca)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Reconstructs a `Certificate` (including its private key) from a
+/// persisted `TenantCaRecord`, so a loaded or previously-generated CA can
+/// sign new leaf certificates the same way a freshly-minted one does.
+fn decode_tenant_ca(record: &TenantCaRecord) -> PlatformResult<TenantCa> {
+    let key_pair = rcgen::KeyPair::from_pem(&record.private_key_pem)
+        .map_err(|_| PlatformError::Internal("tenant CA key decode failed"))?;
+    let params = CertificateParams::from_ca_cert_pem(&record.certificate_pem, key_pair)
+        .map_err(|_| PlatformError::Internal("tenant CA certificate decode failed"))?;
+    let certificate = Certificate::from_params(params)
+        .map_err(|_| PlatformError::Internal("tenant CA reconstruction failed"))?;
+    Ok(TenantCa { certificate })
+}
+
+fn to_offset_datetime(dt: DateTime<Utc>) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(dt.timestamp())
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+        .replace_nanosecond(dt.timestamp_subsec_nanos())
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::persistence::InMemoryPersistence;
+
+    #[test]
+    fn issues_and_rotates_agent_certificate() {
+        let storage = Arc::new(InMemoryPersistence::new());
+        let certificates: Arc<dyn CertificateStore> = storage;
+        let ca = CertificateAuthority::new(certificates);
+        let tenant_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let agent_id = Uuid::new_v4();
+
+        let bundle = ca
+            .issue_agent_certificate(tenant_id, project_id, agent_id)
+            .unwrap();
+        assert_eq!(bundle.record.tenant_id, tenant_id);
+        assert_eq!(bundle.record.agent_id, agent_id);
+        assert!(bundle.record.rotated_from.is_none());
+        assert!(!bundle.certificate_pem.is_empty());
+        assert!(!bundle.to_bundle_bytes().is_empty());
+
+        let rotated = ca.rotate_agent_certificate(bundle.record.id).unwrap();
+        assert_eq!(rotated.record.rotated_from, Some(bundle.record.id));
+    }
+
+    #[test]
+    fn tenant_ca_survives_process_restart_via_shared_storage() {
+        let storage = Arc::new(InMemoryPersistence::new());
+        let tenant_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+
+        // First process mints the tenant's CA and signs an agent cert.
+        let certificates: Arc<dyn CertificateStore> = storage.clone();
+        let first = CertificateAuthority::new(certificates);
+        let agent_a = first
+            .issue_agent_certificate(tenant_id, project_id, Uuid::new_v4())
+            .unwrap();
+
+        // A fresh `CertificateAuthority` over the same storage (standing in
+        // for a restarted process or a second replica) must load the same
+        // CA rather than minting an unrelated one.
+        let certificates: Arc<dyn CertificateStore> = storage;
+        let second = CertificateAuthority::new(certificates);
+        let agent_b = second
+            .issue_agent_certificate(tenant_id, project_id, Uuid::new_v4())
+            .unwrap();
+
+        assert_eq!(agent_a.chain_pem, agent_b.chain_pem);
+    }
+}
@@ -0,0 +1,262 @@
+//! Seeded concurrency simulation for [`super::provisioning::ProvisioningService`].
+//!
+//! The single linear `tenant_and_agent_flow` test in `provisioning.rs`
+//! exercises one call at a time; it can't catch a race between two actors
+//! hitting the same `RwLock`-guarded state concurrently. This harness spins
+//! up several "actor" threads against one shared, in-memory
+//! `ProvisioningService` and has each perform a seeded, randomly chosen mix
+//! of operations, then checks invariants that must hold no matter how the
+//! threads interleaved.
+//!
+//! True lock-step determinism across real OS threads would need a custom
+//! cooperative scheduler (e.g. `loom`); absent that, this harness pins
+//! everything *except* the OS's actual thread scheduling to the seed —
+//! which operations each actor performs, in what order, and with what
+//! arguments, are all derived from a single seeded RNG, so a failing seed
+//! reproduces the same workload every run even though the precise
+//! low-level interleaving of lock acquisitions can still vary. The
+//! invariants below are chosen to hold under *any* interleaving of that
+//! workload, so the harness still gives meaningful coverage despite that
+//! limitation.
+use super::auth::AuthService;
+use super::ca::CertificateAuthority;
+use super::models::AgentStatus;
+use super::persistence::{
+    AgentStateStore, AgentStore, ApiKeyStore, AuditStore, CertificateStore, IdempotencyStore,
+    InMemoryPersistence, ProjectStore, TenantStore,
+};
+use super::provisioning::{AgentRegistrationOptions, ProvisioningService, TenantCreateRequest};
+use chrono::{DateTime, Duration, Utc};
+use cncommon::auth::Scope;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use uuid::Uuid;
+
+/// Idempotency key every actor races `create_tenant_with_options` against,
+/// always with the same tenant name, so every successful call must resolve
+/// to the exact same tenant id.
+const SHARED_TENANT_KEY: &str = "sim-shared-tenant-key";
+
+/// How long a heartbeat is considered fresh. Generous relative to the
+/// microseconds a single `record_agent_heartbeat` call takes internally,
+/// so the invariant below is a meaningful check of the *intended*
+/// behavior rather than a coin flip against scheduler jitter.
+const HEARTBEAT_TIMEOUT: Duration = Duration::milliseconds(200);
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    CreateSharedTenant,
+    RegisterAgent,
+    Heartbeat,
+    Sweep,
+}
+
+fn pick_operation(rng: &mut StdRng) -> Operation {
+    match rng.gen_range(0..4) {
+        0 => Operation::CreateSharedTenant,
+        1 => Operation::RegisterAgent,
+        2 => Operation::Heartbeat,
+        _ => Operation::Sweep,
+    }
+}
+
+fn build_provisioning(storage: &Arc<InMemoryPersistence>) -> ProvisioningService {
+    let tenant_store: Arc<dyn TenantStore> = storage.clone();
+    let project_store: Arc<dyn ProjectStore> = storage.clone();
+    let agent_store: Arc<dyn AgentStore> = storage.clone();
+    let agent_state_store: Arc<dyn AgentStateStore> = storage.clone();
+    let api_key_store: Arc<dyn ApiKeyStore> = storage.clone();
+    let audit_store: Arc<dyn AuditStore> = storage.clone();
+    let certificate_store: Arc<dyn CertificateStore> = storage.clone();
+    let idempotency_store: Arc<dyn IdempotencyStore> = storage.clone();
+    let auth = Arc::new(AuthService::new(
+        tenant_store.clone(),
+        api_key_store,
+        audit_store.clone(),
+        b"concurrency-sim-secret".to_vec(),
+    ));
+    ProvisioningService::new(
+        tenant_store,
+        project_store,
+        agent_store,
+        agent_state_store,
+        auth,
+        audit_store,
+        CertificateAuthority::new(certificate_store),
+        idempotency_store,
+    )
+    .with_heartbeat_timeout(HEARTBEAT_TIMEOUT)
+}
+
+/// Runs `actor_count` concurrent actors, each performing `ops_per_actor`
+/// randomly chosen operations derived from `seed`, against one shared
+/// `ProvisioningService`. Panics with the seed and the full operation
+/// trace if any invariant is violated.
+fn run_simulation(seed: u64, actor_count: usize, ops_per_actor: usize) {
+    let storage = Arc::new(InMemoryPersistence::new());
+    let provisioning = Arc::new(build_provisioning(&storage));
+
+    // One tenant/project pre-exists so actors have somewhere to register
+    // agents; the shared-idempotency-key race is exercised separately, by
+    // every actor's `CreateSharedTenant` operations racing each other.
+    let base_tenant = provisioning.create_tenant("sim-base-tenant").unwrap();
+    let base_project = provisioning
+        .create_project(base_tenant.id, "sim-base-project")
+        .unwrap();
+
+    let trace: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let barrier = Arc::new(Barrier::new(actor_count));
+    // Recorded across *all* actors, unlike `shared_tenant_id` below (which
+    // only ever compares an actor's own sequential calls against
+    // themselves and so can never observe a cross-actor race). This is
+    // what actually catches two actors' concurrent `CreateSharedTenant`
+    // calls resolving to different tenants.
+    let observed_shared_tenant_ids: Arc<Mutex<Vec<Uuid>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let handles: Vec<_> = (0..actor_count)
+        .map(|actor_id| {
+            let provisioning = provisioning.clone();
+            let trace = trace.clone();
+            let barrier = barrier.clone();
+            let observed_shared_tenant_ids = observed_shared_tenant_ids.clone();
+            let project_id = base_project.id;
+            let tenant_id = base_tenant.id;
+            // Each actor's RNG is derived from the shared seed and its own
+            // index, so the same seed always assigns the same operation
+            // sequence to the same actor regardless of thread scheduling.
+            let mut rng = StdRng::seed_from_u64(
+                seed ^ (actor_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            );
+            thread::spawn(move || -> Vec<(Uuid, DateTime<Utc>)> {
+                barrier.wait();
+                let mut local_trace = Vec::new();
+                let mut shared_tenant_id: Option<Uuid> = None;
+                let mut owned_agents: Vec<(Uuid, DateTime<Utc>)> = Vec::new();
+                for round in 0..ops_per_actor {
+                    match pick_operation(&mut rng) {
+                        Operation::CreateSharedTenant => {
+                            let mut request = TenantCreateRequest::new("Shared Tenant");
+                            request.idempotency_key = Some(SHARED_TENANT_KEY.to_string());
+                            match provisioning.create_tenant_with_options(request) {
+                                Ok(bundle) => {
+                                    if let Some(expected) = shared_tenant_id {
+                                        assert_eq!(
+                                            expected, bundle.tenant.id,
+                                            "actor {actor_id} round {round}: shared idempotency \
+                                             key resolved to two different tenant ids"
+                                        );
+                                    }
+                                    shared_tenant_id = Some(bundle.tenant.id);
+                                    observed_shared_tenant_ids.lock().unwrap().push(bundle.tenant.id);
+                                    local_trace.push(format!(
+                                        "actor{actor_id}#{round}: create_shared_tenant -> {}",
+                                        bundle.tenant.id
+                                    ));
+                                }
+                                Err(err) => panic!(
+                                    "actor {actor_id} round {round}: shared idempotency key \
+                                     unexpectedly conflicted: {err:?}"
+                                ),
+                            }
+                        }
+                        Operation::RegisterAgent => {
+                            let hostname = format!("actor-{actor_id}-agent-{round}");
+                            let mut options = AgentRegistrationOptions::default();
+                            options.bootstrap_commands = vec!["install.sh".into()];
+                            let provisioned = provisioning
+                                .register_agent_with_options(
+                                    tenant_id,
+                                    project_id,
+                                    hostname.clone(),
+                                    options,
+                                )
+                                .unwrap();
+                            assert!(
+                                provisioned
+                                    .api_key
+                                    .scopes
+                                    .iter()
+                                    .any(|s| *s == Scope::Custom(format!("project:{project_id}"))),
+                                "actor {actor_id} round {round}: {hostname}'s api key is missing \
+                                 its project scope"
+                            );
+                            owned_agents.push((provisioned.agent.id, Utc::now()));
+                            local_trace.push(format!(
+                                "actor{actor_id}#{round}: register_agent -> {}",
+                                provisioned.agent.id
+                            ));
+                        }
+                        Operation::Heartbeat => {
+                            if let Some((agent_id, _)) = owned_agents.last().copied() {
+                                provisioning.record_agent_heartbeat(agent_id, None).unwrap();
+                                let last = owned_agents.last_mut().unwrap();
+                                last.1 = Utc::now();
+                                local_trace
+                                    .push(format!("actor{actor_id}#{round}: heartbeat {agent_id}"));
+                            }
+                        }
+                        Operation::Sweep => {
+                            let suspended = provisioning.sweep_inactive_agents().unwrap();
+                            local_trace.push(format!(
+                                "actor{actor_id}#{round}: sweep -> {} suspended",
+                                suspended.len()
+                            ));
+                        }
+                    }
+                }
+                trace.lock().unwrap().extend(local_trace);
+                owned_agents
+            })
+        })
+        .collect();
+
+    let owned_agents: Vec<(Uuid, DateTime<Utc>)> =
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+
+    // Invariant: every actor's `CreateSharedTenant` call, no matter which
+    // thread it ran on or how the calls interleaved, must resolve to the
+    // same tenant id — the whole point of racing them against one shared
+    // idempotency key.
+    let observed_shared_tenant_ids = observed_shared_tenant_ids.lock().unwrap();
+    if let Some(first) = observed_shared_tenant_ids.first() {
+        for id in observed_shared_tenant_ids.iter() {
+            assert_eq!(
+                *first, *id,
+                "seed {seed}: shared idempotency key resolved to two different tenant ids \
+                 across actors: {observed_shared_tenant_ids:?}"
+            );
+        }
+    }
+    drop(observed_shared_tenant_ids);
+
+    // Invariant: an agent whose most recent heartbeat (as observed by the
+    // actor that sent it) is still within `HEARTBEAT_TIMEOUT` must never
+    // show up as suspended by any sweep that ran afterward.
+    for (agent_id, last_heartbeat) in &owned_agents {
+        let history = provisioning.agent_state_history(*agent_id).unwrap();
+        for event in history {
+            if event.to == AgentStatus::Suspended
+                && event.at > *last_heartbeat
+                && event.at - *last_heartbeat < HEARTBEAT_TIMEOUT
+            {
+                let full_trace = trace.lock().unwrap().join("\n");
+                panic!(
+                    "seed {seed}: agent {agent_id} was suspended at {} only {}ms after a \
+                     heartbeat at {}, within the {}ms heartbeat timeout\n\nfull trace:\n{full_trace}",
+                    event.at,
+                    (event.at - *last_heartbeat).num_milliseconds(),
+                    last_heartbeat,
+                    HEARTBEAT_TIMEOUT.num_milliseconds(),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn provisioning_concurrency_invariants_hold() {
+    for seed in 0..8 {
+        run_simulation(seed, 6, 25);
+    }
+}
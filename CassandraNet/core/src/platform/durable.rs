@@ -0,0 +1,455 @@
+//! Write-ahead-logged, snapshotting persistence backend for
+//! `OrchestrationStore`/`ModerationStore`/`MessagingStore`.
+//!
+//! `InMemoryPersistence` loses everything on restart, and not every
+//! deployment wants the `redb` dependency `EmbeddedPersistence` brings in or
+//! an external Postgres instance. `DurablePersistence` instead wraps an
+//! `InMemoryPersistence` directly: every mutating call on the three traits
+//! above is applied to that in-memory state and then appended to `wal.log`
+//! as one JSON-encoded [`Mutation`] line, so the same trait surface
+//! (`create_content`, `enqueue_message`, `list_messages`, etc.) serves both
+//! backends without a caller ever knowing which one it's talking to.
+//!
+//! `open` rebuilds state by loading `snapshot.json` (if present) and
+//! replaying the `wal.log` lines written after it. Replay stops at the
+//! first line that fails to decode — a write torn by a crash mid-append
+//! always lands at the end of the file — so a corrupt trailing record
+//! can't poison recovery of everything before it. A snapshot is taken every
+//! `snapshot_every` applied mutations by serializing the full durable state
+//! to `snapshot.json` and truncating `wal.log`, bounding how much of the
+//! log a future `open` ever has to replay.
+use super::error::{PlatformError, PlatformResult};
+use super::models::*;
+use super::persistence::{
+    AssignmentLifecycleConfig, InMemoryPersistence, MessagingStore, ModerationStore,
+    OrchestrationStore,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// One durable mutation, covering every mutating call on
+/// `OrchestrationStore`/`ModerationStore`/`MessagingStore` whose effect
+/// needs to survive a restart. `claim_messages`/`extend_lease`/
+/// `nack_message` and `claim_pending`/`requeue_stale` are deliberately
+/// excluded: those hand out or revoke a lease tied to a specific consumer
+/// process, and a consumer that was mid-lease when the store crashed no
+/// longer holds anything to resume — forgetting an in-flight lease on
+/// restart is the same fail-safe behavior a lapsed lease already gives
+/// `claim_messages`, not a gap in durability.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Mutation {
+    CreateAssignment(NewAssignment),
+    UpdateAssignmentStatus {
+        id: AssignmentId,
+        status: WorkStatus,
+        status_message: Option<String>,
+    },
+    HeartbeatAssignment {
+        id: AssignmentId,
+    },
+    CreateContent(NewModeratedContent),
+    UpdateContentState {
+        id: ContentId,
+        state: ModerationState,
+        reason: Option<String>,
+        actor_id: uuid::Uuid,
+    },
+    CreateReport {
+        content_id: ContentId,
+        reporter_id: uuid::Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    },
+    EnqueueMessage(NewMessageRecord),
+    AckMessage {
+        topic: String,
+        id: MessageId,
+    },
+    // `chrono::Duration` isn't `Serialize`, so the two sweeps below log
+    // their span as plain seconds instead.
+    EvictExpiredMessages {
+        now: DateTime<Utc>,
+        ttl_seconds: i64,
+    },
+    ExpirePendingModeration {
+        now: DateTime<Utc>,
+        deadline_seconds: i64,
+        to_state: ModerationState,
+    },
+}
+
+pub struct DurablePersistence {
+    inner: InMemoryPersistence,
+    log: Mutex<File>,
+    log_path: PathBuf,
+    snapshot_path: PathBuf,
+    mutations_since_snapshot: Mutex<u64>,
+    snapshot_every: u64,
+}
+
+impl DurablePersistence {
+    /// Opens (creating if necessary) the durable store rooted at `dir`,
+    /// replaying `dir/wal.log` on top of `dir/snapshot.json` if either
+    /// exists, and snapshots again every `snapshot_every` mutations applied
+    /// from here on.
+    pub fn open(dir: impl AsRef<Path>, snapshot_every: u64) -> PlatformResult<Self> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|_| PlatformError::Internal("durable store directory"))?;
+        let snapshot_path = dir.join("snapshot.json");
+        let log_path = dir.join("wal.log");
+
+        let inner = match fs::read(&snapshot_path) {
+            Ok(bytes) => {
+                let snapshot = serde_json::from_slice(&bytes)
+                    .map_err(|_| PlatformError::Internal("corrupt durable snapshot"))?;
+                InMemoryPersistence::restore_durable_state(snapshot)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => InMemoryPersistence::new(),
+            Err(_) => return Err(PlatformError::Internal("durable snapshot")),
+        };
+
+        let replayed = match File::open(&log_path) {
+            Ok(file) => replay(&inner, BufReader::new(file)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(_) => return Err(PlatformError::Internal("durable log")),
+        };
+
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .map_err(|_| PlatformError::Internal("durable log"))?;
+
+        Ok(Self {
+            inner,
+            log: Mutex::new(log),
+            log_path,
+            snapshot_path,
+            mutations_since_snapshot: Mutex::new(replayed),
+            snapshot_every,
+        })
+    }
+
+    fn append(&self, mutation: &Mutation) -> PlatformResult<()> {
+        let mut line = serde_json::to_vec(mutation)
+            .map_err(|_| PlatformError::Internal("unserializable durable mutation"))?;
+        line.push(b'\n');
+        {
+            let mut log = self.log.lock();
+            log.write_all(&line)
+                .and_then(|_| log.flush())
+                .map_err(|_| PlatformError::Internal("durable log"))?;
+        }
+        let snapshot_due = {
+            let mut count = self.mutations_since_snapshot.lock();
+            *count += 1;
+            *count >= self.snapshot_every
+        };
+        if snapshot_due {
+            self.snapshot()?;
+        }
+        Ok(())
+    }
+
+    /// Serializes the full durable state to `snapshot.json` and truncates
+    /// `wal.log`, so a future `open` only has to replay whatever is
+    /// appended after this point.
+    fn snapshot(&self) -> PlatformResult<()> {
+        let snapshot = self.inner.snapshot_durable_state();
+        let bytes = serde_json::to_vec(&snapshot)
+            .map_err(|_| PlatformError::Internal("unserializable durable snapshot"))?;
+        fs::write(&self.snapshot_path, bytes)
+            .map_err(|_| PlatformError::Internal("durable snapshot"))?;
+        let mut log = self.log.lock();
+        *log = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.log_path)
+            .map_err(|_| PlatformError::Internal("durable log"))?;
+        *self.mutations_since_snapshot.lock() = 0;
+        Ok(())
+    }
+}
+
+/// Replays WAL lines onto `inner`, stopping at the first line that fails to
+/// decode as a [`Mutation`] rather than erroring the whole recovery, since
+/// a crash only ever tears the write in progress at the moment it died —
+/// everything before that line is intact. Returns how many mutations were
+/// applied, to seed `mutations_since_snapshot`.
+fn replay(inner: &InMemoryPersistence, reader: BufReader<File>) -> u64 {
+    let mut applied = 0;
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        if line.is_empty() {
+            continue;
+        }
+        let Ok(mutation) = serde_json::from_str::<Mutation>(&line) else {
+            break;
+        };
+        apply(inner, mutation);
+        applied += 1;
+    }
+    applied
+}
+
+fn apply(inner: &InMemoryPersistence, mutation: Mutation) {
+    let _ = match mutation {
+        Mutation::CreateAssignment(input) => inner.apply_create_assignment(input).map(|_| ()),
+        Mutation::UpdateAssignmentStatus {
+            id,
+            status,
+            status_message,
+        } => inner
+            .apply_update_assignment_status(id, status, status_message)
+            .map(|_| ()),
+        Mutation::HeartbeatAssignment { id } => inner.apply_heartbeat_assignment(id),
+        Mutation::CreateContent(input) => inner.apply_create_content(input).map(|_| ()),
+        Mutation::UpdateContentState {
+            id,
+            state,
+            reason,
+            actor_id,
+        } => inner
+            .apply_update_content_state(id, state, reason, actor_id)
+            .map(|_| ()),
+        Mutation::CreateReport {
+            content_id,
+            reporter_id,
+            category,
+            detail,
+        } => inner
+            .apply_create_report(content_id, reporter_id, category, detail)
+            .map(|_| ()),
+        Mutation::EnqueueMessage(input) => inner.apply_enqueue_message(input).map(|_| ()),
+        Mutation::AckMessage { topic, id } => inner.apply_ack_message(&topic, id),
+        Mutation::EvictExpiredMessages { now, ttl_seconds } => {
+            inner.apply_evict_expired_messages(now, chrono::Duration::seconds(ttl_seconds));
+            Ok(())
+        }
+        Mutation::ExpirePendingModeration {
+            now,
+            deadline_seconds,
+            to_state,
+        } => {
+            inner.apply_expire_pending_moderation(
+                now,
+                chrono::Duration::seconds(deadline_seconds),
+                to_state,
+            );
+            Ok(())
+        }
+    };
+}
+
+#[async_trait]
+impl OrchestrationStore for DurablePersistence {
+    async fn create_assignment(&self, input: NewAssignment) -> PlatformResult<WorkAssignment> {
+        let assignment = self.inner.apply_create_assignment(input.clone())?;
+        self.append(&Mutation::CreateAssignment(input))?;
+        Ok(assignment)
+    }
+
+    async fn update_assignment_status(
+        &self,
+        id: AssignmentId,
+        status: WorkStatus,
+        status_message: Option<String>,
+    ) -> PlatformResult<WorkAssignment> {
+        let assignment = self.inner.apply_update_assignment_status(
+            id,
+            status.clone(),
+            status_message.clone(),
+        )?;
+        self.append(&Mutation::UpdateAssignmentStatus {
+            id,
+            status,
+            status_message,
+        })?;
+        Ok(assignment)
+    }
+
+    async fn list_assignments(
+        &self,
+        query: AssignmentQuery,
+    ) -> PlatformResult<Vec<WorkAssignment>> {
+        self.inner.list_assignments(query).await
+    }
+
+    async fn heartbeat_assignment(&self, id: AssignmentId) -> PlatformResult<()> {
+        self.inner.apply_heartbeat_assignment(id)?;
+        self.append(&Mutation::HeartbeatAssignment { id })
+    }
+
+    async fn claim_pending(&self, agent_id: AgentId, max: u32) -> PlatformResult<Vec<WorkAssignment>> {
+        self.inner.claim_pending(agent_id, max).await
+    }
+
+    async fn requeue_stale(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+        config: &AssignmentLifecycleConfig,
+    ) -> PlatformResult<Vec<WorkAssignment>> {
+        self.inner.requeue_stale(now, ttl, config).await
+    }
+}
+
+#[async_trait]
+impl ModerationStore for DurablePersistence {
+    async fn create_content(&self, input: NewModeratedContent) -> PlatformResult<ModeratedContent> {
+        let content = self.inner.apply_create_content(input.clone())?;
+        self.append(&Mutation::CreateContent(input))?;
+        Ok(content)
+    }
+
+    async fn update_content_state(
+        &self,
+        id: ContentId,
+        state: ModerationState,
+        reason: Option<String>,
+        actor_id: uuid::Uuid,
+    ) -> PlatformResult<ModeratedContent> {
+        let content = self
+            .inner
+            .apply_update_content_state(id, state.clone(), reason.clone(), actor_id)?;
+        self.append(&Mutation::UpdateContentState {
+            id,
+            state,
+            reason,
+            actor_id,
+        })?;
+        Ok(content)
+    }
+
+    async fn list_content_events(
+        &self,
+        content_id: ContentId,
+    ) -> PlatformResult<Vec<ModerationEvent>> {
+        self.inner.list_content_events(content_id).await
+    }
+
+    async fn list_content(&self, query: ModerationQuery) -> PlatformResult<Vec<ModeratedContent>> {
+        self.inner.list_content(query).await
+    }
+
+    async fn create_report(
+        &self,
+        content_id: ContentId,
+        reporter_id: uuid::Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    ) -> PlatformResult<ModerationReport> {
+        let report = self.inner.apply_create_report(
+            content_id,
+            reporter_id,
+            category.clone(),
+            detail.clone(),
+        )?;
+        self.append(&Mutation::CreateReport {
+            content_id,
+            reporter_id,
+            category,
+            detail,
+        })?;
+        Ok(report)
+    }
+
+    async fn expire_pending_moderation(
+        &self,
+        now: DateTime<Utc>,
+        deadline: chrono::Duration,
+        to_state: ModerationState,
+    ) -> PlatformResult<Vec<ModeratedContent>> {
+        let expired = self
+            .inner
+            .apply_expire_pending_moderation(now, deadline, to_state.clone());
+        self.append(&Mutation::ExpirePendingModeration {
+            now,
+            deadline_seconds: deadline.num_seconds(),
+            to_state,
+        })?;
+        Ok(expired)
+    }
+
+    async fn list_audit(&self, content_id: ContentId) -> PlatformResult<Vec<ModerationAuditEntry>> {
+        self.inner.list_audit(content_id).await
+    }
+
+    async fn audit_chain_head(&self, content_id: ContentId) -> PlatformResult<Option<String>> {
+        self.inner.audit_chain_head(content_id).await
+    }
+}
+
+#[async_trait]
+impl MessagingStore for DurablePersistence {
+    async fn enqueue_message(&self, input: NewMessageRecord) -> PlatformResult<MessageRecord> {
+        let message = self.inner.apply_enqueue_message(input.clone())?;
+        self.append(&Mutation::EnqueueMessage(input))?;
+        Ok(message)
+    }
+
+    async fn list_messages(&self, query: MessageQuery) -> PlatformResult<Vec<MessageRecord>> {
+        self.inner.list_messages(query).await
+    }
+
+    async fn claim_messages(
+        &self,
+        topic: &str,
+        consumer: &str,
+        max: u32,
+        visibility_timeout: chrono::Duration,
+    ) -> PlatformResult<Vec<MessageRecord>> {
+        self.inner
+            .claim_messages(topic, consumer, max, visibility_timeout)
+            .await
+    }
+
+    async fn extend_lease(
+        &self,
+        topic: &str,
+        id: MessageId,
+        extension: chrono::Duration,
+    ) -> PlatformResult<()> {
+        self.inner.extend_lease(topic, id, extension).await
+    }
+
+    async fn ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        self.inner.apply_ack_message(topic, id)?;
+        self.append(&Mutation::AckMessage {
+            topic: topic.to_string(),
+            id,
+        })
+    }
+
+    async fn nack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        self.inner.nack_message(topic, id).await
+    }
+
+    async fn poll_topic(
+        &self,
+        query: MessageQuery,
+        since_token: u64,
+        timeout: chrono::Duration,
+    ) -> PlatformResult<(Vec<MessageRecord>, u64)> {
+        self.inner.poll_topic(query, since_token, timeout).await
+    }
+
+    async fn evict_expired_messages(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) -> PlatformResult<u64> {
+        let evicted = self.inner.apply_evict_expired_messages(now, ttl);
+        self.append(&Mutation::EvictExpiredMessages {
+            now,
+            ttl_seconds: ttl.num_seconds(),
+        })?;
+        Ok(evicted)
+    }
+}
@@ -0,0 +1,1780 @@
+//! Embedded, single-process persistence backend built on [`redb`].
+//!
+//! `InMemoryPersistence` loses everything on restart and Postgres
+//! (`feature = "db"`) needs an external database, so neither fits a
+//! single-node or edge deployment that still wants crash-durable storage.
+//! `EmbeddedPersistence` implements every store trait against one on-disk
+//! `redb::Database`, with one logical table per entity plus secondary-index
+//! tables mirroring `InMemoryPersistence`'s own helper maps
+//! (`api_keys_by_prefix`, `task_queue`, `messages_by_topic`) so the lookups
+//! built on top of those stay cheap instead of falling back to a full scan.
+//!
+//! Every value is stored JSON-encoded; this keeps the table definitions
+//! simple (`&[u8] -> &[u8]` throughout) at the cost of a little density
+//! compared to a binary encoding, which doesn't matter for the single-node
+//! deployments this backend targets.
+use super::error::{PlatformError, PlatformResult};
+use super::models::*;
+use super::persistence::{
+    moderation_audit_hash, AgentStateStore, AgentStore, ApiKeyStore, AuditQuery, AuditStore,
+    CertificateStore, ContentStore, IdempotencyRecord, IdempotencyStore, MessagingStore,
+    ModerationStore, OrchestrationStore, ProjectStore, TaskStore, TenantStore, WorkflowStore,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const TENANTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("tenants");
+const PROJECTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("projects");
+const AGENTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("agents");
+const API_KEYS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("api_keys");
+const API_KEYS_BY_PREFIX: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("api_keys_by_prefix");
+const CERTIFICATES: TableDefinition<&[u8], &[u8]> = TableDefinition::new("certificates");
+const TENANT_CAS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("tenant_cas");
+const AUDIT_EVENTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("audit_events");
+const TASKS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("tasks");
+const TASK_QUEUE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("task_queue");
+const WORKFLOWS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("workflows");
+const UPLOAD_SESSIONS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("upload_sessions");
+const CONTENT_METADATA: TableDefinition<&[u8], &[u8]> = TableDefinition::new("content_metadata");
+const ASSIGNMENTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("assignments");
+const MODERATION_CONTENT: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("moderation_content");
+const MESSAGES: TableDefinition<&[u8], &[u8]> = TableDefinition::new("messages");
+const MESSAGES_BY_TOPIC: TableDefinition<&[u8], &[u8]> = TableDefinition::new("messages_by_topic");
+const LIFECYCLE_POLICIES: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("lifecycle_policies");
+const MESSAGE_DEAD_LETTERS: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("message_dead_letters");
+const MODERATION_EVENTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("moderation_events");
+const MODERATION_REPORTS: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("moderation_reports");
+const MODERATION_AUDIT: TableDefinition<&[u8], &[u8]> = TableDefinition::new("moderation_audit");
+const AGENT_STATE_EVENTS: TableDefinition<&[u8], &[u8]> =
+    TableDefinition::new("agent_state_events");
+const IDEMPOTENCY: TableDefinition<&[u8], &[u8]> = TableDefinition::new("idempotency");
+
+const ALL_TABLES: &[TableDefinition<&[u8], &[u8]>] = &[
+    TENANTS,
+    PROJECTS,
+    AGENTS,
+    API_KEYS,
+    API_KEYS_BY_PREFIX,
+    CERTIFICATES,
+    TENANT_CAS,
+    AUDIT_EVENTS,
+    TASKS,
+    TASK_QUEUE,
+    WORKFLOWS,
+    UPLOAD_SESSIONS,
+    CONTENT_METADATA,
+    ASSIGNMENTS,
+    MODERATION_CONTENT,
+    MESSAGES,
+    MESSAGES_BY_TOPIC,
+    LIFECYCLE_POLICIES,
+    MESSAGE_DEAD_LETTERS,
+    MODERATION_EVENTS,
+    MODERATION_REPORTS,
+    MODERATION_AUDIT,
+    AGENT_STATE_EVENTS,
+    IDEMPOTENCY,
+];
+
+fn uuid_key(id: Uuid) -> [u8; 16] {
+    *id.as_bytes()
+}
+
+/// Big-endian encoding of a signed timestamp that sorts the same way the
+/// timestamps themselves compare, so a byte-key range scan over a table
+/// keyed by one of these is already in chronological order.
+fn order_preserving_micros(micros: i64) -> [u8; 8] {
+    ((micros as u64) ^ (1u64 << 63)).to_be_bytes()
+}
+
+/// Approximates Postgres's `ts_rank_cd` for `list_content_metadata`: counts
+/// how many `tokens` appear in `filename`/`labels`/`attributes`, weighting a
+/// filename hit higher since that's the field users actually read, so
+/// relative ordering between results matches the Postgres backend even
+/// though the exact scores don't.
+fn content_relevance_score(item: &ContentMetadata, tokens: &[String]) -> f32 {
+    const FILENAME_WEIGHT: f32 = 2.0;
+    let filename_lower = item.filename.to_ascii_lowercase();
+    let mut score = 0.0;
+    for token in tokens {
+        if filename_lower.contains(token.as_str()) {
+            score += FILENAME_WEIGHT;
+        }
+        if item
+            .labels
+            .iter()
+            .any(|label| label.to_ascii_lowercase().contains(token.as_str()))
+        {
+            score += 1.0;
+        }
+        if item.attributes.iter().any(|(k, v)| {
+            k.to_ascii_lowercase().contains(token.as_str()) || v.to_ascii_lowercase().contains(token.as_str())
+        }) {
+            score += 1.0;
+        }
+    }
+    score
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> PlatformResult<T> {
+    serde_json::from_slice(bytes).map_err(|_| PlatformError::Internal("corrupt embedded record"))
+}
+
+fn encode<T: Serialize>(value: &T) -> PlatformResult<Vec<u8>> {
+    serde_json::to_vec(value).map_err(|_| PlatformError::Internal("unserializable record"))
+}
+
+/// Single-node, crash-durable store backing every `*Store` trait. See the
+/// module docs for the table layout.
+#[derive(Clone)]
+pub struct EmbeddedPersistence {
+    db: Arc<Database>,
+}
+
+impl EmbeddedPersistence {
+    /// Opens (creating if necessary) the `redb` database file at `path` and
+    /// ensures every table this backend uses exists.
+    pub fn open(path: impl AsRef<Path>) -> PlatformResult<Self> {
+        let db = Database::create(path).map_err(|_| PlatformError::Internal("embedded store"))?;
+        let txn = db
+            .begin_write()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        for table in ALL_TABLES {
+            txn.open_table(*table)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+        }
+        txn.commit()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn get<T: DeserializeOwned>(
+        &self,
+        table: TableDefinition<&[u8], &[u8]>,
+        key: &[u8],
+    ) -> PlatformResult<Option<T>> {
+        let txn = self
+            .db
+            .begin_read()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let table = txn
+            .open_table(table)
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        match table
+            .get(key)
+            .map_err(|_| PlatformError::Internal("embedded store"))?
+        {
+            Some(guard) => Ok(Some(decode(guard.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put<T: Serialize>(
+        &self,
+        table: TableDefinition<&[u8], &[u8]>,
+        key: &[u8],
+        value: &T,
+    ) -> PlatformResult<()> {
+        let bytes = encode(value)?;
+        let txn = self
+            .db
+            .begin_write()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        {
+            let mut table = txn
+                .open_table(table)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            table
+                .insert(key, bytes.as_slice())
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+        }
+        txn.commit()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(())
+    }
+
+    /// Inserts `value` at `key` only if the table has nothing there yet,
+    /// returning the pre-existing value instead if it does. Both the read
+    /// and the write happen inside one `redb` write transaction, so a
+    /// concurrent caller racing the same key can never observe an empty slot
+    /// at the same moment this one does.
+    fn put_if_absent<T: Serialize + DeserializeOwned>(
+        &self,
+        table: TableDefinition<&[u8], &[u8]>,
+        key: &[u8],
+        value: &T,
+    ) -> PlatformResult<Option<T>> {
+        let bytes = encode(value)?;
+        let txn = self
+            .db
+            .begin_write()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let existing: Option<T> = {
+            let mut table = txn
+                .open_table(table)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            let existing = match table
+                .get(key)
+                .map_err(|_| PlatformError::Internal("embedded store"))?
+            {
+                Some(guard) => Some(decode(guard.value())?),
+                None => None,
+            };
+            if existing.is_none() {
+                table
+                    .insert(key, bytes.as_slice())
+                    .map_err(|_| PlatformError::Internal("embedded store"))?;
+            }
+            existing
+        };
+        txn.commit()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(existing)
+    }
+
+    fn contains(&self, table: TableDefinition<&[u8], &[u8]>, key: &[u8]) -> PlatformResult<bool> {
+        let txn = self
+            .db
+            .begin_read()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let table = txn
+            .open_table(table)
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(table
+            .get(key)
+            .map_err(|_| PlatformError::Internal("embedded store"))?
+            .is_some())
+    }
+
+    fn scan<T: DeserializeOwned>(&self, table: TableDefinition<&[u8], &[u8]>) -> PlatformResult<Vec<T>> {
+        let txn = self
+            .db
+            .begin_read()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let table = txn
+            .open_table(table)
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let mut out = Vec::new();
+        for entry in table
+            .iter()
+            .map_err(|_| PlatformError::Internal("embedded store"))?
+        {
+            let (_, value) = entry.map_err(|_| PlatformError::Internal("embedded store"))?;
+            out.push(decode(value.value())?);
+        }
+        Ok(out)
+    }
+
+    fn index_put(
+        &self,
+        table: TableDefinition<&[u8], &[u8]>,
+        key: &[u8],
+        value: &[u8],
+    ) -> PlatformResult<()> {
+        let txn = self
+            .db
+            .begin_write()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        {
+            let mut table = txn
+                .open_table(table)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            table
+                .insert(key, value)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+        }
+        txn.commit()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(())
+    }
+
+    fn index_remove(&self, table: TableDefinition<&[u8], &[u8]>, key: &[u8]) -> PlatformResult<()> {
+        let txn = self
+            .db
+            .begin_write()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        {
+            let mut table = txn
+                .open_table(table)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            table
+                .remove(key)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+        }
+        txn.commit()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(())
+    }
+
+    /// Every message id in `MESSAGES_BY_TOPIC` under `topic`'s prefix, in
+    /// publish order.
+    fn topic_message_ids(&self, topic: &str) -> PlatformResult<Vec<MessageId>> {
+        let prefix = topic_prefix(topic);
+        let txn = self
+            .db
+            .begin_read()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let index = txn
+            .open_table(MESSAGES_BY_TOPIC)
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let mut message_ids = Vec::new();
+        for entry in index
+            .range(prefix.as_slice()..)
+            .map_err(|_| PlatformError::Internal("embedded store"))?
+        {
+            let (key, _) = entry.map_err(|_| PlatformError::Internal("embedded store"))?;
+            let key = key.value();
+            if !key.starts_with(prefix.as_slice()) {
+                break;
+            }
+            message_ids.push(message_id_from_topic_key(key));
+        }
+        Ok(message_ids)
+    }
+
+    /// `content_id`'s hash chain, oldest first. Used to answer
+    /// `ModerationStore::list_audit`/`audit_chain_head`.
+    fn audit_chain(&self, content_id: ContentId) -> PlatformResult<Vec<ModerationAuditEntry>> {
+        let mut chain: Vec<ModerationAuditEntry> = self
+            .scan::<ModerationAuditEntry>(MODERATION_AUDIT)?
+            .into_iter()
+            .filter(|entry| entry.content_id == content_id)
+            .collect();
+        chain.sort_by_key(|entry| entry.sequence);
+        Ok(chain)
+    }
+
+    /// Computes and inserts the next hash-chain link for `content_id` inside
+    /// a single `redb` write transaction that also reads the current tail —
+    /// the same read-then-write-in-one-txn pattern `put_if_absent` uses. Two
+    /// concurrent callers can therefore never both observe the same tail and
+    /// commit an entry with the same `sequence`/`prev_hash`, which is what
+    /// let the chain silently fork when the tail read and the entry write
+    /// were separate transactions.
+    fn append_audit_entry(
+        &self,
+        content_id: ContentId,
+        from_state: ModerationState,
+        to_state: ModerationState,
+        reason: Option<String>,
+        actor_id: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> PlatformResult<ModerationAuditEntry> {
+        let txn = self
+            .db
+            .begin_write()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        let entry = {
+            let mut table = txn
+                .open_table(MODERATION_AUDIT)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            let mut chain: Vec<ModerationAuditEntry> = Vec::new();
+            for item in table
+                .iter()
+                .map_err(|_| PlatformError::Internal("embedded store"))?
+            {
+                let (_, value) = item.map_err(|_| PlatformError::Internal("embedded store"))?;
+                let candidate: ModerationAuditEntry = decode(value.value())?;
+                if candidate.content_id == content_id {
+                    chain.push(candidate);
+                }
+            }
+            chain.sort_by_key(|entry| entry.sequence);
+            let prev_hash = chain
+                .last()
+                .map(|entry| entry.hash.clone())
+                .unwrap_or_else(|| MODERATION_AUDIT_GENESIS_HASH.to_string());
+            let sequence = chain.len() as u64 + 1;
+            let hash = moderation_audit_hash(
+                &prev_hash,
+                content_id,
+                sequence,
+                &from_state,
+                &to_state,
+                &reason,
+                actor_id,
+                created_at,
+            );
+            let entry = ModerationAuditEntry {
+                id: Uuid::new_v4(),
+                content_id,
+                sequence,
+                from_state,
+                to_state,
+                reason,
+                actor_id,
+                created_at,
+                hash,
+            };
+            let bytes = encode(&entry)?;
+            table
+                .insert(uuid_key(entry.id).as_slice(), bytes.as_slice())
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            entry
+        };
+        txn.commit()
+            .map_err(|_| PlatformError::Internal("embedded store"))?;
+        Ok(entry)
+    }
+}
+
+impl TenantStore for EmbeddedPersistence {
+    fn insert_tenant(&self, tenant: Tenant) -> PlatformResult<()> {
+        let key = uuid_key(tenant.id);
+        if self.contains(TENANTS, &key)? {
+            return Err(PlatformError::Conflict("tenant"));
+        }
+        self.put(TENANTS, &key, &tenant)
+    }
+
+    fn get_tenant(&self, id: TenantId) -> PlatformResult<Option<Tenant>> {
+        self.get(TENANTS, &uuid_key(id))
+    }
+
+    fn list_tenants(&self) -> PlatformResult<Vec<Tenant>> {
+        let mut tenants: Vec<Tenant> = self.scan(TENANTS)?;
+        tenants.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tenants)
+    }
+
+    fn delete_tenant(&self, id: TenantId) -> PlatformResult<()> {
+        self.index_remove(TENANTS, &uuid_key(id))
+    }
+}
+
+impl ProjectStore for EmbeddedPersistence {
+    fn insert_project(&self, project: Project) -> PlatformResult<()> {
+        if !self.contains(TENANTS, &uuid_key(project.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        let key = uuid_key(project.id);
+        if self.contains(PROJECTS, &key)? {
+            return Err(PlatformError::Conflict("project"));
+        }
+        self.put(PROJECTS, &key, &project)
+    }
+
+    fn delete_project(&self, id: ProjectId) -> PlatformResult<()> {
+        self.index_remove(PROJECTS, &uuid_key(id))
+    }
+
+    fn list_projects(&self, tenant_id: TenantId) -> PlatformResult<Vec<Project>> {
+        let mut projects: Vec<Project> = self
+            .scan::<Project>(PROJECTS)?
+            .into_iter()
+            .filter(|p| p.tenant_id == tenant_id)
+            .collect();
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(projects)
+    }
+
+    fn get_project(&self, id: ProjectId) -> PlatformResult<Option<Project>> {
+        self.get(PROJECTS, &uuid_key(id))
+    }
+}
+
+impl AgentStore for EmbeddedPersistence {
+    fn insert_agent(&self, agent: Agent) -> PlatformResult<()> {
+        if !self.contains(TENANTS, &uuid_key(agent.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        if !self.contains(PROJECTS, &uuid_key(agent.project_id))? {
+            return Err(PlatformError::NotFound("project"));
+        }
+        let key = uuid_key(agent.id);
+        if self.contains(AGENTS, &key)? {
+            return Err(PlatformError::Conflict("agent"));
+        }
+        self.put(AGENTS, &key, &agent)
+    }
+
+    fn update_agent(&self, agent: Agent) -> PlatformResult<()> {
+        let key = uuid_key(agent.id);
+        if !self.contains(AGENTS, &key)? {
+            return Err(PlatformError::NotFound("agent"));
+        }
+        self.put(AGENTS, &key, &agent)
+    }
+
+    fn list_agents(&self, tenant_id: TenantId) -> PlatformResult<Vec<Agent>> {
+        let mut agents: Vec<Agent> = self
+            .scan::<Agent>(AGENTS)?
+            .into_iter()
+            .filter(|a| a.tenant_id == tenant_id)
+            .collect();
+        agents.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+        Ok(agents)
+    }
+
+    fn get_agent(&self, id: AgentId) -> PlatformResult<Option<Agent>> {
+        self.get(AGENTS, &uuid_key(id))
+    }
+}
+
+impl AgentStateStore for EmbeddedPersistence {
+    fn record_agent_state_event(&self, event: AgentStateEvent) -> PlatformResult<()> {
+        let key = uuid_key(event.id);
+        self.put(AGENT_STATE_EVENTS, &key, &event)
+    }
+
+    fn list_agent_state_events(&self, agent_id: AgentId) -> PlatformResult<Vec<AgentStateEvent>> {
+        let mut events: Vec<AgentStateEvent> = self
+            .scan::<AgentStateEvent>(AGENT_STATE_EVENTS)?
+            .into_iter()
+            .filter(|e| e.agent_id == agent_id)
+            .collect();
+        events.sort_by(|a, b| a.at.cmp(&b.at));
+        Ok(events)
+    }
+}
+
+impl ApiKeyStore for EmbeddedPersistence {
+    fn insert_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()> {
+        if self.contains(API_KEYS_BY_PREFIX, record.token_prefix.as_bytes())? {
+            return Err(PlatformError::Conflict("api_key"));
+        }
+        self.index_put(
+            API_KEYS_BY_PREFIX,
+            record.token_prefix.as_bytes(),
+            &uuid_key(record.id),
+        )?;
+        self.put(API_KEYS, &uuid_key(record.id), &record)
+    }
+
+    fn get_api_key(&self, id: ApiKeyId) -> PlatformResult<Option<ApiKeyRecord>> {
+        self.get(API_KEYS, &uuid_key(id))
+    }
+
+    fn get_api_key_by_prefix(&self, prefix: &str) -> PlatformResult<Option<ApiKeyRecord>> {
+        match self.get::<[u8; 16]>(API_KEYS_BY_PREFIX, prefix.as_bytes())? {
+            Some(id_bytes) => self.get(API_KEYS, &id_bytes),
+            None => Ok(None),
+        }
+    }
+
+    fn list_api_keys(&self, tenant_id: TenantId) -> PlatformResult<Vec<ApiKeyRecord>> {
+        let mut keys: Vec<ApiKeyRecord> = self
+            .scan::<ApiKeyRecord>(API_KEYS)?
+            .into_iter()
+            .filter(|k| k.tenant_id == tenant_id)
+            .collect();
+        keys.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(keys)
+    }
+
+    fn update_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()> {
+        if !self.contains(API_KEYS, &uuid_key(record.id))? {
+            return Err(PlatformError::NotFound("api_key"));
+        }
+        self.index_put(
+            API_KEYS_BY_PREFIX,
+            record.token_prefix.as_bytes(),
+            &uuid_key(record.id),
+        )?;
+        self.put(API_KEYS, &uuid_key(record.id), &record)
+    }
+}
+
+impl CertificateStore for EmbeddedPersistence {
+    fn insert_certificate(&self, record: AgentCertificateRecord) -> PlatformResult<()> {
+        let key = uuid_key(record.id);
+        if self.contains(CERTIFICATES, &key)? {
+            return Err(PlatformError::Conflict("agent_certificate"));
+        }
+        self.put(CERTIFICATES, &key, &record)
+    }
+
+    fn get_certificate(&self, id: Uuid) -> PlatformResult<Option<AgentCertificateRecord>> {
+        self.get(CERTIFICATES, &uuid_key(id))
+    }
+
+    fn list_certificates_for_agent(
+        &self,
+        agent_id: AgentId,
+    ) -> PlatformResult<Vec<AgentCertificateRecord>> {
+        let mut certs: Vec<AgentCertificateRecord> = self
+            .scan::<AgentCertificateRecord>(CERTIFICATES)?
+            .into_iter()
+            .filter(|c| c.agent_id == agent_id)
+            .collect();
+        certs.sort_by(|a, b| a.issued_at.cmp(&b.issued_at));
+        Ok(certs)
+    }
+
+    fn update_certificate(&self, record: AgentCertificateRecord) -> PlatformResult<()> {
+        let key = uuid_key(record.id);
+        if !self.contains(CERTIFICATES, &key)? {
+            return Err(PlatformError::NotFound("agent_certificate"));
+        }
+        self.put(CERTIFICATES, &key, &record)
+    }
+
+    fn insert_tenant_ca(&self, record: TenantCaRecord) -> PlatformResult<()> {
+        let key = uuid_key(record.tenant_id);
+        if self.contains(TENANT_CAS, &key)? {
+            return Err(PlatformError::Conflict("tenant_ca"));
+        }
+        self.put(TENANT_CAS, &key, &record)
+    }
+
+    fn get_tenant_ca(&self, tenant_id: TenantId) -> PlatformResult<Option<TenantCaRecord>> {
+        self.get(TENANT_CAS, &uuid_key(tenant_id))
+    }
+}
+
+impl AuditStore for EmbeddedPersistence {
+    fn record_event(&self, event: AuditEvent) -> PlatformResult<()> {
+        let key = uuid_key(event.id);
+        self.put(AUDIT_EVENTS, &key, &event)
+    }
+
+    fn list_events(&self, query: &AuditQuery) -> PlatformResult<Vec<AuditEvent>> {
+        let mut events: Vec<AuditEvent> = self
+            .scan::<AuditEvent>(AUDIT_EVENTS)?
+            .into_iter()
+            .filter(|event| {
+                if event.tenant_id != query.tenant_id {
+                    return false;
+                }
+                if let Some(area) = &query.area {
+                    if &event.area != area {
+                        return false;
+                    }
+                }
+                if let Some(category) = &query.category {
+                    if &event.category != category {
+                        return false;
+                    }
+                }
+                if let Some(actor_id) = query.actor_id {
+                    if event.actor_id != actor_id {
+                        return false;
+                    }
+                }
+                if let Some(from) = query.time_from {
+                    if event.timestamp < from {
+                        return false;
+                    }
+                }
+                if let Some(to) = query.time_to {
+                    if event.timestamp > to {
+                        return false;
+                    }
+                }
+                if let (Some(cursor_ts), Some(cursor_id)) =
+                    (query.cursor_timestamp, query.cursor_id)
+                {
+                    if !(event.timestamp < cursor_ts
+                        || (event.timestamp == cursor_ts && event.id > cursor_id))
+                    {
+                        return false;
+                    }
+                }
+                true
+            })
+            .collect();
+
+        events.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(a.id.cmp(&b.id)));
+        let limit = query.limit.unwrap_or(events.len() as u32) as usize;
+        events.truncate(limit);
+        Ok(events)
+    }
+}
+
+impl TaskStore for EmbeddedPersistence {
+    fn enqueue_task(&self, task: Task) -> PlatformResult<()> {
+        let key = uuid_key(task.id);
+        if self.contains(TASKS, &key)? {
+            return Err(PlatformError::Conflict("task"));
+        }
+        let queue_key = task_queue_key(task.scheduled_at, task.id);
+        self.index_put(TASK_QUEUE, &queue_key, &[])?;
+        self.put(TASKS, &key, &task)
+    }
+
+    fn peek_next_task(&self, tenant_id: TenantId) -> PlatformResult<Option<Task>> {
+        let due = {
+            let txn = self
+                .db
+                .begin_read()
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            let queue = txn
+                .open_table(TASK_QUEUE)
+                .map_err(|_| PlatformError::Internal("embedded store"))?;
+            let mut found = None;
+            for entry in queue
+                .iter()
+                .map_err(|_| PlatformError::Internal("embedded store"))?
+            {
+                let (key, _) = entry.map_err(|_| PlatformError::Internal("embedded store"))?;
+                let task_id = task_id_from_queue_key(key.value());
+                if let Some(task) = self.get::<Task>(TASKS, &uuid_key(task_id))? {
+                    if task.tenant_id == tenant_id && task.status == TaskStatus::Pending {
+                        found = Some(task);
+                        break;
+                    }
+                }
+            }
+            found
+        };
+        match due {
+            Some(mut task) => {
+                self.index_remove(TASK_QUEUE, &task_queue_key(task.scheduled_at, task.id))?;
+                task.status = TaskStatus::InProgress;
+                task.started_at = Some(Utc::now());
+                self.put(TASKS, &uuid_key(task.id), &task)?;
+                Ok(Some(task))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn update_task(&self, task: Task) -> PlatformResult<()> {
+        let key = uuid_key(task.id);
+        let Some(existing) = self.get::<Task>(TASKS, &key)? else {
+            return Err(PlatformError::NotFound("task"));
+        };
+        self.index_remove(TASK_QUEUE, &task_queue_key(existing.scheduled_at, existing.id))?;
+        if task.status == TaskStatus::Pending {
+            self.index_put(TASK_QUEUE, &task_queue_key(task.scheduled_at, task.id), &[])?;
+        }
+        self.put(TASKS, &key, &task)
+    }
+
+    fn get_task(&self, id: TaskId) -> PlatformResult<Option<Task>> {
+        self.get(TASKS, &uuid_key(id))
+    }
+
+    fn list_pending_tasks(&self, tenant_id: TenantId) -> PlatformResult<Vec<Task>> {
+        let mut tasks: Vec<Task> = self
+            .scan::<Task>(TASKS)?
+            .into_iter()
+            .filter(|task| task.tenant_id == tenant_id && task.status == TaskStatus::Pending)
+            .collect();
+        tasks.sort_by(|a, b| a.scheduled_at.cmp(&b.scheduled_at));
+        Ok(tasks)
+    }
+
+    fn list_tasks_by_kind(&self, tenant_id: TenantId, kind: &str) -> PlatformResult<Vec<Task>> {
+        let mut tasks: Vec<Task> = self
+            .scan::<Task>(TASKS)?
+            .into_iter()
+            .filter(|task| task.tenant_id == tenant_id && task.kind == kind)
+            .collect();
+        tasks.sort_by(|a, b| a.scheduled_at.cmp(&b.scheduled_at));
+        Ok(tasks)
+    }
+}
+
+/// `task_queue` secondary-index key: `scheduled_at` (order-preserving) then
+/// the task id, so an ascending table scan yields the oldest-scheduled
+/// pending task first without touching the `tasks` table.
+fn task_queue_key(scheduled_at: chrono::DateTime<Utc>, id: TaskId) -> [u8; 24] {
+    let mut key = [0u8; 24];
+    key[..8].copy_from_slice(&order_preserving_micros(scheduled_at.timestamp_micros()));
+    key[8..].copy_from_slice(&uuid_key(id));
+    key
+}
+
+fn task_id_from_queue_key(key: &[u8]) -> TaskId {
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&key[8..24]);
+    Uuid::from_bytes(id)
+}
+
+impl WorkflowStore for EmbeddedPersistence {
+    fn insert_workflow(&self, workflow: Workflow) -> PlatformResult<()> {
+        let key = uuid_key(workflow.id);
+        if self.contains(WORKFLOWS, &key)? {
+            return Err(PlatformError::Conflict("workflow"));
+        }
+        self.put(WORKFLOWS, &key, &workflow)
+    }
+
+    fn get_workflow(&self, id: WorkflowId) -> PlatformResult<Option<Workflow>> {
+        self.get(WORKFLOWS, &uuid_key(id))
+    }
+
+    fn list_workflows(&self, tenant_id: TenantId) -> PlatformResult<Vec<Workflow>> {
+        let mut workflows: Vec<Workflow> = self
+            .scan::<Workflow>(WORKFLOWS)?
+            .into_iter()
+            .filter(|w| w.tenant_id == tenant_id)
+            .collect();
+        workflows.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(workflows)
+    }
+}
+
+#[async_trait]
+impl ContentStore for EmbeddedPersistence {
+    async fn create_upload_session(&self, session: UploadSession) -> PlatformResult<()> {
+        if !self.contains(TENANTS, &uuid_key(session.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        if !self.contains(PROJECTS, &uuid_key(session.project_id))? {
+            return Err(PlatformError::NotFound("project"));
+        }
+        let key = uuid_key(session.id);
+        if self.contains(UPLOAD_SESSIONS, &key)? {
+            return Err(PlatformError::Conflict("upload_session"));
+        }
+        self.put(UPLOAD_SESSIONS, &key, &session)
+    }
+
+    async fn update_upload_session(&self, session: UploadSession) -> PlatformResult<()> {
+        let key = uuid_key(session.id);
+        if !self.contains(UPLOAD_SESSIONS, &key)? {
+            return Err(PlatformError::NotFound("upload_session"));
+        }
+        self.put(UPLOAD_SESSIONS, &key, &session)
+    }
+
+    async fn get_upload_session(&self, id: UploadId) -> PlatformResult<Option<UploadSession>> {
+        self.get(UPLOAD_SESSIONS, &uuid_key(id))
+    }
+
+    async fn register_upload_part(&self, upload_id: UploadId, part: UploadPart) -> PlatformResult<()> {
+        let key = uuid_key(upload_id);
+        let Some(mut session) = self.get::<UploadSession>(UPLOAD_SESSIONS, &key)? else {
+            return Err(PlatformError::NotFound("upload_session"));
+        };
+        session.parts.retain(|existing| existing.part_number != part.part_number);
+        session.parts.push(part);
+        session.parts.sort_by_key(|part| part.part_number);
+        session.updated_at = Utc::now();
+        self.put(UPLOAD_SESSIONS, &key, &session)
+    }
+
+    async fn list_upload_parts(&self, upload_id: UploadId) -> PlatformResult<Vec<UploadPart>> {
+        let Some(session) = self.get::<UploadSession>(UPLOAD_SESSIONS, &uuid_key(upload_id))? else {
+            return Err(PlatformError::NotFound("upload_session"));
+        };
+        let mut parts = session.parts;
+        parts.sort_by_key(|part| part.part_number);
+        Ok(parts)
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        upload_id: UploadId,
+        filename: String,
+        mime_type: Option<String>,
+        visibility: ContentVisibility,
+    ) -> PlatformResult<ContentMetadata> {
+        let key = uuid_key(upload_id);
+        let mut session = self
+            .get::<UploadSession>(UPLOAD_SESSIONS, &key)?
+            .ok_or(PlatformError::NotFound("upload_session"))?;
+        let mut parts = session.parts.clone();
+        parts.sort_by_key(|part| part.part_number);
+        crate::platform::ingest::validate_parts_contiguous(&parts)?;
+        let size_bytes = parts
+            .iter()
+            .map(|part| part.size_bytes)
+            .sum::<Option<u64>>()
+            .ok_or(PlatformError::InvalidInput(
+                "every part must report its size before a multipart upload can be completed",
+            ))?;
+        let etags: Vec<&str> = parts.iter().map(|part| part.etag.as_str()).collect();
+        let checksum = crate::platform::ingest::composite_etag(
+            &etags,
+            crate::platform::ingest::ChecksumAlgorithm::Sha256,
+        );
+        let now = Utc::now();
+        let metadata = ContentMetadata {
+            id: session.content_id,
+            tenant_id: session.tenant_id,
+            project_id: session.project_id,
+            filename,
+            mime_type,
+            size_bytes: Some(size_bytes),
+            checksum: Some(checksum),
+            storage_path: None,
+            labels: Vec::new(),
+            attributes: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+            uploaded_by: None,
+            visibility,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        };
+        self.record_content_metadata(metadata.clone()).await?;
+        session.status = UploadStatus::Completed;
+        session.updated_at = now;
+        session.parts = parts;
+        self.put(UPLOAD_SESSIONS, &key, &session)?;
+        Ok(metadata)
+    }
+
+    async fn record_content_metadata(&self, metadata: ContentMetadata) -> PlatformResult<()> {
+        if !self.contains(TENANTS, &uuid_key(metadata.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        if !self.contains(PROJECTS, &uuid_key(metadata.project_id))? {
+            return Err(PlatformError::NotFound("project"));
+        }
+        let key = uuid_key(metadata.id);
+        if let Some(existing) = self.get::<ContentMetadata>(CONTENT_METADATA, &key)? {
+            existing.guard_mutation(Utc::now())?;
+        }
+        self.put(CONTENT_METADATA, &key, &metadata)
+    }
+
+    async fn get_content_metadata(&self, id: ContentId) -> PlatformResult<Option<ContentMetadata>> {
+        self.get(CONTENT_METADATA, &uuid_key(id))
+    }
+
+    async fn delete_content_metadata(&self, id: ContentId) -> PlatformResult<()> {
+        let key = uuid_key(id);
+        if let Some(existing) = self.get::<ContentMetadata>(CONTENT_METADATA, &key)? {
+            existing.guard_mutation(Utc::now())?;
+        }
+        self.index_remove(CONTENT_METADATA, &key)
+    }
+
+    async fn set_content_labels(&self, id: ContentId, labels: Vec<String>) -> PlatformResult<()> {
+        let key = uuid_key(id);
+        let mut metadata = self
+            .get::<ContentMetadata>(CONTENT_METADATA, &key)?
+            .ok_or(PlatformError::NotFound("content"))?;
+        metadata.labels = labels;
+        metadata.updated_at = Utc::now();
+        self.put(CONTENT_METADATA, &key, &metadata)
+    }
+
+    async fn set_content_retention(
+        &self,
+        id: ContentId,
+        legal_hold: Option<bool>,
+        immutability: Option<ImmutabilityPolicy>,
+    ) -> PlatformResult<()> {
+        let key = uuid_key(id);
+        let mut metadata = self
+            .get::<ContentMetadata>(CONTENT_METADATA, &key)?
+            .ok_or(PlatformError::NotFound("content"))?;
+        if let Some(legal_hold) = legal_hold {
+            metadata.legal_hold = legal_hold;
+        }
+        if let Some(policy) = immutability {
+            metadata.apply_immutability_policy(policy)?;
+        }
+        metadata.updated_at = Utc::now();
+        self.put(CONTENT_METADATA, &key, &metadata)
+    }
+
+    async fn list_content_metadata(
+        &self,
+        query: &ContentQuery,
+    ) -> PlatformResult<Vec<ContentMetadata>> {
+        query.validate()?;
+        let tokens: Vec<String> = query
+            .search_term
+            .as_deref()
+            .map(|term| {
+                term.split_whitespace()
+                    .map(|token| token.to_ascii_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut entries: Vec<ContentMetadata> = self
+            .scan::<ContentMetadata>(CONTENT_METADATA)?
+            .into_iter()
+            .filter(|item| {
+                if item.tenant_id != query.tenant_id {
+                    return false;
+                }
+                if let Some(project_id) = query.project_id {
+                    if item.project_id != project_id {
+                        return false;
+                    }
+                }
+                if !tokens.is_empty() && content_relevance_score(item, &tokens) == 0.0 {
+                    return false;
+                }
+                if !query.tags.is_empty()
+                    && !query
+                        .tags
+                        .iter()
+                        .all(|tag| item.labels.iter().any(|label| label == tag))
+                {
+                    return false;
+                }
+                if let (Some(cursor_ts), Some(cursor_id)) =
+                    (query.cursor_created_at, query.cursor_id)
+                {
+                    if !(item.created_at < cursor_ts
+                        || (item.created_at == cursor_ts && item.id > cursor_id))
+                    {
+                        return false;
+                    }
+                }
+                true
+            })
+            .map(|mut item| {
+                if !tokens.is_empty() {
+                    item.relevance = Some(content_relevance_score(&item, &tokens));
+                }
+                item
+            })
+            .collect();
+
+        if tokens.is_empty() {
+            entries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+        } else {
+            entries.sort_by(|a, b| {
+                b.relevance
+                    .partial_cmp(&a.relevance)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.created_at.cmp(&a.created_at))
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+        }
+
+        let limit = query.limit.unwrap_or(entries.len() as u32) as usize;
+        let slice = if query.cursor_created_at.is_some() {
+            entries.into_iter().take(limit).collect()
+        } else {
+            let offset = query.offset.unwrap_or(0) as usize;
+            entries.into_iter().skip(offset).take(limit).collect()
+        };
+        Ok(slice)
+    }
+
+    async fn find_content_by_digest(
+        &self,
+        tenant_id: TenantId,
+        digest: &str,
+        size_bytes: u64,
+    ) -> PlatformResult<Option<ContentMetadata>> {
+        let mut matches: Vec<ContentMetadata> = self
+            .scan::<ContentMetadata>(CONTENT_METADATA)?
+            .into_iter()
+            .filter(|item| {
+                item.tenant_id == tenant_id
+                    && item.checksum.as_deref() == Some(digest)
+                    && item.size_bytes == Some(size_bytes)
+            })
+            .collect();
+        matches.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(matches.into_iter().next())
+    }
+
+    async fn set_lifecycle_policy(&self, policy: ContentLifecyclePolicy) -> PlatformResult<()> {
+        if !self.contains(TENANTS, &uuid_key(policy.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        self.put(LIFECYCLE_POLICIES, &uuid_key(policy.id), &policy)
+    }
+
+    async fn list_lifecycle_policies(
+        &self,
+        tenant_id: TenantId,
+    ) -> PlatformResult<Vec<ContentLifecyclePolicy>> {
+        let mut policies: Vec<ContentLifecyclePolicy> = self
+            .scan::<ContentLifecyclePolicy>(LIFECYCLE_POLICIES)?
+            .into_iter()
+            .filter(|p| p.tenant_id == tenant_id)
+            .collect();
+        policies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(policies)
+    }
+
+    async fn delete_lifecycle_policy(
+        &self,
+        tenant_id: TenantId,
+        policy_id: LifecyclePolicyId,
+    ) -> PlatformResult<()> {
+        if let Some(policy) = self.get::<ContentLifecyclePolicy>(LIFECYCLE_POLICIES, &uuid_key(policy_id))? {
+            if policy.tenant_id == tenant_id {
+                self.index_remove(LIFECYCLE_POLICIES, &uuid_key(policy_id))?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn sweep_expired_content(
+        &self,
+        tenant_id: TenantId,
+        now: chrono::DateTime<Utc>,
+    ) -> PlatformResult<Vec<ContentLifecycleOutcome>> {
+        let policies: Vec<ContentLifecyclePolicy> = self
+            .scan::<ContentLifecyclePolicy>(LIFECYCLE_POLICIES)?
+            .into_iter()
+            .filter(|p| p.tenant_id == tenant_id)
+            .collect();
+        let content = self.scan::<ContentMetadata>(CONTENT_METADATA)?;
+        let mut outcomes = Vec::new();
+        for policy in &policies {
+            for item in &content {
+                if policy.matches(item) && policy.is_expired(item, now) {
+                    outcomes.push(ContentLifecycleOutcome {
+                        content_id: item.id,
+                        policy_id: policy.id,
+                        action: policy.action.clone(),
+                    });
+                }
+            }
+        }
+        outcomes.sort_by_key(|outcome| outcome.content_id);
+        Ok(outcomes)
+    }
+
+    async fn apply_lifecycle_outcome(&self, outcome: ContentLifecycleOutcome) -> PlatformResult<()> {
+        match outcome.action {
+            LifecycleAction::Delete => {
+                self.index_remove(CONTENT_METADATA, &uuid_key(outcome.content_id))?;
+            }
+            LifecycleAction::TransitionVisibility(visibility) => {
+                let key = uuid_key(outcome.content_id);
+                if let Some(mut content) = self.get::<ContentMetadata>(CONTENT_METADATA, &key)? {
+                    content.visibility = visibility;
+                    self.put(CONTENT_METADATA, &key, &content)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn reap_expired_upload_sessions(&self, now: chrono::DateTime<Utc>) -> PlatformResult<u64> {
+        let sessions = self.scan::<UploadSession>(UPLOAD_SESSIONS)?;
+        let mut removed = 0u64;
+        for session in sessions {
+            if session.expires_at.map(|e| e < now).unwrap_or(false) {
+                self.index_remove(UPLOAD_SESSIONS, &uuid_key(session.id))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[async_trait]
+impl OrchestrationStore for EmbeddedPersistence {
+    async fn create_assignment(&self, input: NewAssignment) -> PlatformResult<WorkAssignment> {
+        if input.workload_id.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("workload_id required"));
+        }
+        if let Some(tenant_id) = input.tenant_id {
+            if !self.contains(TENANTS, &uuid_key(tenant_id))? {
+                return Err(PlatformError::NotFound("tenant"));
+            }
+        }
+        if let Some(project_id) = input.project_id {
+            if !self.contains(PROJECTS, &uuid_key(project_id))? {
+                return Err(PlatformError::NotFound("project"));
+            }
+        }
+        let key = uuid_key(input.id);
+        if self.contains(ASSIGNMENTS, &key)? {
+            return Err(PlatformError::Conflict("assignment"));
+        }
+        let now = Utc::now();
+        let assignment = WorkAssignment {
+            id: input.id,
+            agent_id: input.agent_id,
+            workload_id: input.workload_id,
+            tenant_id: input.tenant_id,
+            project_id: input.project_id,
+            status: WorkStatus::Pending,
+            status_message: Some("queued".to_string()),
+            metadata: input.metadata,
+            last_heartbeat: None,
+            attempt: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        self.put(ASSIGNMENTS, &key, &assignment)?;
+        Ok(assignment)
+    }
+
+    async fn update_assignment_status(
+        &self,
+        id: AssignmentId,
+        status: WorkStatus,
+        status_message: Option<String>,
+    ) -> PlatformResult<WorkAssignment> {
+        let key = uuid_key(id);
+        let Some(mut assignment) = self.get::<WorkAssignment>(ASSIGNMENTS, &key)? else {
+            return Err(PlatformError::NotFound("assignment"));
+        };
+        assignment.status = status;
+        assignment.status_message = status_message;
+        assignment.updated_at = Utc::now();
+        self.put(ASSIGNMENTS, &key, &assignment)?;
+        Ok(assignment)
+    }
+
+    async fn list_assignments(
+        &self,
+        query: AssignmentQuery,
+    ) -> PlatformResult<Vec<WorkAssignment>> {
+        let mut assignments: Vec<WorkAssignment> = self
+            .scan::<WorkAssignment>(ASSIGNMENTS)?
+            .into_iter()
+            .filter(|assignment| {
+                if let Some(agent_id) = query.agent_id {
+                    if assignment.agent_id != agent_id {
+                        return false;
+                    }
+                }
+                if let Some(tenant_id) = query.tenant_id {
+                    if assignment.tenant_id != Some(tenant_id) {
+                        return false;
+                    }
+                }
+                if let Some(project_id) = query.project_id {
+                    if assignment.project_id != Some(project_id) {
+                        return false;
+                    }
+                }
+                if let Some(status) = &query.status {
+                    if &assignment.status != status {
+                        return false;
+                    }
+                }
+                true
+            })
+            .collect();
+        assignments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(assignments)
+    }
+
+    async fn heartbeat_assignment(&self, id: AssignmentId) -> PlatformResult<()> {
+        let key = uuid_key(id);
+        let Some(mut assignment) = self.get::<WorkAssignment>(ASSIGNMENTS, &key)? else {
+            return Err(PlatformError::NotFound("assignment"));
+        };
+        assignment.last_heartbeat = Some(Utc::now());
+        self.put(ASSIGNMENTS, &key, &assignment)?;
+        Ok(())
+    }
+
+    async fn claim_pending(&self, agent_id: AgentId, max: u32) -> PlatformResult<Vec<WorkAssignment>> {
+        let now = Utc::now();
+        let mut pending: Vec<WorkAssignment> = self
+            .scan::<WorkAssignment>(ASSIGNMENTS)?
+            .into_iter()
+            .filter(|a| a.agent_id == agent_id && a.status == WorkStatus::Pending)
+            .collect();
+        pending.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        pending.truncate(max as usize);
+
+        let mut claimed = Vec::with_capacity(pending.len());
+        for mut assignment in pending {
+            assignment.status = WorkStatus::Running;
+            assignment.status_message = Some("running".to_string());
+            assignment.last_heartbeat = Some(now);
+            assignment.updated_at = now;
+            self.put(ASSIGNMENTS, &uuid_key(assignment.id), &assignment)?;
+            claimed.push(assignment);
+        }
+        Ok(claimed)
+    }
+
+    async fn requeue_stale(
+        &self,
+        now: chrono::DateTime<Utc>,
+        ttl: chrono::Duration,
+        config: &AssignmentLifecycleConfig,
+    ) -> PlatformResult<Vec<WorkAssignment>> {
+        let cutoff = now - ttl;
+        let stale: Vec<WorkAssignment> = self
+            .scan::<WorkAssignment>(ASSIGNMENTS)?
+            .into_iter()
+            .filter(|a| {
+                a.status == WorkStatus::Running
+                    && a.last_heartbeat.map(|heartbeat| heartbeat < cutoff).unwrap_or(true)
+            })
+            .collect();
+
+        let mut changed = Vec::with_capacity(stale.len());
+        for mut assignment in stale {
+            assignment.attempt += 1;
+            assignment.updated_at = now;
+            if assignment.attempt > config.max_attempts {
+                assignment.status = WorkStatus::Failed;
+                assignment.status_message = Some("exceeded max requeue attempts".to_string());
+            } else {
+                assignment.status = WorkStatus::Pending;
+                assignment.status_message = Some("requeued after stale lease".to_string());
+                assignment.last_heartbeat = None;
+            }
+            self.put(ASSIGNMENTS, &uuid_key(assignment.id), &assignment)?;
+            changed.push(assignment);
+        }
+        Ok(changed)
+    }
+}
+
+#[async_trait]
+impl ModerationStore for EmbeddedPersistence {
+    async fn create_content(&self, input: NewModeratedContent) -> PlatformResult<ModeratedContent> {
+        let key = uuid_key(input.id);
+        if self.contains(MODERATION_CONTENT, &key)? {
+            return Err(PlatformError::Conflict("ugc_content"));
+        }
+        if !self.contains(TENANTS, &uuid_key(input.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        if !self.contains(PROJECTS, &uuid_key(input.project_id))? {
+            return Err(PlatformError::NotFound("project"));
+        }
+        let now = Utc::now();
+        let record = ModeratedContent {
+            id: input.id,
+            tenant_id: input.tenant_id,
+            project_id: input.project_id,
+            filename: input.filename,
+            mime_type: input.mime_type,
+            size_bytes: input.size_bytes,
+            state: ModerationState::Pending,
+            reason: None,
+            labels: input.labels,
+            attributes: input.attributes,
+            submitted_at: now,
+            updated_at: now,
+        };
+        self.put(MODERATION_CONTENT, &key, &record)?;
+        Ok(record)
+    }
+
+    async fn update_content_state(
+        &self,
+        id: ContentId,
+        state: ModerationState,
+        reason: Option<String>,
+        actor_id: Uuid,
+    ) -> PlatformResult<ModeratedContent> {
+        let key = uuid_key(id);
+        let Some(mut record) = self.get::<ModeratedContent>(MODERATION_CONTENT, &key)? else {
+            return Err(PlatformError::NotFound("ugc_content"));
+        };
+        let from_state = record.state.clone();
+        record.state = state.clone();
+        record.reason = reason.clone();
+        record.updated_at = Utc::now();
+        self.put(MODERATION_CONTENT, &key, &record)?;
+        let event = ModerationEvent {
+            id: Uuid::new_v4(),
+            content_id: id,
+            from_state: from_state.clone(),
+            to_state: state.clone(),
+            reason: reason.clone(),
+            actor_id,
+            created_at: record.updated_at,
+        };
+        self.put(MODERATION_EVENTS, &uuid_key(event.id), &event)?;
+        self.append_audit_entry(id, from_state, state, reason, actor_id, record.updated_at)?;
+        Ok(record)
+    }
+
+    async fn list_content_events(
+        &self,
+        content_id: ContentId,
+    ) -> PlatformResult<Vec<ModerationEvent>> {
+        let mut events: Vec<ModerationEvent> = self
+            .scan::<ModerationEvent>(MODERATION_EVENTS)?
+            .into_iter()
+            .filter(|event| event.content_id == content_id)
+            .collect();
+        events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(events)
+    }
+
+    async fn list_content(&self, query: ModerationQuery) -> PlatformResult<Vec<ModeratedContent>> {
+        let reports = self.scan::<ModerationReport>(MODERATION_REPORTS)?;
+        let open_report_count = |content_id: ContentId| {
+            reports
+                .iter()
+                .filter(|report| report.content_id == content_id && !report.resolved)
+                .count() as i64
+        };
+        let mut items: Vec<ModeratedContent> = self
+            .scan::<ModeratedContent>(MODERATION_CONTENT)?
+            .into_iter()
+            .filter(|item| {
+                if let Some(tenant_id) = query.tenant_id {
+                    if item.tenant_id != tenant_id {
+                        return false;
+                    }
+                }
+                if let Some(project_id) = query.project_id {
+                    if item.project_id != project_id {
+                        return false;
+                    }
+                }
+                if let Some(state_filter) = &query.state {
+                    if &item.state != state_filter {
+                        return false;
+                    }
+                }
+                if let Some(min_open_reports) = query.min_open_reports {
+                    if open_report_count(item.id) < min_open_reports {
+                        return false;
+                    }
+                }
+                true
+            })
+            .collect();
+        if query.sort_by_open_reports {
+            items.sort_by(|a, b| {
+                open_report_count(b.id)
+                    .cmp(&open_report_count(a.id))
+                    .then(b.submitted_at.cmp(&a.submitted_at))
+            });
+        } else {
+            items.sort_by(|a, b| b.submitted_at.cmp(&a.submitted_at));
+        }
+        Ok(items)
+    }
+
+    async fn create_report(
+        &self,
+        content_id: ContentId,
+        reporter_id: Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    ) -> PlatformResult<ModerationReport> {
+        if !self.contains(MODERATION_CONTENT, &uuid_key(content_id))? {
+            return Err(PlatformError::NotFound("ugc_content"));
+        }
+        let report = ModerationReport {
+            id: Uuid::new_v4(),
+            content_id,
+            reporter_id,
+            category,
+            detail,
+            created_at: Utc::now(),
+            resolved: false,
+        };
+        self.put(MODERATION_REPORTS, &uuid_key(report.id), &report)?;
+        Ok(report)
+    }
+
+    async fn expire_pending_moderation(
+        &self,
+        now: DateTime<Utc>,
+        deadline: chrono::Duration,
+        to_state: ModerationState,
+    ) -> PlatformResult<Vec<ModeratedContent>> {
+        let cutoff = now - deadline;
+        let stale: Vec<ModeratedContent> = self
+            .scan::<ModeratedContent>(MODERATION_CONTENT)?
+            .into_iter()
+            .filter(|item| item.state == ModerationState::Pending && item.submitted_at < cutoff)
+            .collect();
+        let mut expired = Vec::with_capacity(stale.len());
+        for content in stale {
+            expired.push(
+                self.update_content_state(
+                    content.id,
+                    to_state.clone(),
+                    Some("expired after moderation deadline".to_string()),
+                    Uuid::nil(),
+                )
+                .await?,
+            );
+        }
+        Ok(expired)
+    }
+
+    async fn list_audit(&self, content_id: ContentId) -> PlatformResult<Vec<ModerationAuditEntry>> {
+        self.audit_chain(content_id)
+    }
+
+    async fn audit_chain_head(&self, content_id: ContentId) -> PlatformResult<Option<String>> {
+        Ok(self
+            .audit_chain(content_id)?
+            .last()
+            .map(|entry| entry.hash.clone()))
+    }
+}
+
+#[async_trait]
+impl MessagingStore for EmbeddedPersistence {
+    async fn enqueue_message(&self, input: NewMessageRecord) -> PlatformResult<MessageRecord> {
+        if input.topic.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("topic required"));
+        }
+        if !self.contains(TENANTS, &uuid_key(input.tenant_id))? {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        if !self.contains(PROJECTS, &uuid_key(input.project_id))? {
+            return Err(PlatformError::NotFound("project"));
+        }
+        let key = uuid_key(input.id);
+        if self.contains(MESSAGES, &key)? {
+            return Err(PlatformError::Conflict("message"));
+        }
+        let record = MessageRecord {
+            id: input.id,
+            tenant_id: input.tenant_id,
+            project_id: input.project_id,
+            topic: input.topic.clone(),
+            key: input.key,
+            payload: input.payload,
+            priority: input.priority,
+            attributes: input.attributes,
+            published_at: Utc::now(),
+            delivery_attempts: 0,
+            max_attempts: input.max_attempts,
+            lease_until: None,
+            leased_by: None,
+        };
+        self.index_put(
+            MESSAGES_BY_TOPIC,
+            &message_topic_key(&record.topic, record.published_at, record.id),
+            &[],
+        )?;
+        self.put(MESSAGES, &key, &record)?;
+        Ok(record)
+    }
+
+    async fn list_messages(&self, query: MessageQuery) -> PlatformResult<Vec<MessageRecord>> {
+        if query.topic.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("topic required"));
+        }
+        let message_ids = self.topic_message_ids(&query.topic)?;
+
+        let mut results = Vec::new();
+        for id in message_ids {
+            let Some(message) = self.get::<MessageRecord>(MESSAGES, &uuid_key(id))? else {
+                continue;
+            };
+            if let Some(tenant_id) = query.tenant_id {
+                if message.tenant_id != tenant_id {
+                    continue;
+                }
+            }
+            if let Some(project_id) = query.project_id {
+                if message.project_id != project_id {
+                    continue;
+                }
+            }
+            results.push(message);
+            if let Some(limit) = query.limit {
+                if results.len() as u32 >= limit {
+                    break;
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    async fn claim_messages(
+        &self,
+        topic: &str,
+        consumer: &str,
+        max: u32,
+        visibility_timeout: chrono::Duration,
+    ) -> PlatformResult<Vec<MessageRecord>> {
+        if topic.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("topic required"));
+        }
+        let ids = self.topic_message_ids(topic)?;
+        let now = Utc::now();
+        let mut eligible: Vec<MessageRecord> = ids
+            .into_iter()
+            .filter_map(|id| self.get::<MessageRecord>(MESSAGES, &uuid_key(id)).transpose())
+            .collect::<PlatformResult<Vec<_>>>()?
+            .into_iter()
+            .filter(|message| message.lease_until.map(|until| until <= now).unwrap_or(true))
+            .collect();
+        eligible.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.published_at.cmp(&b.published_at)));
+
+        let mut claimed = Vec::new();
+        for mut message in eligible {
+            if claimed.len() as u32 >= max {
+                break;
+            }
+            if message.delivery_attempts + 1 > message.max_attempts {
+                self.index_remove(
+                    MESSAGES_BY_TOPIC,
+                    &message_topic_key(topic, message.published_at, message.id),
+                )?;
+                self.index_remove(MESSAGES, &uuid_key(message.id))?;
+                message.lease_until = None;
+                message.leased_by = None;
+                self.put(MESSAGE_DEAD_LETTERS, &uuid_key(message.id), &message)?;
+                continue;
+            }
+            message.delivery_attempts += 1;
+            message.lease_until = Some(now + visibility_timeout);
+            message.leased_by = Some(consumer.to_string());
+            self.put(MESSAGES, &uuid_key(message.id), &message)?;
+            claimed.push(message);
+        }
+        Ok(claimed)
+    }
+
+    async fn extend_lease(
+        &self,
+        topic: &str,
+        id: MessageId,
+        extension: chrono::Duration,
+    ) -> PlatformResult<()> {
+        let Some(mut message) = self.get::<MessageRecord>(MESSAGES, &uuid_key(id))? else {
+            return Err(PlatformError::NotFound("message"));
+        };
+        if message.topic != topic {
+            return Err(PlatformError::NotFound("message"));
+        }
+        let now = Utc::now();
+        match message.lease_until {
+            Some(lease_until) if lease_until > now => {
+                message.lease_until = Some(now + extension);
+                self.put(MESSAGES, &uuid_key(id), &message)
+            }
+            _ => Err(PlatformError::NotFound("message")),
+        }
+    }
+
+    async fn ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        let Some(message) = self.get::<MessageRecord>(MESSAGES, &uuid_key(id))? else {
+            return Err(PlatformError::NotFound("message"));
+        };
+        if let Some(lease_until) = message.lease_until {
+            if lease_until <= Utc::now() {
+                // The lease already lapsed, so the message may have been
+                // reclaimed by another consumer via `claim_messages` —
+                // treat this late ack as a no-op.
+                return Ok(());
+            }
+        }
+        self.index_remove(MESSAGES, &uuid_key(id))?;
+        self.index_remove(
+            MESSAGES_BY_TOPIC,
+            &message_topic_key(topic, message.published_at, message.id),
+        )?;
+        Ok(())
+    }
+
+    async fn nack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        let Some(mut message) = self.get::<MessageRecord>(MESSAGES, &uuid_key(id))? else {
+            return Err(PlatformError::NotFound("message"));
+        };
+        if message.topic != topic {
+            return Err(PlatformError::NotFound("message"));
+        }
+        match message.lease_until {
+            Some(lease_until) if lease_until > Utc::now() => {
+                message.lease_until = None;
+                message.leased_by = None;
+                self.put(MESSAGES, &uuid_key(id), &message)
+            }
+            // The lease already lapsed, so the message may have been
+            // reclaimed by another consumer via `claim_messages` — treat
+            // this late nack as a no-op instead of clearing their claim.
+            _ => Ok(()),
+        }
+    }
+
+    async fn evict_expired_messages(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) -> PlatformResult<u64> {
+        let cutoff = now - ttl;
+        let expired: Vec<MessageRecord> = self
+            .scan::<MessageRecord>(MESSAGES)?
+            .into_iter()
+            .filter(|message| message.published_at < cutoff)
+            .collect();
+        for message in &expired {
+            self.index_remove(MESSAGES, &uuid_key(message.id))?;
+            self.index_remove(
+                MESSAGES_BY_TOPIC,
+                &message_topic_key(&message.topic, message.published_at, message.id),
+            )?;
+        }
+        Ok(expired.len() as u64)
+    }
+}
+
+/// `messages_by_topic` secondary-index key: the topic, a NUL separator (safe
+/// since topics are plain strings that can't themselves contain one), then
+/// `published_at`/id so a per-topic range scan comes back in publish order.
+fn message_topic_key(topic: &str, published_at: chrono::DateTime<Utc>, id: MessageId) -> Vec<u8> {
+    let mut key = Vec::with_capacity(topic.len() + 1 + 8 + 16);
+    key.extend_from_slice(topic.as_bytes());
+    key.push(0);
+    key.extend_from_slice(&order_preserving_micros(published_at.timestamp_micros()));
+    key.extend_from_slice(&uuid_key(id));
+    key
+}
+
+fn topic_prefix(topic: &str) -> Vec<u8> {
+    let mut prefix = topic.as_bytes().to_vec();
+    prefix.push(0);
+    prefix
+}
+
+fn message_id_from_topic_key(key: &[u8]) -> MessageId {
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&key[key.len() - 16..]);
+    Uuid::from_bytes(id)
+}
+
+/// `idempotency` key: `scope`, a NUL separator (safe since scopes are plain
+/// strings that can't themselves contain one), then the idempotency key
+/// itself, mirroring [`message_topic_key`]'s namespacing.
+fn idempotency_key(scope: &str, key: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(scope.len() + 1 + key.len());
+    out.extend_from_slice(scope.as_bytes());
+    out.push(0);
+    out.extend_from_slice(key.as_bytes());
+    out
+}
+
+impl IdempotencyStore for EmbeddedPersistence {
+    fn get(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Option<IdempotencyRecord>> {
+        let storage_key = idempotency_key(scope, key);
+        match self.get::<IdempotencyRecord>(IDEMPOTENCY, &storage_key)? {
+            Some(record) if record.expires_at <= now => {
+                self.index_remove(IDEMPOTENCY, &storage_key)?;
+                Ok(None)
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn put(&self, scope: &str, key: &str, record: IdempotencyRecord) -> PlatformResult<()> {
+        self.put(IDEMPOTENCY, &idempotency_key(scope, key), &record)
+    }
+
+    fn put_if_absent(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+        record: IdempotencyRecord,
+    ) -> PlatformResult<Option<IdempotencyRecord>> {
+        let storage_key = idempotency_key(scope, key);
+        if matches!(self.get::<IdempotencyRecord>(IDEMPOTENCY, &storage_key)?, Some(existing) if existing.expires_at <= now)
+        {
+            self.index_remove(IDEMPOTENCY, &storage_key)?;
+        }
+        self.put_if_absent(IDEMPOTENCY, &storage_key, &record)
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,22 +15,160 @@ pub enum PlatformError {
     InvalidInput(&'static str),
     #[error("internal error: {0}")]
     Internal(&'static str),
+    #[error("locked: {0}")]
+    Locked(&'static str),
+    #[error("token audience not allowed for this tenant")]
+    AudienceNotAllowed,
+    #[error("token issuer not trusted for this tenant")]
+    IssuerNotTrusted,
+    /// One or more fields failed validation; `details` carries every
+    /// offending field at once rather than just the first one found. See
+    /// [`ErrorDetail`].
+    #[error("validation failed")]
+    Validation(Vec<ErrorDetail>),
+    /// Rejected by `ProvisioningService::transition_agent`'s state machine:
+    /// no edge exists from `from` to `to` for the given `AgentEvent`. Carries
+    /// `AgentStatus::as_str()` values rather than the enum itself so this
+    /// module doesn't need to depend on `platform::models`.
+    #[error("invalid agent state transition from {from} to {to}")]
+    InvalidTransition { from: String, to: String },
+    /// An idempotency key was replayed with a request body that hashes
+    /// differently from the one originally stored against it, per
+    /// [`IdempotencyStore`](crate::platform::persistence::IdempotencyStore).
+    /// Carries `scope`/`key` rather than the original request so this module
+    /// doesn't need to depend on `platform::provisioning`'s request types.
+    #[error("idempotency key {key} in scope {scope} was reused with a different request")]
+    IdempotencyConflict { scope: String, key: String },
 }
 
 pub type PlatformResult<T> = Result<T, PlatformError>;
 
+/// Machine-readable detail for a single API failure: a stable dotted `code`
+/// per [`PlatformError`] variant, a human-readable `message`, an optional
+/// `target` naming the offending field (e.g. `content.mime_type`), nested
+/// `details` so a single request with several invalid fields (a bad
+/// `TaskRequest` or `ContentQuery`) can report all of them in one round
+/// trip, and `additional_info` for other machine-consumable context such as
+/// the accepted values for an invalid enum.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ErrorDetail {
+    pub code: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ErrorDetail>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_info: Vec<ErrorAdditionalInfo>,
+}
+
+/// Machine-consumable context attached to an [`ErrorDetail`], e.g. the
+/// accepted values for an invalid `UploadStatus`/`ContentVisibility`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ErrorAdditionalInfo {
+    pub info_type: String,
+    pub info: serde_json::Value,
+}
+
+impl ErrorDetail {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            target: None,
+            details: Vec::new(),
+            additional_info: Vec::new(),
+        }
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn with_additional_info(
+        mut self,
+        info_type: impl Into<String>,
+        info: serde_json::Value,
+    ) -> Self {
+        self.additional_info.push(ErrorAdditionalInfo {
+            info_type: info_type.into(),
+            info,
+        });
+        self
+    }
+}
+
+impl From<&PlatformError> for ErrorDetail {
+    fn from(err: &PlatformError) -> Self {
+        match err {
+            PlatformError::NotFound(what) => {
+                ErrorDetail::new("platform.not_found", err.to_string()).with_target(*what)
+            }
+            PlatformError::Conflict(what) => {
+                ErrorDetail::new("platform.conflict", err.to_string()).with_target(*what)
+            }
+            PlatformError::Unauthorized => {
+                ErrorDetail::new("platform.unauthorized", err.to_string())
+            }
+            PlatformError::Forbidden => ErrorDetail::new("platform.forbidden", err.to_string()),
+            PlatformError::InvalidInput(what) => {
+                ErrorDetail::new("platform.invalid_input", err.to_string()).with_target(*what)
+            }
+            PlatformError::Locked(what) => {
+                ErrorDetail::new("platform.locked", err.to_string()).with_target(*what)
+            }
+            PlatformError::AudienceNotAllowed => {
+                ErrorDetail::new("platform.audience_not_allowed", err.to_string())
+            }
+            PlatformError::IssuerNotTrusted => {
+                ErrorDetail::new("platform.issuer_not_trusted", err.to_string())
+            }
+            PlatformError::Internal(_) => {
+                // Never echo the raw internal/database text into the
+                // machine-readable error body; the real cause belongs in
+                // the logs, not the API response.
+                ErrorDetail::new("platform.internal", "an internal error occurred")
+            }
+            PlatformError::Validation(details) => ErrorDetail {
+                code: "platform.validation".to_string(),
+                message: "one or more fields were invalid".to_string(),
+                target: None,
+                details: details.clone(),
+                additional_info: Vec::new(),
+            },
+            PlatformError::InvalidTransition { from, to } => {
+                ErrorDetail::new("platform.invalid_transition", err.to_string())
+                    .with_additional_info(
+                        "accepted_transition",
+                        serde_json::json!({ "from": from, "to": to }),
+                    )
+            }
+            PlatformError::IdempotencyConflict { scope, key } => {
+                ErrorDetail::new("platform.idempotency_conflict", err.to_string())
+                    .with_additional_info(
+                        "idempotency_key",
+                        serde_json::json!({ "scope": scope, "key": key }),
+                    )
+            }
+        }
+    }
+}
+
 #[cfg(feature = "db")]
 impl From<sqlx::Error> for PlatformError {
     fn from(err: sqlx::Error) -> Self {
         match err {
             sqlx::Error::RowNotFound => PlatformError::NotFound("record"),
-            sqlx::Error::Database(db_err) => {
-                if db_err.code().as_deref() == Some("23505") {
-                    PlatformError::Conflict("record")
-                } else {
-                    PlatformError::Internal("database error")
-                }
-            }
+            sqlx::Error::Database(db_err) => match db_err.code().as_deref() {
+                // unique_violation: the row already exists.
+                Some("23505") => PlatformError::Conflict("record already exists"),
+                // foreign_key_violation: references a row that doesn't exist.
+                Some("23503") => PlatformError::InvalidInput("referenced record does not exist"),
+                // check_violation: a column constraint rejected the value.
+                Some("23514") => PlatformError::InvalidInput("value violates a data constraint"),
+                _ => PlatformError::Internal("database error"),
+            },
             _ => PlatformError::Internal("database error"),
         }
     }
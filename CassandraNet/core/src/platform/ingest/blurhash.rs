@@ -0,0 +1,194 @@
+//! Blurhash placeholder generation for image content, run during
+//! `complete_upload_session` right after format sniffing so clients have a
+//! tiny, inline-able placeholder to paint before any derived rendition
+//! exists. Decoding goes through the `image` crate; the blurhash encode
+//! itself is hand-rolled from the public algorithm
+//! (https://github.com/woltapp/blurhash) since the encode side is small and
+//! this is the only place in the tree that needs it.
+
+use image::GenericImageView;
+
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Components used for the DCT basis on each axis. 4x3 matches what most
+/// blurhash clients expect and keeps the encoded string short (~28 chars).
+const X_COMPONENTS: u32 = 4;
+const Y_COMPONENTS: u32 = 3;
+
+/// Decode `bytes` as an image and return its blurhash placeholder, or
+/// `None` if the sniffed mime type isn't an image format we generate
+/// placeholders for, or the bytes don't decode as a valid image.
+pub fn encode_placeholder(bytes: &[u8], mime_type: &str) -> Option<String> {
+    if !is_supported(mime_type) {
+        return None;
+    }
+    let image = image::load_from_memory(bytes).ok()?;
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let rgba = image.to_rgba8();
+    Some(encode(X_COMPONENTS, Y_COMPONENTS, width, height, rgba.as_raw()))
+}
+
+fn is_supported(mime_type: &str) -> bool {
+    matches!(
+        mime_type,
+        "image/png" | "image/jpeg" | "image/gif" | "image/webp"
+    )
+}
+
+/// Encode an 8-bit RGBA buffer (row-major, top to bottom) into a blurhash
+/// string with `x_components` x `y_components` AC terms.
+fn encode(x_components: u32, y_components: u32, width: u32, height: u32, rgba: &[u8]) -> String {
+    let mut factors = Vec::with_capacity((x_components * y_components) as usize);
+    for y in 0..y_components {
+        for x in 0..x_components {
+            let normalization = if x == 0 && y == 0 { 1.0 } else { 2.0 };
+            factors.push(multiply_basis_function(
+                x,
+                y,
+                width,
+                height,
+                rgba,
+                normalization,
+            ));
+        }
+    }
+
+    let dc = factors[0];
+    let ac = &factors[1..];
+
+    let mut result = String::new();
+    let size_flag = (x_components - 1) + (y_components - 1) * 9;
+    result.push_str(&encode_base83(size_flag, 1));
+
+    let maximum_value = if !ac.is_empty() {
+        let actual_maximum_value = ac
+            .iter()
+            .flat_map(|c| c.iter().copied())
+            .fold(0.0f32, f32::max);
+        let quantized_maximum_value =
+            ((actual_maximum_value * 166.0 - 0.5).floor() as i32).clamp(0, 82) as u32;
+        result.push_str(&encode_base83(quantized_maximum_value, 1));
+        (quantized_maximum_value + 1) as f32 / 166.0
+    } else {
+        result.push_str(&encode_base83(0, 1));
+        1.0
+    };
+
+    result.push_str(&encode_base83(encode_dc(dc), 4));
+    for factor in ac {
+        result.push_str(&encode_base83(encode_ac(*factor, maximum_value), 2));
+    }
+    result
+}
+
+fn multiply_basis_function(
+    x_component: u32,
+    y_component: u32,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    normalization: f32,
+) -> [f32; 3] {
+    let mut sum = [0.0f32; 3];
+    let width = width as usize;
+    let height = height as usize;
+    for y in 0..height {
+        let basis_y =
+            (std::f32::consts::PI * y_component as f32 * y as f32 / height as f32).cos();
+        for x in 0..width {
+            let basis_x =
+                (std::f32::consts::PI * x_component as f32 * x as f32 / width as f32).cos();
+            let basis = basis_x * basis_y;
+            let offset = (y * width + x) * 4;
+            sum[0] += basis * srgb_to_linear(rgba[offset]);
+            sum[1] += basis * srgb_to_linear(rgba[offset + 1]);
+            sum[2] += basis * srgb_to_linear(rgba[offset + 2]);
+        }
+    }
+    let scale = normalization / (width * height) as f32;
+    [sum[0] * scale, sum[1] * scale, sum[2] * scale]
+}
+
+fn encode_dc(value: [f32; 3]) -> u32 {
+    let r = linear_to_srgb(value[0]);
+    let g = linear_to_srgb(value[1]);
+    let b = linear_to_srgb(value[2]);
+    (r << 16) + (g << 8) + b
+}
+
+fn encode_ac(value: [f32; 3], maximum_value: f32) -> u32 {
+    let quantize = |component: f32| -> u32 {
+        (signed_pow(component / maximum_value, 0.5) * 9.0 + 9.5)
+            .floor()
+            .clamp(0.0, 18.0) as u32
+    };
+    quantize(value[0]) * 19 * 19 + quantize(value[1]) * 19 + quantize(value[2])
+}
+
+fn signed_pow(value: f32, exponent: f32) -> f32 {
+    value.abs().powf(exponent).copysign(value)
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let v = value as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    let encoded = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round().clamp(0.0, 255.0) as u32
+}
+
+fn encode_base83(value: u32, length: usize) -> String {
+    let mut result = vec![0u8; length];
+    let mut value = value;
+    for slot in result.iter_mut().rev() {
+        *slot = BASE83_CHARS[(value % 83) as usize];
+        value /= 83;
+    }
+    String::from_utf8(result).expect("base83 alphabet is ascii")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_unsupported_mime_types() {
+        assert_eq!(encode_placeholder(b"%PDF-1.7", "application/pdf"), None);
+    }
+
+    #[test]
+    fn skips_malformed_image_bytes() {
+        assert_eq!(
+            encode_placeholder(b"\x89PNG\r\n\x1a\nnot actually a png", "image/png"),
+            None
+        );
+    }
+
+    #[test]
+    fn encodes_a_solid_color_buffer_to_a_stable_hash() {
+        let width = 4;
+        let height = 4;
+        let rgba = vec![128u8, 64, 192, 255].repeat((width * height) as usize);
+        let hash = encode(X_COMPONENTS, Y_COMPONENTS, width, height, &rgba);
+        assert_eq!(
+            hash.len(),
+            1 + 1 + 4 + (X_COMPONENTS * Y_COMPONENTS - 1) as usize * 2
+        );
+        assert_eq!(hash, encode(X_COMPONENTS, Y_COMPONENTS, width, height, &rgba));
+    }
+}
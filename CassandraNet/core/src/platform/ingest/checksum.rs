@@ -0,0 +1,122 @@
+//! Server-computed content digests, run during `complete_upload_session`
+//! once the uploaded object's bytes are in hand. The digest is both the
+//! authoritative `ContentMetadata.checksum` (never trust the client's claim)
+//! and the dedup key content-addressable storage is keyed on, so the string
+//! form is prefixed with the algorithm (`sha256:<hex>`) to stay unambiguous
+//! if a deployment switches algorithms later.
+
+use blake2::Blake2b512;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Sha256,
+    Blake2b,
+}
+
+impl ChecksumAlgorithm {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Blake2b => "blake2b",
+        }
+    }
+}
+
+impl std::str::FromStr for ChecksumAlgorithm {
+    type Err = crate::platform::error::PlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(ChecksumAlgorithm::Sha256),
+            "blake2b" => Ok(ChecksumAlgorithm::Blake2b),
+            _ => Err(crate::platform::error::PlatformError::InvalidInput(
+                "unsupported checksum algorithm",
+            )),
+        }
+    }
+}
+
+/// S3-style composite ETag for a finished multipart upload: `algorithm`'s
+/// digest of the concatenated per-part checksums, with `-<part count>`
+/// appended so a composite ETag is visually distinguishable from a
+/// single-part one (matches S3's own multipart-ETag convention). Doesn't
+/// require the parts' actual bytes, only their already-recorded checksums,
+/// so it can be computed purely from `ContentStore::list_upload_parts`.
+pub fn composite_etag(part_checksums: &[&str], algorithm: ChecksumAlgorithm) -> String {
+    let concatenated = part_checksums.concat();
+    let digest = compute_digest(concatenated.as_bytes(), algorithm);
+    format!("{digest}-{}", part_checksums.len())
+}
+
+/// Digest `bytes` with `algorithm`, formatted as `<algorithm>:<hex>` so the
+/// stored string is self-describing and two digests never collide across
+/// algorithms.
+pub fn compute_digest(bytes: &[u8], algorithm: ChecksumAlgorithm) -> String {
+    let hex = match algorithm {
+        ChecksumAlgorithm::Sha256 => {
+            let mut hasher = Sha256::new();
+            hasher.update(bytes);
+            format!("{:x}", hasher.finalize())
+        }
+        ChecksumAlgorithm::Blake2b => {
+            let mut hasher = Blake2b512::new();
+            hasher.update(bytes);
+            format!("{:x}", hasher.finalize())
+        }
+    };
+    format!("{}:{}", algorithm.as_str(), hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_digest_is_stable_and_prefixed() {
+        let a = compute_digest(b"hello world", ChecksumAlgorithm::Sha256);
+        let b = compute_digest(b"hello world", ChecksumAlgorithm::Sha256);
+        assert_eq!(a, b);
+        assert!(a.starts_with("sha256:"));
+    }
+
+    #[test]
+    fn blake2b_digest_is_stable_and_prefixed() {
+        let a = compute_digest(b"hello world", ChecksumAlgorithm::Blake2b);
+        let b = compute_digest(b"hello world", ChecksumAlgorithm::Blake2b);
+        assert_eq!(a, b);
+        assert!(a.starts_with("blake2b:"));
+    }
+
+    #[test]
+    fn different_bytes_produce_different_digests() {
+        let a = compute_digest(b"hello world", ChecksumAlgorithm::Sha256);
+        let b = compute_digest(b"goodbye world", ChecksumAlgorithm::Sha256);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_algorithm() {
+        let err = "md5".parse::<ChecksumAlgorithm>().unwrap_err();
+        assert!(matches!(
+            err,
+            crate::platform::error::PlatformError::InvalidInput(_)
+        ));
+    }
+
+    #[test]
+    fn composite_etag_is_stable_and_suffixed_with_part_count() {
+        let a = composite_etag(&["etag-1", "etag-2", "etag-3"], ChecksumAlgorithm::Sha256);
+        let b = composite_etag(&["etag-1", "etag-2", "etag-3"], ChecksumAlgorithm::Sha256);
+        assert_eq!(a, b);
+        assert!(a.ends_with("-3"));
+    }
+
+    #[test]
+    fn composite_etag_differs_from_a_reordered_part_list() {
+        let a = composite_etag(&["etag-1", "etag-2"], ChecksumAlgorithm::Sha256);
+        let b = composite_etag(&["etag-2", "etag-1"], ChecksumAlgorithm::Sha256);
+        assert_ne!(a, b);
+    }
+}
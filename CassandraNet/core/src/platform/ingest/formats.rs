@@ -0,0 +1,57 @@
+//! Magic-byte format sniffing for uploaded content, independent of whatever
+//! `Content-Type`/filename the client claimed. Deliberately conservative: a
+//! format not recognized here is treated as undetermined rather than guessed
+//! from the claimed MIME type, since the whole point is to not trust that.
+
+/// Sniff the real format of `bytes` from its leading magic bytes. Returns
+/// `None` when nothing in the known set matches, which callers should treat
+/// as "can't vouch for this content", not as a free pass.
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some("image/png");
+    }
+    if bytes.starts_with(b"\xff\xd8\xff") {
+        return Some("image/jpeg");
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some("image/gif");
+    }
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    if bytes.starts_with(b"%PDF-") {
+        return Some("application/pdf");
+    }
+    if bytes.starts_with(b"PK\x03\x04") {
+        return Some("application/zip");
+    }
+    if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        return Some("video/mp4");
+    }
+    if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+        return Some("video/webm");
+    }
+    if bytes.starts_with(b"ID3") || (bytes.len() >= 2 && bytes[0] == 0xff && bytes[1] & 0xe0 == 0xe0) {
+        return Some("audio/mpeg");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_known_magic_bytes() {
+        assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\nrest"), Some("image/png"));
+        assert_eq!(sniff_mime_type(b"\xff\xd8\xffrest"), Some("image/jpeg"));
+        assert_eq!(sniff_mime_type(b"GIF89arest"), Some("image/gif"));
+        assert_eq!(sniff_mime_type(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff_mime_type(b"PK\x03\x04rest"), Some("application/zip"));
+    }
+
+    #[test]
+    fn unrecognized_bytes_are_undetermined() {
+        assert_eq!(sniff_mime_type(b"just some plain text"), None);
+    }
+}
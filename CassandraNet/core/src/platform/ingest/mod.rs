@@ -0,0 +1,127 @@
+//! Server-side ingest pipeline invoked from `complete_upload_session` once
+//! the client's object has landed in storage. Mirrors pict-rs's split: format
+//! sniffing (`formats`) feeds allow-list/size enforcement (`validate`), and
+//! this module wires the two together so a handler never has to trust
+//! client-supplied `mime_type`/`size_bytes` directly.
+
+pub mod blurhash;
+pub mod checksum;
+pub mod formats;
+pub mod rendition;
+pub mod validate;
+
+pub use checksum::{compute_digest, composite_etag, ChecksumAlgorithm};
+pub use rendition::{generate as generate_rendition, RenditionJobPayload, RenditionSpec, RENDITION_TASK_KIND};
+pub use validate::{validate_part_sizes, validate_parts_contiguous, MIN_MULTIPART_PART_SIZE_BYTES};
+
+use crate::platform::error::PlatformResult;
+use crate::platform::models::{ContentVisibility, TenantSettings};
+use std::collections::HashMap;
+
+/// Resolved ingest limits for a single upload, derived from
+/// `TenantSettings.default_storage` (falling back to permissive defaults
+/// when a tenant hasn't configured one).
+#[derive(Debug, Clone, Default)]
+pub struct IngestPolicy {
+    pub allowed_mime_types: HashMap<String, Vec<String>>,
+    pub max_size_bytes: Option<u64>,
+    pub checksum_algorithm: ChecksumAlgorithm,
+}
+
+impl IngestPolicy {
+    pub fn from_settings(settings: Option<&TenantSettings>) -> Self {
+        match settings.and_then(|s| s.default_storage.as_ref()) {
+            Some(storage) => Self {
+                allowed_mime_types: storage.allowed_mime_types.clone(),
+                max_size_bytes: storage.max_object_size_bytes,
+                checksum_algorithm: storage
+                    .checksum_algorithm
+                    .as_deref()
+                    .and_then(|algo| algo.parse().ok())
+                    .unwrap_or_default(),
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+/// The real format and size of an uploaded object, as observed by the
+/// server rather than claimed by the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestedObject {
+    pub mime_type: String,
+    pub size_bytes: u64,
+    /// Blurhash placeholder, when `mime_type` is an image format we know
+    /// how to decode. `None` for non-image content or a decode failure;
+    /// ingest doesn't fail the upload over a missing placeholder.
+    pub blurhash: Option<String>,
+    /// Authoritative digest of the uploaded bytes, prefixed with the
+    /// algorithm that produced it (e.g. `sha256:...`). This is what gets
+    /// persisted as `ContentMetadata.checksum` and used as the dedup key.
+    pub digest: String,
+}
+
+/// Inspect a completed upload's bytes and validate them against `policy`.
+/// Returns the server-observed mime type/size to persist on
+/// `ContentMetadata`, or a `PlatformError::InvalidInput` describing why the
+/// object was rejected.
+pub fn inspect_upload(
+    bytes: &[u8],
+    visibility: &ContentVisibility,
+    claimed_mime_type: Option<&str>,
+    policy: &IngestPolicy,
+) -> PlatformResult<IngestedObject> {
+    let size_bytes = bytes.len() as u64;
+    validate::validate_size(size_bytes, policy.max_size_bytes)?;
+    let detected = formats::sniff_mime_type(bytes).ok_or(crate::platform::error::PlatformError::InvalidInput(
+        "unable to determine the uploaded content's type",
+    ))?;
+    validate::validate_mime_type(detected, claimed_mime_type, visibility, &policy.allowed_mime_types)?;
+    Ok(IngestedObject {
+        mime_type: detected.to_string(),
+        size_bytes,
+        blurhash: blurhash::encode_placeholder(bytes, detected),
+        digest: checksum::compute_digest(bytes, policy.checksum_algorithm),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_a_well_formed_png() {
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        bytes.extend_from_slice(&[0u8; 32]);
+        let policy = IngestPolicy::default();
+        let result = inspect_upload(&bytes, &ContentVisibility::Project, Some("image/png"), &policy).unwrap();
+        assert_eq!(result.mime_type, "image/png");
+        assert_eq!(result.size_bytes, bytes.len() as u64);
+        // Magic bytes alone aren't a decodable PNG, so no placeholder is
+        // generated for this fixture; inspect_upload still succeeds.
+        assert_eq!(result.blurhash, None);
+        assert_eq!(result.digest, checksum::compute_digest(&bytes, ChecksumAlgorithm::Sha256));
+    }
+
+    #[test]
+    fn rejects_content_claiming_a_different_format() {
+        let mut bytes = b"\xff\xd8\xff".to_vec();
+        bytes.extend_from_slice(&[0u8; 16]);
+        let policy = IngestPolicy::default();
+        let err = inspect_upload(&bytes, &ContentVisibility::Project, Some("image/png"), &policy).unwrap_err();
+        assert!(matches!(err, crate::platform::error::PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn rejects_oversized_object() {
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        bytes.extend_from_slice(&[0u8; 32]);
+        let policy = IngestPolicy {
+            allowed_mime_types: HashMap::new(),
+            max_size_bytes: Some(8),
+            checksum_algorithm: ChecksumAlgorithm::default(),
+        };
+        let err = inspect_upload(&bytes, &ContentVisibility::Project, None, &policy).unwrap_err();
+        assert!(matches!(err, crate::platform::error::PlatformError::InvalidInput(_)));
+    }
+}
@@ -0,0 +1,149 @@
+//! Derived-rendition generation (resized thumbnails, format transcodes),
+//! run by the gateway's rendition worker once a job is leased off the
+//! `ugc.rendition` task queue. Decoding/resizing/encoding all go through the
+//! `image` crate; this module only owns the spec normalization and cache key
+//! used to dedupe identical transform requests.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::platform::error::{PlatformError, PlatformResult};
+
+/// Task kind for derived-rendition jobs, scheduled on the shared
+/// `OrchestrationEngine` queue alongside any other background work.
+pub const RENDITION_TASK_KIND: &str = "ugc.rendition";
+
+const MAX_DIMENSION: u32 = 4096;
+
+/// A normalized thumbnail/transcode request: target bounding box and output
+/// format. Width/height behave like `image`'s `resize` (aspect ratio
+/// preserved, bounded by whichever side is tighter); omit either to bound
+/// only by the other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenditionSpec {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub format: String,
+}
+
+impl RenditionSpec {
+    /// Validate and normalize raw query params into a `RenditionSpec`,
+    /// defaulting an unset format to `webp` (small, broadly supported).
+    pub fn normalize(
+        width: Option<u32>,
+        height: Option<u32>,
+        format: Option<String>,
+    ) -> PlatformResult<Self> {
+        if width.is_none() && height.is_none() {
+            return Err(PlatformError::InvalidInput("at least one of w/h is required"));
+        }
+        for dimension in [width, height].into_iter().flatten() {
+            if dimension == 0 || dimension > MAX_DIMENSION {
+                return Err(PlatformError::InvalidInput("w/h out of range"));
+            }
+        }
+        let format = format.unwrap_or_else(|| "webp".to_string());
+        if !matches!(format.as_str(), "webp" | "png" | "jpeg") {
+            return Err(PlatformError::InvalidInput("unsupported rendition format"));
+        }
+        Ok(Self { width, height, format })
+    }
+
+    /// Deterministic cache key for this spec against a given source
+    /// checksum; identical source + spec always hash to the same key so
+    /// concurrent requesters and repeat requests share one generation.
+    pub fn cache_key(&self, source_checksum: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(source_checksum.as_bytes());
+        hasher.update(self.width.map(|w| w.to_string()).unwrap_or_default().as_bytes());
+        hasher.update(b":");
+        hasher.update(self.height.map(|h| h.to_string()).unwrap_or_default().as_bytes());
+        hasher.update(b":");
+        hasher.update(self.format.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Payload stored on the `ugc.rendition` task's JSON body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenditionJobPayload {
+    pub tenant_id: crate::platform::models::TenantId,
+    pub project_id: crate::platform::models::ProjectId,
+    pub parent_content_id: crate::platform::models::ContentId,
+    pub spec: RenditionSpec,
+    pub cache_key: String,
+}
+
+/// Decode `source_bytes`, resize to `spec`'s bounding box, and re-encode in
+/// `spec.format`. Returns the encoded bytes and the resulting mime type.
+pub fn generate(source_bytes: &[u8], spec: &RenditionSpec) -> PlatformResult<(Vec<u8>, String)> {
+    let image = image::load_from_memory(source_bytes)
+        .map_err(|_| PlatformError::InvalidInput("source content is not a decodable image"))?;
+    let (width, height) = match (spec.width, spec.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, (w as f64 * image.height() as f64 / image.width() as f64).round() as u32),
+        (None, Some(h)) => ((h as f64 * image.width() as f64 / image.height() as f64).round() as u32, h),
+        (None, None) => (image.width(), image.height()),
+    };
+    let resized = image.resize(
+        width.max(1),
+        height.max(1),
+        image::imageops::FilterType::Lanczos3,
+    );
+
+    let mut encoded = Vec::new();
+    let (mime_type, format) = match spec.format.as_str() {
+        "png" => ("image/png", image::ImageFormat::Png),
+        "jpeg" => ("image/jpeg", image::ImageFormat::Jpeg),
+        _ => ("image/webp", image::ImageFormat::WebP),
+    };
+    resized
+        .write_to(&mut std::io::Cursor::new(&mut encoded), format)
+        .map_err(|_| PlatformError::Internal("failed to encode rendition"))?;
+    Ok((encoded, mime_type.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_rejects_missing_dimensions() {
+        let err = RenditionSpec::normalize(None, None, None).unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn normalize_rejects_oversized_dimensions() {
+        let err = RenditionSpec::normalize(Some(8192), None, None).unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn normalize_defaults_format_to_webp() {
+        let spec = RenditionSpec::normalize(Some(128), None, None).unwrap();
+        assert_eq!(spec.format, "webp");
+    }
+
+    #[test]
+    fn cache_key_is_stable_for_identical_spec_and_source() {
+        let spec = RenditionSpec::normalize(Some(128), Some(128), Some("png".to_string())).unwrap();
+        let a = spec.cache_key("abc123");
+        let b = spec.cache_key("abc123");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn cache_key_differs_for_different_specs() {
+        let a = RenditionSpec::normalize(Some(128), None, None).unwrap().cache_key("abc123");
+        let b = RenditionSpec::normalize(Some(256), None, None).unwrap().cache_key("abc123");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn generate_rejects_non_image_bytes() {
+        let spec = RenditionSpec::normalize(Some(64), None, None).unwrap();
+        let err = generate(b"not an image", &spec).unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+}
@@ -0,0 +1,222 @@
+//! Allow-list and size checks applied to a sniffed format before it's
+//! trusted as `ContentMetadata.mime_type`.
+
+use crate::platform::error::{PlatformError, PlatformResult};
+use crate::platform::models::{ContentVisibility, UploadPart};
+use std::collections::HashMap;
+
+/// Fallback MIME types permitted for every `ContentVisibility` when a
+/// project hasn't configured its own allow-list via
+/// `ProjectStorageSettings.allowed_mime_types`.
+const DEFAULT_ALLOWED_MIME_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+];
+
+fn allowed_for(visibility: &ContentVisibility, allow_list: &HashMap<String, Vec<String>>) -> Vec<String> {
+    match allow_list.get(visibility.as_str()) {
+        Some(types) if !types.is_empty() => types.clone(),
+        _ => DEFAULT_ALLOWED_MIME_TYPES
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+    }
+}
+
+/// Reject a detected format that isn't on the visibility's allow-list, or
+/// that disagrees with what the client claimed.
+pub fn validate_mime_type(
+    detected: &str,
+    claimed: Option<&str>,
+    visibility: &ContentVisibility,
+    allow_list: &HashMap<String, Vec<String>>,
+) -> PlatformResult<()> {
+    if let Some(claimed) = claimed {
+        if claimed != detected {
+            return Err(PlatformError::InvalidInput(
+                "claimed mime type does not match the uploaded content",
+            ));
+        }
+    }
+    let allowed = allowed_for(visibility, allow_list);
+    if !allowed.iter().any(|mime| mime == detected) {
+        return Err(PlatformError::InvalidInput(
+            "uploaded content type is not allowed for this visibility",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject an object larger than `max_bytes`, when a limit is configured.
+pub fn validate_size(actual_bytes: u64, max_bytes: Option<u64>) -> PlatformResult<()> {
+    if let Some(max_bytes) = max_bytes {
+        if actual_bytes > max_bytes {
+            return Err(PlatformError::InvalidInput(
+                "uploaded content exceeds the configured size limit",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// S3 requires every part but the last to be at least 5 MiB; enforcing the
+/// same floor here means a multipart client written against S3 doesn't need
+/// a special case for this backend, and keeps the number of staged parts
+/// (and therefore the cost of `complete_upload_session` assembling them)
+/// bounded for a given object size.
+pub const MIN_MULTIPART_PART_SIZE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Reject a multipart completion whose parts aren't numbered contiguously
+/// from 1, or that carry an empty `etag`. Mirrors S3's own multipart
+/// completion rules so a client library written against S3 needs no
+/// special-casing for this backend.
+pub fn validate_parts_contiguous(parts: &[UploadPart]) -> PlatformResult<()> {
+    if parts.is_empty() {
+        return Err(PlatformError::InvalidInput(
+            "multipart upload has no reported parts",
+        ));
+    }
+    let mut numbers: Vec<u32> = parts.iter().map(|part| part.part_number).collect();
+    numbers.sort_unstable();
+    for (expected, actual) in (1u32..).zip(numbers) {
+        if expected != actual {
+            return Err(PlatformError::InvalidInput(
+                "multipart upload part numbers must be contiguous starting at 1",
+            ));
+        }
+    }
+    if parts.iter().any(|part| part.etag.trim().is_empty()) {
+        return Err(PlatformError::InvalidInput(
+            "multipart upload part is missing its etag",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject a multipart completion where any part other than the highest-
+/// numbered one falls under `MIN_MULTIPART_PART_SIZE_BYTES`. Only call once
+/// `validate_parts_contiguous` has already passed, so "highest-numbered" is
+/// guaranteed to mean "last".
+pub fn validate_part_sizes(parts: &[UploadPart]) -> PlatformResult<()> {
+    let Some(last) = parts.iter().map(|part| part.part_number).max() else {
+        return Ok(());
+    };
+    for part in parts {
+        if part.part_number == last {
+            continue;
+        }
+        match part.size_bytes {
+            Some(size) if size >= MIN_MULTIPART_PART_SIZE_BYTES => {}
+            _ => {
+                return Err(PlatformError::InvalidInput(
+                    "every multipart part but the last must be at least 5 MiB",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn rejects_type_outside_allow_list() {
+        let allow_list = HashMap::new();
+        let err = validate_mime_type("application/zip", None, &ContentVisibility::Private, &allow_list)
+            .unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn rejects_claimed_mismatch() {
+        let allow_list = HashMap::new();
+        let err = validate_mime_type(
+            "image/jpeg",
+            Some("image/png"),
+            &ContentVisibility::Project,
+            &allow_list,
+        )
+        .unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn allows_configured_project_override() {
+        let mut allow_list = HashMap::new();
+        allow_list.insert("private".to_string(), vec!["application/zip".to_string()]);
+        validate_mime_type("application/zip", None, &ContentVisibility::Private, &allow_list).unwrap();
+    }
+
+    #[test]
+    fn rejects_oversized_object() {
+        let err = validate_size(2_000, Some(1_000)).unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn allows_object_within_limit() {
+        validate_size(500, Some(1_000)).unwrap();
+        validate_size(500, None).unwrap();
+    }
+
+    fn part(part_number: u32, etag: &str) -> UploadPart {
+        UploadPart {
+            part_number,
+            etag: etag.to_string(),
+            size_bytes: None,
+            uploaded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rejects_non_contiguous_parts() {
+        let err = validate_parts_contiguous(&[part(1, "a"), part(3, "b")]).unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn rejects_part_missing_etag() {
+        let err = validate_parts_contiguous(&[part(1, "a"), part(2, "")]).unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn allows_contiguous_parts_out_of_order() {
+        validate_parts_contiguous(&[part(2, "b"), part(1, "a"), part(3, "c")]).unwrap();
+    }
+
+    fn sized_part(part_number: u32, size_bytes: u64) -> UploadPart {
+        UploadPart {
+            part_number,
+            etag: "etag".to_string(),
+            size_bytes: Some(size_bytes),
+            uploaded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rejects_undersized_non_final_part() {
+        let err = validate_part_sizes(&[
+            sized_part(1, MIN_MULTIPART_PART_SIZE_BYTES - 1),
+            sized_part(2, 10),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, PlatformError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn allows_undersized_final_part() {
+        validate_part_sizes(&[sized_part(1, MIN_MULTIPART_PART_SIZE_BYTES), sized_part(2, 10)]).unwrap();
+    }
+
+    #[test]
+    fn allows_a_single_part_of_any_size() {
+        validate_part_sizes(&[sized_part(1, 10)]).unwrap();
+    }
+}
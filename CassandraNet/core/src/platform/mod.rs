@@ -1,15 +1,37 @@
 pub mod auth;
+pub mod ca;
+#[cfg(test)]
+mod concurrency_sim;
+pub mod durable;
+#[cfg(feature = "embedded")]
+pub mod embedded;
 pub mod error;
+pub mod ingest;
 pub mod models;
 pub mod orchestration;
 pub mod persistence;
+#[cfg(feature = "db")]
+pub mod pg_enum;
+pub mod placement;
 pub mod provisioning;
 pub mod registry;
 
 pub use auth::*;
-pub use error::PlatformError;
+pub use ca::*;
+pub use durable::DurablePersistence;
+#[cfg(feature = "embedded")]
+pub use embedded::EmbeddedPersistence;
+pub use error::{ErrorAdditionalInfo, ErrorDetail, PlatformError, PlatformResult};
+pub use ingest::{
+    compute_digest, composite_etag, generate_rendition, inspect_upload, validate_part_sizes,
+    validate_parts_contiguous, ChecksumAlgorithm, IngestPolicy, IngestedObject, RenditionJobPayload,
+    RenditionSpec, MIN_MULTIPART_PART_SIZE_BYTES, RENDITION_TASK_KIND,
+};
 pub use models::*;
 pub use orchestration::*;
 pub use persistence::*;
+pub use placement::{select_agents, AgentCandidate, AgentCandidateSource};
+#[cfg(feature = "db")]
+pub use pg_enum::create_enum_types_sql;
 pub use provisioning::*;
 pub use registry::*;
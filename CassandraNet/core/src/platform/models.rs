@@ -1,10 +1,30 @@
-use crate::platform::error::PlatformError;
-use chrono::{DateTime, Utc};
+use crate::platform::error::{ErrorDetail, PlatformError, PlatformResult};
+use chrono::{DateTime, Duration, Utc};
 use cncommon::auth::Scope;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Shared `Deserialize` body for the status-like enums below: parse via the
+/// type's own `FromStr`, and on no match fall back to `UnknownValue(s)`
+/// instead of erroring. This keeps the gateway forward-compatible with a
+/// newer peer's status values during a rolling upgrade, mirroring the
+/// fallback `Scope::Custom` already provides for scopes.
+fn deserialize_status_or_unknown<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr<Err = PlatformError> + UnknownValueVariant,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(T::from_str(&s).unwrap_or_else(|_| T::unknown_value(s)))
+}
+
+/// Implemented by each status-like enum so the shared deserialize helper can
+/// construct its `UnknownValue` variant without matching on the concrete type.
+trait UnknownValueVariant {
+    fn unknown_value(raw: String) -> Self;
+}
+
 pub type TenantId = Uuid;
 pub type ProjectId = Uuid;
 pub type AgentId = Uuid;
@@ -35,13 +55,91 @@ pub struct ProjectStorageSettings {
     pub bucket: Option<String>,
     pub prefix: Option<String>,
     pub max_object_size_bytes: Option<u64>,
+    /// MIME types allowed per `ContentVisibility`, keyed by
+    /// `ContentVisibility::as_str()`. An empty/missing entry for a
+    /// visibility falls back to the ingest pipeline's built-in defaults.
+    #[serde(default)]
+    pub allowed_mime_types: HashMap<String, Vec<String>>,
+    /// Digest algorithm used to verify and dedup uploads for this tenant
+    /// (`"sha256"` or `"blake2b"`). Falls back to the ingest pipeline's
+    /// default when unset or unrecognized.
+    #[serde(default)]
+    pub checksum_algorithm: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AgentStatus {
     Registered,
     Active,
     Suspended,
+    /// Heartbeats have gone quiet for longer than
+    /// `AgentLifecycleConfig::degraded_after` but not yet
+    /// `unreachable_after`; set by `PostgresAgentStore::reap_stale_agents`.
+    Degraded,
+    /// Heartbeats have gone quiet for longer than
+    /// `AgentLifecycleConfig::unreachable_after`; set by
+    /// `PostgresAgentStore::reap_stale_agents`.
+    Unreachable,
+    /// Missed `heartbeat_interval_seconds * missed_heartbeat_threshold`
+    /// worth of heartbeats in the gateway's in-memory liveness sweep; set by
+    /// `AgentRegistry::mark_stale_offline`.
+    Offline,
+    /// Permanently retired via `ProvisioningService::transition_agent`'s
+    /// `AgentEvent::Decommission`; terminal, no event transitions out of it.
+    Decommissioned,
+    /// A status value this build doesn't recognize, preserved verbatim so
+    /// it round-trips unchanged instead of failing to deserialize.
+    UnknownValue(String),
+}
+
+impl AgentStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AgentStatus::Registered => "registered",
+            AgentStatus::Active => "active",
+            AgentStatus::Suspended => "suspended",
+            AgentStatus::Degraded => "degraded",
+            AgentStatus::Unreachable => "unreachable",
+            AgentStatus::Offline => "offline",
+            AgentStatus::Decommissioned => "decommissioned",
+            AgentStatus::UnknownValue(value) => value.as_str(),
+        }
+    }
+}
+
+impl std::str::FromStr for AgentStatus {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "registered" => Ok(AgentStatus::Registered),
+            "active" => Ok(AgentStatus::Active),
+            "suspended" => Ok(AgentStatus::Suspended),
+            "degraded" => Ok(AgentStatus::Degraded),
+            "unreachable" => Ok(AgentStatus::Unreachable),
+            "offline" => Ok(AgentStatus::Offline),
+            "decommissioned" => Ok(AgentStatus::Decommissioned),
+            _ => Err(PlatformError::InvalidInput("invalid agent status")),
+        }
+    }
+}
+
+impl UnknownValueVariant for AgentStatus {
+    fn unknown_value(raw: String) -> Self {
+        AgentStatus::UnknownValue(raw)
+    }
+}
+
+impl Serialize for AgentStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AgentStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_status_or_unknown(deserializer)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -56,6 +154,63 @@ pub struct Agent {
     pub metadata: AgentMetadata,
 }
 
+/// An input to `ProvisioningService::transition_agent`'s lifecycle state
+/// machine: `Registered -> Active -> Suspended -> (Active | Decommissioned)`.
+/// Any event that doesn't have a defined edge out of the agent's current
+/// status is rejected with `PlatformError::InvalidTransition`, except
+/// `Heartbeat` against a `Decommissioned` agent, which no-ops instead of
+/// erroring (a late heartbeat from a retired agent isn't a bug).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentEvent {
+    /// `Registered -> Active`; the agent's first successful check-in.
+    Enroll,
+    /// `Active -> Active`, or a no-op against `Decommissioned`; refreshes
+    /// `last_seen` without changing status otherwise.
+    Heartbeat,
+    /// `Active -> Suspended`; driven by `sweep_inactive_agents` once
+    /// heartbeats have gone stale past `heartbeat_timeout`.
+    SuspendStale,
+    /// `Suspended -> Active`; an operator or the agent itself clearing a
+    /// stale suspension.
+    Reactivate,
+    /// `Active | Suspended -> Decommissioned`; terminal.
+    Decommission,
+}
+
+impl AgentEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AgentEvent::Enroll => "enroll",
+            AgentEvent::Heartbeat => "heartbeat",
+            AgentEvent::SuspendStale => "suspend_stale",
+            AgentEvent::Reactivate => "reactivate",
+            AgentEvent::Decommission => "decommission",
+        }
+    }
+}
+
+impl std::fmt::Display for AgentEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+pub type AgentStateEventId = Uuid;
+
+/// One accepted transition out of `ProvisioningService::transition_agent`,
+/// recorded append-only in `AgentStateStore` so an agent's full lifecycle
+/// history can be reconstructed instead of only ever seeing its current
+/// `AgentStatus`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentStateEvent {
+    pub id: AgentStateEventId,
+    pub agent_id: AgentId,
+    pub from: AgentStatus,
+    pub to: AgentStatus,
+    pub at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ApiKeyRecord {
     pub id: ApiKeyId,
@@ -91,6 +246,60 @@ pub struct ProvisionedAgent {
     pub certificate_bundle: Option<Vec<u8>>,
 }
 
+/// A per-agent mTLS client certificate minted by the platform certificate
+/// authority, mirroring the `rotated_from`/`rotated_to` linkage used for
+/// [`ApiKeyRecord`]. The subject CN is the `agent_id` and the SAN carries
+/// the `tenant_id`/`project_id` so the gateway can derive an `AuthContext`
+/// straight from the peer certificate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentCertificateRecord {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub project_id: ProjectId,
+    pub agent_id: AgentId,
+    pub serial: String,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked: bool,
+    pub rotated_from: Option<Uuid>,
+    pub rotated_to: Option<Uuid>,
+}
+
+/// The PEM artifacts handed to an agent after certificate issuance. Its
+/// leaf certificate, the signing chain up to the tenant's intermediate CA,
+/// and the private key — concatenated via [`AgentCertificateBundle::to_bundle_bytes`]
+/// this is what gets stored in `ProvisionedAgent::certificate_bundle`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentCertificateBundle {
+    pub record: AgentCertificateRecord,
+    pub certificate_pem: String,
+    pub chain_pem: String,
+    pub private_key_pem: String,
+}
+
+impl AgentCertificateBundle {
+    pub fn to_bundle_bytes(&self) -> Vec<u8> {
+        format!(
+            "{}{}{}",
+            self.certificate_pem, self.chain_pem, self.private_key_pem
+        )
+        .into_bytes()
+    }
+}
+
+/// A tenant's intermediate CA keypair, persisted so every gateway process —
+/// and every replica in a multi-instance deployment — signs that tenant's
+/// agent certificates with the same CA instead of each one lazily minting
+/// its own on first use. Loaded once at startup (or on first use) and
+/// cached in memory from then on; see `CertificateAuthority::tenant_ca`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantCaRecord {
+    pub tenant_id: TenantId,
+    pub certificate_pem: String,
+    pub private_key_pem: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PrincipalType {
     Tenant,
@@ -132,6 +341,57 @@ pub struct TenantSettings {
     pub token_ttl_seconds: Option<i64>,
     pub refresh_token_ttl_seconds: Option<i64>,
     pub default_storage: Option<ProjectStorageSettings>,
+    /// Audiences a presented token's `aud` claim must match. Empty accepts
+    /// any audience, preserving behavior for tenants that haven't opted in
+    /// to restricting it. See [`super::auth::AuthService::validate_token`].
+    #[serde(default)]
+    pub allowed_audiences: Vec<String>,
+    /// Issuers a presented token's `iss` claim must match. Empty accepts
+    /// any issuer; this is the gate federated (non-gateway-minted) tokens
+    /// must pass once a tenant opts in.
+    #[serde(default)]
+    pub trusted_issuers: Vec<String>,
+    /// Cross-origin rules evaluated in order for browser-based uploaders;
+    /// the first rule whose `allowed_origins` matches the request's
+    /// `Origin` wins. Empty means the tenant has no browser-facing access
+    /// configured, so the gateway's CORS layer emits no `Access-Control-*`
+    /// headers for it. See [`CorsRule::matches_origin`].
+    #[serde(default)]
+    pub cors_rules: Vec<CorsRule>,
+}
+
+/// One per-tenant CORS rule: an origin allow-list plus the response headers
+/// to emit when a request matches it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct CorsRule {
+    /// Exact origins this rule applies to, or `["*"]` to match any origin.
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Response headers browser JS is permitted to read via
+    /// `Access-Control-Expose-Headers`.
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    /// `Access-Control-Max-Age`, how long a browser may cache a preflight.
+    #[serde(default)]
+    pub max_age_seconds: Option<i64>,
+}
+
+impl CorsRule {
+    /// Whether `origin` is covered by this rule's `allowed_origins`.
+    pub fn matches_origin(&self, origin: &str) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == origin)
+    }
+
+    /// Whether `method` (case-insensitive, as sent in
+    /// `Access-Control-Request-Method`) is covered by this rule.
+    pub fn matches_method(&self, method: &str) -> bool {
+        self.allowed_methods
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(method))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -140,12 +400,59 @@ pub struct AgentMetadata {
     pub tags: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TaskStatus {
     Pending,
     InProgress,
     Completed,
     Failed,
+    /// A status value this build doesn't recognize, preserved verbatim so
+    /// it round-trips unchanged instead of failing to deserialize.
+    UnknownValue(String),
+}
+
+impl TaskStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Failed => "failed",
+            TaskStatus::UnknownValue(value) => value.as_str(),
+        }
+    }
+}
+
+impl std::str::FromStr for TaskStatus {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(TaskStatus::Pending),
+            "in_progress" => Ok(TaskStatus::InProgress),
+            "completed" => Ok(TaskStatus::Completed),
+            "failed" => Ok(TaskStatus::Failed),
+            _ => Err(PlatformError::InvalidInput("invalid task status")),
+        }
+    }
+}
+
+impl UnknownValueVariant for TaskStatus {
+    fn unknown_value(raw: String) -> Self {
+        TaskStatus::UnknownValue(raw)
+    }
+}
+
+impl Serialize for TaskStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_status_or_unknown(deserializer)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -162,6 +469,12 @@ pub struct Task {
     pub last_error: Option<String>,
     pub result: Option<serde_json::Value>,
     pub timeouts: Option<TaskTimeouts>,
+    /// Agent ids `OrchestrationEngine::schedule_task` placed this task on,
+    /// via `placement::select_agents`. Empty when no
+    /// `AgentCandidateSource` was wired in (the common case for tests and
+    /// the bare FFI, which have no notion of a fleet to place onto).
+    #[serde(default)]
+    pub assigned_agent_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -169,6 +482,36 @@ pub struct TaskRequest {
     pub tenant_id: TenantId,
     pub kind: String,
     pub payload: serde_json::Value,
+    /// How many agents this task should be placed onto. `0` and `1` are
+    /// equivalent: `schedule_task` always creates exactly one `Task`, this
+    /// only controls how many ids land in `Task::assigned_agent_ids`.
+    pub replicas: u32,
+}
+
+impl TaskRequest {
+    /// Collects every invalid field at once into a single
+    /// [`PlatformError::Validation`] rather than erroring on the first one
+    /// found.
+    pub fn validate(&self) -> PlatformResult<()> {
+        let mut details = Vec::new();
+        if self.tenant_id.is_nil() {
+            details.push(
+                ErrorDetail::new("platform.required", "tenant_id is required")
+                    .with_target("task.tenant_id"),
+            );
+        }
+        if self.kind.trim().is_empty() {
+            details.push(
+                ErrorDetail::new("platform.required", "kind must not be empty")
+                    .with_target("task.kind"),
+            );
+        }
+        if details.is_empty() {
+            Ok(())
+        } else {
+            Err(PlatformError::Validation(details))
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -212,13 +555,62 @@ pub struct WorkflowRun {
     pub context: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WorkflowRunStatus {
     Pending,
     Running,
     Completed,
     Failed,
     Cancelled,
+    /// A status value this build doesn't recognize, preserved verbatim so
+    /// it round-trips unchanged instead of failing to deserialize.
+    UnknownValue(String),
+}
+
+impl WorkflowRunStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            WorkflowRunStatus::Pending => "pending",
+            WorkflowRunStatus::Running => "running",
+            WorkflowRunStatus::Completed => "completed",
+            WorkflowRunStatus::Failed => "failed",
+            WorkflowRunStatus::Cancelled => "cancelled",
+            WorkflowRunStatus::UnknownValue(value) => value.as_str(),
+        }
+    }
+}
+
+impl std::str::FromStr for WorkflowRunStatus {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(WorkflowRunStatus::Pending),
+            "running" => Ok(WorkflowRunStatus::Running),
+            "completed" => Ok(WorkflowRunStatus::Completed),
+            "failed" => Ok(WorkflowRunStatus::Failed),
+            "cancelled" => Ok(WorkflowRunStatus::Cancelled),
+            _ => Err(PlatformError::InvalidInput("invalid workflow run status")),
+        }
+    }
+}
+
+impl UnknownValueVariant for WorkflowRunStatus {
+    fn unknown_value(raw: String) -> Self {
+        WorkflowRunStatus::UnknownValue(raw)
+    }
+}
+
+impl Serialize for WorkflowRunStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for WorkflowRunStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_status_or_unknown(deserializer)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -234,24 +626,27 @@ pub struct TaskTimeouts {
     pub retry_backoff_seconds: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UploadStatus {
     Pending,
     Uploading,
     Completed,
     Failed,
     Cancelled,
+    Aborted,
+    UnknownValue(String),
 }
 
 impl UploadStatus {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             UploadStatus::Pending => "pending",
             UploadStatus::Uploading => "uploading",
             UploadStatus::Completed => "completed",
             UploadStatus::Failed => "failed",
             UploadStatus::Cancelled => "cancelled",
+            UploadStatus::Aborted => "aborted",
+            UploadStatus::UnknownValue(value) => value.as_str(),
         }
     }
 }
@@ -266,17 +661,41 @@ impl std::str::FromStr for UploadStatus {
             "completed" => Ok(UploadStatus::Completed),
             "failed" => Ok(UploadStatus::Failed),
             "cancelled" => Ok(UploadStatus::Cancelled),
+            "aborted" => Ok(UploadStatus::Aborted),
             _ => Err(PlatformError::InvalidInput("invalid upload status")),
         }
     }
 }
 
-impl From<UploadStatus> for &'static str {
-    fn from(value: UploadStatus) -> Self {
-        value.as_str()
+impl UnknownValueVariant for UploadStatus {
+    fn unknown_value(raw: String) -> Self {
+        UploadStatus::UnknownValue(raw)
+    }
+}
+
+impl Serialize for UploadStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for UploadStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_status_or_unknown(deserializer)
     }
 }
 
+/// One part of a multipart upload, recorded once the client reports having
+/// PUT it to its presigned URL. `etag` is opaque (whatever the storage
+/// backend returned) and is only checked for presence, not format.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UploadPart {
+    pub part_number: u32,
+    pub etag: String,
+    pub size_bytes: Option<u64>,
+    pub uploaded_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct UploadSession {
     pub id: UploadId,
@@ -289,9 +708,13 @@ pub struct UploadSession {
     pub expires_at: Option<DateTime<Utc>>,
     pub upload_url: Option<String>,
     pub headers: HashMap<String, String>,
+    /// Parts reported so far for a multipart upload. Empty for a
+    /// single-PUT session.
+    #[serde(default)]
+    pub parts: Vec<UploadPart>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ContentMetadata {
     pub id: ContentId,
     pub tenant_id: TenantId,
@@ -307,24 +730,168 @@ pub struct ContentMetadata {
     pub updated_at: DateTime<Utc>,
     pub uploaded_by: Option<Uuid>,
     pub visibility: ContentVisibility,
+    /// Blurhash placeholder for image content; `None` for non-image
+    /// content or when placeholder generation didn't run/succeed.
+    #[serde(default)]
+    pub blurhash: Option<String>,
+    /// Write-once-read-many retention policy, if any. See
+    /// [`ContentMetadata::guard_mutation`].
+    #[serde(default)]
+    pub immutability: Option<ImmutabilityPolicy>,
+    /// Independent of `immutability`: while set, mutation is rejected
+    /// regardless of the retention policy's own state or window.
+    #[serde(default)]
+    pub legal_hold: bool,
+    /// `ts_rank_cd` score from a `search_term`-filtered
+    /// `list_content_metadata` call, highest first. `None` when the query
+    /// had no `search_term` (no ranking to report) or for a backend that
+    /// can only approximate it.
+    #[serde(default)]
+    pub relevance: Option<f32>,
 }
 
+impl ContentMetadata {
+    /// `true` while a `Locked` [`ImmutabilityPolicy`] is within its
+    /// retention window, or while `legal_hold` is set.
+    pub fn retention_active(&self, now: DateTime<Utc>) -> bool {
+        if self.legal_hold {
+            return true;
+        }
+        match &self.immutability {
+            Some(policy) if policy.state == ImmutabilityState::Locked => {
+                now < self.created_at + Duration::days(policy.period_since_creation_days as i64)
+            }
+            _ => false,
+        }
+    }
+
+    /// Rejects a mutating operation (overwrite, delete, visibility
+    /// downgrade) with [`PlatformError::Locked`] while
+    /// [`Self::retention_active`] holds.
+    pub fn guard_mutation(&self, now: DateTime<Utc>) -> PlatformResult<()> {
+        if self.retention_active(now) {
+            return Err(PlatformError::Locked(
+                "content is under retention or legal hold",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Applies `new_policy`, enforcing that `Locked` is a one-way state
+    /// (once locked, the policy can no longer be replaced) and that an
+    /// `Unlocked` policy's retention period can only be extended, never
+    /// shortened.
+    pub fn apply_immutability_policy(
+        &mut self,
+        new_policy: ImmutabilityPolicy,
+    ) -> PlatformResult<()> {
+        if let Some(current) = &self.immutability {
+            if current.state == ImmutabilityState::Locked {
+                return Err(PlatformError::Locked("immutability policy is locked"));
+            }
+            if new_policy.period_since_creation_days < current.period_since_creation_days {
+                return Err(PlatformError::InvalidInput(
+                    "retention period cannot be shortened",
+                ));
+            }
+        }
+        self.immutability = Some(new_policy);
+        Ok(())
+    }
+}
+
+/// A write-once-read-many retention policy attached to [`ContentMetadata`].
+/// While `state` is [`ImmutabilityState::Locked`] and
+/// `period_since_creation_days` hasn't elapsed since the content's
+/// `created_at`, mutating operations on the content are rejected.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+pub struct ImmutabilityPolicy {
+    pub period_since_creation_days: u32,
+    pub state: ImmutabilityState,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ImmutabilityState {
+    Locked,
+    Unlocked,
+}
+
+pub type LifecyclePolicyId = Uuid;
+
+/// What `sweep_expired_content` should report for content a
+/// [`ContentLifecyclePolicy`] has aged out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LifecycleAction {
+    Delete,
+    TransitionVisibility(ContentVisibility),
+}
+
+/// A retention rule for [`ContentMetadata`], evaluated by
+/// `ContentStore::sweep_expired_content`. Scoped to a tenant and optionally
+/// narrowed to a project and/or a set of labels that must all be present
+/// (same AND semantics as `ContentQuery::tags`); an empty `label_selector`
+/// matches every object in scope.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContentLifecyclePolicy {
+    pub id: LifecyclePolicyId,
+    pub tenant_id: TenantId,
+    pub project_id: Option<ProjectId>,
+    pub label_selector: Vec<String>,
+    pub max_age_days: u32,
+    pub action: LifecycleAction,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ContentLifecyclePolicy {
+    /// `true` if `content` is in this policy's scope, regardless of age.
+    pub fn matches(&self, content: &ContentMetadata) -> bool {
+        if content.tenant_id != self.tenant_id {
+            return false;
+        }
+        if let Some(project_id) = self.project_id {
+            if content.project_id != project_id {
+                return false;
+            }
+        }
+        self.label_selector
+            .iter()
+            .all(|label| content.labels.iter().any(|l| l == label))
+    }
+
+    /// `true` if `content` has aged past `max_age_days` since its last
+    /// update, as of `now`.
+    pub fn is_expired(&self, content: &ContentMetadata, now: DateTime<Utc>) -> bool {
+        now >= content.updated_at + Duration::days(self.max_age_days as i64)
+    }
+}
+
+/// One row of `ContentStore::sweep_expired_content`'s result: `content_id`
+/// matched `policy_id` and aged out, and should have `action` applied.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContentLifecycleOutcome {
+    pub content_id: ContentId,
+    pub policy_id: LifecyclePolicyId,
+    pub action: LifecycleAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ContentVisibility {
     Private,
     Project,
     Tenant,
     Public,
+    UnknownValue(String),
 }
 
 impl ContentVisibility {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             ContentVisibility::Private => "private",
             ContentVisibility::Project => "project",
             ContentVisibility::Tenant => "tenant",
             ContentVisibility::Public => "public",
+            ContentVisibility::UnknownValue(value) => value.as_str(),
         }
     }
 }
@@ -343,9 +910,21 @@ impl std::str::FromStr for ContentVisibility {
     }
 }
 
-impl From<ContentVisibility> for &'static str {
-    fn from(value: ContentVisibility) -> Self {
-        value.as_str()
+impl UnknownValueVariant for ContentVisibility {
+    fn unknown_value(raw: String) -> Self {
+        ContentVisibility::UnknownValue(raw)
+    }
+}
+
+impl Serialize for ContentVisibility {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentVisibility {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_status_or_unknown(deserializer)
     }
 }
 
@@ -357,4 +936,364 @@ pub struct ContentQuery {
     pub tags: Vec<String>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    /// Keyset cursor: only rows that sort strictly after `(created_at, id)`
+    /// in the query's `(created_at DESC, id ASC)` order are returned. Set
+    /// together from a previously-decoded cursor; takes priority over
+    /// `offset` when both are present.
+    ///
+    /// This invariant only holds when `search_term` is unset — a
+    /// `search_term` query orders by `(relevance DESC, created_at DESC, id
+    /// ASC)` instead, which a `(created_at, id)` cursor can't express, so
+    /// [`Self::validate`] rejects the two together rather than silently
+    /// skipping or duplicating rows across pages.
+    pub cursor_created_at: Option<DateTime<Utc>>,
+    pub cursor_id: Option<ContentId>,
+}
+
+impl ContentQuery {
+    /// The largest `limit` a store implementation will accept per page.
+    pub const MAX_LIMIT: u32 = 1000;
+
+    /// Collects every invalid field at once into a single
+    /// [`PlatformError::Validation`] rather than erroring on the first one
+    /// found.
+    pub fn validate(&self) -> PlatformResult<()> {
+        let mut details = Vec::new();
+        if self.tenant_id.is_nil() {
+            details.push(
+                ErrorDetail::new("platform.required", "tenant_id is required")
+                    .with_target("content_query.tenant_id"),
+            );
+        }
+        if let Some(limit) = self.limit {
+            if limit == 0 || limit > Self::MAX_LIMIT {
+                details.push(
+                    ErrorDetail::new("platform.out_of_range", "limit is out of range")
+                        .with_target("content_query.limit")
+                        .with_additional_info(
+                            "accepted_range",
+                            serde_json::json!({ "min": 1, "max": Self::MAX_LIMIT }),
+                        ),
+                );
+            }
+        }
+        if self.search_term.is_some() && (self.cursor_created_at.is_some() || self.cursor_id.is_some())
+        {
+            details.push(
+                ErrorDetail::new(
+                    "platform.invalid_combination",
+                    "cursor pagination is not supported together with search_term, since a \
+                     search-ranked page doesn't sort by (created_at, id)",
+                )
+                .with_target("content_query.cursor_created_at"),
+            );
+        }
+        if details.is_empty() {
+            Ok(())
+        } else {
+            Err(PlatformError::Validation(details))
+        }
+    }
+}
+
+/// The subsystem a privileged mutation belongs to, for filtering the audit
+/// trail (`AuditEvent::area`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditArea {
+    Provisioning,
+    Orchestration,
+    Content,
+    Auth,
+}
+
+/// The kind of mutation an audit event records.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditCategory {
+    Create,
+    Modify,
+    Remove,
+    Access,
+}
+
+/// An immutable record of a privileged mutation, tagged with the acting
+/// principal and always scoped to a tenant so queries can't cross tenant
+/// boundaries. `action_id` is a dotted string (`apikey.rotate`,
+/// `agent.suspend`, `content.visibility_change`) identifying the specific
+/// operation; `diff` is an optional before/after payload for operations
+/// where that's meaningful.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub action_id: String,
+    pub area: AuditArea,
+    pub category: AuditCategory,
+    pub actor_id: Uuid,
+    pub actor_type: PrincipalType,
+    pub target_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub diff: Option<serde_json::Value>,
+}
+
+pub type ModerationEventId = Uuid;
+
+/// One state transition in a `ModeratedContent` item's review history,
+/// written by `ModerationStore::update_content_state` so appeals and
+/// compliance reviews can see who reversed a decision and why —
+/// `ugc_moderation_content` itself only ever holds the *current* state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModerationEvent {
+    pub id: ModerationEventId,
+    pub content_id: ContentId,
+    pub from_state: ModerationState,
+    pub to_state: ModerationState,
+    pub reason: Option<String>,
+    pub actor_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type ModerationReportId = Uuid;
+
+/// Why a user flagged a piece of content via `ModerationStore::create_report`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportCategory {
+    Spam,
+    Abuse,
+    Illegal,
+    IntellectualProperty,
+    Other,
+    UnknownValue(String),
+}
+
+impl ReportCategory {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ReportCategory::Spam => "spam",
+            ReportCategory::Abuse => "abuse",
+            ReportCategory::Illegal => "illegal",
+            ReportCategory::IntellectualProperty => "intellectual_property",
+            ReportCategory::Other => "other",
+            ReportCategory::UnknownValue(value) => value.as_str(),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportCategory {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spam" => Ok(ReportCategory::Spam),
+            "abuse" => Ok(ReportCategory::Abuse),
+            "illegal" => Ok(ReportCategory::Illegal),
+            "intellectual_property" => Ok(ReportCategory::IntellectualProperty),
+            "other" => Ok(ReportCategory::Other),
+            _ => Err(PlatformError::InvalidInput("invalid report category")),
+        }
+    }
+}
+
+impl UnknownValueVariant for ReportCategory {
+    fn unknown_value(raw: String) -> Self {
+        ReportCategory::UnknownValue(raw)
+    }
+}
+
+impl Serialize for ReportCategory {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ReportCategory {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_status_or_unknown(deserializer)
+    }
+}
+
+/// A user-submitted flag against a piece of content, independent of
+/// `ModeratedContent.state`: a report can come in well before (or after) the
+/// content enters moderation review.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModerationReport {
+    pub id: ModerationReportId,
+    pub content_id: ContentId,
+    pub reporter_id: Uuid,
+    pub category: ReportCategory,
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Cleared once a moderator records a state transition for the
+    /// reported content; `list_content`'s triage sort counts reports with
+    /// `resolved: false` so actioned items drop out of the queue.
+    pub resolved: bool,
+}
+
+pub type ModerationAuditEntryId = Uuid;
+
+/// Genesis value `sequence: 1`'s `hash` chains from, for a `content_id`
+/// that has no prior audit entry. Not itself a valid hash output, so it
+/// can never collide with a real entry's `hash`.
+pub const MODERATION_AUDIT_GENESIS_HASH: &str = "genesis";
+
+/// One entry in the tamper-evident audit log `ModerationStore::list_audit`
+/// returns for a `content_id`. Unlike `ModerationEvent` (which
+/// `list_content_events` already exposes and which a write to
+/// `ugc_moderation_events` could in principle be edited or deleted
+/// without a trace), each entry also carries `hash` —
+/// `H(previous_entry.hash || this_entry)`, with the first entry for a
+/// `content_id` chaining from [`MODERATION_AUDIT_GENESIS_HASH`] — so an
+/// auditor who recomputes the chain from `sequence: 1` onward and gets a
+/// different hash for the last entry than `ModerationStore::audit_chain_head`
+/// reports knows something in between was altered, reordered, or removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModerationAuditEntry {
+    pub id: ModerationAuditEntryId,
+    pub content_id: ContentId,
+    /// 1-based position of this entry in `content_id`'s chain.
+    pub sequence: u64,
+    pub from_state: ModerationState,
+    pub to_state: ModerationState,
+    pub reason: Option<String>,
+    pub actor_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub hash: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_content(created_at: DateTime<Utc>) -> ContentMetadata {
+        ContentMetadata {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            filename: "report.pdf".to_string(),
+            mime_type: Some("application/pdf".to_string()),
+            size_bytes: Some(1024),
+            checksum: None,
+            storage_path: None,
+            labels: vec![],
+            attributes: HashMap::new(),
+            created_at,
+            updated_at: created_at,
+            uploaded_by: None,
+            visibility: ContentVisibility::Private,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        }
+    }
+
+    #[test]
+    fn guard_mutation_rejects_while_locked_and_within_window() {
+        let now = Utc::now();
+        let mut content = fixture_content(now);
+        content.immutability = Some(ImmutabilityPolicy {
+            period_since_creation_days: 30,
+            state: ImmutabilityState::Locked,
+        });
+        assert!(content.guard_mutation(now).is_err());
+    }
+
+    #[test]
+    fn guard_mutation_allows_once_the_retention_window_has_elapsed() {
+        let created_at = Utc::now() - Duration::days(31);
+        let mut content = fixture_content(created_at);
+        content.immutability = Some(ImmutabilityPolicy {
+            period_since_creation_days: 30,
+            state: ImmutabilityState::Locked,
+        });
+        assert!(content.guard_mutation(Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn guard_mutation_rejects_under_legal_hold_regardless_of_policy() {
+        let now = Utc::now();
+        let mut content = fixture_content(now);
+        content.legal_hold = true;
+        assert!(content.guard_mutation(now).is_err());
+    }
+
+    #[test]
+    fn guard_mutation_allows_unlocked_policy() {
+        let now = Utc::now();
+        let mut content = fixture_content(now);
+        content.immutability = Some(ImmutabilityPolicy {
+            period_since_creation_days: 30,
+            state: ImmutabilityState::Unlocked,
+        });
+        assert!(content.guard_mutation(now).is_ok());
+    }
+
+    #[test]
+    fn apply_immutability_policy_allows_extending_an_unlocked_period() {
+        let mut content = fixture_content(Utc::now());
+        content.immutability = Some(ImmutabilityPolicy {
+            period_since_creation_days: 30,
+            state: ImmutabilityState::Unlocked,
+        });
+        let result = content.apply_immutability_policy(ImmutabilityPolicy {
+            period_since_creation_days: 60,
+            state: ImmutabilityState::Unlocked,
+        });
+        assert!(result.is_ok());
+        assert_eq!(content.immutability.unwrap().period_since_creation_days, 60);
+    }
+
+    #[test]
+    fn apply_immutability_policy_rejects_shortening_an_unlocked_period() {
+        let mut content = fixture_content(Utc::now());
+        content.immutability = Some(ImmutabilityPolicy {
+            period_since_creation_days: 30,
+            state: ImmutabilityState::Unlocked,
+        });
+        let result = content.apply_immutability_policy(ImmutabilityPolicy {
+            period_since_creation_days: 10,
+            state: ImmutabilityState::Unlocked,
+        });
+        assert!(result.is_err());
+        assert_eq!(content.immutability.unwrap().period_since_creation_days, 30);
+    }
+
+    #[test]
+    fn apply_immutability_policy_cannot_replace_a_locked_policy() {
+        let mut content = fixture_content(Utc::now());
+        content.immutability = Some(ImmutabilityPolicy {
+            period_since_creation_days: 30,
+            state: ImmutabilityState::Locked,
+        });
+        let result = content.apply_immutability_policy(ImmutabilityPolicy {
+            period_since_creation_days: 90,
+            state: ImmutabilityState::Locked,
+        });
+        assert!(result.is_err());
+        assert_eq!(content.immutability.unwrap().period_since_creation_days, 30);
+    }
+
+    #[test]
+    fn content_query_rejects_cursor_together_with_search_term() {
+        let query = ContentQuery {
+            tenant_id: Uuid::new_v4(),
+            search_term: Some("report".to_string()),
+            cursor_created_at: Some(Utc::now()),
+            cursor_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(query.validate().is_err());
+    }
+
+    #[test]
+    fn content_query_allows_cursor_without_search_term() {
+        let query = ContentQuery {
+            tenant_id: Uuid::new_v4(),
+            cursor_created_at: Some(Utc::now()),
+            cursor_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(query.validate().is_ok());
+    }
 }
@@ -1,7 +1,12 @@
 use super::error::{PlatformError, PlatformResult};
 use super::models::*;
-use super::persistence::{TaskStore, WorkflowStore};
-use chrono::{Duration, Utc};
+use super::persistence::{
+    InMemoryLeaseStore, LeaseConflict, LeaseRecord, LeaseStore, TaskStore, WorkflowStore,
+};
+use super::placement::{self, AgentCandidateSource};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use cncommon::observability::{EventSink, FleetEvent, InMemoryMetricsRegistry, NoopEventSink};
 use parking_lot::RwLock;
 use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
@@ -14,6 +19,12 @@ pub struct TaskPolicy {
     pub max_retries: u32,
     pub backoff_seconds: Option<u64>,
     pub priority: u32,
+    /// How much to throttle scheduling of this kind when many of its tasks
+    /// are already inflight, 0 (no throttle) to 10 (heaviest). `0` keeps
+    /// `scheduled_at` unchanged regardless of inflight count; values above
+    /// it add that many seconds of delay per task of this kind currently
+    /// `InProgress`. Values above 10 are clamped when applied.
+    pub tranquility: u8,
 }
 
 impl Default for TaskPolicy {
@@ -23,6 +34,7 @@ impl Default for TaskPolicy {
             max_retries: 3,
             backoff_seconds: Some(30),
             priority: 100,
+            tranquility: 0,
         }
     }
 }
@@ -34,6 +46,119 @@ pub enum SchedulerStrategy {
     FairnessByKind,
 }
 
+/// How long a registered worker can go without a [`OrchestrationEngine::heartbeat`]
+/// before [`OrchestrationEngine::list_workers`] stops calling it `Active`.
+const WORKER_IDLE_AFTER: Duration = Duration::seconds(30);
+/// How long past its last heartbeat a worker is presumed dead — its leases
+/// are fair game for [`OrchestrationEngine::reap_expired_leases`] even if
+/// they haven't individually expired yet.
+const WORKER_DEAD_AFTER: Duration = Duration::seconds(120);
+
+/// A worker's liveness as inferred from how long it's been since its last
+/// heartbeat, mirroring a typical background-task manager's worker list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerStatus {
+    Active,
+    Idle,
+    Dead,
+}
+
+#[derive(Debug, Clone)]
+struct WorkerRecord {
+    registered_at: DateTime<Utc>,
+    last_heartbeat: DateTime<Utc>,
+}
+
+/// A worker as seen from the outside: its liveness classification plus the
+/// tasks its current leases cover, so an operator can tell which workers
+/// are alive and what they're holding.
+#[derive(Debug, Clone)]
+pub struct WorkerView {
+    pub worker_id: Uuid,
+    pub status: WorkerStatus,
+    pub registered_at: DateTime<Utc>,
+    pub last_heartbeat: DateTime<Utc>,
+    pub held_tasks: Vec<TaskId>,
+}
+
+/// What a [`ScheduleEntry`] fires when its cadence is due.
+#[derive(Debug, Clone)]
+pub enum ScheduleTarget {
+    Task { kind: String },
+    Workflow { workflow_id: WorkflowId },
+}
+
+/// How often a [`ScheduleEntry`] recurs.
+#[derive(Debug, Clone)]
+pub enum Cadence {
+    /// Fires every `interval`, counted from whenever it last fired (or from
+    /// registration for the first fire).
+    Interval(Duration),
+    /// A standard cron expression, evaluated against UTC.
+    Cron(String),
+}
+
+impl Cadence {
+    /// The next fire time strictly after `after`, or `None` if `self` is an
+    /// unparseable cron expression.
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Cadence::Interval(interval) => Some(after + *interval),
+            Cadence::Cron(expr) => {
+                let schedule: cron::Schedule = expr.parse().ok()?;
+                schedule.after(&after).next()
+            }
+        }
+    }
+}
+
+/// A recurring `schedule_task`/`schedule_workflow` entry, ticked forward by
+/// [`OrchestrationEngine::tick`] instead of firing once on demand.
+#[derive(Debug, Clone)]
+pub struct ScheduleEntry {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub target: ScheduleTarget,
+    pub payload: Value,
+    pub cadence: Cadence,
+    pub next_fire_at: DateTime<Utc>,
+    pub last_fired_at: Option<DateTime<Utc>>,
+}
+
+/// Runs the work for one task kind, registered against [`OrchestrationEngine`]
+/// via `register_handler` so `run_worker` can dispatch leased tasks to it
+/// without the embedding application building its own worker harness.
+/// `Ok(result)` completes the task (via `complete_task`); `Err(message)`
+/// fails it through the normal retry/backoff path (`fail_task`, `retry:
+/// true`).
+#[async_trait]
+pub trait TaskHandler: Send + Sync {
+    async fn run(&self, ctx: TaskContext) -> Result<Option<Value>, String>;
+}
+
+/// What a [`TaskHandler`] sees for the task it's running: the task itself,
+/// the workflow step it belongs to (if scheduled as one), and a way to
+/// renew its own lease for work that outlives the original lease window.
+pub struct TaskContext {
+    pub task: Task,
+    pub worker_id: Uuid,
+    pub lease_token: Uuid,
+    pub workflow_run_id: Option<Uuid>,
+    pub workflow_step_id: Option<Uuid>,
+    engine: Arc<OrchestrationEngine>,
+}
+
+impl TaskContext {
+    /// Extends this task's own lease by `extend_by`. Fails the same way
+    /// `renew_task_lease` would (expired lease, version conflict, etc.) —
+    /// a handler doing long-running work should check the result rather
+    /// than assuming the extension always lands.
+    pub fn renew_lease(&self, extend_by: Duration) -> PlatformResult<TaskLease> {
+        self.engine
+            .renew_task_lease(self.task.id, self.worker_id, self.lease_token, extend_by)
+    }
+}
+
 struct WorkflowRunState {
     run: WorkflowRun,
     step_lookup: HashMap<Uuid, WorkflowStep>,
@@ -76,7 +201,9 @@ impl WorkflowRunState {
                                     self.completed_kinds.contains(&dep.task_kind)
                                 }
                                 TaskStatus::Failed => self.failed_kinds.contains(&dep.task_kind),
-                                TaskStatus::Pending | TaskStatus::InProgress => true,
+                                TaskStatus::Pending
+                                | TaskStatus::InProgress
+                                | TaskStatus::UnknownValue(_) => true,
                             })
                     })
                     .unwrap_or(false)
@@ -117,15 +244,6 @@ impl WorkflowRunState {
     }
 }
 
-#[derive(Clone)]
-struct LeaseState {
-    version: u64,
-    token: Uuid,
-    worker_id: Uuid,
-    leased_at: chrono::DateTime<Utc>,
-    lease_expires_at: chrono::DateTime<Utc>,
-}
-
 struct WorkflowContext {
     _workflow_id: Uuid,
     run_id: Uuid,
@@ -138,8 +256,26 @@ pub struct OrchestrationEngine {
     scheduler: RwLock<SchedulerStrategy>,
     task_policies: RwLock<HashMap<String, TaskPolicy>>,
     workflow_runs: RwLock<HashMap<Uuid, WorkflowRunState>>,
-    lease_states: RwLock<HashMap<TaskId, LeaseState>>,
+    leases: Arc<dyn LeaseStore>,
     last_kind: RwLock<Option<String>>,
+    workers: RwLock<HashMap<Uuid, WorkerRecord>>,
+    paused_kinds: RwLock<HashSet<String>>,
+    schedules: RwLock<HashMap<Uuid, ScheduleEntry>>,
+    handlers: RwLock<HashMap<String, Arc<dyn TaskHandler>>>,
+    /// Supplies live agents to `schedule_task`'s placement step. `None`
+    /// (the default) means callers get a `Task` with an empty
+    /// `assigned_agent_ids` — the right behavior for tests and the bare FFI,
+    /// which have no registry of agents to place work onto.
+    candidate_source: RwLock<Option<Arc<dyn AgentCandidateSource>>>,
+    /// Fans out `TaskScheduled` for every `schedule_task` call. Defaults to
+    /// `NoopEventSink` so embedding this engine without a bus configured
+    /// costs nothing.
+    events: RwLock<Arc<dyn EventSink>>,
+    /// Records `cass_tasks_scheduled_total` for every `schedule_task` call.
+    /// Owned rather than optional since it's a plain in-memory counter map;
+    /// defaults to a private instance, so callers that never `set_metrics`
+    /// just get series nobody scrapes rather than a branch on every call.
+    metrics: RwLock<InMemoryMetricsRegistry>,
 }
 
 impl OrchestrationEngine {
@@ -150,11 +286,50 @@ impl OrchestrationEngine {
             scheduler: RwLock::new(SchedulerStrategy::Fifo),
             task_policies: RwLock::new(HashMap::new()),
             workflow_runs: RwLock::new(HashMap::new()),
-            lease_states: RwLock::new(HashMap::new()),
+            leases: Arc::new(InMemoryLeaseStore::new()),
             last_kind: RwLock::new(None),
+            workers: RwLock::new(HashMap::new()),
+            paused_kinds: RwLock::new(HashSet::new()),
+            schedules: RwLock::new(HashMap::new()),
+            handlers: RwLock::new(HashMap::new()),
+            candidate_source: RwLock::new(None),
+            events: RwLock::new(Arc::new(NoopEventSink)),
+            metrics: RwLock::new(InMemoryMetricsRegistry::new()),
         }
     }
 
+    /// Wires in a source of live agents (e.g. the gateway's
+    /// `AgentRegistry`) so `schedule_task` can place tasks with
+    /// `placement::select_agents` instead of always leaving
+    /// `Task::assigned_agent_ids` empty.
+    pub fn set_candidate_source(&self, source: Arc<dyn AgentCandidateSource>) {
+        *self.candidate_source.write() = Some(source);
+    }
+
+    /// Swaps in a sink that gets a `TaskScheduled` event for every
+    /// `schedule_task` call (including ones `schedule_workflow`/`tick`
+    /// trigger internally), so dashboards or other workers can react without
+    /// polling. Defaults to `NoopEventSink`.
+    pub fn set_events(&self, events: Arc<dyn EventSink>) {
+        *self.events.write() = events;
+    }
+
+    /// Points this engine's scheduling counter at a shared registry (e.g.
+    /// the gateway's `AppState::telemetry.metrics`) instead of its own
+    /// private, unscraped one.
+    pub fn set_metrics(&self, metrics: InMemoryMetricsRegistry) {
+        *self.metrics.write() = metrics;
+    }
+
+    /// Swaps in a shared [`LeaseStore`] (e.g. a Postgres/etcd-backed one) so
+    /// several `OrchestrationEngine`s can point at the same `TaskStore`
+    /// without double-leasing a task. Defaults to an in-process
+    /// [`InMemoryLeaseStore`], which is only safe for a single instance.
+    pub fn with_leases(mut self, leases: Arc<dyn LeaseStore>) -> Self {
+        self.leases = leases;
+        self
+    }
+
     pub fn set_scheduler_strategy(&self, strategy: SchedulerStrategy) {
         *self.last_kind.write() = None;
         *self.scheduler.write() = strategy;
@@ -164,6 +339,26 @@ impl OrchestrationEngine {
         self.task_policies.write().insert(kind.into(), policy);
     }
 
+    /// Stops `select_task` from handing out any task of `kind`, without
+    /// touching tasks already leased. Lets an operator calm a noisy kind
+    /// while leaving everything else scheduling normally.
+    pub fn pause_kind(&self, kind: impl Into<String>) {
+        self.paused_kinds.write().insert(kind.into());
+    }
+
+    /// Undoes [`pause_kind`](Self::pause_kind), letting `kind` be scheduled
+    /// again.
+    pub fn resume_kind(&self, kind: &str) {
+        self.paused_kinds.write().remove(kind);
+    }
+
+    /// Registers `handler` to run every task of `kind` leased by
+    /// `run_worker`. Replaces whatever was previously registered for that
+    /// kind.
+    pub fn register_handler(&self, kind: impl Into<String>, handler: Arc<dyn TaskHandler>) {
+        self.handlers.write().insert(kind.into(), handler);
+    }
+
     pub fn register_workflow(
         &self,
         tenant_id: TenantId,
@@ -173,6 +368,7 @@ impl OrchestrationEngine {
         if steps.is_empty() {
             return Err(PlatformError::InvalidInput("workflow steps required"));
         }
+        Self::validate_steps(&steps)?;
         let workflow = Workflow {
             id: Uuid::new_v4(),
             tenant_id,
@@ -185,6 +381,7 @@ impl OrchestrationEngine {
     }
 
     pub fn schedule_task(&self, request: TaskRequest) -> PlatformResult<Task> {
+        request.validate()?;
         let policy = self
             .task_policies
             .read()
@@ -193,6 +390,26 @@ impl OrchestrationEngine {
             .unwrap_or_default();
         let timeouts = policy.timeouts.clone();
         let now = Utc::now();
+        let mut scheduled_at = now;
+        if policy.tranquility > 0 {
+            let inflight = self
+                .tasks
+                .list_tasks_by_kind(request.tenant_id, &request.kind)?
+                .iter()
+                .filter(|task| task.status == TaskStatus::InProgress)
+                .count();
+            if inflight > 0 {
+                let delay = inflight as i64 * policy.tranquility.min(10) as i64;
+                scheduled_at = now + Duration::seconds(delay);
+            }
+        }
+        let assigned_agent_ids = match self.candidate_source.read().as_ref() {
+            Some(source) => placement::select_agents(
+                &source.candidates(),
+                request.replicas.max(1) as usize,
+            )?,
+            None => Vec::new(),
+        };
         let task = Task {
             id: Uuid::new_v4(),
             tenant_id: request.tenant_id,
@@ -200,14 +417,27 @@ impl OrchestrationEngine {
             payload: request.payload,
             status: TaskStatus::Pending,
             attempts: 0,
-            scheduled_at: now,
+            scheduled_at,
             started_at: None,
             completed_at: None,
             last_error: None,
             result: None,
             timeouts,
+            assigned_agent_ids,
         };
         self.tasks.enqueue_task(task.clone())?;
+        let mut kind_label = HashMap::new();
+        kind_label.insert("kind".to_string(), task.kind.clone());
+        self.metrics
+            .read()
+            .increment_counter("cass_tasks_scheduled_total", 1.0, Some(kind_label));
+        self.events.read().publish(&FleetEvent::TaskScheduled {
+            task_id: task.id.to_string(),
+            tenant_id: task.tenant_id.to_string(),
+            kind: task.kind.clone(),
+            assigned_agent_ids: task.assigned_agent_ids.clone(),
+            timestamp: Utc::now(),
+        });
         Ok(task)
     }
 
@@ -251,6 +481,7 @@ impl OrchestrationEngine {
                 tenant_id,
                 kind: step.task_kind.clone(),
                 payload,
+                replicas: 1,
             })?;
             scheduled.push(task);
         }
@@ -268,16 +499,60 @@ impl OrchestrationEngine {
         worker_id: Uuid,
         lease_ttl: Duration,
     ) -> PlatformResult<Option<TaskLease>> {
-        let pending = self.tasks.list_pending_tasks(tenant_id)?;
-        let Some(mut task) = self.select_task(&pending) else {
-            return Ok(None);
-        };
-        let now = Utc::now();
-        task.status = TaskStatus::InProgress;
-        task.started_at = Some(now);
-        self.tasks.update_task(task.clone())?;
-        let lease = self.start_lease(&task, worker_id, lease_ttl);
-        Ok(Some(lease))
+        Ok(self
+            .lease_next_tasks(tenant_id, worker_id, 1, lease_ttl)?
+            .into_iter()
+            .next())
+    }
+
+    /// Task-first batch leasing: picks up to `max` distinct pending tasks in
+    /// one pass (applying the current `SchedulerStrategy` repeatedly against
+    /// what's left after each pick) and opens a lease for each, instead of a
+    /// worker with spare capacity round-tripping `lease_next_task` once per
+    /// task. Under `FairnessByKind`, `last_kind` rotates across the whole
+    /// batch the same way it does across separate `lease_next_task` calls
+    /// (`select_task` updates it on every pick), so a single worker asking
+    /// for a big batch still can't drain one kind at everyone else's
+    /// expense. A task that loses its lease race (`start_lease` returns
+    /// `None`) is put back to `Pending` and skipped rather than aborting
+    /// the rest of the batch; the returned `Vec` can be shorter than `max`
+    /// if fewer tasks were available or won.
+    pub fn lease_next_tasks(
+        &self,
+        tenant_id: TenantId,
+        worker_id: Uuid,
+        max: usize,
+        lease_ttl: Duration,
+    ) -> PlatformResult<Vec<TaskLease>> {
+        if max == 0 {
+            return Ok(Vec::new());
+        }
+        let mut remaining = self.tasks.list_pending_tasks(tenant_id)?;
+        let mut leases = Vec::new();
+        while leases.len() < max {
+            let Some(mut task) = self.select_task(&remaining) else {
+                break;
+            };
+            remaining.retain(|candidate| candidate.id != task.id);
+            let now = Utc::now();
+            task.status = TaskStatus::InProgress;
+            task.started_at = Some(now);
+            self.tasks.update_task(task.clone())?;
+            match self.start_lease(&task, worker_id, lease_ttl) {
+                Some(lease) => leases.push(lease),
+                None => {
+                    // Another node's `OrchestrationEngine` already won this
+                    // task's lease between `list_pending_tasks` and here;
+                    // put it back to `Pending` and keep filling the rest of
+                    // the batch instead of leaving it stuck `InProgress`
+                    // with no lease behind it, or aborting the whole batch.
+                    task.status = TaskStatus::Pending;
+                    task.started_at = None;
+                    self.tasks.update_task(task)?;
+                }
+            }
+        }
+        Ok(leases)
     }
 
     pub fn complete_task(
@@ -347,37 +622,137 @@ impl OrchestrationEngine {
         lease_token: Uuid,
         extend_by: Duration,
     ) -> PlatformResult<TaskLease> {
-        let mut leases = self.lease_states.write();
-        let state = leases
-            .get_mut(&task_id)
+        let current = self
+            .leases
+            .get(task_id)
             .ok_or(PlatformError::InvalidInput("lease not found"))?;
-        if state.worker_id != worker_id {
+        if current.worker_id != worker_id {
             return Err(PlatformError::InvalidInput("worker mismatch"));
         }
-        if state.token != lease_token {
+        if current.token != lease_token {
             return Err(PlatformError::InvalidInput("invalid lease token"));
         }
-        if state.lease_expires_at < Utc::now() {
+        if current.lease_expires_at < Utc::now() {
             return Err(PlatformError::InvalidInput("lease expired"));
         }
-        state.version += 1;
-        state.lease_expires_at += extend_by;
-        let lease_state = state.clone();
-        drop(leases);
+        let new_expiry = current.lease_expires_at + extend_by;
+        let renewed = self
+            .leases
+            .renew(task_id, lease_token, worker_id, current.version, new_expiry)
+            .map_err(|_| {
+                PlatformError::InvalidInput(
+                    "lease renewal conflict; another node already moved this lease forward",
+                )
+            })?;
         let task = self
             .tasks
             .get_task(task_id)?
             .ok_or(PlatformError::NotFound("task"))?;
         Ok(TaskLease {
             task,
-            worker_id: lease_state.worker_id,
-            leased_at: lease_state.leased_at,
-            lease_expires_at: lease_state.lease_expires_at,
-            lease_version: lease_state.version,
-            lease_token: lease_state.token,
+            worker_id: renewed.worker_id,
+            leased_at: renewed.leased_at,
+            lease_expires_at: renewed.lease_expires_at,
+            lease_version: renewed.version,
+            lease_token: renewed.token,
         })
     }
 
+    /// Registers `worker_id` so its heartbeats (and any leases it later
+    /// wins) show up in [`list_workers`](Self::list_workers). A no-op if the
+    /// worker is already registered — its `registered_at`/heartbeat history
+    /// is left alone rather than reset.
+    pub fn register_worker(&self, worker_id: Uuid) {
+        let now = Utc::now();
+        self.workers
+            .write()
+            .entry(worker_id)
+            .or_insert(WorkerRecord {
+                registered_at: now,
+                last_heartbeat: now,
+            });
+    }
+
+    /// Records that `worker_id` is still alive. Errors if the worker was
+    /// never registered, since a heartbeat from an unknown worker usually
+    /// means it restarted with a stale id and should re-register first.
+    pub fn heartbeat(&self, worker_id: Uuid) -> PlatformResult<()> {
+        let mut guard = self.workers.write();
+        let record = guard
+            .get_mut(&worker_id)
+            .ok_or(PlatformError::NotFound("worker"))?;
+        record.last_heartbeat = Utc::now();
+        Ok(())
+    }
+
+    /// Every registered worker, classified `Active`/`Idle`/`Dead` by how
+    /// long it's been since its last heartbeat, alongside the tasks its
+    /// current leases cover.
+    pub fn list_workers(&self) -> Vec<WorkerView> {
+        let now = Utc::now();
+        let leases = self.leases.list_all();
+        self.workers
+            .read()
+            .iter()
+            .map(|(worker_id, record)| {
+                let since_heartbeat = now - record.last_heartbeat;
+                let status = if since_heartbeat <= WORKER_IDLE_AFTER {
+                    WorkerStatus::Active
+                } else if since_heartbeat <= WORKER_DEAD_AFTER {
+                    WorkerStatus::Idle
+                } else {
+                    WorkerStatus::Dead
+                };
+                let held_tasks = leases
+                    .iter()
+                    .filter(|(_, lease)| lease.worker_id == *worker_id)
+                    .map(|(task_id, _)| *task_id)
+                    .collect();
+                WorkerView {
+                    worker_id: *worker_id,
+                    status,
+                    registered_at: record.registered_at,
+                    last_heartbeat: record.last_heartbeat,
+                    held_tasks,
+                }
+            })
+            .collect()
+    }
+
+    /// Scans every outstanding lease for one that's past its expiry, or
+    /// whose worker hasn't heartbeat in [`WORKER_DEAD_AFTER`], and requeues
+    /// the task it covers through the same retry/backoff path as
+    /// [`fail_task`](Self::fail_task) — incrementing `attempts` and honoring
+    /// `max_retries` rather than assuming the task can always go straight
+    /// back to `Pending`. Returns every task it touched.
+    pub fn reap_expired_leases(&self, now: DateTime<Utc>) -> PlatformResult<Vec<Task>> {
+        let dead_workers: HashSet<Uuid> = self
+            .workers
+            .read()
+            .iter()
+            .filter(|(_, record)| now - record.last_heartbeat > WORKER_DEAD_AFTER)
+            .map(|(worker_id, _)| *worker_id)
+            .collect();
+        let mut reaped = Vec::new();
+        for (task_id, lease) in self.leases.list_all() {
+            let expired = lease.lease_expires_at < now || dead_workers.contains(&lease.worker_id);
+            if !expired {
+                continue;
+            }
+            match self.tasks.get_task(task_id)? {
+                Some(task) if task.status == TaskStatus::InProgress => {
+                    reaped.push(self.fail_task(
+                        task_id,
+                        "lease expired without heartbeat; requeued by reaper",
+                        true,
+                    )?);
+                }
+                _ => {}
+            }
+        }
+        Ok(reaped)
+    }
+
     pub fn get_workflow_run(&self, run_id: Uuid) -> Option<WorkflowRun> {
         self.workflow_runs
             .read()
@@ -385,7 +760,232 @@ impl OrchestrationEngine {
             .map(|state| state.run.clone())
     }
 
+    /// Drains `run_id`: marks it `Cancelled` and removes it from the engine,
+    /// taking its waiting and inflight step bookkeeping with it — no
+    /// further steps will be scheduled for it, and outcomes reported for
+    /// steps already leased are ignored once it's gone (`handle_task_outcome`
+    /// looks the run up by id and finds nothing). Tasks already leased for
+    /// the run are left alone; this stops the workflow from progressing, it
+    /// doesn't reach out and cancel in-flight work.
+    pub fn cancel_workflow_run(&self, run_id: Uuid) -> PlatformResult<WorkflowRun> {
+        let mut state = self
+            .workflow_runs
+            .write()
+            .remove(&run_id)
+            .ok_or(PlatformError::NotFound("workflow run"))?;
+        state.run.status = WorkflowRunStatus::Cancelled;
+        state.run.completed_at = Some(Utc::now());
+        state.run.updated_at = Utc::now();
+        Ok(state.run)
+    }
+
+    /// Registers a recurring `target` that [`tick`](Self::tick) fires
+    /// according to `cadence`, starting from its first fire after `now`.
+    pub fn register_schedule(
+        &self,
+        tenant_id: TenantId,
+        target: ScheduleTarget,
+        payload: Value,
+        cadence: Cadence,
+    ) -> PlatformResult<Uuid> {
+        let now = Utc::now();
+        let next_fire_at = cadence
+            .next_after(now)
+            .ok_or(PlatformError::InvalidInput("invalid cadence"))?;
+        let id = Uuid::new_v4();
+        self.schedules.write().insert(
+            id,
+            ScheduleEntry {
+                id,
+                tenant_id,
+                target,
+                payload,
+                cadence,
+                next_fire_at,
+                last_fired_at: None,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Stops a [`ScheduleEntry`] from firing again.
+    pub fn cancel_schedule(&self, id: Uuid) {
+        self.schedules.write().remove(&id);
+    }
+
+    pub fn list_schedules(&self) -> Vec<ScheduleEntry> {
+        self.schedules.read().values().cloned().collect()
+    }
+
+    /// Fires every [`ScheduleEntry`] whose `next_fire_at` has passed,
+    /// exactly once per entry per call. A process that was down for several
+    /// missed intervals still only fires once on its next tick — the next
+    /// `next_fire_at` is computed from `now`, not from the entry's old one,
+    /// so there's no backfill/thundering-herd of queued-up catch-up work.
+    /// Returns every task produced by a fired entry (a fired workflow
+    /// contributes its initial batch of tasks, same as `schedule_workflow`).
+    pub fn tick(&self, now: DateTime<Utc>) -> PlatformResult<Vec<Task>> {
+        let due: Vec<ScheduleEntry> = self
+            .schedules
+            .read()
+            .values()
+            .filter(|entry| entry.next_fire_at <= now)
+            .cloned()
+            .collect();
+        let mut fired = Vec::new();
+        for entry in due {
+            let tasks = match &entry.target {
+                ScheduleTarget::Task { kind } => vec![self.schedule_task(TaskRequest {
+                    tenant_id: entry.tenant_id,
+                    kind: kind.clone(),
+                    payload: entry.payload.clone(),
+                    replicas: 1,
+                })?],
+                ScheduleTarget::Workflow { workflow_id } => {
+                    self.schedule_workflow(*workflow_id, entry.tenant_id, entry.payload.clone())?
+                }
+            };
+            fired.extend(tasks);
+            let next_fire_at = entry.cadence.next_after(now).unwrap_or(entry.next_fire_at);
+            if let Some(scheduled) = self.schedules.write().get_mut(&entry.id) {
+                scheduled.last_fired_at = Some(now);
+                scheduled.next_fire_at = next_fire_at;
+            }
+        }
+        Ok(fired)
+    }
+
+    /// Runs until cancelled (dropping every clone of the returned future, or
+    /// the process exiting): registers `worker_id`, then repeatedly leases
+    /// up to `concurrency` ready tasks at a time and dispatches each to its
+    /// [`TaskHandler`] (via [`register_handler`](Self::register_handler)),
+    /// completing or failing it based on the result. A task whose kind has
+    /// no registered handler fails immediately without a retry, since no
+    /// amount of backoff will make a handler appear. This is the "embed
+    /// Cassandra as a library" path — an equally valid caller can ignore
+    /// this and drive `lease_next_task`/`complete_task`/`fail_task` by hand.
+    pub async fn run_worker(
+        self: Arc<Self>,
+        tenant_id: TenantId,
+        worker_id: Uuid,
+        lease_ttl: Duration,
+        concurrency: usize,
+    ) {
+        self.register_worker(worker_id);
+        let concurrency = concurrency.max(1);
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
+        loop {
+            let permits = semaphore.available_permits();
+            if permits == 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                continue;
+            }
+            let leases = match self.lease_next_tasks(tenant_id, worker_id, permits, lease_ttl) {
+                Ok(leases) => leases,
+                Err(err) => {
+                    tracing::error!(error = %err, "task_worker.lease_failed");
+                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                    continue;
+                }
+            };
+            if leases.is_empty() {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                continue;
+            }
+            for lease in leases {
+                let engine = self.clone();
+                let permit = semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("worker semaphore is never closed");
+                tokio::spawn(async move {
+                    let _permit = permit;
+                    engine.dispatch_leased_task(lease, worker_id).await;
+                });
+            }
+        }
+    }
+
+    async fn dispatch_leased_task(self: Arc<Self>, lease: TaskLease, worker_id: Uuid) {
+        let handler = self.handlers.read().get(&lease.task.kind).cloned();
+        let Some(handler) = handler else {
+            if let Err(err) = self.fail_task(
+                lease.task.id,
+                format!("no handler registered for kind '{}'", lease.task.kind),
+                false,
+            ) {
+                tracing::error!(error = %err, "task_worker.fail_unhandled_failed");
+            }
+            return;
+        };
+        let workflow_ctx = Self::workflow_context(&lease.task);
+        let ctx = TaskContext {
+            workflow_run_id: workflow_ctx.as_ref().map(|ctx| ctx.run_id),
+            workflow_step_id: workflow_ctx.as_ref().map(|ctx| ctx.step_id),
+            task: lease.task.clone(),
+            worker_id,
+            lease_token: lease.lease_token,
+            engine: self.clone(),
+        };
+        match handler.run(ctx).await {
+            Ok(result) => {
+                if let Err(err) = self.complete_task(lease.task.id, result) {
+                    tracing::error!(error = %err, "task_worker.complete_failed");
+                }
+            }
+            Err(message) => {
+                if let Err(err) = self.fail_task(lease.task.id, message, true) {
+                    tracing::error!(error = %err, "task_worker.fail_failed");
+                }
+            }
+        }
+    }
+
+    pub fn get_task(&self, task_id: TaskId) -> PlatformResult<Option<Task>> {
+        self.tasks.get_task(task_id)
+    }
+
+    /// All tasks of `kind` for `tenant_id`, regardless of status. Callers
+    /// use this to dedupe in-flight work rather than scheduling a
+    /// duplicate job for the same transform.
+    pub fn list_tasks(&self, tenant_id: TenantId, kind: &str) -> PlatformResult<Vec<Task>> {
+        self.tasks.list_tasks_by_kind(tenant_id, kind)
+    }
+
+    /// Lease a specific task by id rather than pulling the next one off the
+    /// queue. Used when a caller schedules a task and wants to start
+    /// processing it immediately instead of waiting for a generic worker
+    /// to pick it up via `lease_next_task`.
+    pub fn lease_task(
+        &self,
+        task_id: TaskId,
+        worker_id: Uuid,
+        lease_ttl: Duration,
+    ) -> PlatformResult<TaskLease> {
+        let mut task = self
+            .tasks
+            .get_task(task_id)?
+            .ok_or(PlatformError::NotFound("task"))?;
+        let now = Utc::now();
+        task.status = TaskStatus::InProgress;
+        task.started_at = Some(now);
+        self.tasks.update_task(task.clone())?;
+        self.start_lease(&task, worker_id, lease_ttl)
+            .ok_or(PlatformError::InvalidInput(
+                "task already leased by another worker",
+            ))
+    }
+
     fn select_task(&self, pending: &[Task]) -> Option<Task> {
+        let paused = self.paused_kinds.read();
+        let pending: Vec<Task> = pending
+            .iter()
+            .filter(|task| !paused.contains(&task.kind))
+            .cloned()
+            .collect();
+        drop(paused);
+        let pending = pending.as_slice();
         if pending.is_empty() {
             return None;
         }
@@ -429,7 +1029,13 @@ impl OrchestrationEngine {
         candidate
     }
 
-    fn start_lease(&self, task: &Task, worker_id: Uuid, lease_ttl: Duration) -> TaskLease {
+    /// Attempts to win `task.id`'s lease via `LeaseStore::acquire`. Returns
+    /// `None` (rather than erroring) on a version conflict, since "someone
+    /// else already holds this lease" is an expected outcome of two engines
+    /// racing for the same task, not a failure — callers treat it as "try a
+    /// different task" (`lease_next_task`) or "this specific task is
+    /// already spoken for" (`lease_task`).
+    fn start_lease(&self, task: &Task, worker_id: Uuid, lease_ttl: Duration) -> Option<TaskLease> {
         let lease_window = task
             .timeouts
             .as_ref()
@@ -438,31 +1044,103 @@ impl OrchestrationEngine {
             .unwrap_or(lease_ttl);
         let now = Utc::now();
         let expires_at = now + lease_window;
-        let mut leases = self.lease_states.write();
-        let version = leases
-            .get(&task.id)
-            .map(|state| state.version + 1)
-            .unwrap_or(1);
-        let lease_state = LeaseState {
-            version,
+        let expected_version = self.leases.get(task.id).map(|state| state.version);
+        let new_state = LeaseRecord {
+            version: expected_version.unwrap_or(0) + 1,
             token: Uuid::new_v4(),
             worker_id,
             leased_at: now,
             lease_expires_at: expires_at,
         };
-        leases.insert(task.id, lease_state.clone());
-        TaskLease {
-            task: task.clone(),
-            worker_id,
-            leased_at: lease_state.leased_at,
-            lease_expires_at: lease_state.lease_expires_at,
-            lease_version: lease_state.version,
-            lease_token: lease_state.token,
+        match self
+            .leases
+            .acquire(task.id, expected_version, new_state.clone())
+        {
+            Ok(()) => Some(TaskLease {
+                task: task.clone(),
+                worker_id,
+                leased_at: new_state.leased_at,
+                lease_expires_at: new_state.lease_expires_at,
+                lease_version: new_state.version,
+                lease_token: new_state.token,
+            }),
+            Err(LeaseConflict::VersionMismatch) | Err(LeaseConflict::NotFound) => None,
         }
     }
 
     fn clear_lease(&self, task_id: TaskId) {
-        self.lease_states.write().remove(&task_id);
+        if let Some(current) = self.leases.get(task_id) {
+            let _ = self.leases.release(task_id, current.token);
+        }
+    }
+
+    /// Rejects a workflow before it's ever registered if it could never
+    /// progress: a `Completed` dependency on a `task_kind` no step in the
+    /// workflow produces (an unsatisfiable edge — `Failed` dependencies are
+    /// exempt, since those are explicit compensation edges that may
+    /// legitimately point at a kind scheduled outside this workflow), or a
+    /// cycle in the kind-level dependency graph (`pop_ready_steps` would
+    /// leave every step in that cycle waiting on the others forever).
+    fn validate_steps(steps: &[WorkflowStep]) -> PlatformResult<()> {
+        let produced_kinds: HashSet<&str> =
+            steps.iter().map(|step| step.task_kind.as_str()).collect();
+
+        let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+        for step in steps {
+            graph.entry(step.task_kind.as_str()).or_default();
+            for dep in &step.dependencies {
+                if dep.required_status == TaskStatus::Completed
+                    && !produced_kinds.contains(dep.task_kind.as_str())
+                {
+                    return Err(PlatformError::InvalidInput(
+                        "workflow step depends on a task_kind no step in this workflow produces",
+                    ));
+                }
+                graph
+                    .entry(dep.task_kind.as_str())
+                    .or_default()
+                    .push(step.task_kind.as_str());
+            }
+        }
+
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Mark {
+            Unvisited,
+            Visiting,
+            Done,
+        }
+
+        fn visit<'a>(
+            node: &'a str,
+            graph: &HashMap<&'a str, Vec<&'a str>>,
+            marks: &mut HashMap<&'a str, Mark>,
+        ) -> bool {
+            match marks.get(node).copied().unwrap_or(Mark::Unvisited) {
+                Mark::Done => return true,
+                Mark::Visiting => return false,
+                Mark::Unvisited => {}
+            }
+            marks.insert(node, Mark::Visiting);
+            if let Some(children) = graph.get(node) {
+                for child in children {
+                    if !visit(child, graph, marks) {
+                        return false;
+                    }
+                }
+            }
+            marks.insert(node, Mark::Done);
+            true
+        }
+
+        let mut marks: HashMap<&str, Mark> = HashMap::new();
+        for kind in graph.keys() {
+            if !visit(kind, &graph, &mut marks) {
+                return Err(PlatformError::InvalidInput(
+                    "workflow has a cyclic task_kind dependency and could never complete",
+                ));
+            }
+        }
+        Ok(())
     }
 
     fn workflow_context(task: &Task) -> Option<WorkflowContext> {
@@ -516,6 +1194,7 @@ impl OrchestrationEngine {
                 tenant_id: run.tenant_id,
                 kind: step.task_kind.clone(),
                 payload,
+                replicas: 1,
             })?;
         }
         if finished {
@@ -595,6 +1274,7 @@ mod tests {
                 tenant_id,
                 kind: "simple".into(),
                 payload: json!({"foo": "bar"}),
+                replicas: 1,
             })
             .unwrap();
 
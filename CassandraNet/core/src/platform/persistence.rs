@@ -2,7 +2,9 @@ use crate::platform::error::{PlatformError, PlatformResult};
 use crate::platform::models::*;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use cncommon::auth::Scope;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "db")]
 use sqlx::{postgres::PgRow, Pool, Postgres, QueryBuilder, Row};
 use std::collections::{HashMap, VecDeque};
@@ -13,12 +15,20 @@ pub trait TenantStore: Send + Sync {
     fn insert_tenant(&self, tenant: Tenant) -> PlatformResult<()>;
     fn get_tenant(&self, id: TenantId) -> PlatformResult<Option<Tenant>>;
     fn list_tenants(&self) -> PlatformResult<Vec<Tenant>>;
+    /// Removes `id` outright, no error if it's already gone. Only ever
+    /// called by [`super::provisioning::ProvisioningService`] to unwind a
+    /// tenant created by a caller that went on to lose an idempotent-create
+    /// race — a real, already-visible tenant is never deleted this way.
+    fn delete_tenant(&self, id: TenantId) -> PlatformResult<()>;
 }
 
 pub trait ProjectStore: Send + Sync {
     fn insert_project(&self, project: Project) -> PlatformResult<()>;
     fn list_projects(&self, tenant_id: TenantId) -> PlatformResult<Vec<Project>>;
     fn get_project(&self, id: ProjectId) -> PlatformResult<Option<Project>>;
+    /// Removes `id` outright, no error if it's already gone. See
+    /// [`TenantStore::delete_tenant`] for why this exists.
+    fn delete_project(&self, id: ProjectId) -> PlatformResult<()>;
 }
 
 pub trait AgentStore: Send + Sync {
@@ -28,6 +38,65 @@ pub trait AgentStore: Send + Sync {
     fn get_agent(&self, id: AgentId) -> PlatformResult<Option<Agent>>;
 }
 
+/// Append-only history of `ProvisioningService::transition_agent`'s accepted
+/// transitions, separate from `AgentStore` since `Agent` itself only ever
+/// holds the *current* `AgentStatus` — mirrors `AuditStore`/`ModerationStore`
+/// keeping their event logs in their own trait rather than folding them into
+/// the entity store.
+pub trait AgentStateStore: Send + Sync {
+    fn record_agent_state_event(&self, event: AgentStateEvent) -> PlatformResult<()>;
+    /// Oldest first, so a caller reconstructing history doesn't need to
+    /// reverse it.
+    fn list_agent_state_events(&self, agent_id: AgentId) -> PlatformResult<Vec<AgentStateEvent>>;
+}
+
+/// A cached response to a client-supplied idempotency key, as stored by
+/// [`IdempotencyStore`]. `request_hash` is a digest of the canonicalized
+/// request that produced `response`, so a key replayed with a different
+/// body can be told apart from a genuine retry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdempotencyRecord {
+    pub request_hash: String,
+    pub response: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Durable, TTL-bounded cache for idempotency keys, keyed by `(scope,
+/// key)`. `scope` namespaces keys across call sites that each mint their
+/// own key values independently — e.g. `create_tenant_with_options` has no
+/// tenant yet to scope by, so it uses a fixed scope, while
+/// `create_project_with_options` scopes by its parent tenant — so the same
+/// key value reused by two callers can't collide.
+pub trait IdempotencyStore: Send + Sync {
+    /// Returns the record for `(scope, key)`, or `None` if absent or
+    /// expired as of `now`; an expired record is evicted as a side effect
+    /// of the read instead of lingering until some separate sweep runs.
+    fn get(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Option<IdempotencyRecord>>;
+    fn put(&self, scope: &str, key: &str, record: IdempotencyRecord) -> PlatformResult<()>;
+
+    /// Atomically claims `(scope, key)` for `record`: if no live record is
+    /// already there, inserts `record` and returns `None` — the caller is
+    /// the one whose response is now authoritative. If a live record
+    /// already exists (inserted by a caller that won a race against this
+    /// one), leaves it untouched and returns `Some(existing)` instead, so a
+    /// check-then-act gap between an initial `get` and this call can never
+    /// let two concurrent callers each believe they're the first to use the
+    /// key. Unlike `put`, this never overwrites a live record.
+    fn put_if_absent(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+        record: IdempotencyRecord,
+    ) -> PlatformResult<Option<IdempotencyRecord>>;
+}
+
 pub trait ApiKeyStore: Send + Sync {
     fn insert_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()>;
     fn get_api_key(&self, id: ApiKeyId) -> PlatformResult<Option<ApiKeyRecord>>;
@@ -36,12 +105,58 @@ pub trait ApiKeyStore: Send + Sync {
     fn update_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()>;
 }
 
+/// Per-agent mTLS client certificates minted by [`crate::platform::ca::CertificateAuthority`].
+pub trait CertificateStore: Send + Sync {
+    fn insert_certificate(&self, record: AgentCertificateRecord) -> PlatformResult<()>;
+    fn get_certificate(&self, id: uuid::Uuid) -> PlatformResult<Option<AgentCertificateRecord>>;
+    fn list_certificates_for_agent(
+        &self,
+        agent_id: AgentId,
+    ) -> PlatformResult<Vec<AgentCertificateRecord>>;
+    fn update_certificate(&self, record: AgentCertificateRecord) -> PlatformResult<()>;
+
+    /// Persists a tenant's intermediate CA keypair. Fails with `Conflict` if
+    /// one is already on file — a caller that loses this race should
+    /// `get_tenant_ca` and sign with the winner's CA instead of its own, so
+    /// every process (and every replica) converges on one CA per tenant.
+    fn insert_tenant_ca(&self, record: TenantCaRecord) -> PlatformResult<()>;
+    fn get_tenant_ca(&self, tenant_id: TenantId) -> PlatformResult<Option<TenantCaRecord>>;
+}
+
+/// Filters for [`AuditStore::list_events`]. Always tenant-scoped; the other
+/// fields narrow further and are ANDed together.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub tenant_id: TenantId,
+    pub area: Option<AuditArea>,
+    pub category: Option<AuditCategory>,
+    pub actor_id: Option<uuid::Uuid>,
+    pub time_from: Option<DateTime<Utc>>,
+    pub time_to: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    /// Keyset cursor: only rows that sort strictly after `(timestamp, id)`
+    /// in the query's `(timestamp DESC, id ASC)` order are returned.
+    pub cursor_timestamp: Option<DateTime<Utc>>,
+    pub cursor_id: Option<uuid::Uuid>,
+}
+
+/// Append-only audit trail for privileged mutations. Events are immutable
+/// once written — there is deliberately no update/delete method.
+pub trait AuditStore: Send + Sync {
+    fn record_event(&self, event: AuditEvent) -> PlatformResult<()>;
+    fn list_events(&self, query: &AuditQuery) -> PlatformResult<Vec<AuditEvent>>;
+}
+
 pub trait TaskStore: Send + Sync {
     fn enqueue_task(&self, task: Task) -> PlatformResult<()>;
     fn peek_next_task(&self, tenant_id: TenantId) -> PlatformResult<Option<Task>>;
     fn update_task(&self, task: Task) -> PlatformResult<()>;
     fn get_task(&self, id: TaskId) -> PlatformResult<Option<Task>>;
     fn list_pending_tasks(&self, tenant_id: TenantId) -> PlatformResult<Vec<Task>>;
+    /// All tasks of `kind` for `tenant_id`, regardless of status. Used to
+    /// dedupe in-flight work (e.g. a rendition job already queued or
+    /// running for the same cache key) before scheduling a new one.
+    fn list_tasks_by_kind(&self, tenant_id: TenantId, kind: &str) -> PlatformResult<Vec<Task>>;
 }
 
 pub trait WorkflowStore: Send + Sync {
@@ -50,17 +165,250 @@ pub trait WorkflowStore: Send + Sync {
     fn list_workflows(&self, tenant_id: TenantId) -> PlatformResult<Vec<Workflow>>;
 }
 
+/// A task's current lease, with the monotonic `version` that makes
+/// [`LeaseStore`]'s writes compare-and-swap instead of last-writer-wins.
+#[derive(Debug, Clone)]
+pub struct LeaseRecord {
+    pub version: u64,
+    pub token: uuid::Uuid,
+    pub worker_id: uuid::Uuid,
+    pub leased_at: DateTime<Utc>,
+    pub lease_expires_at: DateTime<Utc>,
+}
+
+/// Why a [`LeaseStore`] compare-and-swap was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaseConflict {
+    /// The stored version didn't match what the caller expected — another
+    /// node already won, renewed, or released this lease in the meantime.
+    VersionMismatch,
+    /// `renew`/`release` targeted a task with no lease on record.
+    NotFound,
+}
+
+/// Persists [`LeaseRecord`]s with compare-and-swap writes, so multiple
+/// `OrchestrationEngine`s sharing one backend never double-lease a task the
+/// way two independent in-process `HashMap`s would. Mirrors the etcd-backed
+/// locking used by multi-scheduler deployments: every write carries the
+/// version the caller last observed, and a mismatch means someone else
+/// already moved the lease forward. The in-memory [`InMemoryLeaseStore`] is
+/// the default for a single-instance engine; a Postgres/etcd-backed
+/// implementation is what makes several engines safe to point at the same
+/// `TaskStore`.
+pub trait LeaseStore: Send + Sync {
+    /// Installs `new_state` for `task_id` iff the stored version equals
+    /// `expected_version` (`None` meaning "no lease exists for this task
+    /// yet"). `new_state.version` must be `expected_version.unwrap_or(0) + 1`.
+    fn acquire(
+        &self,
+        task_id: TaskId,
+        expected_version: Option<u64>,
+        new_state: LeaseRecord,
+    ) -> Result<(), LeaseConflict>;
+    /// Extends `task_id`'s lease to `new_expiry` iff it's still held by
+    /// `worker_id` under `token` at `expected_version`, returning the
+    /// renewed record (with `version` incremented by one).
+    fn renew(
+        &self,
+        task_id: TaskId,
+        token: uuid::Uuid,
+        worker_id: uuid::Uuid,
+        expected_version: u64,
+        new_expiry: DateTime<Utc>,
+    ) -> Result<LeaseRecord, LeaseConflict>;
+    /// Drops `task_id`'s lease iff it's still held under `token`. A no-op
+    /// (not an error) if the lease is already gone, since the caller's
+    /// intent — "nobody should hold this lease" — is already satisfied.
+    fn release(&self, task_id: TaskId, token: uuid::Uuid) -> Result<(), LeaseConflict>;
+    /// The current lease for `task_id`, if any.
+    fn get(&self, task_id: TaskId) -> Option<LeaseRecord>;
+    /// Every lease currently outstanding, across all tasks. Used by the
+    /// expired-lease reaper, which has to scan for leases past their expiry
+    /// (or owned by a worker that's stopped heartbeating) rather than
+    /// looking any single one up by task id.
+    fn list_all(&self) -> Vec<(TaskId, LeaseRecord)>;
+}
+
+/// Default single-process [`LeaseStore`]. Resets on restart, same as the
+/// `OrchestrationEngine` lease bookkeeping it replaces — safe for a
+/// single-instance deployment, but two instances pointed at it
+/// independently (i.e. not sharing the same `Arc`) would still double-lease.
+#[derive(Default)]
+pub struct InMemoryLeaseStore {
+    inner: parking_lot::RwLock<HashMap<TaskId, LeaseRecord>>,
+}
+
+impl InMemoryLeaseStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LeaseStore for InMemoryLeaseStore {
+    fn acquire(
+        &self,
+        task_id: TaskId,
+        expected_version: Option<u64>,
+        new_state: LeaseRecord,
+    ) -> Result<(), LeaseConflict> {
+        let mut guard = self.inner.write();
+        let current_version = guard.get(&task_id).map(|state| state.version);
+        if current_version != expected_version {
+            return Err(LeaseConflict::VersionMismatch);
+        }
+        guard.insert(task_id, new_state);
+        Ok(())
+    }
+
+    fn renew(
+        &self,
+        task_id: TaskId,
+        token: uuid::Uuid,
+        worker_id: uuid::Uuid,
+        expected_version: u64,
+        new_expiry: DateTime<Utc>,
+    ) -> Result<LeaseRecord, LeaseConflict> {
+        let mut guard = self.inner.write();
+        let state = guard.get_mut(&task_id).ok_or(LeaseConflict::NotFound)?;
+        if state.version != expected_version || state.token != token || state.worker_id != worker_id
+        {
+            return Err(LeaseConflict::VersionMismatch);
+        }
+        state.version += 1;
+        state.lease_expires_at = new_expiry;
+        Ok(state.clone())
+    }
+
+    fn release(&self, task_id: TaskId, token: uuid::Uuid) -> Result<(), LeaseConflict> {
+        let mut guard = self.inner.write();
+        match guard.get(&task_id) {
+            Some(state) if state.token == token => {
+                guard.remove(&task_id);
+                Ok(())
+            }
+            Some(_) => Err(LeaseConflict::VersionMismatch),
+            None => Ok(()),
+        }
+    }
+
+    fn get(&self, task_id: TaskId) -> Option<LeaseRecord> {
+        self.inner.read().get(&task_id).cloned()
+    }
+
+    fn list_all(&self) -> Vec<(TaskId, LeaseRecord)> {
+        self.inner
+            .read()
+            .iter()
+            .map(|(task_id, state)| (*task_id, state.clone()))
+            .collect()
+    }
+}
+
 #[async_trait]
 pub trait ContentStore: Send + Sync {
     async fn create_upload_session(&self, session: UploadSession) -> PlatformResult<()>;
     async fn update_upload_session(&self, session: UploadSession) -> PlatformResult<()>;
     async fn get_upload_session(&self, id: UploadId) -> PlatformResult<Option<UploadSession>>;
+    /// Records (or replaces) one part of a multipart upload. Independent of
+    /// `update_upload_session` so concurrent part uploads don't contend on
+    /// the same session row, and so `complete_upload_session` can trust the
+    /// server's own record of what was uploaded instead of whatever the
+    /// client claims.
+    async fn register_upload_part(&self, upload_id: UploadId, part: UploadPart) -> PlatformResult<()>;
+    /// Parts registered so far for `upload_id`, ordered by `part_number`.
+    async fn list_upload_parts(&self, upload_id: UploadId) -> PlatformResult<Vec<UploadPart>>;
+    /// Finishes a multipart upload purely from its registered part manifest,
+    /// without fetching the object's bytes back from storage: validates the
+    /// parts are contiguous from 1 and each carries a checksum, sums their
+    /// sizes, and derives a `composite_etag` the way S3's own multipart
+    /// completion does. Complements `complete_upload_session`'s
+    /// fetch-and-sniff flow for callers that already trust the client's
+    /// declared `filename`/`mime_type` (e.g. an internal migration) and just
+    /// need the session finalized and its `ContentMetadata` recorded.
+    async fn complete_multipart_upload(
+        &self,
+        upload_id: UploadId,
+        filename: String,
+        mime_type: Option<String>,
+        visibility: ContentVisibility,
+    ) -> PlatformResult<ContentMetadata>;
     async fn record_content_metadata(&self, metadata: ContentMetadata) -> PlatformResult<()>;
     async fn get_content_metadata(&self, id: ContentId) -> PlatformResult<Option<ContentMetadata>>;
+    /// When `query.search_term` is set, ranks matches by relevance (highest
+    /// first) instead of sorting by `created_at`; the Postgres backend
+    /// expects `ugc_content_metadata` to carry a generated `search_vector
+    /// tsvector` column (filename + labels + attribute values) with a GIN
+    /// index, queried via `websearch_to_tsquery`/`ts_rank_cd` so callers can
+    /// pass quoted phrases and `-exclusions`. Populates
+    /// `ContentMetadata.relevance` with the rank score in that case.
     async fn list_content_metadata(
         &self,
         query: &ContentQuery,
     ) -> PlatformResult<Vec<ContentMetadata>>;
+    /// Most recent completed `ContentMetadata` in `tenant_id` whose checksum
+    /// and size match exactly, if any. Backs content-addressable dedup in
+    /// `complete_upload_session`: a hit means the new upload is a byte-for-byte
+    /// duplicate and can reuse the existing object's `storage_path`.
+    async fn find_content_by_digest(
+        &self,
+        tenant_id: TenantId,
+        digest: &str,
+        size_bytes: u64,
+    ) -> PlatformResult<Option<ContentMetadata>>;
+    /// Deletes a content object's metadata record; a no-op if it's already
+    /// gone, so batch callers don't need to re-check existence first.
+    /// Metadata-only, like `apply_lifecycle_outcome`'s `Delete` action —
+    /// doesn't reach into object storage.
+    async fn delete_content_metadata(&self, id: ContentId) -> PlatformResult<()>;
+    /// Replaces a content object's `labels` in place and bumps `updated_at`.
+    /// Errors `NotFound` if `id` doesn't exist.
+    async fn set_content_labels(&self, id: ContentId, labels: Vec<String>) -> PlatformResult<()>;
+    /// Sets `legal_hold` and/or applies an [`ImmutabilityPolicy`] to a
+    /// content object, the only way either is ever set (`record_content_metadata`
+    /// only guards against mutating content that's already under one).
+    /// `legal_hold` is replaced outright when given; `immutability` goes
+    /// through [`ContentMetadata::apply_immutability_policy`], so a `Locked`
+    /// policy can't be replaced and an `Unlocked` one can't have its
+    /// retention period shortened. Errors `NotFound` if `id` doesn't exist.
+    async fn set_content_retention(
+        &self,
+        id: ContentId,
+        legal_hold: Option<bool>,
+        immutability: Option<ImmutabilityPolicy>,
+    ) -> PlatformResult<()>;
+
+    async fn set_lifecycle_policy(&self, policy: ContentLifecyclePolicy) -> PlatformResult<()>;
+    async fn list_lifecycle_policies(
+        &self,
+        tenant_id: TenantId,
+    ) -> PlatformResult<Vec<ContentLifecyclePolicy>>;
+    /// Removes a tenant's lifecycle policy; a no-op if it's already gone, so
+    /// callers don't need to re-check existence first.
+    async fn delete_lifecycle_policy(
+        &self,
+        tenant_id: TenantId,
+        policy_id: LifecyclePolicyId,
+    ) -> PlatformResult<()>;
+    /// Evaluates every policy in `tenant_id` against its matching content as
+    /// of `now` and reports what aged out; doesn't itself delete or
+    /// transition anything, so it's safe to call repeatedly (e.g. on a
+    /// timer) and the caller decides how to act on each outcome.
+    async fn sweep_expired_content(
+        &self,
+        tenant_id: TenantId,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Vec<ContentLifecycleOutcome>>;
+    /// Performs the `action` a `sweep_expired_content` outcome reported: a
+    /// no-op if `content_id` no longer exists (another sweep pass, or the
+    /// object itself, may already have removed it), so callers can apply a
+    /// stale outcome list without re-checking it first.
+    async fn apply_lifecycle_outcome(&self, outcome: ContentLifecycleOutcome) -> PlatformResult<()>;
+    /// Deletes every `UploadSession` whose `expires_at` has passed as of
+    /// `now`, in one set-based statement per backend. Abandoned
+    /// sessions otherwise linger indefinitely: nothing else ever revisits a
+    /// session once its client stops polling it. Returns the number of
+    /// sessions removed.
+    async fn reap_expired_upload_sessions(&self, now: DateTime<Utc>) -> PlatformResult<u64>;
 }
 
 #[async_trait]
@@ -74,25 +422,261 @@ pub trait OrchestrationStore: Send + Sync {
     ) -> PlatformResult<WorkAssignment>;
     async fn list_assignments(&self, query: AssignmentQuery)
         -> PlatformResult<Vec<WorkAssignment>>;
+    /// Heartbeats an in-progress assignment by pushing its `last_heartbeat`
+    /// up to `now`, so `requeue_stale` doesn't reclaim work a scheduler is
+    /// still actively running. Errors with `NotFound` if `id` doesn't exist.
+    async fn heartbeat_assignment(&self, id: AssignmentId) -> PlatformResult<()>;
+    /// Atomically flips up to `max` `Pending` assignments for `agent_id` to
+    /// `Running` and stamps their `last_heartbeat`, using `FOR UPDATE SKIP
+    /// LOCKED` so two schedulers racing on the same agent never claim the
+    /// same assignment twice.
+    async fn claim_pending(&self, agent_id: AgentId, max: u32) -> PlatformResult<Vec<WorkAssignment>>;
+    /// Finds every `Running` assignment whose `last_heartbeat` is older than
+    /// `now - ttl` — a scheduler that died or lost its connection without
+    /// ever transitioning the assignment out of `Running` — and either
+    /// requeues it as `Pending` with `attempt` incremented, or, once
+    /// `attempt` exceeds `config.max_attempts`, marks it `Failed` with a
+    /// status_message so a poison workload stops looping forever. Returns
+    /// every assignment this call changed, for the caller to log.
+    async fn requeue_stale(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+        config: &AssignmentLifecycleConfig,
+    ) -> PlatformResult<Vec<WorkAssignment>>;
 }
 
 #[async_trait]
 pub trait ModerationStore: Send + Sync {
     async fn create_content(&self, input: NewModeratedContent) -> PlatformResult<ModeratedContent>;
+    /// Transitions `id` to `state`, recording `actor_id` and `reason` as a
+    /// new [`ModerationEvent`] in the same transaction as the state change,
+    /// so the two can never drift apart.
     async fn update_content_state(
         &self,
         id: ContentId,
         state: ModerationState,
         reason: Option<String>,
+        actor_id: uuid::Uuid,
     ) -> PlatformResult<ModeratedContent>;
+    /// Ordered (oldest first) review history for `content_id`.
+    async fn list_content_events(
+        &self,
+        content_id: ContentId,
+    ) -> PlatformResult<Vec<ModerationEvent>>;
     async fn list_content(&self, query: ModerationQuery) -> PlatformResult<Vec<ModeratedContent>>;
+    /// Records a user flag against `content_id`. Doesn't itself change
+    /// `ModeratedContent.state` — it's input for a moderator's triage queue,
+    /// not an automatic action.
+    async fn create_report(
+        &self,
+        content_id: ContentId,
+        reporter_id: uuid::Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    ) -> PlatformResult<ModerationReport>;
+    /// Sweeps content still `Pending` after `now - deadline`, flipping each
+    /// to `to_state` via `update_content_state` (so the transition is
+    /// recorded as a `ModerationEvent` the same way a human reviewer's
+    /// decision would be) and returning what was touched. Exists for
+    /// callers like `spawn_moderation_expiry_sweeper` that need to turn an
+    /// unreviewed backlog into a terminal state instead of leaving it
+    /// pending forever.
+    ///
+    /// The default implementation is a no-op: backends such as
+    /// `PostgresModerationStore` that already run their own scheduled
+    /// retention job (e.g. via `pg_cron`) don't need the generic sweep to
+    /// do anything.
+    async fn expire_pending_moderation(
+        &self,
+        _now: DateTime<Utc>,
+        _deadline: chrono::Duration,
+        _to_state: ModerationState,
+    ) -> PlatformResult<Vec<ModeratedContent>> {
+        Ok(Vec::new())
+    }
+    /// Ordered (oldest first, `sequence` ascending) hash-chained audit trail
+    /// for `content_id`, written alongside every `update_content_state` call.
+    /// See [`ModerationAuditEntry`] for what makes this different from
+    /// `list_content_events`.
+    async fn list_audit(&self, content_id: ContentId) -> PlatformResult<Vec<ModerationAuditEntry>>;
+    /// The `hash` of `content_id`'s most recent audit entry — the "chain
+    /// head" — or `None` if `update_content_state` has never run for it.
+    /// An auditor recomputes the chain by replaying `list_audit` from
+    /// `sequence: 1` and compares their own last hash against this value to
+    /// confirm nothing in between was altered, reordered, or dropped.
+    async fn audit_chain_head(&self, content_id: ContentId) -> PlatformResult<Option<String>>;
 }
 
 #[async_trait]
 pub trait MessagingStore: Send + Sync {
     async fn enqueue_message(&self, input: NewMessageRecord) -> PlatformResult<MessageRecord>;
     async fn list_messages(&self, query: MessageQuery) -> PlatformResult<Vec<MessageRecord>>;
+    /// Atomically leases up to `max` unacked `topic` messages, ordered
+    /// `priority DESC, published_at ASC`, stamping each with a `lease_until`
+    /// of `now + visibility_timeout` so a consumer that dies mid-processing
+    /// doesn't strand the message. A message whose lease lapses without an
+    /// `ack_message` becomes claimable again; one that has already hit its
+    /// `max_attempts` is moved to the dead-letter store instead of being
+    /// redelivered.
+    async fn claim_messages(
+        &self,
+        topic: &str,
+        consumer: &str,
+        max: u32,
+        visibility_timeout: chrono::Duration,
+    ) -> PlatformResult<Vec<MessageRecord>>;
+    /// Heartbeats a still-in-progress claim by pushing `id`'s `lease_until`
+    /// out to `now + extension`, so a consumer working through a slow
+    /// message can keep it from being reclaimed out from under it. Errors
+    /// with `NotFound` if `id` doesn't exist or its lease already lapsed
+    /// (in which case it may belong to another consumer now).
+    async fn extend_lease(
+        &self,
+        topic: &str,
+        id: MessageId,
+        extension: chrono::Duration,
+    ) -> PlatformResult<()>;
+    /// A no-op if `id`'s lease already lapsed (and so may have been
+    /// reclaimed by another consumer via `claim_messages`) rather than an
+    /// error, so a late ack from the original consumer can't finalize work
+    /// someone else now owns.
     async fn ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()>;
+    /// Clears `id`'s claim (`lease_until`/`leased_by`) so the next
+    /// `claim_messages` scan can redeliver it immediately instead of
+    /// waiting out the visibility timeout, without touching
+    /// `delivery_attempts` since the attempt was already counted at claim
+    /// time. Like `ack_message`, a lease that already lapsed (and so may
+    /// belong to another consumer now) is left alone rather than erroring.
+    async fn nack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()>;
+    /// Long-polls `query.topic`: blocks until a message published after
+    /// `since_token` is visible or `timeout` elapses, returning whatever
+    /// matched alongside the continuation token to pass as `since_token` on
+    /// the next call. The token is opaque and monotonically increasing, so
+    /// a consumer looping on its own return value never misses or
+    /// re-receives a message regardless of what else happened to the topic
+    /// (claims, acks, dead-lettering) in between calls. A timeout returns an
+    /// empty batch with `since_token` unchanged rather than an error.
+    ///
+    /// The default implementation falls back to short-interval polling
+    /// against `list_messages`, using each record's `published_at` as the
+    /// token; `InMemoryPersistence` overrides it with a per-topic
+    /// `tokio::sync::Notify` so it wakes on `enqueue_message` instead of
+    /// sleeping.
+    async fn poll_topic(
+        &self,
+        query: MessageQuery,
+        since_token: u64,
+        timeout: chrono::Duration,
+    ) -> PlatformResult<(Vec<MessageRecord>, u64)> {
+        let deadline = Utc::now() + timeout;
+        loop {
+            let records = self.list_messages(query.clone()).await?;
+            let mut highest = since_token;
+            let mut fresh = Vec::new();
+            for record in records {
+                let token = record.published_at.timestamp_micros() as u64;
+                if token > since_token {
+                    highest = highest.max(token);
+                    fresh.push(record);
+                }
+            }
+            if !fresh.is_empty() {
+                return Ok((fresh, highest));
+            }
+            if Utc::now() >= deadline {
+                return Ok((Vec::new(), since_token));
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        }
+    }
+    /// Permanently deletes messages whose `published_at` is older than
+    /// `now - ttl`, regardless of ack state — unlike `ack_message`, this
+    /// discards work that was never claimed at all. Exists for callers like
+    /// `MessageRetentionWorker` that need to bound how long an unconsumed
+    /// topic can grow.
+    ///
+    /// The default implementation is a no-op, for the same reason as
+    /// [`ModerationStore::expire_pending_moderation`]: a backend that
+    /// already enforces retention itself (a Postgres TTL job, say) doesn't
+    /// need the generic sweep to do anything.
+    async fn evict_expired_messages(
+        &self,
+        _now: DateTime<Utc>,
+        _ttl: chrono::Duration,
+    ) -> PlatformResult<u64> {
+        Ok(0)
+    }
+}
+
+/// One operation in an [`BatchStore::execute_batch`] call, covering the
+/// record-level mutations/reads a caller would otherwise have to issue as
+/// separate requests against [`MessagingStore`], [`ModerationStore`], or
+/// [`OrchestrationStore`].
+#[derive(Debug, Clone)]
+pub enum BatchOp {
+    EnqueueMessage(NewMessageRecord),
+    AckMessage { topic: String, id: MessageId },
+    CreateContent(NewModeratedContent),
+    UpdateContentState {
+        id: ContentId,
+        state: ModerationState,
+        reason: Option<String>,
+        actor_id: uuid::Uuid,
+    },
+    CreateAssignment(NewAssignment),
+    QueryAssignments(AssignmentQuery),
+}
+
+/// The outcome of one [`BatchOp`], in the same position as its op in the
+/// `ops` vector passed to `execute_batch`.
+#[derive(Debug)]
+pub enum BatchResult {
+    Message(MessageRecord),
+    Acked,
+    Content(ModeratedContent),
+    Assignment(WorkAssignment),
+    Assignments(Vec<WorkAssignment>),
+    Error(PlatformError),
+}
+
+#[async_trait]
+pub trait BatchStore: Send + Sync {
+    /// Executes `ops` in order against a single `state.write()` lock, so
+    /// every read and write in the batch observes the same snapshot — e.g.
+    /// flipping a `ModeratedContent` to `Rejected` and enqueueing a
+    /// notification `MessageRecord` never interleaves with another
+    /// request's writes partway through. Stops at the first op that
+    /// returns an error rather than running the rest against state the
+    /// caller now knows violated an invariant; the returned vector holds
+    /// one `BatchResult` per op actually attempted (the failing one
+    /// included as `BatchResult::Error`), so its length can be shorter than
+    /// `ops`.
+    async fn execute_batch(&self, ops: Vec<BatchOp>) -> Vec<BatchResult>;
+}
+
+/// Shared fixed-window counters for request rate limiting, so every
+/// control-plane replica enforces a single quota per tenant instead of each
+/// keeping its own in-process count. A window is identified by its start
+/// timestamp, so moving into a new window is just a fresh key rather than
+/// an explicit reset, and old windows can be reaped on a timer the same way
+/// `reap_expired_upload_sessions` reaps abandoned uploads.
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Atomically increments the counter for `(tenant_id, route)` in the
+    /// window starting at `window_start` and returns the count after the
+    /// increment, so the caller can compare it against its limit without a
+    /// separate read.
+    async fn increment_rate_window(
+        &self,
+        tenant_id: TenantId,
+        route: &str,
+        window_start: DateTime<Utc>,
+    ) -> PlatformResult<u32>;
+    /// Deletes every window whose start is before `before`, the same way
+    /// `reap_expired_upload_sessions` clears abandoned uploads. Returns the
+    /// number of windows removed.
+    async fn reap_expired_rate_windows(&self, before: DateTime<Utc>) -> PlatformResult<u64>;
 }
 
 #[derive(Default)]
@@ -107,10 +691,51 @@ struct PlatformState {
     workflows: HashMap<WorkflowId, Workflow>,
     upload_sessions: HashMap<UploadId, UploadSession>,
     content_metadata: HashMap<ContentId, ContentMetadata>,
+    lifecycle_policies: HashMap<LifecyclePolicyId, ContentLifecyclePolicy>,
     assignments: HashMap<AssignmentId, WorkAssignment>,
     moderation_content: HashMap<ContentId, ModeratedContent>,
+    /// Append-only review history; never mutated or removed, only appended
+    /// to by `update_content_state`.
+    moderation_events: Vec<ModerationEvent>,
+    moderation_reports: Vec<ModerationReport>,
+    /// Hash-chained audit trail, one chain per `ContentId`; see
+    /// [`ModerationAuditEntry`]. Append-only, like `moderation_events`.
+    moderation_audit: HashMap<ContentId, Vec<ModerationAuditEntry>>,
     messages: HashMap<MessageId, MessageRecord>,
-    messages_by_topic: HashMap<String, VecDeque<MessageId>>,
+    messages_by_topic: HashMap<String, TopicQueue>,
+    /// Messages `claim_messages` gave up on redelivering after
+    /// `max_attempts`, keyed by their original id.
+    dead_letters: HashMap<MessageId, MessageRecord>,
+    audit_events: Vec<AuditEvent>,
+    /// Append-only, like `audit_events`; see [`AgentStateStore`].
+    agent_state_events: Vec<AgentStateEvent>,
+    certificates: HashMap<uuid::Uuid, AgentCertificateRecord>,
+    tenant_cas: HashMap<TenantId, TenantCaRecord>,
+    /// Keyed by `(tenant_id, route, window_start)`; see [`RateLimitStore`].
+    rate_limit_windows: HashMap<(TenantId, String, DateTime<Utc>), u32>,
+    /// Keyed by `(scope, key)`; see [`IdempotencyStore`].
+    idempotency: HashMap<(String, String), IdempotencyRecord>,
+}
+
+/// A topic's message ids plus the bookkeeping `poll_topic` needs to turn
+/// `enqueue_message` into a wakeup instead of a busy-poll. `sequence`
+/// increments on every enqueue and never resets, including across a topic
+/// going empty, so a continuation token comparison against it can never
+/// miss an id (dequeued via claim/ack) or replay one.
+struct TopicQueue {
+    entries: VecDeque<(MessageId, u64)>,
+    sequence: u64,
+    notify: Arc<tokio::sync::Notify>,
+}
+
+impl Default for TopicQueue {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            sequence: 0,
+            notify: Arc::new(tokio::sync::Notify::new()),
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -122,6 +747,172 @@ impl InMemoryPersistence {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Point-in-time copy of the `OrchestrationStore`/`ModerationStore`/
+    /// `MessagingStore` state, for [`crate::platform::durable::DurablePersistence`]
+    /// to write out as a snapshot file.
+    pub(crate) fn snapshot_durable_state(&self) -> DurableSnapshot {
+        let state = self.state.read();
+        DurableSnapshot {
+            assignments: state.assignments.values().cloned().collect(),
+            moderation_content: state.moderation_content.values().cloned().collect(),
+            moderation_events: state.moderation_events.clone(),
+            moderation_reports: state.moderation_reports.clone(),
+            moderation_audit: state.moderation_audit.values().flatten().cloned().collect(),
+            messages: state.messages.values().cloned().collect(),
+            dead_letters: state.dead_letters.values().cloned().collect(),
+        }
+    }
+
+    /// Rebuilds a store from a `DurableSnapshot`, e.g. one `DurablePersistence`
+    /// loaded from disk before replaying its WAL tail on top.
+    pub(crate) fn restore_durable_state(snapshot: DurableSnapshot) -> Self {
+        let store = Self::new();
+        {
+            let mut state = store.state.write();
+            for assignment in snapshot.assignments {
+                state.assignments.insert(assignment.id, assignment);
+            }
+            for content in snapshot.moderation_content {
+                state.moderation_content.insert(content.id, content);
+            }
+            state.moderation_events = snapshot.moderation_events;
+            state.moderation_reports = snapshot.moderation_reports;
+            for entry in snapshot.moderation_audit {
+                state
+                    .moderation_audit
+                    .entry(entry.content_id)
+                    .or_default()
+                    .push(entry);
+            }
+            for message in snapshot.messages {
+                let topic_queue = state
+                    .messages_by_topic
+                    .entry(message.topic.clone())
+                    .or_insert_with(TopicQueue::default);
+                topic_queue.sequence += 1;
+                topic_queue.entries.push_back((message.id, topic_queue.sequence));
+                state.messages.insert(message.id, message);
+            }
+            for message in snapshot.dead_letters {
+                state.dead_letters.insert(message.id, message);
+            }
+        }
+        store
+    }
+
+    /// Applies a single durable mutation synchronously, re-using the same
+    /// validation as the matching trait method. Used by
+    /// [`crate::platform::durable::DurablePersistence`] both to apply a
+    /// live write before appending it to the WAL and to replay the WAL tail
+    /// on startup, so the two paths can never drift apart.
+    pub(crate) fn apply_create_assignment(&self, input: NewAssignment) -> PlatformResult<WorkAssignment> {
+        let mut state = self.state.write();
+        match batch_create_assignment(&mut state, input)? {
+            BatchResult::Assignment(assignment) => Ok(assignment),
+            _ => unreachable!("batch_create_assignment always returns BatchResult::Assignment"),
+        }
+    }
+
+    pub(crate) fn apply_update_assignment_status(
+        &self,
+        id: AssignmentId,
+        status: WorkStatus,
+        status_message: Option<String>,
+    ) -> PlatformResult<WorkAssignment> {
+        let mut state = self.state.write();
+        batch_update_assignment_status(&mut state, id, status, status_message)
+    }
+
+    pub(crate) fn apply_heartbeat_assignment(&self, id: AssignmentId) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        batch_heartbeat_assignment(&mut state, id)
+    }
+
+    pub(crate) fn apply_create_content(
+        &self,
+        input: NewModeratedContent,
+    ) -> PlatformResult<ModeratedContent> {
+        let mut state = self.state.write();
+        match batch_create_content(&mut state, input)? {
+            BatchResult::Content(content) => Ok(content),
+            _ => unreachable!("batch_create_content always returns BatchResult::Content"),
+        }
+    }
+
+    pub(crate) fn apply_update_content_state(
+        &self,
+        id: ContentId,
+        state_value: ModerationState,
+        reason: Option<String>,
+        actor_id: uuid::Uuid,
+    ) -> PlatformResult<ModeratedContent> {
+        let mut state = self.state.write();
+        match batch_update_content_state(&mut state, id, state_value, reason, actor_id)? {
+            BatchResult::Content(content) => Ok(content),
+            _ => unreachable!("batch_update_content_state always returns BatchResult::Content"),
+        }
+    }
+
+    pub(crate) fn apply_create_report(
+        &self,
+        content_id: ContentId,
+        reporter_id: uuid::Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    ) -> PlatformResult<ModerationReport> {
+        let mut state = self.state.write();
+        batch_create_report(&mut state, content_id, reporter_id, category, detail)
+    }
+
+    pub(crate) fn apply_enqueue_message(&self, input: NewMessageRecord) -> PlatformResult<MessageRecord> {
+        let mut state = self.state.write();
+        match batch_enqueue_message(&mut state, input)? {
+            BatchResult::Message(message) => Ok(message),
+            _ => unreachable!("batch_enqueue_message always returns BatchResult::Message"),
+        }
+    }
+
+    pub(crate) fn apply_ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        batch_ack_message(&mut state, topic, id).map(|_| ())
+    }
+
+    pub(crate) fn apply_evict_expired_messages(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) -> u64 {
+        let mut state = self.state.write();
+        batch_evict_expired_messages(&mut state, now, ttl)
+    }
+
+    pub(crate) fn apply_expire_pending_moderation(
+        &self,
+        now: DateTime<Utc>,
+        deadline: chrono::Duration,
+        to_state: ModerationState,
+    ) -> Vec<ModeratedContent> {
+        let mut state = self.state.write();
+        batch_expire_pending_moderation(&mut state, now, deadline, to_state)
+    }
+}
+
+/// Point-in-time copy of the durable-relevant slice of `PlatformState` —
+/// `OrchestrationStore`/`ModerationStore`/`MessagingStore` records — that
+/// `DurablePersistence` serializes to its snapshot file. Deliberately holds
+/// plain `Vec`s rather than `PlatformState` itself so it stays serializable
+/// without requiring every id type used as a `HashMap` key to round-trip
+/// through JSON.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DurableSnapshot {
+    assignments: Vec<WorkAssignment>,
+    moderation_content: Vec<ModeratedContent>,
+    moderation_events: Vec<ModerationEvent>,
+    moderation_reports: Vec<ModerationReport>,
+    moderation_audit: Vec<ModerationAuditEntry>,
+    messages: Vec<MessageRecord>,
+    dead_letters: Vec<MessageRecord>,
 }
 
 impl TenantStore for InMemoryPersistence {
@@ -143,6 +934,11 @@ impl TenantStore for InMemoryPersistence {
         tenants.sort_by(|a, b| a.name.cmp(&b.name));
         Ok(tenants)
     }
+
+    fn delete_tenant(&self, id: TenantId) -> PlatformResult<()> {
+        self.state.write().tenants.remove(&id);
+        Ok(())
+    }
 }
 
 impl ProjectStore for InMemoryPersistence {
@@ -158,6 +954,11 @@ impl ProjectStore for InMemoryPersistence {
         Ok(())
     }
 
+    fn delete_project(&self, id: ProjectId) -> PlatformResult<()> {
+        self.state.write().projects.remove(&id);
+        Ok(())
+    }
+
     fn list_projects(&self, tenant_id: TenantId) -> PlatformResult<Vec<Project>> {
         let mut projects: Vec<_> = self
             .state
@@ -219,6 +1020,26 @@ impl AgentStore for InMemoryPersistence {
     }
 }
 
+impl AgentStateStore for InMemoryPersistence {
+    fn record_agent_state_event(&self, event: AgentStateEvent) -> PlatformResult<()> {
+        self.state.write().agent_state_events.push(event);
+        Ok(())
+    }
+
+    fn list_agent_state_events(&self, agent_id: AgentId) -> PlatformResult<Vec<AgentStateEvent>> {
+        let mut events: Vec<_> = self
+            .state
+            .read()
+            .agent_state_events
+            .iter()
+            .filter(|e| e.agent_id == agent_id)
+            .cloned()
+            .collect();
+        events.sort_by(|a, b| a.at.cmp(&b.at));
+        Ok(events)
+    }
+}
+
 impl ApiKeyStore for InMemoryPersistence {
     fn insert_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()> {
         let mut state = self.state.write();
@@ -271,6 +1092,172 @@ impl ApiKeyStore for InMemoryPersistence {
     }
 }
 
+impl IdempotencyStore for InMemoryPersistence {
+    fn get(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Option<IdempotencyRecord>> {
+        let id_key = (scope.to_string(), key.to_string());
+        let mut state = self.state.write();
+        match state.idempotency.get(&id_key) {
+            Some(record) if record.expires_at <= now => {
+                state.idempotency.remove(&id_key);
+                Ok(None)
+            }
+            Some(record) => Ok(Some(record.clone())),
+            None => Ok(None),
+        }
+    }
+
+    fn put(&self, scope: &str, key: &str, record: IdempotencyRecord) -> PlatformResult<()> {
+        self.state
+            .write()
+            .idempotency
+            .insert((scope.to_string(), key.to_string()), record);
+        Ok(())
+    }
+
+    fn put_if_absent(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+        record: IdempotencyRecord,
+    ) -> PlatformResult<Option<IdempotencyRecord>> {
+        let id_key = (scope.to_string(), key.to_string());
+        let mut state = self.state.write();
+        // A live entry already expired is the same as no entry at all;
+        // evict it so it can't block a fresh claim.
+        if matches!(state.idempotency.get(&id_key), Some(existing) if existing.expires_at <= now) {
+            state.idempotency.remove(&id_key);
+        }
+        match state.idempotency.get(&id_key) {
+            Some(existing) => Ok(Some(existing.clone())),
+            None => {
+                state.idempotency.insert(id_key, record);
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl CertificateStore for InMemoryPersistence {
+    fn insert_certificate(&self, record: AgentCertificateRecord) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        if state.certificates.contains_key(&record.id) {
+            return Err(PlatformError::Conflict("agent_certificate"));
+        }
+        state.certificates.insert(record.id, record);
+        Ok(())
+    }
+
+    fn get_certificate(&self, id: uuid::Uuid) -> PlatformResult<Option<AgentCertificateRecord>> {
+        Ok(self.state.read().certificates.get(&id).cloned())
+    }
+
+    fn list_certificates_for_agent(
+        &self,
+        agent_id: AgentId,
+    ) -> PlatformResult<Vec<AgentCertificateRecord>> {
+        let mut certs: Vec<_> = self
+            .state
+            .read()
+            .certificates
+            .values()
+            .filter(|c| c.agent_id == agent_id)
+            .cloned()
+            .collect();
+        certs.sort_by(|a, b| a.issued_at.cmp(&b.issued_at));
+        Ok(certs)
+    }
+
+    fn update_certificate(&self, record: AgentCertificateRecord) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        if !state.certificates.contains_key(&record.id) {
+            return Err(PlatformError::NotFound("agent_certificate"));
+        }
+        state.certificates.insert(record.id, record);
+        Ok(())
+    }
+
+    fn insert_tenant_ca(&self, record: TenantCaRecord) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        if state.tenant_cas.contains_key(&record.tenant_id) {
+            return Err(PlatformError::Conflict("tenant_ca"));
+        }
+        state.tenant_cas.insert(record.tenant_id, record);
+        Ok(())
+    }
+
+    fn get_tenant_ca(&self, tenant_id: TenantId) -> PlatformResult<Option<TenantCaRecord>> {
+        Ok(self.state.read().tenant_cas.get(&tenant_id).cloned())
+    }
+}
+
+impl AuditStore for InMemoryPersistence {
+    fn record_event(&self, event: AuditEvent) -> PlatformResult<()> {
+        self.state.write().audit_events.push(event);
+        Ok(())
+    }
+
+    fn list_events(&self, query: &AuditQuery) -> PlatformResult<Vec<AuditEvent>> {
+        let mut events: Vec<_> = self
+            .state
+            .read()
+            .audit_events
+            .iter()
+            .filter(|event| {
+                if event.tenant_id != query.tenant_id {
+                    return false;
+                }
+                if let Some(area) = &query.area {
+                    if &event.area != area {
+                        return false;
+                    }
+                }
+                if let Some(category) = &query.category {
+                    if &event.category != category {
+                        return false;
+                    }
+                }
+                if let Some(actor_id) = query.actor_id {
+                    if event.actor_id != actor_id {
+                        return false;
+                    }
+                }
+                if let Some(from) = query.time_from {
+                    if event.timestamp < from {
+                        return false;
+                    }
+                }
+                if let Some(to) = query.time_to {
+                    if event.timestamp > to {
+                        return false;
+                    }
+                }
+                if let (Some(cursor_ts), Some(cursor_id)) =
+                    (query.cursor_timestamp, query.cursor_id)
+                {
+                    if !(event.timestamp < cursor_ts
+                        || (event.timestamp == cursor_ts && event.id > cursor_id))
+                    {
+                        return false;
+                    }
+                }
+                true
+            })
+            .cloned()
+            .collect();
+
+        events.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(a.id.cmp(&b.id)));
+        let limit = query.limit.unwrap_or(events.len() as u32) as usize;
+        events.truncate(limit);
+        Ok(events)
+    }
+}
+
 impl TaskStore for InMemoryPersistence {
     fn enqueue_task(&self, task: Task) -> PlatformResult<()> {
         let mut state = self.state.write();
@@ -334,6 +1321,19 @@ impl TaskStore for InMemoryPersistence {
         tasks.sort_by(|a, b| a.scheduled_at.cmp(&b.scheduled_at));
         Ok(tasks)
     }
+
+    fn list_tasks_by_kind(&self, tenant_id: TenantId, kind: &str) -> PlatformResult<Vec<Task>> {
+        let mut tasks: Vec<_> = self
+            .state
+            .read()
+            .tasks
+            .values()
+            .filter(|task| task.tenant_id == tenant_id && task.kind == kind)
+            .cloned()
+            .collect();
+        tasks.sort_by(|a, b| a.scheduled_at.cmp(&b.scheduled_at));
+        Ok(tasks)
+    }
 }
 
 impl WorkflowStore for InMemoryPersistence {
@@ -376,11 +1376,13 @@ impl PostgresContentStore {
     }
 
     async fn map_upload_row(row: PgRow) -> PlatformResult<UploadSession> {
-        let status: String = row.try_get("status")?;
-        let status: UploadStatus = status.parse()?;
+        let status: UploadStatus = row.try_get("status")?;
         let headers: serde_json::Value = row.try_get("headers")?;
         let headers: HashMap<String, String> = serde_json::from_value(headers)
             .map_err(|_| PlatformError::Internal("invalid headers"))?;
+        let parts: serde_json::Value = row.try_get("parts")?;
+        let parts: Vec<UploadPart> = serde_json::from_value(parts)
+            .map_err(|_| PlatformError::Internal("invalid upload parts"))?;
         Ok(UploadSession {
             id: row.try_get("id")?,
             tenant_id: row.try_get("tenant_id")?,
@@ -392,17 +1394,22 @@ impl PostgresContentStore {
             expires_at: row.try_get("expires_at")?,
             upload_url: row.try_get("upload_url")?,
             headers,
+            parts,
         })
     }
 
     async fn map_metadata_row(row: PgRow) -> PlatformResult<ContentMetadata> {
-        let visibility: String = row.try_get("visibility")?;
-        let visibility: ContentVisibility = visibility.parse()?;
+        let visibility: ContentVisibility = row.try_get("visibility")?;
         let labels: Vec<String> = row.try_get("labels")?;
         let attributes: serde_json::Value = row.try_get("attributes")?;
         let attributes: HashMap<String, String> = serde_json::from_value(attributes)
             .map_err(|_| PlatformError::Internal("invalid attributes"))?;
         let size_bytes: Option<i64> = row.try_get("size_bytes")?;
+        let immutability: Option<serde_json::Value> = row.try_get("immutability")?;
+        let immutability: Option<ImmutabilityPolicy> = immutability
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|_| PlatformError::Internal("invalid immutability policy"))?;
         Ok(ContentMetadata {
             id: row.try_get("id")?,
             tenant_id: row.try_get("tenant_id")?,
@@ -418,6 +1425,29 @@ impl PostgresContentStore {
             updated_at: row.try_get("updated_at")?,
             uploaded_by: row.try_get("uploaded_by")?,
             visibility,
+            blurhash: row.try_get("blurhash")?,
+            immutability,
+            legal_hold: row.try_get("legal_hold")?,
+            // Only present when the query computed a `ts_rank_cd` column
+            // (i.e. `ContentQuery.search_term` was set); absent otherwise.
+            relevance: row.try_get("relevance").ok(),
+        })
+    }
+
+    async fn map_lifecycle_policy_row(row: PgRow) -> PlatformResult<ContentLifecyclePolicy> {
+        let action: serde_json::Value = row.try_get("action")?;
+        let action: LifecycleAction = serde_json::from_value(action)
+            .map_err(|_| PlatformError::Internal("invalid lifecycle action"))?;
+        let max_age_days: i32 = row.try_get("max_age_days")?;
+        Ok(ContentLifecyclePolicy {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            project_id: row.try_get("project_id")?,
+            label_selector: row.try_get("label_selector")?,
+            max_age_days: max_age_days.max(0) as u32,
+            action,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
         })
     }
 }
@@ -494,6 +1524,18 @@ pub struct AgentQuery {
     pub last_seen_before: Option<DateTime<Utc>>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Keyset cursor: only rows that sort strictly after `(last_seen, id)`
+    /// in the query's `(last_seen DESC, id ASC)` order are returned. Set
+    /// together from a previously-returned cursor; takes priority over
+    /// `offset` when both are present.
+    pub cursor_last_seen: Option<DateTime<Utc>>,
+    pub cursor_id: Option<AgentId>,
+    /// Agent ids opted out of discovery (data-minimization/retention).
+    /// Matching rows are excluded entirely rather than erroring.
+    pub excluded_agent_ids: Vec<AgentId>,
+    /// Tenant ids opted out of discovery; excludes every agent belonging
+    /// to the tenant regardless of its own opt-out status.
+    pub excluded_tenant_ids: Vec<TenantId>,
 }
 
 #[cfg(feature = "db")]
@@ -510,6 +1552,31 @@ pub struct AgentSummaryRecord {
     pub metadata: AgentMetadata,
 }
 
+/// Thresholds for [`PostgresAgentStore::reap_stale_agents`]'s
+/// `Active -> Degraded -> Unreachable` transitions, measured against how
+/// long ago an agent's `last_seen` heartbeat landed.
+#[cfg(feature = "db")]
+#[derive(Debug, Clone)]
+pub struct AgentLifecycleConfig {
+    /// An agent with no heartbeat for at least this long is marked
+    /// `Degraded`.
+    pub degraded_after: chrono::Duration,
+    /// An agent with no heartbeat for at least this long is marked
+    /// `Unreachable`. Must be `>= degraded_after` or every stale agent
+    /// jumps straight to `Unreachable`.
+    pub unreachable_after: chrono::Duration,
+}
+
+#[cfg(feature = "db")]
+impl Default for AgentLifecycleConfig {
+    fn default() -> Self {
+        Self {
+            degraded_after: chrono::Duration::seconds(90),
+            unreachable_after: chrono::Duration::minutes(10),
+        }
+    }
+}
+
 #[cfg(feature = "db")]
 #[derive(sqlx::FromRow)]
 struct AgentSummaryRow {
@@ -543,6 +1610,21 @@ impl AgentSummaryRow {
     }
 }
 
+/// Caps how many times `OrchestrationStore::requeue_stale` will put a
+/// timed-out assignment back on the queue before giving up on it.
+#[derive(Debug, Clone)]
+pub struct AssignmentLifecycleConfig {
+    /// An assignment requeued this many times already is marked `Failed`
+    /// instead of being requeued again.
+    pub max_attempts: u32,
+}
+
+impl Default for AssignmentLifecycleConfig {
+    fn default() -> Self {
+        Self { max_attempts: 5 }
+    }
+}
+
 #[cfg(feature = "db")]
 #[derive(sqlx::FromRow)]
 struct AssignmentRow {
@@ -554,6 +1636,8 @@ struct AssignmentRow {
     status: String,
     status_message: Option<String>,
     metadata: serde_json::Value,
+    last_heartbeat: Option<DateTime<Utc>>,
+    attempt: i32,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -573,6 +1657,8 @@ impl AssignmentRow {
             status,
             status_message: self.status_message,
             metadata,
+            last_heartbeat: self.last_heartbeat,
+            attempt: self.attempt as u32,
             created_at: self.created_at,
             updated_at: self.updated_at,
         })
@@ -621,6 +1707,140 @@ impl ModerationRow {
     }
 }
 
+/// Same columns as [`ModerationRow`] plus a LATERAL-joined open-report
+/// count, for `list_content`'s triage sort. Kept separate from
+/// `ModerationRow` rather than making the column optional there, matching
+/// how `AgentSummaryRow`/`AssignmentRow` each get their own shape.
+#[cfg(feature = "db")]
+#[derive(sqlx::FromRow)]
+struct ModerationRowWithReportCount {
+    id: uuid::Uuid,
+    tenant_id: uuid::Uuid,
+    project_id: uuid::Uuid,
+    filename: String,
+    mime_type: Option<String>,
+    size_bytes: Option<i64>,
+    state: String,
+    reason: Option<String>,
+    labels: serde_json::Value,
+    attributes: serde_json::Value,
+    submitted_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[allow(dead_code)]
+    open_report_count: i64,
+}
+
+#[cfg(feature = "db")]
+impl ModerationRowWithReportCount {
+    fn into_model(self) -> PlatformResult<ModeratedContent> {
+        let labels: HashMap<String, String> = serde_json::from_value(self.labels)
+            .map_err(|_| PlatformError::Internal("invalid labels"))?;
+        let attributes: HashMap<String, String> = serde_json::from_value(self.attributes)
+            .map_err(|_| PlatformError::Internal("invalid attributes"))?;
+        let state = ModerationState::from_str(self.state.to_ascii_lowercase().as_str())?;
+        Ok(ModeratedContent {
+            id: self.id,
+            tenant_id: self.tenant_id,
+            project_id: self.project_id,
+            filename: self.filename,
+            mime_type: self.mime_type,
+            size_bytes: self.size_bytes.map(|v| v.max(0) as u64),
+            state,
+            reason: self.reason,
+            labels,
+            attributes,
+            submitted_at: self.submitted_at,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+#[cfg(feature = "db")]
+#[derive(sqlx::FromRow)]
+struct ModerationEventRow {
+    id: uuid::Uuid,
+    content_id: uuid::Uuid,
+    from_state: String,
+    to_state: String,
+    reason: Option<String>,
+    actor_id: uuid::Uuid,
+    created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "db")]
+impl ModerationEventRow {
+    fn into_model(self) -> PlatformResult<ModerationEvent> {
+        Ok(ModerationEvent {
+            id: self.id,
+            content_id: self.content_id,
+            from_state: ModerationState::from_str(self.from_state.to_ascii_lowercase().as_str())?,
+            to_state: ModerationState::from_str(self.to_state.to_ascii_lowercase().as_str())?,
+            reason: self.reason,
+            actor_id: self.actor_id,
+            created_at: self.created_at,
+        })
+    }
+}
+
+#[cfg(feature = "db")]
+#[derive(sqlx::FromRow)]
+struct ModerationAuditRow {
+    id: uuid::Uuid,
+    content_id: uuid::Uuid,
+    sequence: i64,
+    from_state: String,
+    to_state: String,
+    reason: Option<String>,
+    actor_id: uuid::Uuid,
+    created_at: DateTime<Utc>,
+    hash: String,
+}
+
+#[cfg(feature = "db")]
+impl ModerationAuditRow {
+    fn into_model(self) -> PlatformResult<ModerationAuditEntry> {
+        Ok(ModerationAuditEntry {
+            id: self.id,
+            content_id: self.content_id,
+            sequence: self.sequence.max(0) as u64,
+            from_state: ModerationState::from_str(self.from_state.to_ascii_lowercase().as_str())?,
+            to_state: ModerationState::from_str(self.to_state.to_ascii_lowercase().as_str())?,
+            reason: self.reason,
+            actor_id: self.actor_id,
+            created_at: self.created_at,
+            hash: self.hash,
+        })
+    }
+}
+
+#[cfg(feature = "db")]
+#[derive(sqlx::FromRow)]
+struct ModerationReportRow {
+    id: uuid::Uuid,
+    content_id: uuid::Uuid,
+    reporter_id: uuid::Uuid,
+    category: String,
+    detail: Option<String>,
+    created_at: DateTime<Utc>,
+    resolved: bool,
+}
+
+#[cfg(feature = "db")]
+impl ModerationReportRow {
+    fn into_model(self) -> PlatformResult<ModerationReport> {
+        let category = ReportCategory::from_str(self.category.to_ascii_lowercase().as_str())?;
+        Ok(ModerationReport {
+            id: self.id,
+            content_id: self.content_id,
+            reporter_id: self.reporter_id,
+            category,
+            detail: self.detail,
+            created_at: self.created_at,
+            resolved: self.resolved,
+        })
+    }
+}
+
 #[cfg(feature = "db")]
 #[derive(sqlx::FromRow)]
 struct MessageRow {
@@ -633,6 +1853,10 @@ struct MessageRow {
     priority: String,
     attributes: serde_json::Value,
     published_at: DateTime<Utc>,
+    delivery_attempts: i32,
+    max_attempts: i32,
+    lease_until: Option<DateTime<Utc>>,
+    leased_by: Option<String>,
 }
 
 #[cfg(feature = "db")]
@@ -651,6 +1875,10 @@ impl MessageRow {
             priority,
             attributes,
             published_at: self.published_at,
+            delivery_attempts: self.delivery_attempts.max(0) as u32,
+            max_attempts: self.max_attempts.max(0) as u32,
+            lease_until: self.lease_until,
+            leased_by: self.leased_by,
         })
     }
 }
@@ -697,13 +1925,17 @@ impl PostgresAgentStore {
         .bind(input.tenant_id)
         .bind(input.project_id)
         .bind(metadata)
-        .bind(input.status.map(|s| s.as_str().to_string()))
+        .bind(input.status)
         .bind(input.last_seen)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
+    /// Records a heartbeat sample and marks the agent `Active` again,
+    /// regardless of how long it had been `Degraded`/`Unreachable`.
+    /// `reap_stale_agents` is what moves it back out of `Active` once
+    /// heartbeats stop.
     pub async fn record_heartbeat(&self, record: AgentHeartbeatRecord) -> PlatformResult<()> {
         sqlx::query(
             "INSERT INTO node_metrics (node_id, ts, cpu_percent, memory_used_bytes)
@@ -721,7 +1953,7 @@ impl PostgresAgentStore {
         )
         .bind(record.agent_id)
         .bind(record.timestamp)
-        .bind("active")
+        .bind(AgentStatus::Active)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -772,6 +2004,25 @@ impl PostgresAgentStore {
             builder.push(" AND n.last_seen <= ");
             builder.push_bind(before);
         }
+        if let (Some(cursor_ts), Some(cursor_id)) = (query.cursor_last_seen, query.cursor_id) {
+            builder.push(" AND (n.last_seen < ");
+            builder.push_bind(cursor_ts);
+            builder.push(" OR (n.last_seen = ");
+            builder.push_bind(cursor_ts);
+            builder.push(" AND n.id > ");
+            builder.push_bind(cursor_id);
+            builder.push("))");
+        }
+        if !query.excluded_agent_ids.is_empty() {
+            builder.push(" AND NOT (n.id = ANY(");
+            builder.push_bind(query.excluded_agent_ids.clone());
+            builder.push("))");
+        }
+        if !query.excluded_tenant_ids.is_empty() {
+            builder.push(" AND (n.tenant_id IS NULL OR NOT (n.tenant_id = ANY(");
+            builder.push_bind(query.excluded_tenant_ids.clone());
+            builder.push(")))");
+        }
 
         builder.push(" ORDER BY n.last_seen DESC NULLS LAST, n.id ASC");
 
@@ -780,185 +2031,1295 @@ impl PostgresAgentStore {
             builder.push(" LIMIT ");
             builder.push_bind(limit);
         }
-        if let Some(offset) = query.offset {
-            let offset: i64 = offset.max(0);
-            builder.push(" OFFSET ");
-            builder.push_bind(offset);
+        if query.cursor_last_seen.is_none() {
+            if let Some(offset) = query.offset {
+                let offset: i64 = offset.max(0);
+                builder.push(" OFFSET ");
+                builder.push_bind(offset);
+            }
         }
 
         let rows: Vec<AgentSummaryRow> = builder.build_query_as().fetch_all(&self.pool).await?;
         rows.into_iter().map(|row| row.into_record()).collect()
     }
-}
 
-#[cfg(feature = "db")]
-#[async_trait]
-impl ContentStore for PostgresContentStore {
-    async fn create_upload_session(&self, session: UploadSession) -> PlatformResult<()> {
-        let headers = serde_json::to_value(&session.headers)
-            .map_err(|_| PlatformError::InvalidInput("invalid headers"))?;
-        sqlx::query(
-            "INSERT INTO ugc_upload_sessions (
-                id, tenant_id, project_id, content_id, status,
-                created_at, updated_at, expires_at, upload_url, headers
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10)",
+    /// Transitions agents whose heartbeats have gone quiet from `Active` to
+    /// `Degraded` to `Unreachable`, per `config`'s thresholds, and returns
+    /// every agent whose status changed so callers (e.g. a scheduler or
+    /// alerting hook) can react. Set-based rather than row-by-row so it
+    /// scales to fleets with many agents; `Registered`/`Suspended` agents
+    /// are left alone since those reflect an administrative state, not
+    /// liveness.
+    pub async fn reap_stale_agents(
+        &self,
+        now: DateTime<Utc>,
+        config: &AgentLifecycleConfig,
+    ) -> PlatformResult<Vec<AgentSummaryRecord>> {
+        let degraded_cutoff = now - config.degraded_after;
+        let unreachable_cutoff = now - config.unreachable_after;
+
+        let mut changed = Vec::new();
+
+        // Unreachable first so the degraded sweep's `status <> 'unreachable'`
+        // guard excludes agents this call already moved past it.
+        let unreachable: Vec<AgentSummaryRow> = sqlx::query_as(
+            "UPDATE nodes SET status = 'unreachable', updated_at = NOW()
+             WHERE last_seen < $1 AND status NOT IN ('unreachable', 'suspended', 'registered')
+             RETURNING id, hostname, tenant_id, project_id, status, last_seen, metadata,
+                       NULL::float8 AS cpu_percent, NULL::bigint AS memory_used_bytes",
         )
-        .bind(session.id)
-        .bind(session.tenant_id)
-        .bind(session.project_id)
-        .bind(session.content_id)
-        .bind(session.status.as_str())
-        .bind(session.created_at)
-        .bind(session.updated_at)
-        .bind(session.expires_at)
-        .bind(session.upload_url)
-        .bind(headers)
-        .execute(&self.pool)
+        .bind(unreachable_cutoff)
+        .fetch_all(&self.pool)
+        .await?;
+        for row in unreachable {
+            changed.push(row.into_record()?);
+        }
+
+        let degraded: Vec<AgentSummaryRow> = sqlx::query_as(
+            "UPDATE nodes SET status = 'degraded', updated_at = NOW()
+             WHERE last_seen < $1 AND status NOT IN ('degraded', 'unreachable', 'suspended', 'registered')
+             RETURNING id, hostname, tenant_id, project_id, status, last_seen, metadata,
+                       NULL::float8 AS cpu_percent, NULL::bigint AS memory_used_bytes",
+        )
+        .bind(degraded_cutoff)
+        .fetch_all(&self.pool)
         .await?;
+        for row in degraded {
+            changed.push(row.into_record()?);
+        }
+
+        Ok(changed)
+    }
+
+    /// Marks a single agent `Offline`, called by the gateway's in-memory
+    /// liveness sweep (`AgentRegistry::mark_stale_offline`) rather than on a
+    /// timer of its own, since the in-memory registry is already doing the
+    /// per-agent staleness check against `heartbeat_interval_seconds`.
+    pub async fn mark_offline(&self, agent_id: AgentId) -> PlatformResult<()> {
+        sqlx::query("UPDATE nodes SET status = $2, updated_at = NOW() WHERE id = $1")
+            .bind(agent_id)
+            .bind(AgentStatus::Offline)
+            .execute(&self.pool)
+            .await?;
         Ok(())
     }
+}
 
-    async fn update_upload_session(&self, session: UploadSession) -> PlatformResult<()> {
-        let headers = serde_json::to_value(&session.headers)
-            .map_err(|_| PlatformError::InvalidInput("invalid headers"))?;
+/// Postgres-backed work queue for [`Task`]s.
+///
+/// `TaskStore` itself is synchronous, so it can't be implemented directly
+/// against an async `sqlx` pool (the same reason [`PostgresAgentStore`]
+/// exposes its own async methods rather than implementing `AgentStore`).
+/// Callers that want real multi-worker claim semantics use this type's
+/// methods directly instead of going through `Arc<dyn TaskStore>`.
+#[cfg(feature = "db")]
+pub struct PostgresTaskStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresTaskStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    async fn map_task_row(row: PgRow) -> PlatformResult<Task> {
+        let status: String = row.try_get("status")?;
+        let status: TaskStatus = status.parse()?;
+        let attempts: i32 = row.try_get("attempts")?;
+        let timeouts: Option<serde_json::Value> = row.try_get("timeouts")?;
+        let timeouts: Option<TaskTimeouts> = timeouts
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|_| PlatformError::Internal("invalid task timeouts"))?;
+        Ok(Task {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            kind: row.try_get("kind")?,
+            payload: row.try_get("payload")?,
+            status,
+            attempts: attempts.max(0) as u32,
+            scheduled_at: row.try_get("scheduled_at")?,
+            started_at: row.try_get("started_at")?,
+            completed_at: row.try_get("completed_at")?,
+            last_error: row.try_get("last_error")?,
+            result: row.try_get("result")?,
+            timeouts,
+            // Placement isn't persisted in the `tasks` table; a task leased
+            // back out of Postgres reports no assignment regardless of what
+            // `schedule_task` computed when it was first enqueued.
+            assigned_agent_ids: Vec::new(),
+        })
+    }
+
+    pub async fn enqueue_task(&self, task: Task) -> PlatformResult<()> {
+        let timeouts = task
+            .timeouts
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|_| PlatformError::InvalidInput("invalid task timeouts"))?;
         sqlx::query(
-            "UPDATE ugc_upload_sessions SET
-                tenant_id = $2,
-                project_id = $3,
-                content_id = $4,
-                status = $5,
-                created_at = $6,
-                updated_at = $7,
-                expires_at = $8,
-                upload_url = $9,
-                headers = $10
-            WHERE id = $1",
+            "INSERT INTO tasks (
+                id, tenant_id, kind, payload, status, attempts, scheduled_at,
+                started_at, completed_at, last_error, result, timeouts, heartbeat
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,NULL)",
         )
-        .bind(session.id)
-        .bind(session.tenant_id)
-        .bind(session.project_id)
-        .bind(session.content_id)
-        .bind(session.status.as_str())
-        .bind(session.created_at)
-        .bind(session.updated_at)
-        .bind(session.expires_at)
-        .bind(session.upload_url)
-        .bind(headers)
+        .bind(task.id)
+        .bind(task.tenant_id)
+        .bind(task.kind)
+        .bind(task.payload)
+        .bind(task.status.as_str())
+        .bind(task.attempts as i32)
+        .bind(task.scheduled_at)
+        .bind(task.started_at)
+        .bind(task.completed_at)
+        .bind(task.last_error)
+        .bind(task.result)
+        .bind(timeouts)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
-    async fn get_upload_session(&self, id: UploadId) -> PlatformResult<Option<UploadSession>> {
-        let row = sqlx::query("SELECT * FROM ugc_upload_sessions WHERE id = $1")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await?;
-        if let Some(row) = row {
-            Ok(Some(Self::map_upload_row(row).await?))
-        } else {
-            Ok(None)
+    /// Atomically claims the oldest pending task for `tenant_id`, flipping it
+    /// to `in_progress` and stamping `heartbeat` in a single statement so two
+    /// workers racing `peek_next_task` never claim the same row.
+    pub async fn peek_next_task(&self, tenant_id: TenantId) -> PlatformResult<Option<Task>> {
+        let row = sqlx::query(
+            "UPDATE tasks SET status = 'in_progress', started_at = NOW(), heartbeat = NOW()
+             WHERE id = (
+                 SELECT id FROM tasks
+                 WHERE tenant_id = $1 AND status = 'pending'
+                 ORDER BY scheduled_at
+                 FOR UPDATE SKIP LOCKED
+                 LIMIT 1
+             )
+             RETURNING *",
+        )
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        match row {
+            Some(row) => Ok(Some(Self::map_task_row(row).await?)),
+            None => Ok(None),
         }
     }
 
-    async fn record_content_metadata(&self, metadata: ContentMetadata) -> PlatformResult<()> {
-        let attributes = serde_json::to_value(&metadata.attributes)
-            .map_err(|_| PlatformError::InvalidInput("invalid attributes"))?;
-        let size_bytes = metadata.size_bytes.map(|v| v as i64);
-        sqlx::query(
-            "INSERT INTO ugc_content_metadata (
-                id, tenant_id, project_id, filename, mime_type, size_bytes,
-                checksum, storage_path, labels, attributes, created_at,
-                updated_at, uploaded_by, visibility
-            ) VALUES (
-                $1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14
-            ) ON CONFLICT (id) DO UPDATE SET
-                tenant_id = EXCLUDED.tenant_id,
-                project_id = EXCLUDED.project_id,
-                filename = EXCLUDED.filename,
-                mime_type = EXCLUDED.mime_type,
-                size_bytes = EXCLUDED.size_bytes,
-                checksum = EXCLUDED.checksum,
-                storage_path = EXCLUDED.storage_path,
-                labels = EXCLUDED.labels,
-                attributes = EXCLUDED.attributes,
-                created_at = EXCLUDED.created_at,
-                updated_at = EXCLUDED.updated_at,
-                uploaded_by = EXCLUDED.uploaded_by,
-                visibility = EXCLUDED.visibility",
+    /// Writes a task back in full. The heartbeat is cleared whenever the
+    /// task isn't `in_progress`, so a task that just completed or failed
+    /// can't be mistaken for a worker that's still alive.
+    pub async fn update_task(&self, task: Task) -> PlatformResult<()> {
+        let timeouts = task
+            .timeouts
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|_| PlatformError::InvalidInput("invalid task timeouts"))?;
+        let heartbeat = matches!(task.status, TaskStatus::InProgress).then(Utc::now);
+        let result = sqlx::query(
+            "UPDATE tasks SET
+                tenant_id = $2, kind = $3, payload = $4, status = $5, attempts = $6,
+                scheduled_at = $7, started_at = $8, completed_at = $9, last_error = $10,
+                result = $11, timeouts = $12, heartbeat = $13
+             WHERE id = $1",
         )
-        .bind(metadata.id)
-        .bind(metadata.tenant_id)
-        .bind(metadata.project_id)
-        .bind(metadata.filename)
-        .bind(metadata.mime_type)
-        .bind(size_bytes)
-        .bind(metadata.checksum)
-        .bind(metadata.storage_path)
-        .bind(metadata.labels)
-        .bind(attributes)
-        .bind(metadata.created_at)
-        .bind(metadata.updated_at)
-        .bind(metadata.uploaded_by)
-        .bind(metadata.visibility.as_str())
+        .bind(task.id)
+        .bind(task.tenant_id)
+        .bind(task.kind)
+        .bind(task.payload)
+        .bind(task.status.as_str())
+        .bind(task.attempts as i32)
+        .bind(task.scheduled_at)
+        .bind(task.started_at)
+        .bind(task.completed_at)
+        .bind(task.last_error)
+        .bind(task.result)
+        .bind(timeouts)
+        .bind(heartbeat)
         .execute(&self.pool)
         .await?;
+        if result.rows_affected() == 0 {
+            return Err(PlatformError::NotFound("task"));
+        }
         Ok(())
     }
 
-    async fn get_content_metadata(&self, id: ContentId) -> PlatformResult<Option<ContentMetadata>> {
-        let row = sqlx::query("SELECT * FROM ugc_content_metadata WHERE id = $1")
+    pub async fn get_task(&self, id: TaskId) -> PlatformResult<Option<Task>> {
+        let row = sqlx::query("SELECT * FROM tasks WHERE id = $1")
             .bind(id)
             .fetch_optional(&self.pool)
             .await?;
-        if let Some(row) = row {
-            Ok(Some(Self::map_metadata_row(row).await?))
-        } else {
-            Ok(None)
+        match row {
+            Some(row) => Ok(Some(Self::map_task_row(row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn list_pending_tasks(&self, tenant_id: TenantId) -> PlatformResult<Vec<Task>> {
+        let rows = sqlx::query(
+            "SELECT * FROM tasks WHERE tenant_id = $1 AND status = 'pending' ORDER BY scheduled_at",
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            out.push(Self::map_task_row(row).await?);
+        }
+        Ok(out)
+    }
+
+    pub async fn list_tasks_by_kind(
+        &self,
+        tenant_id: TenantId,
+        kind: &str,
+    ) -> PlatformResult<Vec<Task>> {
+        let rows = sqlx::query(
+            "SELECT * FROM tasks WHERE tenant_id = $1 AND kind = $2 ORDER BY scheduled_at",
+        )
+        .bind(tenant_id)
+        .bind(kind)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            out.push(Self::map_task_row(row).await?);
+        }
+        Ok(out)
+    }
+
+    /// Bumps `heartbeat` for a task a worker is still actively executing.
+    /// Workers call this periodically so `reclaim_stalled_tasks` doesn't
+    /// mistake live work for a crashed one.
+    pub async fn renew_task_lease(&self, id: TaskId) -> PlatformResult<()> {
+        let result = sqlx::query(
+            "UPDATE tasks SET heartbeat = NOW() WHERE id = $1 AND status = 'in_progress'",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(PlatformError::NotFound("task"));
+        }
+        Ok(())
+    }
+
+    /// Returns `in_progress` tasks whose heartbeat has lapsed to `pending`
+    /// in a single statement, so a crashed worker's claim doesn't strand the
+    /// task forever. Returns the number of tasks reclaimed; safe to call on
+    /// a timer since a task with a fresh heartbeat is left untouched.
+    pub async fn reclaim_stalled_tasks(&self, timeout: chrono::Duration) -> PlatformResult<u64> {
+        let cutoff = Utc::now() - timeout;
+        let result = sqlx::query(
+            "UPDATE tasks SET status = 'pending', heartbeat = NULL
+             WHERE status = 'in_progress' AND heartbeat < $1",
+        )
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Postgres-backed [`Tenant`] storage for [`super::provisioning::ProvisioningService`].
+///
+/// `TenantStore` is synchronous for the same reason noted on
+/// [`PostgresTaskStore`]: it can't be implemented against an async `sqlx`
+/// pool, so this exposes the same operations as plain async methods instead.
+#[cfg(feature = "db")]
+pub struct PostgresTenantStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresTenantStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> PlatformResult<Tenant> {
+        let settings: serde_json::Value = row.try_get("settings")?;
+        let settings: TenantSettings = serde_json::from_value(settings)
+            .map_err(|_| PlatformError::Internal("invalid tenant settings"))?;
+        Ok(Tenant {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            created_at: row.try_get("created_at")?,
+            settings,
+        })
+    }
+
+    pub async fn insert_tenant(&self, tenant: Tenant) -> PlatformResult<()> {
+        let settings = serde_json::to_value(&tenant.settings)
+            .map_err(|_| PlatformError::InvalidInput("invalid tenant settings"))?;
+        sqlx::query("INSERT INTO tenants (id, name, created_at, settings) VALUES ($1,$2,$3,$4)")
+            .bind(tenant.id)
+            .bind(tenant.name)
+            .bind(tenant.created_at)
+            .bind(settings)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_tenant(&self, id: TenantId) -> PlatformResult<Option<Tenant>> {
+        let row = sqlx::query("SELECT * FROM tenants WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+
+    pub async fn list_tenants(&self) -> PlatformResult<Vec<Tenant>> {
+        let rows = sqlx::query("SELECT * FROM tenants ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter().map(Self::map_row).collect()
+    }
+}
+
+/// Postgres-backed [`Project`] storage; see [`PostgresTenantStore`] for why
+/// this exposes async methods rather than implementing `ProjectStore`.
+#[cfg(feature = "db")]
+pub struct PostgresProjectStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresProjectStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> PlatformResult<Project> {
+        Ok(Project {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            name: row.try_get("name")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+
+    pub async fn insert_project(&self, project: Project) -> PlatformResult<()> {
+        sqlx::query(
+            "INSERT INTO projects (id, tenant_id, name, created_at) VALUES ($1,$2,$3,$4)",
+        )
+        .bind(project.id)
+        .bind(project.tenant_id)
+        .bind(project.name)
+        .bind(project.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_projects(&self, tenant_id: TenantId) -> PlatformResult<Vec<Project>> {
+        let rows = sqlx::query("SELECT * FROM projects WHERE tenant_id = $1 ORDER BY name")
+            .bind(tenant_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    pub async fn get_project(&self, id: ProjectId) -> PlatformResult<Option<Project>> {
+        let row = sqlx::query("SELECT * FROM projects WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+}
+
+/// Postgres-backed [`Agent`] storage for `ProvisioningService`, distinct
+/// from [`PostgresAgentStore`] (which backs the unrelated `nodes`/fleet
+/// telemetry tables used by the gateway's gRPC `AgentControl` service). See
+/// [`PostgresTenantStore`] for why this exposes async methods rather than
+/// implementing `AgentStore`.
+#[cfg(feature = "db")]
+pub struct PostgresProvisioningAgentStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresProvisioningAgentStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> PlatformResult<Agent> {
+        let metadata: serde_json::Value = row.try_get("metadata")?;
+        let metadata: AgentMetadata = serde_json::from_value(metadata)
+            .map_err(|_| PlatformError::Internal("invalid agent metadata"))?;
+        Ok(Agent {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            project_id: row.try_get("project_id")?,
+            hostname: row.try_get("hostname")?,
+            status: row.try_get("status")?,
+            last_seen: row.try_get("last_seen")?,
+            created_at: row.try_get("created_at")?,
+            metadata,
+        })
+    }
+
+    pub async fn insert_agent(&self, agent: Agent) -> PlatformResult<()> {
+        let metadata = serde_json::to_value(&agent.metadata)
+            .map_err(|_| PlatformError::InvalidInput("invalid agent metadata"))?;
+        sqlx::query(
+            "INSERT INTO agents (
+                id, tenant_id, project_id, hostname, status, last_seen, created_at, metadata
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)",
+        )
+        .bind(agent.id)
+        .bind(agent.tenant_id)
+        .bind(agent.project_id)
+        .bind(agent.hostname)
+        .bind(agent.status)
+        .bind(agent.last_seen)
+        .bind(agent.created_at)
+        .bind(metadata)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_agent(&self, agent: Agent) -> PlatformResult<()> {
+        let metadata = serde_json::to_value(&agent.metadata)
+            .map_err(|_| PlatformError::InvalidInput("invalid agent metadata"))?;
+        let result = sqlx::query(
+            "UPDATE agents SET
+                tenant_id = $2, project_id = $3, hostname = $4, status = $5,
+                last_seen = $6, metadata = $7
+             WHERE id = $1",
+        )
+        .bind(agent.id)
+        .bind(agent.tenant_id)
+        .bind(agent.project_id)
+        .bind(agent.hostname)
+        .bind(agent.status)
+        .bind(agent.last_seen)
+        .bind(metadata)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(PlatformError::NotFound("agent"));
         }
+        Ok(())
+    }
+
+    pub async fn list_agents(&self, tenant_id: TenantId) -> PlatformResult<Vec<Agent>> {
+        let rows = sqlx::query("SELECT * FROM agents WHERE tenant_id = $1 ORDER BY hostname")
+            .bind(tenant_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    pub async fn get_agent(&self, id: AgentId) -> PlatformResult<Option<Agent>> {
+        let row = sqlx::query("SELECT * FROM agents WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+
+    /// Transitions every `active` agent whose `last_seen` has lapsed past
+    /// `cutoff` to `suspended` in one statement, mirroring
+    /// [`PostgresAgentStore::reap_stale_agents`]'s set-based approach so
+    /// `ProvisioningService::sweep_inactive_agents` scales past fleets an
+    /// in-memory scan-and-update loop would struggle with. Callers still
+    /// need to record an [`AgentStateEvent`] per returned agent themselves
+    /// (via an `AgentStateStore`) to keep the transition history complete.
+    pub async fn sweep_inactive_agents(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> PlatformResult<Vec<Agent>> {
+        let rows = sqlx::query(
+            "UPDATE agents SET status = 'suspended'
+             WHERE status = 'active' AND last_seen < $1
+             RETURNING *",
+        )
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter().map(Self::map_row).collect()
+    }
+}
+
+/// Postgres-backed [`ApiKeyRecord`] storage; see [`PostgresTenantStore`] for
+/// why this exposes async methods rather than implementing `ApiKeyStore`.
+#[cfg(feature = "db")]
+pub struct PostgresApiKeyStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresApiKeyStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> PlatformResult<ApiKeyRecord> {
+        let scopes: serde_json::Value = row.try_get("scopes")?;
+        let scopes: Vec<Scope> = serde_json::from_value(scopes)
+            .map_err(|_| PlatformError::Internal("invalid api key scopes"))?;
+        Ok(ApiKeyRecord {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            label: row.try_get("label")?,
+            scopes,
+            token_prefix: row.try_get("token_prefix")?,
+            token_hash: row.try_get("token_hash")?,
+            created_at: row.try_get("created_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            revoked: row.try_get("revoked")?,
+            deleted_at: row.try_get("deleted_at")?,
+            rotated_from: row.try_get("rotated_from")?,
+            rotated_to: row.try_get("rotated_to")?,
+        })
+    }
+
+    pub async fn insert_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()> {
+        let scopes = serde_json::to_value(&record.scopes)
+            .map_err(|_| PlatformError::InvalidInput("invalid api key scopes"))?;
+        sqlx::query(
+            "INSERT INTO api_keys (
+                id, tenant_id, label, scopes, token_prefix, token_hash, created_at,
+                last_used_at, revoked, deleted_at, rotated_from, rotated_to
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12)",
+        )
+        .bind(record.id)
+        .bind(record.tenant_id)
+        .bind(record.label)
+        .bind(scopes)
+        .bind(record.token_prefix)
+        .bind(record.token_hash)
+        .bind(record.created_at)
+        .bind(record.last_used_at)
+        .bind(record.revoked)
+        .bind(record.deleted_at)
+        .bind(record.rotated_from)
+        .bind(record.rotated_to)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_api_key(&self, id: ApiKeyId) -> PlatformResult<Option<ApiKeyRecord>> {
+        let row = sqlx::query("SELECT * FROM api_keys WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+
+    pub async fn get_api_key_by_prefix(
+        &self,
+        prefix: &str,
+    ) -> PlatformResult<Option<ApiKeyRecord>> {
+        let row = sqlx::query("SELECT * FROM api_keys WHERE token_prefix = $1")
+            .bind(prefix)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+
+    pub async fn list_api_keys(&self, tenant_id: TenantId) -> PlatformResult<Vec<ApiKeyRecord>> {
+        let rows = sqlx::query("SELECT * FROM api_keys WHERE tenant_id = $1 ORDER BY created_at")
+            .bind(tenant_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    pub async fn update_api_key(&self, record: ApiKeyRecord) -> PlatformResult<()> {
+        let scopes = serde_json::to_value(&record.scopes)
+            .map_err(|_| PlatformError::InvalidInput("invalid api key scopes"))?;
+        let result = sqlx::query(
+            "UPDATE api_keys SET
+                label = $2, scopes = $3, token_prefix = $4, token_hash = $5,
+                last_used_at = $6, revoked = $7, deleted_at = $8, rotated_from = $9,
+                rotated_to = $10
+             WHERE id = $1",
+        )
+        .bind(record.id)
+        .bind(record.label)
+        .bind(scopes)
+        .bind(record.token_prefix)
+        .bind(record.token_hash)
+        .bind(record.last_used_at)
+        .bind(record.revoked)
+        .bind(record.deleted_at)
+        .bind(record.rotated_from)
+        .bind(record.rotated_to)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(PlatformError::NotFound("api_key"));
+        }
+        Ok(())
+    }
+}
+
+/// Postgres-backed [`IdempotencyRecord`] storage; see [`PostgresTenantStore`]
+/// for why this exposes async methods rather than implementing
+/// `IdempotencyStore`.
+#[cfg(feature = "db")]
+pub struct PostgresIdempotencyStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresIdempotencyStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: PgRow) -> PlatformResult<IdempotencyRecord> {
+        let response: serde_json::Value = row.try_get("response")?;
+        Ok(IdempotencyRecord {
+            request_hash: row.try_get("request_hash")?,
+            response,
+            created_at: row.try_get("created_at")?,
+            expires_at: row.try_get("expires_at")?,
+        })
+    }
+
+    /// Returns the record for `(scope, key)`, evicting it first if it has
+    /// already lapsed as of `now`, mirroring
+    /// `InMemoryPersistence`/`EmbeddedPersistence`'s lazy-expiry `get`.
+    pub async fn get(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Option<IdempotencyRecord>> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE scope = $1 AND key = $2 AND expires_at <= $3")
+            .bind(scope)
+            .bind(key)
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        let row = sqlx::query("SELECT * FROM idempotency_keys WHERE scope = $1 AND key = $2")
+            .bind(scope)
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+
+    pub async fn put(
+        &self,
+        scope: &str,
+        key: &str,
+        record: IdempotencyRecord,
+    ) -> PlatformResult<()> {
+        sqlx::query(
+            "INSERT INTO idempotency_keys (scope, key, request_hash, response, created_at, expires_at)
+             VALUES ($1,$2,$3,$4,$5,$6)
+             ON CONFLICT (scope, key) DO UPDATE SET
+                request_hash = EXCLUDED.request_hash,
+                response = EXCLUDED.response,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at",
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(record.request_hash)
+        .bind(record.response)
+        .bind(record.created_at)
+        .bind(record.expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Atomic counterpart to `put`: `INSERT ... ON CONFLICT DO NOTHING`
+    /// followed by a read of whatever row is now there. Either our own
+    /// insert went through (we won the claim) or a concurrent insert got
+    /// there first (we lost it and read back its row) — there is no window
+    /// in between where a second caller can observe an empty row the same
+    /// way this one just did, unlike a separate `get` followed by `put`.
+    pub async fn put_if_absent(
+        &self,
+        scope: &str,
+        key: &str,
+        now: DateTime<Utc>,
+        record: IdempotencyRecord,
+    ) -> PlatformResult<Option<IdempotencyRecord>> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE scope = $1 AND key = $2 AND expires_at <= $3")
+            .bind(scope)
+            .bind(key)
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        let inserted = sqlx::query(
+            "INSERT INTO idempotency_keys (scope, key, request_hash, response, created_at, expires_at)
+             VALUES ($1,$2,$3,$4,$5,$6)
+             ON CONFLICT (scope, key) DO NOTHING",
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(record.request_hash)
+        .bind(record.response)
+        .bind(record.created_at)
+        .bind(record.expires_at)
+        .execute(&self.pool)
+        .await?;
+        if inserted.rows_affected() > 0 {
+            return Ok(None);
+        }
+        let row = sqlx::query("SELECT * FROM idempotency_keys WHERE scope = $1 AND key = $2")
+            .bind(scope)
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(Self::map_row).transpose()
+    }
+}
+
+#[cfg(feature = "db")]
+#[async_trait]
+impl ContentStore for PostgresContentStore {
+    async fn create_upload_session(&self, session: UploadSession) -> PlatformResult<()> {
+        let headers = serde_json::to_value(&session.headers)
+            .map_err(|_| PlatformError::InvalidInput("invalid headers"))?;
+        let parts = serde_json::to_value(&session.parts)
+            .map_err(|_| PlatformError::InvalidInput("invalid upload parts"))?;
+        sqlx::query(
+            "INSERT INTO ugc_upload_sessions (
+                id, tenant_id, project_id, content_id, status,
+                created_at, updated_at, expires_at, upload_url, headers, parts
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)",
+        )
+        .bind(session.id)
+        .bind(session.tenant_id)
+        .bind(session.project_id)
+        .bind(session.content_id)
+        .bind(session.status.as_str())
+        .bind(session.created_at)
+        .bind(session.updated_at)
+        .bind(session.expires_at)
+        .bind(session.upload_url)
+        .bind(headers)
+        .bind(parts)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn update_upload_session(&self, session: UploadSession) -> PlatformResult<()> {
+        let headers = serde_json::to_value(&session.headers)
+            .map_err(|_| PlatformError::InvalidInput("invalid headers"))?;
+        let parts = serde_json::to_value(&session.parts)
+            .map_err(|_| PlatformError::InvalidInput("invalid upload parts"))?;
+        sqlx::query(
+            "UPDATE ugc_upload_sessions SET
+                tenant_id = $2,
+                project_id = $3,
+                content_id = $4,
+                status = $5,
+                created_at = $6,
+                updated_at = $7,
+                expires_at = $8,
+                upload_url = $9,
+                headers = $10,
+                parts = $11
+            WHERE id = $1",
+        )
+        .bind(session.id)
+        .bind(session.tenant_id)
+        .bind(session.project_id)
+        .bind(session.content_id)
+        .bind(session.status.as_str())
+        .bind(session.created_at)
+        .bind(session.updated_at)
+        .bind(session.expires_at)
+        .bind(session.upload_url)
+        .bind(headers)
+        .bind(parts)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_upload_session(&self, id: UploadId) -> PlatformResult<Option<UploadSession>> {
+        let row = sqlx::query("SELECT * FROM ugc_upload_sessions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        if let Some(row) = row {
+            Ok(Some(Self::map_upload_row(row).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Upserts into `ugc_upload_parts`, a table separate from
+    /// `ugc_upload_sessions.parts` so concurrent part uploads each get their
+    /// own row (`UNIQUE (upload_id, part_number)`) instead of racing to
+    /// rewrite one JSON column.
+    async fn register_upload_part(&self, upload_id: UploadId, part: UploadPart) -> PlatformResult<()> {
+        if self.get_upload_session(upload_id).await?.is_none() {
+            return Err(PlatformError::NotFound("upload_session"));
+        }
+        let size_bytes = part.size_bytes.map(|v| v as i64);
+        sqlx::query(
+            "INSERT INTO ugc_upload_parts (upload_id, part_number, etag, size_bytes, uploaded_at)
+             VALUES ($1,$2,$3,$4,$5)
+             ON CONFLICT (upload_id, part_number) DO UPDATE SET
+                etag = EXCLUDED.etag,
+                size_bytes = EXCLUDED.size_bytes,
+                uploaded_at = EXCLUDED.uploaded_at",
+        )
+        .bind(upload_id)
+        .bind(part.part_number as i32)
+        .bind(part.etag)
+        .bind(size_bytes)
+        .bind(part.uploaded_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn list_upload_parts(&self, upload_id: UploadId) -> PlatformResult<Vec<UploadPart>> {
+        let rows = sqlx::query(
+            "SELECT part_number, etag, size_bytes, uploaded_at FROM ugc_upload_parts
+             WHERE upload_id = $1 ORDER BY part_number",
+        )
+        .bind(upload_id)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut parts = Vec::with_capacity(rows.len());
+        for row in rows {
+            let part_number: i32 = row.try_get("part_number")?;
+            let size_bytes: Option<i64> = row.try_get("size_bytes")?;
+            parts.push(UploadPart {
+                part_number: part_number.max(0) as u32,
+                etag: row.try_get("etag")?,
+                size_bytes: size_bytes.map(|v| v.max(0) as u64),
+                uploaded_at: row.try_get("uploaded_at")?,
+            });
+        }
+        Ok(parts)
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        upload_id: UploadId,
+        filename: String,
+        mime_type: Option<String>,
+        visibility: ContentVisibility,
+    ) -> PlatformResult<ContentMetadata> {
+        let mut session = self
+            .get_upload_session(upload_id)
+            .await?
+            .ok_or(PlatformError::NotFound("upload_session"))?;
+        let parts = self.list_upload_parts(upload_id).await?;
+        crate::platform::ingest::validate_parts_contiguous(&parts)?;
+        let size_bytes = parts
+            .iter()
+            .map(|part| part.size_bytes)
+            .sum::<Option<u64>>()
+            .ok_or(PlatformError::InvalidInput(
+                "every part must report its size before a multipart upload can be completed",
+            ))?;
+        let etags: Vec<&str> = parts.iter().map(|part| part.etag.as_str()).collect();
+        let checksum = crate::platform::ingest::composite_etag(
+            &etags,
+            crate::platform::ingest::ChecksumAlgorithm::Sha256,
+        );
+        let now = Utc::now();
+        let metadata = ContentMetadata {
+            id: session.content_id,
+            tenant_id: session.tenant_id,
+            project_id: session.project_id,
+            filename,
+            mime_type,
+            size_bytes: Some(size_bytes),
+            checksum: Some(checksum),
+            storage_path: None,
+            labels: Vec::new(),
+            attributes: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+            uploaded_by: None,
+            visibility,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        };
+        self.record_content_metadata(metadata.clone()).await?;
+        session.status = UploadStatus::Completed;
+        session.updated_at = now;
+        session.parts = parts;
+        self.update_upload_session(session).await?;
+        Ok(metadata)
+    }
+
+    async fn record_content_metadata(&self, metadata: ContentMetadata) -> PlatformResult<()> {
+        let attributes = serde_json::to_value(&metadata.attributes)
+            .map_err(|_| PlatformError::InvalidInput("invalid attributes"))?;
+        let immutability = metadata
+            .immutability
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|_| PlatformError::InvalidInput("invalid immutability policy"))?;
+        let size_bytes = metadata.size_bytes.map(|v| v as i64);
+        let mut tx = self.pool.begin().await?;
+        // Read-then-write under the same transaction so a concurrent
+        // `record_content_metadata`/`delete_content_metadata` can't slip a
+        // retention/legal-hold change in between the check below and the
+        // `INSERT ... ON CONFLICT` (the row lock from `FOR UPDATE` holds
+        // until `tx.commit()`).
+        let existing = sqlx::query("SELECT * FROM ugc_content_metadata WHERE id = $1 FOR UPDATE")
+            .bind(metadata.id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if let Some(row) = existing {
+            Self::map_metadata_row(row).await?.guard_mutation(Utc::now())?;
+        }
+        sqlx::query(
+            "INSERT INTO ugc_content_metadata (
+                id, tenant_id, project_id, filename, mime_type, size_bytes,
+                checksum, storage_path, labels, attributes, created_at,
+                updated_at, uploaded_by, visibility, blurhash, immutability,
+                legal_hold
+            ) VALUES (
+                $1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17
+            ) ON CONFLICT (id) DO UPDATE SET
+                tenant_id = EXCLUDED.tenant_id,
+                project_id = EXCLUDED.project_id,
+                filename = EXCLUDED.filename,
+                mime_type = EXCLUDED.mime_type,
+                size_bytes = EXCLUDED.size_bytes,
+                checksum = EXCLUDED.checksum,
+                storage_path = EXCLUDED.storage_path,
+                labels = EXCLUDED.labels,
+                attributes = EXCLUDED.attributes,
+                created_at = EXCLUDED.created_at,
+                updated_at = EXCLUDED.updated_at,
+                uploaded_by = EXCLUDED.uploaded_by,
+                visibility = EXCLUDED.visibility,
+                blurhash = EXCLUDED.blurhash,
+                immutability = EXCLUDED.immutability,
+                legal_hold = EXCLUDED.legal_hold",
+        )
+        .bind(metadata.id)
+        .bind(metadata.tenant_id)
+        .bind(metadata.project_id)
+        .bind(metadata.filename)
+        .bind(metadata.mime_type)
+        .bind(size_bytes)
+        .bind(metadata.checksum)
+        .bind(metadata.storage_path)
+        .bind(metadata.labels)
+        .bind(attributes)
+        .bind(metadata.created_at)
+        .bind(metadata.updated_at)
+        .bind(metadata.uploaded_by)
+        .bind(metadata.visibility.as_str())
+        .bind(metadata.blurhash)
+        .bind(immutability)
+        .bind(metadata.legal_hold)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_content_metadata(&self, id: ContentId) -> PlatformResult<Option<ContentMetadata>> {
+        let row = sqlx::query("SELECT * FROM ugc_content_metadata WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        if let Some(row) = row {
+            Ok(Some(Self::map_metadata_row(row).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete_content_metadata(&self, id: ContentId) -> PlatformResult<()> {
+        let mut tx = self.pool.begin().await?;
+        let existing = sqlx::query("SELECT * FROM ugc_content_metadata WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if let Some(row) = existing {
+            Self::map_metadata_row(row).await?.guard_mutation(Utc::now())?;
+        }
+        sqlx::query("DELETE FROM ugc_content_metadata WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn set_content_labels(&self, id: ContentId, labels: Vec<String>) -> PlatformResult<()> {
+        let result = sqlx::query(
+            "UPDATE ugc_content_metadata SET labels = $2, updated_at = now() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(labels)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(PlatformError::NotFound("content"));
+        }
+        Ok(())
+    }
+
+    async fn set_content_retention(
+        &self,
+        id: ContentId,
+        legal_hold: Option<bool>,
+        immutability: Option<ImmutabilityPolicy>,
+    ) -> PlatformResult<()> {
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query("SELECT * FROM ugc_content_metadata WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(PlatformError::NotFound("content"))?;
+        let mut metadata = Self::map_metadata_row(row).await?;
+        if let Some(legal_hold) = legal_hold {
+            metadata.legal_hold = legal_hold;
+        }
+        if let Some(policy) = immutability {
+            metadata.apply_immutability_policy(policy)?;
+        }
+        let immutability_value = metadata
+            .immutability
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|_| PlatformError::InvalidInput("invalid immutability policy"))?;
+        sqlx::query(
+            "UPDATE ugc_content_metadata SET legal_hold = $2, immutability = $3, updated_at = now() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(metadata.legal_hold)
+        .bind(immutability_value)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn list_content_metadata(
         &self,
         query: &ContentQuery,
     ) -> PlatformResult<Vec<ContentMetadata>> {
-        let mut builder =
-            QueryBuilder::new("SELECT * FROM ugc_content_metadata WHERE tenant_id = ");
+        query.validate()?;
+        let mut builder = match &query.search_term {
+            Some(term) => {
+                let mut builder = QueryBuilder::new(
+                    "SELECT *, ts_rank_cd(search_vector, websearch_to_tsquery('english', ",
+                );
+                builder.push_bind(term);
+                builder.push(")) AS relevance FROM ugc_content_metadata WHERE search_vector @@ websearch_to_tsquery('english', ");
+                builder.push_bind(term);
+                builder.push(") AND tenant_id = ");
+                builder
+            }
+            None => QueryBuilder::new("SELECT * FROM ugc_content_metadata WHERE tenant_id = "),
+        };
         builder.push_bind(query.tenant_id);
         if let Some(project_id) = query.project_id {
             builder.push(" AND project_id = ");
             builder.push_bind(project_id);
         }
-        if let Some(term) = &query.search_term {
-            let like = format!("%{}%", term.to_ascii_lowercase());
-            builder.push(" AND (LOWER(filename) LIKE ");
-            builder.push_bind(like.clone());
-            builder.push(" OR attributes::text ILIKE ");
-            builder.push_bind(like);
-            builder.push(")");
-        }
         if !query.tags.is_empty() {
             builder.push(" AND labels @> ");
             builder.push_bind(&query.tags);
         }
-        builder.push(" ORDER BY created_at DESC");
+        if let (Some(cursor_ts), Some(cursor_id)) = (query.cursor_created_at, query.cursor_id) {
+            builder.push(" AND (created_at < ");
+            builder.push_bind(cursor_ts);
+            builder.push(" OR (created_at = ");
+            builder.push_bind(cursor_ts);
+            builder.push(" AND id > ");
+            builder.push_bind(cursor_id);
+            builder.push("))");
+        }
+        if query.search_term.is_some() {
+            builder.push(" ORDER BY relevance DESC, created_at DESC, id ASC");
+        } else {
+            builder.push(" ORDER BY created_at DESC, id ASC");
+        }
         if let Some(limit) = query.limit {
             builder.push(" LIMIT ");
             builder.push_bind(limit as i64);
         }
-        if let Some(offset) = query.offset {
-            builder.push(" OFFSET ");
-            builder.push_bind(offset as i64);
+        if query.cursor_created_at.is_none() {
+            if let Some(offset) = query.offset {
+                builder.push(" OFFSET ");
+                builder.push_bind(offset as i64);
+            }
+        }
+
+        let query = builder.build();
+        let rows = query.fetch_all(&self.pool).await?;
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            out.push(Self::map_metadata_row(row).await?);
         }
+        Ok(out)
+    }
+
+    async fn find_content_by_digest(
+        &self,
+        tenant_id: TenantId,
+        digest: &str,
+        size_bytes: u64,
+    ) -> PlatformResult<Option<ContentMetadata>> {
+        let size_bytes = size_bytes as i64;
+        let row = sqlx::query(
+            "SELECT * FROM ugc_content_metadata
+             WHERE tenant_id = $1 AND checksum = $2 AND size_bytes = $3
+             ORDER BY created_at ASC
+             LIMIT 1",
+        )
+        .bind(tenant_id)
+        .bind(digest)
+        .bind(size_bytes)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(row) = row {
+            Ok(Some(Self::map_metadata_row(row).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn set_lifecycle_policy(&self, policy: ContentLifecyclePolicy) -> PlatformResult<()> {
+        let action = serde_json::to_value(&policy.action)
+            .map_err(|_| PlatformError::InvalidInput("invalid lifecycle action"))?;
+        sqlx::query(
+            "INSERT INTO ugc_lifecycle_policies (
+                id, tenant_id, project_id, label_selector, max_age_days,
+                action, created_at, updated_at
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+             ON CONFLICT (id) DO UPDATE SET
+                project_id = EXCLUDED.project_id,
+                label_selector = EXCLUDED.label_selector,
+                max_age_days = EXCLUDED.max_age_days,
+                action = EXCLUDED.action,
+                updated_at = EXCLUDED.updated_at",
+        )
+        .bind(policy.id)
+        .bind(policy.tenant_id)
+        .bind(policy.project_id)
+        .bind(policy.label_selector)
+        .bind(policy.max_age_days as i32)
+        .bind(action)
+        .bind(policy.created_at)
+        .bind(policy.updated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 
-        let query = builder.build();
-        let rows = query.fetch_all(&self.pool).await?;
+    async fn list_lifecycle_policies(
+        &self,
+        tenant_id: TenantId,
+    ) -> PlatformResult<Vec<ContentLifecyclePolicy>> {
+        let rows = sqlx::query(
+            "SELECT * FROM ugc_lifecycle_policies WHERE tenant_id = $1 ORDER BY created_at",
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
         let mut out = Vec::with_capacity(rows.len());
         for row in rows {
-            out.push(Self::map_metadata_row(row).await?);
+            out.push(Self::map_lifecycle_policy_row(row).await?);
         }
         Ok(out)
     }
+
+    async fn delete_lifecycle_policy(
+        &self,
+        tenant_id: TenantId,
+        policy_id: LifecyclePolicyId,
+    ) -> PlatformResult<()> {
+        sqlx::query("DELETE FROM ugc_lifecycle_policies WHERE id = $1 AND tenant_id = $2")
+            .bind(policy_id)
+            .bind(tenant_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Evaluates every policy against its matching content in one query per
+    /// policy (label selector, optional project scope, and age threshold all
+    /// pushed into the `WHERE` clause) rather than pulling every row into
+    /// Rust to filter.
+    async fn sweep_expired_content(
+        &self,
+        tenant_id: TenantId,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Vec<ContentLifecycleOutcome>> {
+        let policies = self.list_lifecycle_policies(tenant_id).await?;
+        let mut outcomes = Vec::new();
+        for policy in policies {
+            let cutoff = now - chrono::Duration::days(policy.max_age_days as i64);
+            let mut builder =
+                QueryBuilder::new("SELECT id FROM ugc_content_metadata WHERE tenant_id = ");
+            builder.push_bind(policy.tenant_id);
+            builder.push(" AND updated_at <= ");
+            builder.push_bind(cutoff);
+            if let Some(project_id) = policy.project_id {
+                builder.push(" AND project_id = ");
+                builder.push_bind(project_id);
+            }
+            if !policy.label_selector.is_empty() {
+                builder.push(" AND labels @> ");
+                builder.push_bind(&policy.label_selector);
+            }
+            let rows = builder.build().fetch_all(&self.pool).await?;
+            for row in rows {
+                outcomes.push(ContentLifecycleOutcome {
+                    content_id: row.try_get("id")?,
+                    policy_id: policy.id,
+                    action: policy.action.clone(),
+                });
+            }
+        }
+        outcomes.sort_by_key(|outcome| outcome.content_id);
+        Ok(outcomes)
+    }
+
+    async fn apply_lifecycle_outcome(&self, outcome: ContentLifecycleOutcome) -> PlatformResult<()> {
+        match outcome.action {
+            LifecycleAction::Delete => {
+                sqlx::query("DELETE FROM ugc_content_metadata WHERE id = $1")
+                    .bind(outcome.content_id)
+                    .execute(&self.pool)
+                    .await?;
+            }
+            LifecycleAction::TransitionVisibility(visibility) => {
+                sqlx::query("UPDATE ugc_content_metadata SET visibility = $2 WHERE id = $1")
+                    .bind(outcome.content_id)
+                    .bind(visibility)
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn reap_expired_upload_sessions(&self, now: DateTime<Utc>) -> PlatformResult<u64> {
+        let result = sqlx::query("DELETE FROM ugc_upload_sessions WHERE expires_at < $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
 }
 
 #[cfg(feature = "db")]
@@ -974,7 +3335,7 @@ impl OrchestrationStore for PostgresOrchestrationStore {
             "INSERT INTO orchestration_assignments (
                 id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata
             ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
-            RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, created_at, updated_at",
+            RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at",
         )
         .bind(input.id)
         .bind(input.agent_id)
@@ -999,7 +3360,7 @@ impl OrchestrationStore for PostgresOrchestrationStore {
             "UPDATE orchestration_assignments
              SET status = $2, status_message = $3, updated_at = NOW()
              WHERE id = $1
-             RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, created_at, updated_at",
+             RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at",
         )
         .bind(id)
         .bind(status.as_str())
@@ -1017,7 +3378,7 @@ impl OrchestrationStore for PostgresOrchestrationStore {
         query: AssignmentQuery,
     ) -> PlatformResult<Vec<WorkAssignment>> {
         let mut builder = QueryBuilder::<Postgres>::new(
-            "SELECT id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, created_at, updated_at
+            "SELECT id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at
              FROM orchestration_assignments WHERE 1=1",
         );
 
@@ -1042,6 +3403,109 @@ impl OrchestrationStore for PostgresOrchestrationStore {
         let rows: Vec<AssignmentRow> = builder.build_query_as().fetch_all(&self.pool).await?;
         rows.into_iter().map(|row| row.into_model()).collect()
     }
+
+    async fn heartbeat_assignment(&self, id: AssignmentId) -> PlatformResult<()> {
+        let result = sqlx::query(
+            "UPDATE orchestration_assignments SET last_heartbeat = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(PlatformError::NotFound("assignment"));
+        }
+        Ok(())
+    }
+
+    async fn claim_pending(&self, agent_id: AgentId, max: u32) -> PlatformResult<Vec<WorkAssignment>> {
+        let mut tx = self.pool.begin().await?;
+        let pending: Vec<AssignmentRow> = sqlx::query_as(
+            "SELECT id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at
+             FROM orchestration_assignments
+             WHERE agent_id = $1 AND status = $2
+             ORDER BY created_at ASC
+             LIMIT $3
+             FOR UPDATE SKIP LOCKED",
+        )
+        .bind(agent_id)
+        .bind(WorkStatus::Pending.as_str())
+        .bind(max as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut claimed = Vec::with_capacity(pending.len());
+        for row in pending {
+            let updated: AssignmentRow = sqlx::query_as(
+                "UPDATE orchestration_assignments
+                 SET status = $2, status_message = $3, last_heartbeat = NOW(), updated_at = NOW()
+                 WHERE id = $1
+                 RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at",
+            )
+            .bind(row.id)
+            .bind(WorkStatus::Running.as_str())
+            .bind(Some(String::from("running")))
+            .fetch_one(&mut *tx)
+            .await?;
+            claimed.push(updated.into_model()?);
+        }
+        tx.commit().await?;
+        Ok(claimed)
+    }
+
+    async fn requeue_stale(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+        config: &AssignmentLifecycleConfig,
+    ) -> PlatformResult<Vec<WorkAssignment>> {
+        let mut tx = self.pool.begin().await?;
+        let cutoff = now - ttl;
+        let stale: Vec<AssignmentRow> = sqlx::query_as(
+            "SELECT id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at
+             FROM orchestration_assignments
+             WHERE status = $1 AND last_heartbeat < $2
+             FOR UPDATE SKIP LOCKED",
+        )
+        .bind(WorkStatus::Running.as_str())
+        .bind(cutoff)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut changed = Vec::with_capacity(stale.len());
+        for row in stale {
+            let next_attempt = row.attempt + 1;
+            let updated: AssignmentRow = if next_attempt as u32 > config.max_attempts {
+                sqlx::query_as(
+                    "UPDATE orchestration_assignments
+                     SET status = $2, status_message = $3, attempt = $4, updated_at = NOW()
+                     WHERE id = $1
+                     RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at",
+                )
+                .bind(row.id)
+                .bind(WorkStatus::Failed.as_str())
+                .bind(Some(String::from("exceeded max requeue attempts")))
+                .bind(next_attempt)
+                .fetch_one(&mut *tx)
+                .await?
+            } else {
+                sqlx::query_as(
+                    "UPDATE orchestration_assignments
+                     SET status = $2, status_message = $3, attempt = $4, last_heartbeat = NULL, updated_at = NOW()
+                     WHERE id = $1
+                     RETURNING id, agent_id, workload_id, tenant_id, project_id, status, status_message, metadata, last_heartbeat, attempt, created_at, updated_at",
+                )
+                .bind(row.id)
+                .bind(WorkStatus::Pending.as_str())
+                .bind(Some(String::from("requeued after stale lease")))
+                .bind(next_attempt)
+                .fetch_one(&mut *tx)
+                .await?
+            };
+            changed.push(updated.into_model()?);
+        }
+        tx.commit().await?;
+        Ok(changed)
+    }
 }
 
 #[cfg(feature = "db")]
@@ -1078,7 +3542,9 @@ impl ModerationStore for PostgresModerationStore {
         id: ContentId,
         state: ModerationState,
         reason: Option<String>,
+        actor_id: uuid::Uuid,
     ) -> PlatformResult<ModeratedContent> {
+        let mut tx = self.pool.begin().await?;
         let row: Option<ModerationRow> = sqlx::query_as(
             "UPDATE ugc_moderation_content
              SET state = $2, reason = $3, updated_at = NOW()
@@ -1087,36 +3553,177 @@ impl ModerationStore for PostgresModerationStore {
         )
         .bind(id)
         .bind(state.as_str())
+        .bind(reason.clone())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(row) = row else {
+            return Err(PlatformError::NotFound("ugc_content"));
+        };
+        let (from_state,): (String,) =
+            sqlx::query_as("SELECT state FROM ugc_moderation_events WHERE content_id = $1 ORDER BY created_at DESC LIMIT 1")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .unwrap_or((ModerationState::Pending.as_str().to_string(),));
+        let from_state = ModerationState::from_str(from_state.to_ascii_lowercase().as_str())?;
+        sqlx::query(
+            "INSERT INTO ugc_moderation_events (id, content_id, from_state, to_state, reason, actor_id)
+             VALUES ($1,$2,$3,$4,$5,$6)",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(id)
+        .bind(from_state.as_str())
+        .bind(state.as_str())
+        .bind(reason.clone())
+        .bind(actor_id)
+        .execute(&mut *tx)
+        .await?;
+        // Lock the chain tail for content_id for the rest of this transaction
+        // so a concurrent update_content_state can't read the same tail and
+        // fork the tamper-evident hash chain -- mirrors the FOR UPDATE guard
+        // record_content_metadata/delete_content_metadata take on
+        // ugc_content_metadata for the same reason.
+        let chain_tail: Option<(i64, String)> = sqlx::query_as(
+            "SELECT sequence, hash FROM ugc_moderation_audit WHERE content_id = $1 ORDER BY sequence DESC LIMIT 1 FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (prev_sequence, prev_hash) = match chain_tail {
+            Some((sequence, hash)) => (sequence, hash),
+            None => (0, MODERATION_AUDIT_GENESIS_HASH.to_string()),
+        };
+        let sequence = prev_sequence + 1;
+        let created_at = row.updated_at;
+        let hash = moderation_audit_hash(
+            &prev_hash,
+            id,
+            sequence as u64,
+            &from_state,
+            &state,
+            &reason,
+            actor_id,
+            created_at,
+        );
+        sqlx::query(
+            "INSERT INTO ugc_moderation_audit
+                (id, content_id, sequence, from_state, to_state, reason, actor_id, created_at, hash)
+             VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(id)
+        .bind(sequence)
+        .bind(from_state.as_str())
+        .bind(state.as_str())
         .bind(reason)
-        .fetch_optional(&self.pool)
+        .bind(actor_id)
+        .bind(created_at)
+        .bind(hash)
+        .execute(&mut *tx)
         .await?;
-        match row {
-            Some(row) => row.into_model(),
-            None => Err(PlatformError::NotFound("ugc_content")),
-        }
+        tx.commit().await?;
+        row.into_model()
+    }
+
+    async fn list_content_events(
+        &self,
+        content_id: ContentId,
+    ) -> PlatformResult<Vec<ModerationEvent>> {
+        let rows: Vec<ModerationEventRow> = sqlx::query_as(
+            "SELECT id, content_id, from_state, to_state, reason, actor_id, created_at
+             FROM ugc_moderation_events
+             WHERE content_id = $1
+             ORDER BY created_at ASC",
+        )
+        .bind(content_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter().map(|row| row.into_model()).collect()
     }
 
     async fn list_content(&self, query: ModerationQuery) -> PlatformResult<Vec<ModeratedContent>> {
         let mut builder = QueryBuilder::<Postgres>::new(
-            "SELECT id, tenant_id, project_id, filename, mime_type, size_bytes, state, reason, labels, attributes, submitted_at, updated_at
-             FROM ugc_moderation_content WHERE 1=1",
+            "SELECT c.id, c.tenant_id, c.project_id, c.filename, c.mime_type, c.size_bytes,
+                    c.state, c.reason, c.labels, c.attributes, c.submitted_at, c.updated_at,
+                    COALESCE(reports.open_report_count, 0) AS open_report_count
+             FROM ugc_moderation_content c
+             LEFT JOIN LATERAL (
+                 SELECT COUNT(*) AS open_report_count
+                 FROM ugc_moderation_reports r
+                 WHERE r.content_id = c.id AND NOT r.resolved
+             ) reports ON TRUE
+             WHERE 1=1",
         );
         if let Some(tenant_id) = query.tenant_id {
-            builder.push(" AND tenant_id = ");
+            builder.push(" AND c.tenant_id = ");
             builder.push_bind(tenant_id);
         }
         if let Some(project_id) = query.project_id {
-            builder.push(" AND project_id = ");
+            builder.push(" AND c.project_id = ");
             builder.push_bind(project_id);
         }
         if let Some(state) = query.state {
-            builder.push(" AND state = ");
+            builder.push(" AND c.state = ");
             builder.push_bind(state.as_str());
         }
-        builder.push(" ORDER BY submitted_at DESC");
-        let rows: Vec<ModerationRow> = builder.build_query_as().fetch_all(&self.pool).await?;
+        if let Some(min_open_reports) = query.min_open_reports {
+            builder.push(" AND COALESCE(reports.open_report_count, 0) >= ");
+            builder.push_bind(min_open_reports);
+        }
+        if query.sort_by_open_reports {
+            builder.push(" ORDER BY open_report_count DESC, c.submitted_at DESC");
+        } else {
+            builder.push(" ORDER BY c.submitted_at DESC");
+        }
+        let rows: Vec<ModerationRowWithReportCount> =
+            builder.build_query_as().fetch_all(&self.pool).await?;
+        rows.into_iter().map(|row| row.into_model()).collect()
+    }
+
+    async fn create_report(
+        &self,
+        content_id: ContentId,
+        reporter_id: uuid::Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    ) -> PlatformResult<ModerationReport> {
+        let row: ModerationReportRow = sqlx::query_as(
+            "INSERT INTO ugc_moderation_reports (id, content_id, reporter_id, category, detail, resolved)
+             VALUES ($1,$2,$3,$4,$5,FALSE)
+             RETURNING id, content_id, reporter_id, category, detail, created_at, resolved",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(content_id)
+        .bind(reporter_id)
+        .bind(category.as_str())
+        .bind(detail)
+        .fetch_one(&self.pool)
+        .await?;
+        row.into_model()
+    }
+
+    async fn list_audit(&self, content_id: ContentId) -> PlatformResult<Vec<ModerationAuditEntry>> {
+        let rows: Vec<ModerationAuditRow> = sqlx::query_as(
+            "SELECT id, content_id, sequence, from_state, to_state, reason, actor_id, created_at, hash
+             FROM ugc_moderation_audit
+             WHERE content_id = $1
+             ORDER BY sequence ASC",
+        )
+        .bind(content_id)
+        .fetch_all(&self.pool)
+        .await?;
         rows.into_iter().map(|row| row.into_model()).collect()
     }
+
+    async fn audit_chain_head(&self, content_id: ContentId) -> PlatformResult<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT hash FROM ugc_moderation_audit WHERE content_id = $1 ORDER BY sequence DESC LIMIT 1",
+        )
+        .bind(content_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|(hash,)| hash))
+    }
 }
 
 #[cfg(feature = "db")]
@@ -1130,9 +3737,10 @@ impl MessagingStore for PostgresMessagingStore {
             .map_err(|_| PlatformError::InvalidInput("invalid attributes"))?;
         let row: MessageRow = sqlx::query_as(
             "INSERT INTO messaging_messages (
-                id, tenant_id, project_id, topic, key, payload, priority, attributes
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
-            RETURNING id, tenant_id, project_id, topic, key, payload, priority, attributes, published_at",
+                id, tenant_id, project_id, topic, key, payload, priority, attributes, max_attempts
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
+            RETURNING id, tenant_id, project_id, topic, key, payload, priority, attributes,
+                      published_at, delivery_attempts, max_attempts, lease_until, leased_by",
         )
         .bind(input.id)
         .bind(input.tenant_id)
@@ -1142,6 +3750,7 @@ impl MessagingStore for PostgresMessagingStore {
         .bind(input.payload)
         .bind(input.priority.as_str())
         .bind(attributes)
+        .bind(input.max_attempts as i32)
         .fetch_one(&self.pool)
         .await?;
         row.into_model()
@@ -1152,7 +3761,8 @@ impl MessagingStore for PostgresMessagingStore {
             return Err(PlatformError::InvalidInput("topic required"));
         }
         let mut builder = QueryBuilder::<Postgres>::new(
-            "SELECT id, tenant_id, project_id, topic, key, payload, priority, attributes, published_at
+            "SELECT id, tenant_id, project_id, topic, key, payload, priority, attributes,
+                    published_at, delivery_attempts, max_attempts, lease_until, leased_by
              FROM messaging_messages WHERE topic = ",
         );
         builder.push_bind(&query.topic);
@@ -1173,17 +3783,154 @@ impl MessagingStore for PostgresMessagingStore {
         rows.into_iter().map(|row| row.into_model()).collect()
     }
 
-    async fn ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
-        let result = sqlx::query("DELETE FROM messaging_messages WHERE id = $1 AND topic = $2")
-            .bind(id)
-            .bind(topic)
-            .execute(&self.pool)
+    async fn claim_messages(
+        &self,
+        topic: &str,
+        consumer: &str,
+        max: u32,
+        visibility_timeout: chrono::Duration,
+    ) -> PlatformResult<Vec<MessageRecord>> {
+        if topic.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("topic required"));
+        }
+        let mut tx = self.pool.begin().await?;
+        let rows: Vec<MessageRow> = sqlx::query_as(
+            "SELECT id, tenant_id, project_id, topic, key, payload, priority, attributes,
+                    published_at, delivery_attempts, max_attempts, lease_until, leased_by
+             FROM messaging_messages
+             WHERE topic = $1 AND (lease_until IS NULL OR lease_until <= NOW())
+             ORDER BY priority DESC, published_at ASC
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED",
+        )
+        .bind(topic)
+        .bind(max as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let lease_interval = sqlx::postgres::types::PgInterval::try_from(visibility_timeout)
+            .map_err(|_| PlatformError::InvalidInput("invalid visibility_timeout"))?;
+        let mut claimed = Vec::with_capacity(rows.len());
+        for row in rows {
+            if row.delivery_attempts + 1 > row.max_attempts {
+                sqlx::query(
+                    "INSERT INTO messaging_dead_letters (
+                        id, tenant_id, project_id, topic, key, payload, priority, attributes,
+                        published_at, delivery_attempts, dead_lettered_at
+                    )
+                    SELECT id, tenant_id, project_id, topic, key, payload, priority, attributes,
+                           published_at, delivery_attempts, NOW()
+                    FROM messaging_messages WHERE id = $1",
+                )
+                .bind(row.id)
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query("DELETE FROM messaging_messages WHERE id = $1")
+                    .bind(row.id)
+                    .execute(&mut *tx)
+                    .await?;
+                continue;
+            }
+            let lease_until: Option<DateTime<Utc>> = sqlx::query_scalar(
+                "UPDATE messaging_messages
+                 SET delivery_attempts = delivery_attempts + 1,
+                     lease_until = NOW() + $2,
+                     leased_by = $3
+                 WHERE id = $1
+                 RETURNING lease_until",
+            )
+            .bind(row.id)
+            .bind(lease_interval.clone())
+            .bind(consumer)
+            .fetch_one(&mut *tx)
             .await?;
+            let lease_until = lease_until.expect("lease_until set by the UPDATE above");
+            let mut record = row.into_model()?;
+            record.delivery_attempts += 1;
+            record.lease_until = Some(lease_until);
+            claimed.push(record);
+        }
+        tx.commit().await?;
+        Ok(claimed)
+    }
+
+    async fn extend_lease(
+        &self,
+        topic: &str,
+        id: MessageId,
+        extension: chrono::Duration,
+    ) -> PlatformResult<()> {
+        let extension = sqlx::postgres::types::PgInterval::try_from(extension)
+            .map_err(|_| PlatformError::InvalidInput("invalid extension"))?;
+        let result = sqlx::query(
+            "UPDATE messaging_messages
+             SET lease_until = NOW() + $3
+             WHERE id = $1 AND topic = $2 AND lease_until > NOW()",
+        )
+        .bind(id)
+        .bind(topic)
+        .bind(extension)
+        .execute(&self.pool)
+        .await?;
         if result.rows_affected() == 0 {
             return Err(PlatformError::NotFound("message"));
         }
         Ok(())
     }
+
+    async fn ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        let result = sqlx::query(
+            "DELETE FROM messaging_messages
+             WHERE id = $1 AND topic = $2
+               AND (lease_until IS NULL OR lease_until > NOW())",
+        )
+        .bind(id)
+        .bind(topic)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            let still_exists: Option<uuid::Uuid> =
+                sqlx::query_scalar("SELECT id FROM messaging_messages WHERE id = $1 AND topic = $2")
+                    .bind(id)
+                    .bind(topic)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if still_exists.is_none() {
+                return Err(PlatformError::NotFound("message"));
+            }
+            // The lease already lapsed, so the message may have been
+            // reclaimed by another consumer via `claim_messages` — treat
+            // this late ack as a no-op instead of finalizing their work.
+        }
+        Ok(())
+    }
+
+    async fn nack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        let result = sqlx::query(
+            "UPDATE messaging_messages
+             SET lease_until = NULL, leased_by = NULL
+             WHERE id = $1 AND topic = $2 AND lease_until > NOW()",
+        )
+        .bind(id)
+        .bind(topic)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            let still_exists: Option<uuid::Uuid> =
+                sqlx::query_scalar("SELECT id FROM messaging_messages WHERE id = $1 AND topic = $2")
+                    .bind(id)
+                    .bind(topic)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if still_exists.is_none() {
+                return Err(PlatformError::NotFound("message"));
+            }
+            // The lease already lapsed, so the message may have been
+            // reclaimed by another consumer via `claim_messages` — treat
+            // this late nack as a no-op instead of clearing their claim.
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -1216,6 +3963,94 @@ impl ContentStore for InMemoryPersistence {
         Ok(self.state.read().upload_sessions.get(&id).cloned())
     }
 
+    async fn register_upload_part(&self, upload_id: UploadId, part: UploadPart) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        let session = state
+            .upload_sessions
+            .get_mut(&upload_id)
+            .ok_or(PlatformError::NotFound("upload_session"))?;
+        session.parts.retain(|existing| existing.part_number != part.part_number);
+        session.parts.push(part);
+        session.parts.sort_by_key(|part| part.part_number);
+        session.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn list_upload_parts(&self, upload_id: UploadId) -> PlatformResult<Vec<UploadPart>> {
+        let state = self.state.read();
+        let session = state
+            .upload_sessions
+            .get(&upload_id)
+            .ok_or(PlatformError::NotFound("upload_session"))?;
+        let mut parts = session.parts.clone();
+        parts.sort_by_key(|part| part.part_number);
+        Ok(parts)
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        upload_id: UploadId,
+        filename: String,
+        mime_type: Option<String>,
+        visibility: ContentVisibility,
+    ) -> PlatformResult<ContentMetadata> {
+        let parts = self.list_upload_parts(upload_id).await?;
+        crate::platform::ingest::validate_parts_contiguous(&parts)?;
+        let size_bytes = parts
+            .iter()
+            .map(|part| part.size_bytes)
+            .sum::<Option<u64>>()
+            .ok_or(PlatformError::InvalidInput(
+                "every part must report its size before a multipart upload can be completed",
+            ))?;
+        let etags: Vec<&str> = parts.iter().map(|part| part.etag.as_str()).collect();
+        let checksum = crate::platform::ingest::composite_etag(
+            &etags,
+            crate::platform::ingest::ChecksumAlgorithm::Sha256,
+        );
+        let now = Utc::now();
+        let (tenant_id, project_id, content_id) = {
+            let state = self.state.read();
+            let session = state
+                .upload_sessions
+                .get(&upload_id)
+                .ok_or(PlatformError::NotFound("upload_session"))?;
+            (session.tenant_id, session.project_id, session.content_id)
+        };
+        let metadata = ContentMetadata {
+            id: content_id,
+            tenant_id,
+            project_id,
+            filename,
+            mime_type,
+            size_bytes: Some(size_bytes),
+            checksum: Some(checksum),
+            storage_path: None,
+            labels: Vec::new(),
+            attributes: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+            uploaded_by: None,
+            visibility,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        };
+        self.record_content_metadata(metadata.clone()).await?;
+        {
+            let mut state = self.state.write();
+            let session = state
+                .upload_sessions
+                .get_mut(&upload_id)
+                .ok_or(PlatformError::NotFound("upload_session"))?;
+            session.status = UploadStatus::Completed;
+            session.updated_at = now;
+            session.parts = parts;
+        }
+        Ok(metadata)
+    }
+
     async fn record_content_metadata(&self, metadata: ContentMetadata) -> PlatformResult<()> {
         let mut state = self.state.write();
         if !state.tenants.contains_key(&metadata.tenant_id) {
@@ -1224,6 +4059,9 @@ impl ContentStore for InMemoryPersistence {
         if !state.projects.contains_key(&metadata.project_id) {
             return Err(PlatformError::NotFound("project"));
         }
+        if let Some(existing) = state.content_metadata.get(&metadata.id) {
+            existing.guard_mutation(Utc::now())?;
+        }
         state.content_metadata.insert(metadata.id, metadata);
         Ok(())
     }
@@ -1232,10 +4070,62 @@ impl ContentStore for InMemoryPersistence {
         Ok(self.state.read().content_metadata.get(&id).cloned())
     }
 
+    async fn delete_content_metadata(&self, id: ContentId) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        if let Some(existing) = state.content_metadata.get(&id) {
+            existing.guard_mutation(Utc::now())?;
+        }
+        state.content_metadata.remove(&id);
+        Ok(())
+    }
+
+    async fn set_content_labels(&self, id: ContentId, labels: Vec<String>) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        let metadata = state
+            .content_metadata
+            .get_mut(&id)
+            .ok_or(PlatformError::NotFound("content"))?;
+        metadata.labels = labels;
+        metadata.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn set_content_retention(
+        &self,
+        id: ContentId,
+        legal_hold: Option<bool>,
+        immutability: Option<ImmutabilityPolicy>,
+    ) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        let metadata = state
+            .content_metadata
+            .get_mut(&id)
+            .ok_or(PlatformError::NotFound("content"))?;
+        if let Some(legal_hold) = legal_hold {
+            metadata.legal_hold = legal_hold;
+        }
+        if let Some(policy) = immutability {
+            metadata.apply_immutability_policy(policy)?;
+        }
+        metadata.updated_at = Utc::now();
+        Ok(())
+    }
+
     async fn list_content_metadata(
         &self,
         query: &ContentQuery,
     ) -> PlatformResult<Vec<ContentMetadata>> {
+        query.validate()?;
+        let tokens: Vec<String> = query
+            .search_term
+            .as_deref()
+            .map(|term| {
+                term.split_whitespace()
+                    .map(|token| token.to_ascii_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let mut entries: Vec<_> = self
             .state
             .read()
@@ -1250,16 +4140,8 @@ impl ContentStore for InMemoryPersistence {
                         return false;
                     }
                 }
-                if let Some(term) = &query.search_term {
-                    let term_lower = term.to_ascii_lowercase();
-                    let filename_match = item.filename.to_ascii_lowercase().contains(&term_lower);
-                    let attribute_match = item.attributes.iter().any(|(k, v)| {
-                        k.to_ascii_lowercase().contains(&term_lower)
-                            || v.to_ascii_lowercase().contains(&term_lower)
-                    });
-                    if !filename_match && !attribute_match {
-                        return false;
-                    }
+                if !tokens.is_empty() && Self::content_relevance_score(item, &tokens) == 0.0 {
+                    return false;
                 }
                 if !query.tags.is_empty()
                     && !query
@@ -1269,17 +4151,187 @@ impl ContentStore for InMemoryPersistence {
                 {
                     return false;
                 }
+                if let (Some(cursor_ts), Some(cursor_id)) =
+                    (query.cursor_created_at, query.cursor_id)
+                {
+                    if !(item.created_at < cursor_ts
+                        || (item.created_at == cursor_ts && item.id > cursor_id))
+                    {
+                        return false;
+                    }
+                }
                 true
             })
             .cloned()
+            .map(|mut item| {
+                if !tokens.is_empty() {
+                    item.relevance = Some(Self::content_relevance_score(&item, &tokens));
+                }
+                item
+            })
+            .collect();
+
+        if tokens.is_empty() {
+            entries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+        } else {
+            entries.sort_by(|a, b| {
+                b.relevance
+                    .partial_cmp(&a.relevance)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.created_at.cmp(&a.created_at))
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+        }
+
+        let limit = query.limit.unwrap_or(entries.len() as u32) as usize;
+        let slice = if query.cursor_created_at.is_some() {
+            entries.into_iter().take(limit).collect()
+        } else {
+            let offset = query.offset.unwrap_or(0) as usize;
+            entries.into_iter().skip(offset).take(limit).collect()
+        };
+        Ok(slice)
+    }
+
+    /// Approximates Postgres's `ts_rank_cd` for `list_content_metadata`:
+    /// counts how many `tokens` appear in `filename`/`labels`/`attributes`,
+    /// weighting a filename hit higher since that's the field users actually
+    /// read, so relative ordering between results matches the Postgres
+    /// backend even though the exact scores don't.
+    fn content_relevance_score(item: &ContentMetadata, tokens: &[String]) -> f32 {
+        const FILENAME_WEIGHT: f32 = 2.0;
+        let filename_lower = item.filename.to_ascii_lowercase();
+        let mut score = 0.0;
+        for token in tokens {
+            if filename_lower.contains(token.as_str()) {
+                score += FILENAME_WEIGHT;
+            }
+            if item
+                .labels
+                .iter()
+                .any(|label| label.to_ascii_lowercase().contains(token.as_str()))
+            {
+                score += 1.0;
+            }
+            if item.attributes.iter().any(|(k, v)| {
+                k.to_ascii_lowercase().contains(token.as_str())
+                    || v.to_ascii_lowercase().contains(token.as_str())
+            }) {
+                score += 1.0;
+            }
+        }
+        score
+    }
+
+    async fn find_content_by_digest(
+        &self,
+        tenant_id: TenantId,
+        digest: &str,
+        size_bytes: u64,
+    ) -> PlatformResult<Option<ContentMetadata>> {
+        let mut matches: Vec<_> = self
+            .state
+            .read()
+            .content_metadata
+            .values()
+            .filter(|item| {
+                item.tenant_id == tenant_id
+                    && item.checksum.as_deref() == Some(digest)
+                    && item.size_bytes == Some(size_bytes)
+            })
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(matches.into_iter().next())
+    }
+
+    async fn set_lifecycle_policy(&self, policy: ContentLifecyclePolicy) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        if !state.tenants.contains_key(&policy.tenant_id) {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+        state.lifecycle_policies.insert(policy.id, policy);
+        Ok(())
+    }
+
+    async fn list_lifecycle_policies(
+        &self,
+        tenant_id: TenantId,
+    ) -> PlatformResult<Vec<ContentLifecyclePolicy>> {
+        let mut policies: Vec<_> = self
+            .state
+            .read()
+            .lifecycle_policies
+            .values()
+            .filter(|p| p.tenant_id == tenant_id)
+            .cloned()
             .collect();
+        policies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(policies)
+    }
+
+    async fn delete_lifecycle_policy(
+        &self,
+        tenant_id: TenantId,
+        policy_id: LifecyclePolicyId,
+    ) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        if let Some(policy) = state.lifecycle_policies.get(&policy_id) {
+            if policy.tenant_id == tenant_id {
+                state.lifecycle_policies.remove(&policy_id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn sweep_expired_content(
+        &self,
+        tenant_id: TenantId,
+        now: DateTime<Utc>,
+    ) -> PlatformResult<Vec<ContentLifecycleOutcome>> {
+        let state = self.state.read();
+        let mut outcomes = Vec::new();
+        let policies = state
+            .lifecycle_policies
+            .values()
+            .filter(|p| p.tenant_id == tenant_id);
+        for policy in policies {
+            for content in state.content_metadata.values() {
+                if policy.matches(content) && policy.is_expired(content, now) {
+                    outcomes.push(ContentLifecycleOutcome {
+                        content_id: content.id,
+                        policy_id: policy.id,
+                        action: policy.action.clone(),
+                    });
+                }
+            }
+        }
+        outcomes.sort_by_key(|outcome| outcome.content_id);
+        Ok(outcomes)
+    }
 
-        entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    async fn apply_lifecycle_outcome(&self, outcome: ContentLifecycleOutcome) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        match outcome.action {
+            LifecycleAction::Delete => {
+                state.content_metadata.remove(&outcome.content_id);
+            }
+            LifecycleAction::TransitionVisibility(visibility) => {
+                if let Some(content) = state.content_metadata.get_mut(&outcome.content_id) {
+                    content.visibility = visibility;
+                }
+            }
+        }
+        Ok(())
+    }
 
-        let offset = query.offset.unwrap_or(0) as usize;
-        let limit = query.limit.unwrap_or(entries.len() as u32) as usize;
-        let slice = entries.into_iter().skip(offset).take(limit).collect();
-        Ok(slice)
+    async fn reap_expired_upload_sessions(&self, now: DateTime<Utc>) -> PlatformResult<u64> {
+        let mut state = self.state.write();
+        let before = state.upload_sessions.len();
+        state
+            .upload_sessions
+            .retain(|_, session| session.expires_at.map(|e| e >= now).unwrap_or(true));
+        Ok((before - state.upload_sessions.len()) as u64)
     }
 }
 
@@ -1313,6 +4365,8 @@ impl OrchestrationStore for InMemoryPersistence {
             status: WorkStatus::Pending,
             status_message: Some("queued".to_string()),
             metadata: input.metadata,
+            last_heartbeat: None,
+            attempt: 0,
             created_at: now,
             updated_at: now,
         };
@@ -1373,6 +4427,82 @@ impl OrchestrationStore for InMemoryPersistence {
         assignments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
         Ok(assignments)
     }
+
+    async fn heartbeat_assignment(&self, id: AssignmentId) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        let assignment = state
+            .assignments
+            .get_mut(&id)
+            .ok_or(PlatformError::NotFound("assignment"))?;
+        assignment.last_heartbeat = Some(Utc::now());
+        Ok(())
+    }
+
+    async fn claim_pending(&self, agent_id: AgentId, max: u32) -> PlatformResult<Vec<WorkAssignment>> {
+        let mut state = self.state.write();
+        let now = Utc::now();
+        let mut pending_ids: Vec<_> = state
+            .assignments
+            .values()
+            .filter(|a| a.agent_id == agent_id && a.status == WorkStatus::Pending)
+            .map(|a| (a.id, a.created_at))
+            .collect();
+        pending_ids.sort_by_key(|(_, created_at)| *created_at);
+        pending_ids.truncate(max as usize);
+
+        let mut claimed = Vec::with_capacity(pending_ids.len());
+        for (id, _) in pending_ids {
+            let assignment = state
+                .assignments
+                .get_mut(&id)
+                .expect("id came from the assignments map above");
+            assignment.status = WorkStatus::Running;
+            assignment.status_message = Some("running".to_string());
+            assignment.last_heartbeat = Some(now);
+            assignment.updated_at = now;
+            claimed.push(assignment.clone());
+        }
+        Ok(claimed)
+    }
+
+    async fn requeue_stale(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+        config: &AssignmentLifecycleConfig,
+    ) -> PlatformResult<Vec<WorkAssignment>> {
+        let cutoff = now - ttl;
+        let mut state = self.state.write();
+        let stale_ids: Vec<_> = state
+            .assignments
+            .values()
+            .filter(|a| {
+                a.status == WorkStatus::Running
+                    && a.last_heartbeat.map(|heartbeat| heartbeat < cutoff).unwrap_or(true)
+            })
+            .map(|a| a.id)
+            .collect();
+
+        let mut changed = Vec::with_capacity(stale_ids.len());
+        for id in stale_ids {
+            let assignment = state
+                .assignments
+                .get_mut(&id)
+                .expect("id came from the assignments map above");
+            assignment.attempt += 1;
+            assignment.updated_at = now;
+            if assignment.attempt > config.max_attempts {
+                assignment.status = WorkStatus::Failed;
+                assignment.status_message = Some("exceeded max requeue attempts".to_string());
+            } else {
+                assignment.status = WorkStatus::Pending;
+                assignment.status_message = Some("requeued after stale lease".to_string());
+                assignment.last_heartbeat = None;
+            }
+            changed.push(assignment.clone());
+        }
+        Ok(changed)
+    }
 }
 
 #[async_trait]
@@ -1412,20 +4542,37 @@ impl ModerationStore for InMemoryPersistence {
         id: ContentId,
         state: ModerationState,
         reason: Option<String>,
+        actor_id: uuid::Uuid,
     ) -> PlatformResult<ModeratedContent> {
         let mut state_data = self.state.write();
-        let record = state_data
-            .moderation_content
-            .get_mut(&id)
-            .ok_or(PlatformError::NotFound("ugc_content"))?;
-        record.state = state;
-        record.reason = reason;
-        record.updated_at = Utc::now();
-        Ok(record.clone())
+        apply_content_state_transition(&mut state_data, id, state, reason, actor_id, Utc::now())
+    }
+
+    async fn list_content_events(
+        &self,
+        content_id: ContentId,
+    ) -> PlatformResult<Vec<ModerationEvent>> {
+        let mut events: Vec<_> = self
+            .state
+            .read()
+            .moderation_events
+            .iter()
+            .filter(|event| event.content_id == content_id)
+            .cloned()
+            .collect();
+        events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(events)
     }
 
     async fn list_content(&self, query: ModerationQuery) -> PlatformResult<Vec<ModeratedContent>> {
         let state = self.state.read();
+        let open_report_count = |content_id: ContentId| {
+            state
+                .moderation_reports
+                .iter()
+                .filter(|report| report.content_id == content_id && !report.resolved)
+                .count() as i64
+        };
         let mut items: Vec<_> = state
             .moderation_content
             .values()
@@ -1445,13 +4592,82 @@ impl ModerationStore for InMemoryPersistence {
                         return false;
                     }
                 }
+                if let Some(min_open_reports) = query.min_open_reports {
+                    if open_report_count(item.id) < min_open_reports {
+                        return false;
+                    }
+                }
                 true
             })
             .cloned()
             .collect();
-        items.sort_by(|a, b| b.submitted_at.cmp(&a.submitted_at));
+        if query.sort_by_open_reports {
+            items.sort_by(|a, b| {
+                open_report_count(b.id)
+                    .cmp(&open_report_count(a.id))
+                    .then(b.submitted_at.cmp(&a.submitted_at))
+            });
+        } else {
+            items.sort_by(|a, b| b.submitted_at.cmp(&a.submitted_at));
+        }
         Ok(items)
     }
+
+    async fn create_report(
+        &self,
+        content_id: ContentId,
+        reporter_id: uuid::Uuid,
+        category: ReportCategory,
+        detail: Option<String>,
+    ) -> PlatformResult<ModerationReport> {
+        let mut state = self.state.write();
+        if !state.moderation_content.contains_key(&content_id) {
+            return Err(PlatformError::NotFound("ugc_content"));
+        }
+        let report = ModerationReport {
+            id: uuid::Uuid::new_v4(),
+            content_id,
+            reporter_id,
+            category,
+            detail,
+            created_at: Utc::now(),
+            resolved: false,
+        };
+        state.moderation_reports.push(report.clone());
+        Ok(report)
+    }
+
+    async fn expire_pending_moderation(
+        &self,
+        now: DateTime<Utc>,
+        deadline: chrono::Duration,
+        to_state: ModerationState,
+    ) -> PlatformResult<Vec<ModeratedContent>> {
+        let mut state = self.state.write();
+        Ok(batch_expire_pending_moderation(&mut state, now, deadline, to_state))
+    }
+
+    async fn list_audit(&self, content_id: ContentId) -> PlatformResult<Vec<ModerationAuditEntry>> {
+        let mut entries = self
+            .state
+            .read()
+            .moderation_audit
+            .get(&content_id)
+            .cloned()
+            .unwrap_or_default();
+        entries.sort_by_key(|entry| entry.sequence);
+        Ok(entries)
+    }
+
+    async fn audit_chain_head(&self, content_id: ContentId) -> PlatformResult<Option<String>> {
+        Ok(self
+            .state
+            .read()
+            .moderation_audit
+            .get(&content_id)
+            .and_then(|chain| chain.last())
+            .map(|entry| entry.hash.clone()))
+    }
 }
 
 #[async_trait]
@@ -1477,13 +4693,19 @@ impl MessagingStore for InMemoryPersistence {
             priority: input.priority,
             attributes: input.attributes,
             published_at: Utc::now(),
+            delivery_attempts: 0,
+            max_attempts: input.max_attempts,
+            lease_until: None,
+            leased_by: None,
         };
         state.messages.insert(record.id, record.clone());
-        state
+        let topic_queue = state
             .messages_by_topic
             .entry(record.topic.clone())
-            .or_insert_with(VecDeque::new)
-            .push_back(record.id);
+            .or_insert_with(TopicQueue::default);
+        topic_queue.sequence += 1;
+        topic_queue.entries.push_back((record.id, topic_queue.sequence));
+        topic_queue.notify.notify_waiters();
         Ok(record)
     }
 
@@ -1494,7 +4716,7 @@ impl MessagingStore for InMemoryPersistence {
         let state = self.state.read();
         let mut results = Vec::new();
         if let Some(queue) = state.messages_by_topic.get(&query.topic) {
-            for message_id in queue {
+            for (message_id, _) in &queue.entries {
                 if let Some(message) = state.messages.get(message_id) {
                     if let Some(tenant_id) = query.tenant_id {
                         if message.tenant_id != tenant_id {
@@ -1518,17 +4740,705 @@ impl MessagingStore for InMemoryPersistence {
         Ok(results)
     }
 
+    async fn claim_messages(
+        &self,
+        topic: &str,
+        consumer: &str,
+        max: u32,
+        visibility_timeout: chrono::Duration,
+    ) -> PlatformResult<Vec<MessageRecord>> {
+        if topic.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("topic required"));
+        }
+        let mut state = self.state.write();
+        let now = Utc::now();
+        let Some(queue) = state.messages_by_topic.get(topic) else {
+            return Ok(Vec::new());
+        };
+        let ids: Vec<MessageId> = queue.entries.iter().map(|(id, _)| *id).collect();
+        let mut eligible: Vec<MessageId> = ids
+            .into_iter()
+            .filter(|id| {
+                state
+                    .messages
+                    .get(id)
+                    .map(|message| message.lease_until.map(|until| until <= now).unwrap_or(true))
+                    .unwrap_or(false)
+            })
+            .collect();
+        eligible.sort_by(|a, b| {
+            let message_a = &state.messages[a];
+            let message_b = &state.messages[b];
+            message_b
+                .priority
+                .cmp(&message_a.priority)
+                .then(message_a.published_at.cmp(&message_b.published_at))
+        });
+
+        let mut claimed = Vec::new();
+        for id in eligible {
+            if claimed.len() as u32 >= max {
+                break;
+            }
+            let exceeded_attempts = {
+                let message = &state.messages[&id];
+                message.delivery_attempts + 1 > message.max_attempts
+            };
+            if exceeded_attempts {
+                if let Some(queue) = state.messages_by_topic.get_mut(topic) {
+                    queue.entries.retain(|(msg_id, _)| msg_id != &id);
+                }
+                if let Some(message) = state.messages.remove(&id) {
+                    state.dead_letters.insert(id, message);
+                }
+                continue;
+            }
+            let message = state
+                .messages
+                .get_mut(&id)
+                .expect("message present for eligible id");
+            message.delivery_attempts += 1;
+            message.lease_until = Some(now + visibility_timeout);
+            message.leased_by = Some(consumer.to_string());
+            claimed.push(message.clone());
+        }
+        Ok(claimed)
+    }
+
+    async fn extend_lease(
+        &self,
+        topic: &str,
+        id: MessageId,
+        extension: chrono::Duration,
+    ) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        let now = Utc::now();
+        let Some(message) = state.messages.get_mut(&id) else {
+            return Err(PlatformError::NotFound("message"));
+        };
+        if message.topic != topic {
+            return Err(PlatformError::NotFound("message"));
+        }
+        match message.lease_until {
+            Some(lease_until) if lease_until > now => {
+                message.lease_until = Some(now + extension);
+                Ok(())
+            }
+            _ => Err(PlatformError::NotFound("message")),
+        }
+    }
+
     async fn ack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
         let mut state = self.state.write();
-        if state.messages.remove(&id).is_none() {
+        let Some(message) = state.messages.get(&id) else {
             return Err(PlatformError::NotFound("message"));
+        };
+        if let Some(lease_until) = message.lease_until {
+            if lease_until <= Utc::now() {
+                // The lease already lapsed, so the message may have been
+                // reclaimed by another consumer via `claim_messages` —
+                // treat this late ack as a no-op.
+                return Ok(());
+            }
         }
+        state.messages.remove(&id);
         if let Some(queue) = state.messages_by_topic.get_mut(topic) {
-            queue.retain(|msg_id| msg_id != &id);
-            if queue.is_empty() {
-                state.messages_by_topic.remove(topic);
-            }
+            queue.entries.retain(|(msg_id, _)| msg_id != &id);
         }
         Ok(())
     }
+
+    async fn nack_message(&self, topic: &str, id: MessageId) -> PlatformResult<()> {
+        let mut state = self.state.write();
+        let Some(message) = state.messages.get_mut(&id) else {
+            return Err(PlatformError::NotFound("message"));
+        };
+        if message.topic != topic {
+            return Err(PlatformError::NotFound("message"));
+        }
+        match message.lease_until {
+            Some(lease_until) if lease_until > Utc::now() => {
+                message.lease_until = None;
+                message.leased_by = None;
+                Ok(())
+            }
+            // The lease already lapsed, so the message may have been
+            // reclaimed by another consumer via `claim_messages` — treat
+            // this late nack as a no-op instead of clearing their claim.
+            _ => Ok(()),
+        }
+    }
+
+    async fn poll_topic(
+        &self,
+        query: MessageQuery,
+        since_token: u64,
+        timeout: chrono::Duration,
+    ) -> PlatformResult<(Vec<MessageRecord>, u64)> {
+        if query.topic.trim().is_empty() {
+            return Err(PlatformError::InvalidInput("topic required"));
+        }
+        let deadline = tokio::time::Instant::now() + to_std_duration(timeout);
+        loop {
+            let notify = {
+                let mut state = self.state.write();
+                let topic_queue = state
+                    .messages_by_topic
+                    .entry(query.topic.clone())
+                    .or_insert_with(TopicQueue::default);
+                let (fresh, highest) =
+                    poll_topic_queue(topic_queue, &state.messages, &query, since_token);
+                if !fresh.is_empty() {
+                    return Ok((fresh, highest));
+                }
+                topic_queue.notify.clone()
+            };
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                return Ok((Vec::new(), since_token));
+            }
+            let _ = tokio::time::timeout(remaining, notify.notified()).await;
+        }
+    }
+
+    async fn evict_expired_messages(
+        &self,
+        now: DateTime<Utc>,
+        ttl: chrono::Duration,
+    ) -> PlatformResult<u64> {
+        let mut state = self.state.write();
+        Ok(batch_evict_expired_messages(&mut state, now, ttl))
+    }
+}
+
+#[async_trait]
+impl BatchStore for InMemoryPersistence {
+    async fn execute_batch(&self, ops: Vec<BatchOp>) -> Vec<BatchResult> {
+        let mut state = self.state.write();
+        let mut results = Vec::with_capacity(ops.len());
+        for op in ops {
+            let outcome = match op {
+                BatchOp::EnqueueMessage(input) => batch_enqueue_message(&mut state, input),
+                BatchOp::AckMessage { topic, id } => batch_ack_message(&mut state, &topic, id),
+                BatchOp::CreateContent(input) => batch_create_content(&mut state, input),
+                BatchOp::UpdateContentState {
+                    id,
+                    state: new_state,
+                    reason,
+                    actor_id,
+                } => batch_update_content_state(&mut state, id, new_state, reason, actor_id),
+                BatchOp::CreateAssignment(input) => batch_create_assignment(&mut state, input),
+                BatchOp::QueryAssignments(query) => {
+                    Ok(BatchResult::Assignments(batch_query_assignments(&state, &query)))
+                }
+            };
+            match outcome {
+                Ok(result) => results.push(result),
+                Err(err) => {
+                    results.push(BatchResult::Error(err));
+                    break;
+                }
+            }
+        }
+        results
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryPersistence {
+    async fn increment_rate_window(
+        &self,
+        tenant_id: TenantId,
+        route: &str,
+        window_start: DateTime<Utc>,
+    ) -> PlatformResult<u32> {
+        let mut state = self.state.write();
+        let count = state
+            .rate_limit_windows
+            .entry((tenant_id, route.to_string(), window_start))
+            .or_insert(0);
+        *count += 1;
+        Ok(*count)
+    }
+
+    async fn reap_expired_rate_windows(&self, before: DateTime<Utc>) -> PlatformResult<u64> {
+        let mut state = self.state.write();
+        let before_count = state.rate_limit_windows.len();
+        state
+            .rate_limit_windows
+            .retain(|(_, _, window_start), _| *window_start >= before);
+        Ok((before_count - state.rate_limit_windows.len()) as u64)
+    }
+}
+
+/// Postgres-backed [`RateLimitStore`], for deployments running more than
+/// one gateway replica: `rate_limit_windows` is the single source of truth
+/// every replica increments against, instead of each keeping its own
+/// in-process count.
+#[cfg(feature = "db")]
+pub struct PostgresRateLimitStore {
+    pool: Pool<Postgres>,
+}
+
+#[cfg(feature = "db")]
+impl PostgresRateLimitStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "db")]
+#[async_trait]
+impl RateLimitStore for PostgresRateLimitStore {
+    async fn increment_rate_window(
+        &self,
+        tenant_id: TenantId,
+        route: &str,
+        window_start: DateTime<Utc>,
+    ) -> PlatformResult<u32> {
+        let row = sqlx::query(
+            "INSERT INTO rate_limit_windows (tenant_id, route, window_start, count)
+             VALUES ($1,$2,$3,1)
+             ON CONFLICT (tenant_id, route, window_start) DO UPDATE SET
+                count = rate_limit_windows.count + 1
+             RETURNING count",
+        )
+        .bind(tenant_id)
+        .bind(route)
+        .bind(window_start)
+        .fetch_one(&self.pool)
+        .await?;
+        let count: i32 = row.try_get("count")?;
+        Ok(count as u32)
+    }
+
+    async fn reap_expired_rate_windows(&self, before: DateTime<Utc>) -> PlatformResult<u64> {
+        let result = sqlx::query("DELETE FROM rate_limit_windows WHERE window_start < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Mirrors `MessagingStore::enqueue_message` for `InMemoryPersistence`,
+/// operating on an already-locked `state` instead of acquiring its own lock.
+fn batch_enqueue_message(
+    state: &mut PlatformState,
+    input: NewMessageRecord,
+) -> PlatformResult<BatchResult> {
+    if !state.tenants.contains_key(&input.tenant_id) {
+        return Err(PlatformError::NotFound("tenant"));
+    }
+    if !state.projects.contains_key(&input.project_id) {
+        return Err(PlatformError::NotFound("project"));
+    }
+    if state.messages.contains_key(&input.id) {
+        return Err(PlatformError::Conflict("message"));
+    }
+    let record = MessageRecord {
+        id: input.id,
+        tenant_id: input.tenant_id,
+        project_id: input.project_id,
+        topic: input.topic.clone(),
+        key: input.key,
+        payload: input.payload,
+        priority: input.priority,
+        attributes: input.attributes,
+        published_at: Utc::now(),
+        delivery_attempts: 0,
+        max_attempts: input.max_attempts,
+        lease_until: None,
+        leased_by: None,
+    };
+    state.messages.insert(record.id, record.clone());
+    let topic_queue = state
+        .messages_by_topic
+        .entry(record.topic.clone())
+        .or_insert_with(TopicQueue::default);
+    topic_queue.sequence += 1;
+    topic_queue.entries.push_back((record.id, topic_queue.sequence));
+    topic_queue.notify.notify_waiters();
+    Ok(BatchResult::Message(record))
+}
+
+/// Mirrors `MessagingStore::ack_message` for `InMemoryPersistence`.
+fn batch_ack_message(
+    state: &mut PlatformState,
+    topic: &str,
+    id: MessageId,
+) -> PlatformResult<BatchResult> {
+    let Some(message) = state.messages.get(&id) else {
+        return Err(PlatformError::NotFound("message"));
+    };
+    if let Some(lease_until) = message.lease_until {
+        if lease_until <= Utc::now() {
+            // The lease already lapsed, so the message may have been
+            // reclaimed by another consumer via `claim_messages` — treat
+            // this late ack as a no-op.
+            return Ok(BatchResult::Acked);
+        }
+    }
+    state.messages.remove(&id);
+    if let Some(queue) = state.messages_by_topic.get_mut(topic) {
+        queue.entries.retain(|(msg_id, _)| msg_id != &id);
+    }
+    Ok(BatchResult::Acked)
+}
+
+/// Mirrors `MessagingStore::evict_expired_messages` for `InMemoryPersistence`.
+fn batch_evict_expired_messages(
+    state: &mut PlatformState,
+    now: DateTime<Utc>,
+    ttl: chrono::Duration,
+) -> u64 {
+    let cutoff = now - ttl;
+    let expired_ids: Vec<MessageId> = state
+        .messages
+        .values()
+        .filter(|message| message.published_at < cutoff)
+        .map(|message| message.id)
+        .collect();
+    for id in &expired_ids {
+        if let Some(message) = state.messages.remove(id) {
+            if let Some(queue) = state.messages_by_topic.get_mut(&message.topic) {
+                queue.entries.retain(|(msg_id, _)| msg_id != id);
+            }
+        }
+    }
+    expired_ids.len() as u64
+}
+
+/// Mirrors `ModerationStore::create_content` for `InMemoryPersistence`.
+fn batch_create_content(
+    state: &mut PlatformState,
+    input: NewModeratedContent,
+) -> PlatformResult<BatchResult> {
+    if state.moderation_content.contains_key(&input.id) {
+        return Err(PlatformError::Conflict("ugc_content"));
+    }
+    if !state.tenants.contains_key(&input.tenant_id) {
+        return Err(PlatformError::NotFound("tenant"));
+    }
+    if !state.projects.contains_key(&input.project_id) {
+        return Err(PlatformError::NotFound("project"));
+    }
+    let now = Utc::now();
+    let record = ModeratedContent {
+        id: input.id,
+        tenant_id: input.tenant_id,
+        project_id: input.project_id,
+        filename: input.filename,
+        mime_type: input.mime_type,
+        size_bytes: input.size_bytes,
+        state: ModerationState::Pending,
+        reason: None,
+        labels: input.labels,
+        attributes: input.attributes,
+        submitted_at: now,
+        updated_at: now,
+    };
+    state.moderation_content.insert(record.id, record.clone());
+    Ok(BatchResult::Content(record))
+}
+
+/// Mirrors `ModerationStore::update_content_state` for `InMemoryPersistence`.
+fn batch_update_content_state(
+    state: &mut PlatformState,
+    id: ContentId,
+    new_state: ModerationState,
+    reason: Option<String>,
+    actor_id: uuid::Uuid,
+) -> PlatformResult<BatchResult> {
+    apply_content_state_transition(state, id, new_state, reason, actor_id, Utc::now())
+        .map(BatchResult::Content)
+}
+
+/// Shared by `batch_update_content_state` (live writes, `now = Utc::now()`)
+/// and `batch_expire_pending_moderation` (sweeps, `now` passed in for
+/// determinism) so the two can never record a transition differently:
+/// updates `ModeratedContent.state`, appends a `ModerationEvent`, and
+/// appends the next [`ModerationAuditEntry`] onto `content_id`'s hash chain.
+fn apply_content_state_transition(
+    state: &mut PlatformState,
+    id: ContentId,
+    new_state: ModerationState,
+    reason: Option<String>,
+    actor_id: uuid::Uuid,
+    now: DateTime<Utc>,
+) -> PlatformResult<ModeratedContent> {
+    let record = state
+        .moderation_content
+        .get_mut(&id)
+        .ok_or(PlatformError::NotFound("ugc_content"))?;
+    let from_state = record.state.clone();
+    record.state = new_state.clone();
+    record.reason = reason.clone();
+    record.updated_at = now;
+    let updated = record.clone();
+    state.moderation_events.push(ModerationEvent {
+        id: uuid::Uuid::new_v4(),
+        content_id: id,
+        from_state: from_state.clone(),
+        to_state: new_state.clone(),
+        reason: reason.clone(),
+        actor_id,
+        created_at: now,
+    });
+    let chain = state.moderation_audit.entry(id).or_default();
+    let prev_hash = chain
+        .last()
+        .map(|entry| entry.hash.clone())
+        .unwrap_or_else(|| MODERATION_AUDIT_GENESIS_HASH.to_string());
+    let sequence = chain.len() as u64 + 1;
+    let hash = moderation_audit_hash(
+        &prev_hash,
+        id,
+        sequence,
+        &from_state,
+        &new_state,
+        &reason,
+        actor_id,
+        now,
+    );
+    chain.push(ModerationAuditEntry {
+        id: uuid::Uuid::new_v4(),
+        content_id: id,
+        sequence,
+        from_state,
+        to_state: new_state,
+        reason,
+        actor_id,
+        created_at: now,
+        hash,
+    });
+    Ok(updated)
+}
+
+/// `hash_n = H(hash_{n-1} || entry_n)` for one [`ModerationAuditEntry`] —
+/// shared by every backend's `update_content_state`/`expire_pending_moderation`
+/// so the chain is computed identically regardless of which store produced
+/// it, and an auditor can verify entries written by one backend against a
+/// chain continued by another (e.g. after a migration).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn moderation_audit_hash(
+    prev_hash: &str,
+    content_id: ContentId,
+    sequence: u64,
+    from_state: &ModerationState,
+    to_state: &ModerationState,
+    reason: &Option<String>,
+    actor_id: uuid::Uuid,
+    created_at: DateTime<Utc>,
+) -> String {
+    let payload = serde_json::json!({
+        "content_id": content_id,
+        "sequence": sequence,
+        "from_state": from_state,
+        "to_state": to_state,
+        "reason": reason,
+        "actor_id": actor_id,
+        "created_at": created_at,
+    });
+    let mut bytes = prev_hash.as_bytes().to_vec();
+    bytes.extend_from_slice(payload.to_string().as_bytes());
+    crate::platform::ingest::compute_digest(&bytes, crate::platform::ingest::ChecksumAlgorithm::Sha256)
+}
+
+/// Mirrors `ModerationStore::expire_pending_moderation` for `InMemoryPersistence`.
+fn batch_expire_pending_moderation(
+    state: &mut PlatformState,
+    now: DateTime<Utc>,
+    deadline: chrono::Duration,
+    to_state: ModerationState,
+) -> Vec<ModeratedContent> {
+    let cutoff = now - deadline;
+    let stale_ids: Vec<ContentId> = state
+        .moderation_content
+        .values()
+        .filter(|item| item.state == ModerationState::Pending && item.submitted_at < cutoff)
+        .map(|item| item.id)
+        .collect();
+    let mut expired = Vec::with_capacity(stale_ids.len());
+    for id in stale_ids {
+        let reason = Some("expired after moderation deadline".to_string());
+        match apply_content_state_transition(state, id, to_state.clone(), reason, uuid::Uuid::nil(), now) {
+            Ok(content) => expired.push(content),
+            // The content was already removed from under us by a concurrent
+            // call between the scan above and this update; skip it rather
+            // than erroring the whole sweep over one stale id.
+            Err(_) => continue,
+        }
+    }
+    expired
+}
+
+/// Mirrors `OrchestrationStore::create_assignment` for `InMemoryPersistence`.
+fn batch_create_assignment(
+    state: &mut PlatformState,
+    input: NewAssignment,
+) -> PlatformResult<BatchResult> {
+    if input.workload_id.trim().is_empty() {
+        return Err(PlatformError::InvalidInput("workload_id required"));
+    }
+    if let Some(tenant_id) = input.tenant_id {
+        if !state.tenants.contains_key(&tenant_id) {
+            return Err(PlatformError::NotFound("tenant"));
+        }
+    }
+    if let Some(project_id) = input.project_id {
+        if !state.projects.contains_key(&project_id) {
+            return Err(PlatformError::NotFound("project"));
+        }
+    }
+    if state.assignments.contains_key(&input.id) {
+        return Err(PlatformError::Conflict("assignment"));
+    }
+    let now = Utc::now();
+    let assignment = WorkAssignment {
+        id: input.id,
+        agent_id: input.agent_id,
+        workload_id: input.workload_id,
+        tenant_id: input.tenant_id,
+        project_id: input.project_id,
+        status: WorkStatus::Pending,
+        status_message: Some("queued".to_string()),
+        metadata: input.metadata,
+        last_heartbeat: None,
+        attempt: 0,
+        created_at: now,
+        updated_at: now,
+    };
+    state.assignments.insert(assignment.id, assignment.clone());
+    Ok(BatchResult::Assignment(assignment))
+}
+
+/// Mirrors `OrchestrationStore::update_assignment_status` for `InMemoryPersistence`.
+fn batch_update_assignment_status(
+    state: &mut PlatformState,
+    id: AssignmentId,
+    status: WorkStatus,
+    status_message: Option<String>,
+) -> PlatformResult<WorkAssignment> {
+    let assignment = state
+        .assignments
+        .get_mut(&id)
+        .ok_or(PlatformError::NotFound("assignment"))?;
+    assignment.status = status;
+    assignment.status_message = status_message;
+    assignment.updated_at = Utc::now();
+    Ok(assignment.clone())
+}
+
+/// Mirrors `OrchestrationStore::heartbeat_assignment` for `InMemoryPersistence`.
+fn batch_heartbeat_assignment(state: &mut PlatformState, id: AssignmentId) -> PlatformResult<()> {
+    let assignment = state
+        .assignments
+        .get_mut(&id)
+        .ok_or(PlatformError::NotFound("assignment"))?;
+    assignment.last_heartbeat = Some(Utc::now());
+    Ok(())
+}
+
+/// Mirrors `ModerationStore::create_report` for `InMemoryPersistence`.
+fn batch_create_report(
+    state: &mut PlatformState,
+    content_id: ContentId,
+    reporter_id: uuid::Uuid,
+    category: ReportCategory,
+    detail: Option<String>,
+) -> PlatformResult<ModerationReport> {
+    if !state.moderation_content.contains_key(&content_id) {
+        return Err(PlatformError::NotFound("ugc_content"));
+    }
+    let report = ModerationReport {
+        id: uuid::Uuid::new_v4(),
+        content_id,
+        reporter_id,
+        category,
+        detail,
+        created_at: Utc::now(),
+        resolved: false,
+    };
+    state.moderation_reports.push(report.clone());
+    Ok(report)
+}
+
+/// Mirrors `OrchestrationStore::list_assignments` for `InMemoryPersistence`.
+fn batch_query_assignments(state: &PlatformState, query: &AssignmentQuery) -> Vec<WorkAssignment> {
+    let mut assignments: Vec<_> = state
+        .assignments
+        .values()
+        .filter(|assignment| {
+            if let Some(agent_id) = query.agent_id {
+                if assignment.agent_id != agent_id {
+                    return false;
+                }
+            }
+            if let Some(tenant_id) = query.tenant_id {
+                if assignment.tenant_id != Some(tenant_id) {
+                    return false;
+                }
+            }
+            if let Some(project_id) = query.project_id {
+                if assignment.project_id != Some(project_id) {
+                    return false;
+                }
+            }
+            if let Some(status) = &query.status {
+                if &assignment.status != status {
+                    return false;
+                }
+            }
+            true
+        })
+        .cloned()
+        .collect();
+    assignments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    assignments
+}
+
+fn to_std_duration(duration: chrono::Duration) -> std::time::Duration {
+    duration.to_std().unwrap_or(std::time::Duration::ZERO)
+}
+
+/// Scans `topic_queue.entries` for ids newer than `since_token`, applying
+/// `query`'s tenant/project filters, and returns the matches plus the
+/// highest sequence considered — including ids that were filtered out or
+/// have since been removed from `messages` (acked/dead-lettered) — so the
+/// next call's `since_token` always advances past everything already seen.
+fn poll_topic_queue(
+    topic_queue: &TopicQueue,
+    messages: &HashMap<MessageId, MessageRecord>,
+    query: &MessageQuery,
+    since_token: u64,
+) -> (Vec<MessageRecord>, u64) {
+    let mut results = Vec::new();
+    let mut highest = since_token;
+    for (message_id, sequence) in &topic_queue.entries {
+        if *sequence <= since_token {
+            continue;
+        }
+        highest = highest.max(*sequence);
+        let Some(message) = messages.get(message_id) else {
+            continue;
+        };
+        if let Some(tenant_id) = query.tenant_id {
+            if message.tenant_id != tenant_id {
+                continue;
+            }
+        }
+        if let Some(project_id) = query.project_id {
+            if message.project_id != project_id {
+                continue;
+            }
+        }
+        results.push(message.clone());
+        if let Some(limit) = query.limit {
+            if results.len() as u32 >= limit {
+                break;
+            }
+        }
+    }
+    (results, highest)
 }
@@ -0,0 +1,101 @@
+//! Typed Postgres `CREATE TYPE ... AS ENUM` codec for this crate's
+//! string-backed status enums (in the spirit of `diesel-derive-enum`, but
+//! hand-rolled since this crate is on `sqlx` rather than `diesel`).
+//!
+//! Every enum wired up with [`pg_enum!`] already has an `as_str()`/`FromStr`
+//! pair for its JSON encoding; the macro reuses those so a bind or
+//! `try_get` against the matching Postgres enum column is type-checked by
+//! the database at the call site, instead of round-tripping through a bare
+//! `String` and only surfacing a typo or schema drift as an
+//! `Internal("invalid ...")` once `into_model` happens to parse it.
+
+/// Implements `sqlx::Type`/`Encode`/`Decode` for `$ty` against the Postgres
+/// enum type named `$pg_name`, using `$ty`'s existing `as_str()`/`FromStr`.
+#[macro_export]
+macro_rules! pg_enum {
+    ($ty:ty, $pg_name:literal) => {
+        impl sqlx::Type<sqlx::Postgres> for $ty {
+            fn type_info() -> sqlx::postgres::PgTypeInfo {
+                sqlx::postgres::PgTypeInfo::with_name($pg_name)
+            }
+        }
+
+        impl sqlx::postgres::PgHasArrayType for $ty {
+            fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                sqlx::postgres::PgTypeInfo::with_name(concat!("_", $pg_name))
+            }
+        }
+
+        impl<'q> sqlx::Encode<'q, sqlx::Postgres> for $ty {
+            fn encode_by_ref(
+                &self,
+                buf: &mut sqlx::postgres::PgArgumentBuffer,
+            ) -> sqlx::encode::IsNull {
+                <&str as sqlx::Encode<'q, sqlx::Postgres>>::encode(self.as_str(), buf)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, sqlx::Postgres> for $ty {
+            fn decode(
+                value: sqlx::postgres::PgValueRef<'r>,
+            ) -> Result<Self, sqlx::error::BoxDynError> {
+                let raw = <&str as sqlx::Decode<'r, sqlx::Postgres>>::decode(value)?;
+                raw.parse::<$ty>().map_err(Into::into)
+            }
+        }
+    };
+}
+
+crate::pg_enum!(crate::platform::models::UploadStatus, "upload_status");
+crate::pg_enum!(crate::platform::models::ContentVisibility, "content_visibility");
+crate::pg_enum!(crate::platform::models::AgentStatus, "agent_status");
+
+/// `CREATE TYPE ... AS ENUM (...)` statements for every enum wired up via
+/// [`pg_enum!`], in the order a migration would need to run them in. Only
+/// the concrete variants are listed — each enum's `UnknownValue(String)`
+/// fallback exists for forward-compatible JSON decoding, not as something
+/// a native Postgres enum column could ever hold.
+///
+/// `WorkStatus`, `ModerationState`, and `MessagePriority` are not included:
+/// those status columns are still decoded via the ad hoc `String`/`FromStr`
+/// path this module replaces, pending those enums being defined.
+pub fn create_enum_types_sql() -> Vec<String> {
+    vec![
+        enum_type_sql(
+            "upload_status",
+            &[
+                "pending",
+                "uploading",
+                "completed",
+                "failed",
+                "cancelled",
+                "aborted",
+            ],
+        ),
+        enum_type_sql(
+            "content_visibility",
+            &["private", "project", "tenant", "public"],
+        ),
+        enum_type_sql(
+            "agent_status",
+            &[
+                "registered",
+                "active",
+                "suspended",
+                "degraded",
+                "unreachable",
+                "offline",
+                "decommissioned",
+            ],
+        ),
+    ]
+}
+
+fn enum_type_sql(name: &str, variants: &[&str]) -> String {
+    let values = variants
+        .iter()
+        .map(|v| format!("'{v}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("CREATE TYPE {name} AS ENUM ({values});")
+}
@@ -0,0 +1,143 @@
+//! Zone-aware, capacity-weighted replica placement for scheduled tasks.
+//!
+//! Mirrors Garage's partition-assignment goal of spreading replicas across
+//! failure domains: [`select_agents`] hands out replica slots zone-by-zone
+//! (every distinct zone gets one before any zone gets a second, and so on)
+//! and, within the chosen zone, to whichever agent has the most remaining
+//! capacity. [`OrchestrationEngine`](super::orchestration::OrchestrationEngine)
+//! stays agnostic of where agents actually live; it only knows about
+//! whatever implements [`AgentCandidateSource`].
+
+use super::error::PlatformError;
+use std::collections::HashMap;
+
+/// A live agent as seen by the placement algorithm: its failure domain and
+/// how much headroom it has left to take on another replica.
+#[derive(Debug, Clone)]
+pub struct AgentCandidate {
+    pub id: String,
+    pub zone: String,
+    pub remaining_capacity: f64,
+}
+
+/// Supplies [`AgentCandidate`]s to [`select_agents`]. Implemented by
+/// `gateway::state::AgentRegistry`, which derives `zone` and
+/// `remaining_capacity` from each agent's `cpu_cores`/`memory_bytes` minus
+/// its current load; the core crate stays ignorant of what backs it.
+pub trait AgentCandidateSource: Send + Sync {
+    fn candidates(&self) -> Vec<AgentCandidate>;
+}
+
+/// Picks up to `replicas` agent ids for one task. Replica slots are filled
+/// one at a time: each slot goes to the zone that has been used the fewest
+/// times so far among zones that still have spare capacity (ties broken by
+/// zone name for determinism), so distinct zones are exhausted before any
+/// zone takes a second replica. Within the chosen zone the single agent
+/// with the highest remaining capacity is picked and excluded from the rest
+/// of this call, so a task never lands two replicas on the same agent even
+/// when replicas outnumber distinct zones. Returns fewer than `replicas`
+/// ids if capacity runs out first, and `PlatformError::Conflict` if no
+/// candidate has any remaining capacity at all.
+pub fn select_agents(
+    candidates: &[AgentCandidate],
+    replicas: usize,
+) -> Result<Vec<String>, PlatformError> {
+    if replicas == 0 || candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+    if candidates.iter().all(|c| c.remaining_capacity <= 0.0) {
+        return Err(PlatformError::Conflict("no agent capacity available"));
+    }
+
+    let mut pool: Vec<AgentCandidate> = candidates.to_vec();
+    let mut zones: Vec<String> = pool.iter().map(|c| c.zone.clone()).collect();
+    zones.sort();
+    zones.dedup();
+
+    let mut replicas_per_zone: HashMap<String, usize> =
+        zones.iter().cloned().map(|z| (z, 0)).collect();
+    let mut chosen = Vec::with_capacity(replicas);
+
+    for _ in 0..replicas {
+        let next_zone = zones
+            .iter()
+            .filter(|zone| {
+                pool.iter()
+                    .any(|c| &c.zone == *zone && c.remaining_capacity > 0.0)
+            })
+            .min_by_key(|zone| replicas_per_zone[*zone])
+            .cloned();
+        let Some(zone) = next_zone else {
+            break;
+        };
+
+        let pick = pool
+            .iter_mut()
+            .filter(|c| c.zone == zone && c.remaining_capacity > 0.0)
+            .max_by(|a, b| a.remaining_capacity.total_cmp(&b.remaining_capacity))
+            .expect("zone was selected because it has a candidate with spare capacity");
+        chosen.push(pick.id.clone());
+        // Excluded rather than removed: cheaper than shifting the vector,
+        // and the effect (never picked again this call) is identical.
+        pick.remaining_capacity = 0.0;
+        *replicas_per_zone.get_mut(&zone).unwrap() += 1;
+    }
+
+    Ok(chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, zone: &str, capacity: f64) -> AgentCandidate {
+        AgentCandidate {
+            id: id.to_string(),
+            zone: zone.to_string(),
+            remaining_capacity: capacity,
+        }
+    }
+
+    #[test]
+    fn spreads_replicas_across_zones_before_doubling_up() {
+        let candidates = vec![
+            candidate("a1", "us-east", 10.0),
+            candidate("a2", "us-east", 5.0),
+            candidate("b1", "us-west", 8.0),
+            candidate("c1", "eu-west", 3.0),
+        ];
+        let chosen = select_agents(&candidates, 3).unwrap();
+        assert_eq!(chosen.len(), 3);
+        assert_eq!(chosen, vec!["a1", "b1", "c1"]);
+    }
+
+    #[test]
+    fn never_places_two_replicas_on_the_same_agent() {
+        let candidates = vec![candidate("only", "zone-a", 10.0)];
+        let chosen = select_agents(&candidates, 3).unwrap();
+        assert_eq!(chosen, vec!["only"]);
+    }
+
+    #[test]
+    fn allows_a_second_replica_in_a_zone_once_others_are_exhausted() {
+        let candidates = vec![
+            candidate("a1", "us-east", 10.0),
+            candidate("a2", "us-east", 6.0),
+            candidate("b1", "us-west", 1.0),
+        ];
+        let chosen = select_agents(&candidates, 3).unwrap();
+        assert_eq!(chosen, vec!["a1", "b1", "a2"]);
+    }
+
+    #[test]
+    fn zero_total_capacity_is_a_conflict() {
+        let candidates = vec![candidate("a1", "us-east", 0.0)];
+        let err = select_agents(&candidates, 1).unwrap_err();
+        assert!(matches!(err, PlatformError::Conflict(_)));
+    }
+
+    #[test]
+    fn no_candidates_returns_no_placement() {
+        assert_eq!(select_agents(&[], 2).unwrap(), Vec::<String>::new());
+    }
+}
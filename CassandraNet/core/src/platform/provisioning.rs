@@ -1,15 +1,36 @@
 use super::auth::AuthService;
+use super::ca::CertificateAuthority;
 use super::error::{PlatformError, PlatformResult};
 use super::models::*;
-use super::persistence::{AgentStore, ProjectStore, TenantStore};
+use super::persistence::{
+    AgentStateStore, AgentStore, AuditStore, IdempotencyRecord, IdempotencyStore, ProjectStore,
+    TenantStore,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{Duration, Utc};
-use parking_lot::RwLock;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use cncommon::auth::{default_scope_registry, Scope};
 
+/// Fixed scope tenant creation's idempotency keys are namespaced under,
+/// since a not-yet-created tenant has no id to scope by the way project
+/// creation scopes by its parent `tenant_id`.
+const TENANT_CREATE_SCOPE: &str = "provisioning.create_tenant";
+
+/// Digests the JSON encoding of `value` the same way `auth::hash_secret`
+/// digests a bare string, so a replayed idempotency key can be told apart
+/// from one reused with a different request body.
+fn hash_request(value: &impl Serialize) -> PlatformResult<String> {
+    let encoded = serde_json::to_vec(value)
+        .map_err(|_| PlatformError::Internal("unserializable request"))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&encoded);
+    Ok(URL_SAFE_NO_PAD.encode(hasher.finalize()))
+}
+
 #[derive(Debug, Clone)]
 pub struct TenantCreateRequest {
     pub name: String,
@@ -31,7 +52,7 @@ impl TenantCreateRequest {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TenantBootstrap {
     pub tenant: Tenant,
     pub default_api_key: Option<ApiKey>,
@@ -57,7 +78,7 @@ impl ProjectCreateRequest {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectBootstrap {
     pub project: Project,
     pub bootstrap_scripts: Vec<String>,
@@ -67,7 +88,10 @@ pub struct ProjectBootstrap {
 pub struct AgentRegistrationOptions {
     pub metadata: Option<AgentMetadata>,
     pub bootstrap_commands: Vec<String>,
-    pub certificate_bundle: Option<Vec<u8>>,
+    /// When set, an mTLS client certificate is minted for the agent via the
+    /// platform certificate authority and placed into
+    /// `ProvisionedAgent::certificate_bundle` alongside the API key.
+    pub mint_certificate: bool,
 }
 
 #[derive(Clone)]
@@ -75,9 +99,12 @@ pub struct ProvisioningService {
     tenants: Arc<dyn TenantStore>,
     projects: Arc<dyn ProjectStore>,
     agents: Arc<dyn AgentStore>,
+    agent_states: Arc<dyn AgentStateStore>,
     auth: Arc<AuthService>,
-    tenant_idempotency: Arc<RwLock<HashMap<String, TenantBootstrap>>>,
-    project_idempotency: Arc<RwLock<HashMap<String, ProjectBootstrap>>>,
+    audit: Arc<dyn AuditStore>,
+    ca: CertificateAuthority,
+    idempotency: Arc<dyn IdempotencyStore>,
+    idempotency_ttl: Duration,
     heartbeat_timeout: Duration,
 }
 
@@ -86,15 +113,22 @@ impl ProvisioningService {
         tenants: Arc<dyn TenantStore>,
         projects: Arc<dyn ProjectStore>,
         agents: Arc<dyn AgentStore>,
+        agent_states: Arc<dyn AgentStateStore>,
         auth: Arc<AuthService>,
+        audit: Arc<dyn AuditStore>,
+        ca: CertificateAuthority,
+        idempotency: Arc<dyn IdempotencyStore>,
     ) -> Self {
         Self {
             tenants,
             projects,
             agents,
+            agent_states,
             auth,
-            tenant_idempotency: Arc::new(RwLock::new(HashMap::new())),
-            project_idempotency: Arc::new(RwLock::new(HashMap::new())),
+            audit,
+            ca,
+            idempotency,
+            idempotency_ttl: Duration::hours(24),
             heartbeat_timeout: Duration::minutes(5),
         }
     }
@@ -104,19 +138,100 @@ impl ProvisioningService {
         self
     }
 
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_ttl = ttl;
+        self
+    }
+
+    /// Checks `scope`/`key` against the idempotency store: `Ok(Some(value))`
+    /// on a replay whose request hashed the same as the original, `Ok(None)`
+    /// on a first-time key (or no key at all), and `Err(IdempotencyConflict)`
+    /// if `key` was already used for a request that hashes differently.
+    fn check_idempotency<T: serde::de::DeserializeOwned>(
+        &self,
+        scope: &str,
+        key: Option<&str>,
+        request_hash: &str,
+    ) -> PlatformResult<Option<T>> {
+        let Some(key) = key else {
+            return Ok(None);
+        };
+        let Some(record) = self.idempotency.get(scope, key, Utc::now())? else {
+            return Ok(None);
+        };
+        if record.request_hash != request_hash {
+            return Err(PlatformError::IdempotencyConflict {
+                scope: scope.to_string(),
+                key: key.to_string(),
+            });
+        }
+        serde_json::from_value(record.response)
+            .map_err(|_| PlatformError::Internal("corrupt idempotency record"))
+            .map(Some)
+    }
+
+    /// Reconciles `response` (already created/computed by the caller)
+    /// against the idempotency store once the underlying resource exists.
+    /// `check_idempotency`'s earlier `get` only short-circuits an *already
+    /// settled* replay — it can't stop two concurrent first-time callers
+    /// with the same key from both reaching this point, each having created
+    /// its own distinct resource. `IdempotencyStore::put_if_absent` closes
+    /// that gap: whichever caller's claim lands first has its `response`
+    /// persisted and returned as-is; the other discovers the winner's
+    /// record here and returns that instead of its own, so the two callers
+    /// (and the resources they created) always converge on one answer
+    /// instead of silently diverging.
+    fn reconcile_idempotency<T: Serialize + serde::de::DeserializeOwned>(
+        &self,
+        scope: &str,
+        key: Option<String>,
+        request_hash: &str,
+        response: T,
+    ) -> PlatformResult<T> {
+        let Some(key) = key else {
+            return Ok(response);
+        };
+        let encoded = serde_json::to_value(&response)
+            .map_err(|_| PlatformError::Internal("unserializable idempotency record"))?;
+        let now = Utc::now();
+        let record = IdempotencyRecord {
+            request_hash: request_hash.to_string(),
+            response: encoded,
+            created_at: now,
+            expires_at: now + self.idempotency_ttl,
+        };
+        match self.idempotency.put_if_absent(scope, &key, now, record)? {
+            None => Ok(response),
+            Some(existing) if existing.request_hash == request_hash => {
+                serde_json::from_value(existing.response)
+                    .map_err(|_| PlatformError::Internal("corrupt idempotency record"))
+            }
+            Some(_) => Err(PlatformError::IdempotencyConflict { scope: scope.to_string(), key }),
+        }
+    }
+
     pub fn create_tenant(&self, name: impl Into<String>) -> PlatformResult<Tenant> {
         let result = self.create_tenant_with_options(TenantCreateRequest::new(name))?;
         Ok(result.tenant)
     }
 
+    #[tracing::instrument(skip(self, request), fields(tenant.name = %request.name))]
     pub fn create_tenant_with_options(
         &self,
         request: TenantCreateRequest,
     ) -> PlatformResult<TenantBootstrap> {
-        if let Some(key) = request.idempotency_key.as_ref() {
-            if let Some(existing) = self.tenant_idempotency.read().get(key).cloned() {
-                return Ok(existing);
-            }
+        let request_hash = hash_request(&(
+            &request.name,
+            &request.settings,
+            &request.bootstrap_scopes,
+            &request.bootstrap_scripts,
+        ))?;
+        if let Some(existing) = self.check_idempotency::<TenantBootstrap>(
+            TENANT_CREATE_SCOPE,
+            request.idempotency_key.as_deref(),
+            &request_hash,
+        )? {
+            return Ok(existing);
         }
 
         let TenantCreateRequest {
@@ -160,12 +275,24 @@ impl ProvisioningService {
         };
         let bundle = TenantBootstrap {
             tenant: tenant.clone(),
-            default_api_key,
+            default_api_key: default_api_key.clone(),
             bootstrap_scripts: scripts.clone(),
         };
-        if let Some(key) = idempotency_key {
-            self.tenant_idempotency.write().insert(key, bundle.clone());
+        let bundle =
+            self.reconcile_idempotency(TENANT_CREATE_SCOPE, idempotency_key, &request_hash, bundle)?;
+        if bundle.tenant.id != tenant.id {
+            // Lost the idempotent-create race: `tenant` and
+            // `default_api_key` above were already persisted before
+            // `reconcile_idempotency` could tell us that, so they're now an
+            // orphaned tenant and a live admin-scoped key nobody will ever
+            // reference. Unwind them rather than leaking them.
+            self.tenants.delete_tenant(tenant.id)?;
+            if let Some(key) = default_api_key {
+                self.auth.revoke_api_key_system(key.id)?;
+            }
+            return Ok(bundle);
         }
+        otel_metrics::record_tenant_created();
         Ok(bundle)
     }
 
@@ -179,6 +306,7 @@ impl ProvisioningService {
         Ok(result.project)
     }
 
+    #[tracing::instrument(skip(self, request), fields(tenant_id = %request.tenant_id, project.name = %request.name))]
     pub fn create_project_with_options(
         &self,
         request: ProjectCreateRequest,
@@ -186,10 +314,14 @@ impl ProvisioningService {
         if self.tenants.get_tenant(request.tenant_id)?.is_none() {
             return Err(PlatformError::NotFound("tenant"));
         }
-        if let Some(key) = request.idempotency_key.as_ref() {
-            if let Some(existing) = self.project_idempotency.read().get(key).cloned() {
-                return Ok(existing);
-            }
+        let idempotency_scope = request.tenant_id.to_string();
+        let request_hash = hash_request(&(&request.name, &request.bootstrap_scripts))?;
+        if let Some(existing) = self.check_idempotency::<ProjectBootstrap>(
+            &idempotency_scope,
+            request.idempotency_key.as_deref(),
+            &request_hash,
+        )? {
+            return Ok(existing);
         }
         if request.name.trim().is_empty() {
             return Err(PlatformError::InvalidInput("project name required"));
@@ -216,8 +348,17 @@ impl ProvisioningService {
             project: project.clone(),
             bootstrap_scripts: scripts.clone(),
         };
-        if let Some(key) = idempotency_key {
-            self.project_idempotency.write().insert(key, bundle.clone());
+        let bundle = self.reconcile_idempotency(
+            &idempotency_scope,
+            idempotency_key,
+            &request_hash,
+            bundle,
+        )?;
+        if bundle.project.id != project.id {
+            // Lost the idempotent-create race: `project` was already
+            // persisted before `reconcile_idempotency` could tell us that,
+            // leaving an orphaned project nobody will ever reference.
+            self.projects.delete_project(project.id)?;
         }
         Ok(bundle)
     }
@@ -236,6 +377,7 @@ impl ProvisioningService {
         )
     }
 
+    #[tracing::instrument(skip(self, hostname, options), fields(tenant_id = %tenant_id, project_id = %project_id))]
     pub fn register_agent_with_options(
         &self,
         tenant_id: TenantId,
@@ -260,7 +402,7 @@ impl ProvisioningService {
         let AgentRegistrationOptions {
             metadata,
             bootstrap_commands,
-            certificate_bundle,
+            mint_certificate,
         } = options;
         let metadata = metadata.unwrap_or_default();
         let agent = Agent {
@@ -284,11 +426,29 @@ impl ProvisioningService {
         let api_key = self
             .auth
             .issue_api_key(tenant_id, format!("agent:{hostname}"), scopes)?;
-        let commands = if bootstrap_commands.is_empty() {
+
+        let certificate_bundle = if mint_certificate {
+            let bundle = self
+                .ca
+                .issue_agent_certificate(tenant_id, project_id, agent.id)?;
+            Some(bundle.to_bundle_bytes())
+        } else {
+            None
+        };
+
+        let mut commands = if bootstrap_commands.is_empty() {
             vec![format!("cass-agent enroll --agent {}", agent.id)]
         } else {
             bootstrap_commands
         };
+        if certificate_bundle.is_some() {
+            commands.push(format!(
+                "cass-agent install-cert --agent {} --path /etc/cassandra/agent.pem",
+                agent.id
+            ));
+        }
+
+        otel_metrics::record_agent_registered();
         Ok(ProvisionedAgent {
             agent,
             api_key,
@@ -297,6 +457,12 @@ impl ProvisioningService {
         })
     }
 
+    /// Rotates an agent's mTLS client certificate, mirroring
+    /// `AuthService::rotate_api_key`.
+    pub fn rotate_agent_certificate(&self, certificate_id: Uuid) -> PlatformResult<AgentCertificateBundle> {
+        self.ca.rotate_agent_certificate(certificate_id)
+    }
+
     pub fn provision_service_account(
         &self,
         tenant_id: TenantId,
@@ -309,47 +475,132 @@ impl ProvisioningService {
         self.auth.issue_api_key(tenant_id, label, scopes)
     }
 
+    /// Convenience wrapper over `transition_agent(agent_id, AgentEvent::Heartbeat, None)`
+    /// that also stamps `last_seen`; `when` defaults to now. A no-op against
+    /// a `Decommissioned` agent leaves `last_seen` untouched too, same as
+    /// the transition itself.
     pub fn record_agent_heartbeat(
         &self,
         agent_id: AgentId,
         when: Option<chrono::DateTime<Utc>>,
     ) -> PlatformResult<()> {
-        let mut agent = self
-            .agents
-            .get_agent(agent_id)?
-            .ok_or(PlatformError::NotFound("agent"))?;
+        let agent = self.transition_agent(agent_id, AgentEvent::Heartbeat, None)?;
+        if agent.status == AgentStatus::Decommissioned {
+            return Ok(());
+        }
+        let mut agent = agent;
         agent.last_seen = Some(when.unwrap_or_else(Utc::now));
-        agent.status = AgentStatus::Active;
         self.agents.update_agent(agent)
     }
 
-    pub fn set_agent_status(&self, agent_id: AgentId, status: AgentStatus) -> PlatformResult<()> {
+    /// The only way an `Agent`'s `AgentStatus` changes: validates `event`
+    /// against the agent's current status, applies it, and records an
+    /// [`AgentStateEvent`] for the transition. Rejects an event that has no
+    /// edge out of the current status with
+    /// `PlatformError::InvalidTransition`, except `Heartbeat` against a
+    /// `Decommissioned` agent, which no-ops (a late heartbeat from a retired
+    /// agent isn't a bug) and returns the agent unchanged without recording
+    /// anything.
+    #[tracing::instrument(skip(self, reason), fields(agent_id = %agent_id, event = %event))]
+    pub fn transition_agent(
+        &self,
+        agent_id: AgentId,
+        event: AgentEvent,
+        reason: Option<String>,
+    ) -> PlatformResult<Agent> {
         let mut agent = self
             .agents
             .get_agent(agent_id)?
             .ok_or(PlatformError::NotFound("agent"))?;
-        agent.status = status;
-        self.agents.update_agent(agent)
+        let from = agent.status.clone();
+
+        let to = match (&from, event) {
+            (AgentStatus::Registered, AgentEvent::Enroll) => AgentStatus::Active,
+            (AgentStatus::Registered, AgentEvent::Heartbeat) => AgentStatus::Active,
+            (AgentStatus::Active, AgentEvent::Heartbeat) => AgentStatus::Active,
+            (AgentStatus::Active, AgentEvent::SuspendStale) => AgentStatus::Suspended,
+            (AgentStatus::Suspended, AgentEvent::Reactivate) => AgentStatus::Active,
+            (AgentStatus::Active, AgentEvent::Decommission)
+            | (AgentStatus::Suspended, AgentEvent::Decommission) => AgentStatus::Decommissioned,
+            (AgentStatus::Decommissioned, AgentEvent::Heartbeat) => return Ok(agent),
+            _ => {
+                let attempted = match event {
+                    AgentEvent::Enroll | AgentEvent::Heartbeat | AgentEvent::Reactivate => {
+                        AgentStatus::Active
+                    }
+                    AgentEvent::SuspendStale => AgentStatus::Suspended,
+                    AgentEvent::Decommission => AgentStatus::Decommissioned,
+                };
+                return Err(PlatformError::InvalidTransition {
+                    from: from.as_str().to_string(),
+                    to: attempted.as_str().to_string(),
+                });
+            }
+        };
+
+        agent.status = to.clone();
+        self.agents.update_agent(agent.clone())?;
+        self.agent_states.record_agent_state_event(AgentStateEvent {
+            id: Uuid::new_v4(),
+            agent_id,
+            from,
+            to,
+            at: Utc::now(),
+            reason,
+        })?;
+        Ok(agent)
     }
 
     pub fn list_agents(&self, tenant_id: TenantId) -> PlatformResult<Vec<Agent>> {
         self.agents.list_agents(tenant_id)
     }
 
+    /// Oldest-first lifecycle history for `agent_id`, as recorded by every
+    /// accepted [`transition_agent`](Self::transition_agent) call.
+    pub fn agent_state_history(&self, agent_id: AgentId) -> PlatformResult<Vec<AgentStateEvent>> {
+        self.agent_states.list_agent_state_events(agent_id)
+    }
+
+    #[tracing::instrument(skip(self))]
     pub fn sweep_inactive_agents(&self) -> PlatformResult<Vec<Agent>> {
         let mut suspended = Vec::new();
         let tenants = self.tenants.list_tenants()?;
-        let threshold = Utc::now() - self.heartbeat_timeout;
+        let now = Utc::now();
+        let threshold = now - self.heartbeat_timeout;
         for tenant in tenants {
-            for mut agent in self.agents.list_agents(tenant.id)? {
+            for agent in self.agents.list_agents(tenant.id)? {
                 let is_stale = match agent.last_seen {
                     Some(last_seen) => last_seen < threshold,
                     None => true,
                 };
-                if is_stale && agent.status != AgentStatus::Suspended {
-                    agent.status = AgentStatus::Suspended;
-                    self.agents.update_agent(agent.clone())?;
-                    suspended.push(agent);
+                // Only an `Active` agent has a `SuspendStale` edge; a
+                // `Registered` agent that never enrolled, or one already
+                // `Suspended`/`Decommissioned`, is left alone.
+                if is_stale && agent.status == AgentStatus::Active {
+                    let staleness_seconds = agent
+                        .last_seen
+                        .map(|last_seen| (now - last_seen).num_seconds())
+                        .unwrap_or(0)
+                        .max(0) as u64;
+                    let updated = self.transition_agent(
+                        agent.id,
+                        AgentEvent::SuspendStale,
+                        Some(format!("no heartbeat for {staleness_seconds}s")),
+                    )?;
+                    self.audit.record_event(AuditEvent {
+                        id: Uuid::new_v4(),
+                        tenant_id: updated.tenant_id,
+                        action_id: "agent.suspend".to_string(),
+                        area: AuditArea::Provisioning,
+                        category: AuditCategory::Modify,
+                        actor_id: Uuid::nil(),
+                        actor_type: PrincipalType::Service,
+                        target_id: updated.id.to_string(),
+                        timestamp: Utc::now(),
+                        diff: None,
+                    })?;
+                    otel_metrics::record_agent_suspended(staleness_seconds);
+                    suspended.push(updated);
                 }
             }
         }
@@ -384,11 +635,57 @@ impl ProvisioningService {
     }
 }
 
+/// Counters and histograms reported through the global OTLP meter
+/// [`crate::otel`] installs. Each function is a cheap no-op (an uninstalled
+/// meter just discards recordings) when the `otel` feature is off, so call
+/// sites in [`ProvisioningService`] don't need to be `cfg`-gated themselves.
+mod otel_metrics {
+    #[cfg(feature = "otel")]
+    fn meter() -> opentelemetry::metrics::Meter {
+        opentelemetry::global::meter("cassandra.provisioning")
+    }
+
+    #[cfg(feature = "otel")]
+    pub(super) fn record_tenant_created() {
+        meter().u64_counter("tenants_created_total").init().add(1, &[]);
+    }
+
+    #[cfg(not(feature = "otel"))]
+    pub(super) fn record_tenant_created() {}
+
+    #[cfg(feature = "otel")]
+    pub(super) fn record_agent_registered() {
+        meter()
+            .u64_counter("agents_registered_total")
+            .init()
+            .add(1, &[]);
+    }
+
+    #[cfg(not(feature = "otel"))]
+    pub(super) fn record_agent_registered() {}
+
+    #[cfg(feature = "otel")]
+    pub(super) fn record_agent_suspended(staleness_seconds: u64) {
+        meter()
+            .u64_counter("agents_suspended_total")
+            .init()
+            .add(1, &[]);
+        meter()
+            .u64_histogram("agent_heartbeat_staleness_seconds")
+            .init()
+            .record(staleness_seconds, &[]);
+    }
+
+    #[cfg(not(feature = "otel"))]
+    pub(super) fn record_agent_suspended(_staleness_seconds: u64) {}
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::platform::persistence::{
-        AgentStore, ApiKeyStore, InMemoryPersistence, ProjectStore, TenantStore,
+        AgentStateStore, AgentStore, ApiKeyStore, AuditQuery, AuditStore, CertificateStore,
+        IdempotencyStore, InMemoryPersistence, ProjectStore, TenantStore,
     };
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -399,14 +696,28 @@ mod tests {
         let tenant_store: Arc<dyn TenantStore> = storage.clone();
         let project_store: Arc<dyn ProjectStore> = storage.clone();
         let agent_store: Arc<dyn AgentStore> = storage.clone();
+        let agent_state_store: Arc<dyn AgentStateStore> = storage.clone();
         let api_key_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
+        let certificate_store: Arc<dyn CertificateStore> = storage.clone();
+        let idempotency_store: Arc<dyn IdempotencyStore> = storage.clone();
         let auth = Arc::new(AuthService::new(
             tenant_store.clone(),
             api_key_store,
+            audit_store.clone(),
             b"secret".to_vec(),
         ));
-        let provisioning = ProvisioningService::new(tenant_store, project_store, agent_store, auth)
-            .with_heartbeat_timeout(Duration::minutes(1));
+        let provisioning = ProvisioningService::new(
+            tenant_store,
+            project_store,
+            agent_store,
+            agent_state_store,
+            auth,
+            audit_store.clone(),
+            CertificateAuthority::new(certificate_store),
+            idempotency_store,
+        )
+        .with_heartbeat_timeout(Duration::minutes(1));
 
         let mut tenant_request = TenantCreateRequest::new("Example");
         tenant_request.idempotency_key = Some("tenant-key".into());
@@ -447,12 +758,18 @@ mod tests {
             tags: metadata_tags,
         });
         agent_options.bootstrap_commands = vec!["install.sh".into()];
+        agent_options.mint_certificate = true;
         let provisioned = provisioning
             .register_agent_with_options(tenant.id, project.id, "agent-1", agent_options)
             .unwrap();
         assert_eq!(provisioned.agent.tenant_id, tenant.id);
         assert_eq!(provisioned.api_key.tenant_id, tenant.id);
-        assert_eq!(provisioned.bootstrap_commands.len(), 1);
+        assert_eq!(provisioned.bootstrap_commands.len(), 2);
+        let certificate_bundle = provisioned
+            .certificate_bundle
+            .clone()
+            .expect("certificate should be minted");
+        assert!(!certificate_bundle.is_empty());
 
         provisioning
             .record_agent_heartbeat(provisioned.agent.id, None)
@@ -469,10 +786,146 @@ mod tests {
         let suspended = provisioning.sweep_inactive_agents().unwrap();
         assert_eq!(suspended.len(), 1);
         assert_eq!(suspended[0].status, AgentStatus::Suspended);
+        let audit_events = audit_store
+            .list_events(&AuditQuery {
+                tenant_id: tenant.id,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(audit_events
+            .iter()
+            .any(|e| e.action_id == "agent.suspend" && e.target_id == suspended[0].id.to_string()));
 
         let svc_key = provisioning
             .provision_service_account(tenant.id, "svc:metrics", vec![Scope::ProvisioningManage])
             .unwrap();
         assert_eq!(svc_key.tenant_id, tenant.id);
     }
+
+    #[test]
+    fn idempotency_key_reuse_with_different_body_conflicts() {
+        let storage = Arc::new(InMemoryPersistence::new());
+        let tenant_store: Arc<dyn TenantStore> = storage.clone();
+        let project_store: Arc<dyn ProjectStore> = storage.clone();
+        let agent_store: Arc<dyn AgentStore> = storage.clone();
+        let agent_state_store: Arc<dyn AgentStateStore> = storage.clone();
+        let api_key_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
+        let certificate_store: Arc<dyn CertificateStore> = storage.clone();
+        let idempotency_store: Arc<dyn IdempotencyStore> = storage.clone();
+        let auth = Arc::new(AuthService::new(
+            tenant_store.clone(),
+            api_key_store,
+            audit_store.clone(),
+            b"secret".to_vec(),
+        ));
+        let provisioning = ProvisioningService::new(
+            tenant_store,
+            project_store,
+            agent_store,
+            agent_state_store,
+            auth,
+            audit_store,
+            CertificateAuthority::new(certificate_store),
+            idempotency_store,
+        );
+
+        let mut first = TenantCreateRequest::new("Example");
+        first.idempotency_key = Some("shared-key".into());
+        provisioning.create_tenant_with_options(first).unwrap();
+
+        let mut second = TenantCreateRequest::new("Different");
+        second.idempotency_key = Some("shared-key".into());
+        let err = provisioning
+            .create_tenant_with_options(second)
+            .unwrap_err();
+        assert!(matches!(err, PlatformError::IdempotencyConflict { .. }));
+    }
+
+    /// Races several callers that all supply the same idempotency key
+    /// against each other with a `Barrier`, so every one of them passes its
+    /// own `check_idempotency` read (which sees no record yet) before any
+    /// of them reaches `reconcile_idempotency`. All but one lose the
+    /// atomic `put_if_absent` claim there and must return the winner's
+    /// tenant instead of the one each created for itself along the way —
+    /// and, since `insert_tenant`/`issue_api_key` already ran before that
+    /// claim resolved, each loser must also unwind its own tenant and
+    /// revoke its own API key rather than leaving them stranded in the
+    /// stores. A blind `get`-then-`put` (instead of `put_if_absent`) would
+    /// let every caller's tenant stand; fixing only the returned bundle
+    /// without unwinding the losers' writes would still leak them.
+    #[test]
+    fn concurrent_claim_loser_converges_on_the_winning_tenant() {
+        let storage = Arc::new(InMemoryPersistence::new());
+        let tenant_store: Arc<dyn TenantStore> = storage.clone();
+        let project_store: Arc<dyn ProjectStore> = storage.clone();
+        let agent_store: Arc<dyn AgentStore> = storage.clone();
+        let agent_state_store: Arc<dyn AgentStateStore> = storage.clone();
+        let api_key_store: Arc<dyn ApiKeyStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
+        let certificate_store: Arc<dyn CertificateStore> = storage.clone();
+        let idempotency_store: Arc<dyn IdempotencyStore> = storage.clone();
+        let auth = Arc::new(AuthService::new(
+            tenant_store.clone(),
+            api_key_store,
+            audit_store.clone(),
+            b"secret".to_vec(),
+        ));
+        let provisioning = ProvisioningService::new(
+            tenant_store,
+            project_store,
+            agent_store,
+            agent_state_store,
+            auth,
+            audit_store,
+            CertificateAuthority::new(certificate_store),
+            idempotency_store,
+        );
+
+        const CALLERS: usize = 8;
+        let barrier = Arc::new(std::sync::Barrier::new(CALLERS));
+        let handles: Vec<_> = (0..CALLERS)
+            .map(|_| {
+                let provisioning = provisioning.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    let mut request = TenantCreateRequest::new("Example");
+                    request.idempotency_key = Some("shared-key".into());
+                    barrier.wait();
+                    provisioning.create_tenant_with_options(request)
+                })
+            })
+            .collect();
+        let bundles: Vec<TenantBootstrap> = handles
+            .into_iter()
+            .map(|h| h.join().unwrap().unwrap())
+            .collect();
+
+        let winning_tenant_id = bundles[0].tenant.id;
+        for bundle in &bundles {
+            assert_eq!(
+                bundle.tenant.id, winning_tenant_id,
+                "every caller must converge on the same tenant"
+            );
+        }
+
+        // Store-level check, not just the returned bundles: every loser's
+        // own tenant and API key must have been unwound, leaving exactly
+        // the one tenant (and its one live key) that actually won.
+        let tenants = storage.list_tenants().unwrap();
+        assert_eq!(
+            tenants.len(),
+            1,
+            "losers' tenants must be unwound, not left orphaned: {tenants:?}"
+        );
+        assert_eq!(tenants[0].id, winning_tenant_id);
+
+        let keys = storage.list_api_keys(winning_tenant_id).unwrap();
+        assert_eq!(
+            keys.len(),
+            1,
+            "losers' api keys must be unwound, not left live: {keys:?}"
+        );
+        assert!(!keys[0].revoked);
+    }
 }
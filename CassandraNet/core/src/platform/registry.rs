@@ -1,10 +1,12 @@
 use super::auth::AuthService;
+use super::ca::CertificateAuthority;
 use super::orchestration::OrchestrationEngine;
 use super::persistence::{
-    AgentStore, ApiKeyStore, InMemoryPersistence, ProjectStore, TaskStore, TenantStore,
-    WorkflowStore,
+    AgentStateStore, AgentStore, ApiKeyStore, AuditStore, CertificateStore, IdempotencyStore,
+    InMemoryPersistence, ProjectStore, TaskStore, TenantStore, WorkflowStore,
 };
 use super::provisioning::ProvisioningService;
+use cncommon::observability::InMemoryMetricsRegistry;
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
 
@@ -16,6 +18,8 @@ pub struct PlatformServices {
     auth: Arc<AuthService>,
     provisioning: Arc<ProvisioningService>,
     orchestration: Arc<OrchestrationEngine>,
+    audit: Arc<dyn AuditStore>,
+    metrics: InMemoryMetricsRegistry,
 }
 
 impl PlatformServices {
@@ -24,26 +28,39 @@ impl PlatformServices {
         let tenant_store: Arc<dyn TenantStore> = storage.clone();
         let project_store: Arc<dyn ProjectStore> = storage.clone();
         let agent_store: Arc<dyn AgentStore> = storage.clone();
+        let agent_state_store: Arc<dyn AgentStateStore> = storage.clone();
         let api_key_store: Arc<dyn ApiKeyStore> = storage.clone();
         let task_store: Arc<dyn TaskStore> = storage.clone();
         let workflow_store: Arc<dyn WorkflowStore> = storage.clone();
+        let audit_store: Arc<dyn AuditStore> = storage.clone();
+        let certificate_store: Arc<dyn CertificateStore> = storage.clone();
+        let idempotency_store: Arc<dyn IdempotencyStore> = storage.clone();
         let auth = Arc::new(AuthService::new(
             tenant_store.clone(),
             api_key_store,
+            audit_store.clone(),
             secret,
         ));
         let provisioning = Arc::new(ProvisioningService::new(
             tenant_store,
             project_store,
             agent_store,
+            agent_state_store,
             auth.clone(),
+            audit_store.clone(),
+            CertificateAuthority::new(certificate_store),
+            idempotency_store,
         ));
         let orchestration = Arc::new(OrchestrationEngine::new(task_store, workflow_store));
+        let metrics = InMemoryMetricsRegistry::new();
+        orchestration.set_metrics(metrics.clone());
         Arc::new(Self {
             storage,
             auth,
             provisioning,
             orchestration,
+            audit: audit_store,
+            metrics,
         })
     }
 
@@ -77,9 +94,17 @@ impl PlatformServices {
         self.orchestration.clone()
     }
 
+    pub fn audit(&self) -> Arc<dyn AuditStore> {
+        self.audit.clone()
+    }
+
     pub fn storage(&self) -> Arc<InMemoryPersistence> {
         self.storage.clone()
     }
+
+    pub fn metrics(&self) -> InMemoryMetricsRegistry {
+        self.metrics.clone()
+    }
 }
 
 #[cfg(test)]
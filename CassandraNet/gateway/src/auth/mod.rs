@@ -1,3 +1,5 @@
+pub mod sigv4;
+
 use anyhow::Result;
 use axum::http::HeaderMap;
 use serde_json::Value;
@@ -31,6 +33,29 @@ pub fn validate_jwt(headers: &HeaderMap) -> AuthStatus {
     AuthStatus::Deny
 }
 
+/// Resolve the caller's identity for per-credential features (rate limiting,
+/// audit logging) without re-deriving the full auth decision. Returns the raw
+/// API key on `x-api-key`, or `jwt:<sub>` for a validated bearer token;
+/// `None` means the caller should be keyed by some fallback (e.g. client IP).
+pub fn identity_from_headers(headers: &HeaderMap) -> Option<String> {
+    if let Some(val) = headers.get("x-api-key") {
+        if let Ok(key) = val.to_str() {
+            if !key.is_empty() {
+                return Some(key.to_string());
+            }
+        }
+    }
+    let value = headers.get("authorization")?;
+    let header = value.to_str().ok()?;
+    let token = header.strip_prefix("Bearer ")?;
+    if !hs256_validate(token).unwrap_or(false) {
+        return None;
+    }
+    let payload = decode_payload(token).ok()?;
+    let sub = payload.get("sub").and_then(Value::as_str)?;
+    Some(format!("jwt:{sub}"))
+}
+
 pub fn has_scope(headers: &HeaderMap, required_scope: &str) -> bool {
     if matches!(validate_api_key(headers), AuthStatus::Allow) {
         return true;
@@ -106,7 +131,94 @@ pub fn hs256_validate(token: &str) -> Result<bool> {
         && ConstantTimeEq::ct_eq(provided.as_slice(), sig.as_slice()).into())
 }
 
-fn decode_payload(token: &str) -> Result<Value> {
+/// Claims carried by an agent session JWT, minted by `RegisterAgent` and
+/// re-minted by `Heartbeat` when the current token is near expiry.
+#[derive(Debug, Clone)]
+pub struct AgentSessionClaims {
+    pub agent_id: String,
+    pub tenant_id: Option<String>,
+    pub project_id: Option<String>,
+    pub issued_at: i64,
+    pub expires_at: i64,
+}
+
+/// Signs an agent session token with the same HS256 scheme as
+/// `hs256_generate`, but with agent-specific claims and a caller-supplied
+/// TTL instead of the fixed one-hour default.
+pub fn generate_agent_session_token(
+    agent_id: &str,
+    tenant_id: Option<&str>,
+    project_id: Option<&str>,
+    ttl_seconds: i64,
+) -> Result<String> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use hmac::{Hmac, Mac};
+    use serde_json::json;
+    use sha2::Sha256;
+    type HmacSha256 = Hmac<Sha256>;
+    let secret = std::env::var("CASS_JWT_SECRET").unwrap_or_default();
+    if secret.is_empty() {
+        anyhow::bail!("CASS_JWT_SECRET not set");
+    }
+    let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+    let issued_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let exp = issued_at + ttl_seconds;
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&json!({
+        "sub": agent_id,
+        "tenant_id": tenant_id,
+        "project_id": project_id,
+        "iat": issued_at,
+        "exp": exp,
+    }))?);
+    let signing_input = format!("{header}.{payload}");
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())?;
+    mac.update(signing_input.as_bytes());
+    let sig = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    Ok(format!("{signing_input}.{sig}"))
+}
+
+/// Verifies signature and expiry, returning the claims on success. Callers
+/// needing to compare `agent_id` against the caller's `assigned_id` do so
+/// themselves; a forged or expired token never reaches that point.
+pub fn validate_agent_session_token(token: &str) -> Result<AgentSessionClaims> {
+    if !hs256_validate(token)? {
+        anyhow::bail!("invalid session token signature");
+    }
+    let payload = decode_payload(token)?;
+    let agent_id = payload
+        .get("sub")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("session token missing sub claim"))?
+        .to_string();
+    let expires_at = payload
+        .get("exp")
+        .and_then(Value::as_i64)
+        .ok_or_else(|| anyhow::anyhow!("session token missing exp claim"))?;
+    let issued_at = payload.get("iat").and_then(Value::as_i64).unwrap_or(0);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    if now >= expires_at {
+        anyhow::bail!("session token expired");
+    }
+    Ok(AgentSessionClaims {
+        agent_id,
+        tenant_id: payload
+            .get("tenant_id")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        project_id: payload
+            .get("project_id")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        issued_at,
+        expires_at,
+    })
+}
+
+pub(crate) fn decode_payload(token: &str) -> Result<Value> {
     use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
     let mut parts = token.split('.');
     let (_, payload_b64) = match (parts.next(), parts.next()) {
@@ -0,0 +1,336 @@
+//! Pluggable authentication backends sitting in front of `crate::auth`'s
+//! header-shape checks. `AppState::auth_providers` is consulted in order by
+//! `AppState::authenticate`/`AppState::resolve_scopes`: the first provider
+//! that recognizes the request's credentials decides the outcome, same as
+//! the directory-backed login chains of comparable services (API key first,
+//! then bearer token, then corporate directory, then a demo fallback for
+//! local development).
+//!
+//! **Wired into every `gateway::http` route that calls `ensure_scope`/
+//! `ensure_scope_or_sigv4`**, covering content, upload, lifecycle, agent,
+//! and tenant admin routes — so configuring `LdapAuthProvider` (or a custom
+//! `StaticDemoAuthProvider` table) actually gates those endpoints, not just
+//! `/admin/telemetry/metrics`.
+//!
+//! Still not consulted: `grant.rs`'s `issue_content_grant` (a sync helper
+//! with no `AppState` access, checked directly against `crate::auth::has_scope`
+//! before a grant token is signed) and the gRPC handlers in `grpc.rs`/
+//! `metrics.rs`, which gate through the free functions in `crate::auth`
+//! (`has_scope`/`validate_api_key`/`validate_jwt`) since they predate this
+//! chain and sit on a hot path that doesn't need to consult a directory
+//! server. Wiring either over means threading `&AppState` to where they're
+//! called and auditing what gets passed to `resolve_scopes`, since
+//! [`AuthProvider::resolve_scopes`]'s contract is not the same as
+//! `crate::auth::has_scope`'s (see its doc comment below).
+//!
+//! This module doesn't replace `crate::auth`'s free functions — the hot-path
+//! middleware in `metrics.rs`/`rate_limit.rs` still calls those directly,
+//! since they're synchronous and don't need to consult a directory server.
+//! `AuthProvider` exists for the parts of the stack (route handlers wired
+//! through `AppState`) that want the full, composable chain instead.
+
+use crate::auth::{self, AuthStatus};
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Sentinel scope returned by [`AuthProvider::resolve_scopes`] to mean
+/// "this credential bypasses scope checks entirely", matching
+/// `crate::auth::has_scope`'s historical treatment of a valid `x-api-key`.
+/// Kept as an explicit, checkable value rather than overloading an empty
+/// `Vec` for it — a credential that legitimately carries no scopes (e.g. an
+/// agent session JWT, which has no `scope`/`scopes` claim at all) must
+/// resolve to "no scopes granted", not "unrestricted access".
+pub const UNRESTRICTED_SCOPE: &str = "*";
+
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Short identifier used in logs/diagnostics, e.g. `"api-key"`, `"ldap"`.
+    fn name(&self) -> &str;
+
+    /// Whether this provider recognizes and accepts the credentials on
+    /// `headers`. Providers that don't recognize the credential shape they
+    /// look for (no `x-api-key`, no matching `Authorization` scheme) should
+    /// return `AuthStatus::Deny` so the chain falls through to the next
+    /// provider rather than asserting a verdict on someone else's header.
+    async fn authenticate(&self, headers: &HeaderMap) -> AuthStatus;
+
+    /// Scopes granted to the caller identified by `headers`, valid only
+    /// after `authenticate` returned `Allow` for the same headers. An empty
+    /// `Vec` means exactly "no scopes granted" — a caller checking this
+    /// result must reject access unless the required scope is present, or
+    /// unless the returned `Vec` contains [`UNRESTRICTED_SCOPE`].
+    async fn resolve_scopes(&self, headers: &HeaderMap) -> Vec<String>;
+}
+
+/// Wraps the existing static `x-api-key` check. A valid key is treated as
+/// unrestricted (matches `crate::auth::has_scope`'s historical behavior),
+/// signaled via [`UNRESTRICTED_SCOPE`] rather than an empty `Vec`.
+pub struct ApiKeyAuthProvider;
+
+#[async_trait]
+impl AuthProvider for ApiKeyAuthProvider {
+    fn name(&self) -> &str {
+        "api-key"
+    }
+
+    async fn authenticate(&self, headers: &HeaderMap) -> AuthStatus {
+        auth::validate_api_key(headers)
+    }
+
+    async fn resolve_scopes(&self, _headers: &HeaderMap) -> Vec<String> {
+        vec![UNRESTRICTED_SCOPE.to_string()]
+    }
+}
+
+/// Wraps the existing HS256 bearer-token check, reading the `scope`/`scopes`
+/// claim for `resolve_scopes`.
+pub struct JwtAuthProvider;
+
+#[async_trait]
+impl AuthProvider for JwtAuthProvider {
+    fn name(&self) -> &str {
+        "jwt"
+    }
+
+    async fn authenticate(&self, headers: &HeaderMap) -> AuthStatus {
+        auth::validate_jwt(headers)
+    }
+
+    async fn resolve_scopes(&self, headers: &HeaderMap) -> Vec<String> {
+        bearer_token(headers)
+            .and_then(|token| auth::decode_payload(token).ok())
+            .map(|payload| scopes_from_payload(&payload))
+            .unwrap_or_default()
+    }
+}
+
+/// Static username/password table for local development, configured
+/// in-process rather than read from a directory or signed token. Credentials
+/// arrive the same way an LDAP bind would — `Authorization: Basic
+/// base64(username:password)` — so swapping this provider for
+/// [`LdapAuthProvider`] in a deployed environment doesn't change how clients
+/// authenticate.
+#[derive(Debug, Clone)]
+pub struct DemoCredential {
+    pub password: String,
+    pub scopes: Vec<String>,
+}
+
+pub struct StaticDemoAuthProvider {
+    users: HashMap<String, DemoCredential>,
+}
+
+impl StaticDemoAuthProvider {
+    pub fn new(users: HashMap<String, DemoCredential>) -> Self {
+        Self { users }
+    }
+
+    /// Reads `CASS_DEMO_USERS` as `user:password:scope1,scope2;user2:...`, the
+    /// same flat env-var-config style `CASS_JWT_SECRET` already uses for
+    /// local/dev configuration. Returns an empty table (and therefore a
+    /// provider that denies everything) when unset.
+    pub fn from_env() -> Self {
+        let raw = std::env::var("CASS_DEMO_USERS").unwrap_or_default();
+        let mut users = HashMap::new();
+        for entry in raw.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let mut parts = entry.splitn(3, ':');
+            let (Some(username), Some(password)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let scopes = parts
+                .next()
+                .map(|s| s.split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            users.insert(
+                username.to_string(),
+                DemoCredential {
+                    password: password.to_string(),
+                    scopes,
+                },
+            );
+        }
+        Self::new(users)
+    }
+}
+
+#[async_trait]
+impl AuthProvider for StaticDemoAuthProvider {
+    fn name(&self) -> &str {
+        "demo-static"
+    }
+
+    async fn authenticate(&self, headers: &HeaderMap) -> AuthStatus {
+        match basic_auth_credentials(headers) {
+            Some((username, password)) => match self.users.get(&username) {
+                Some(cred) if cred.password == password => AuthStatus::Allow,
+                _ => AuthStatus::Deny,
+            },
+            None => AuthStatus::Deny,
+        }
+    }
+
+    async fn resolve_scopes(&self, headers: &HeaderMap) -> Vec<String> {
+        basic_auth_credentials(headers)
+            .and_then(|(username, _)| self.users.get(&username))
+            .map(|cred| cred.scopes.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Binds against a directory server to authenticate `Authorization: Basic`
+/// credentials, mapping the caller's group memberships to scopes via
+/// `group_scope_map`. Gated behind `feature = "ldap"` the same way the
+/// Postgres-backed stores are gated behind `feature = "db"`: most
+/// deployments don't run a directory, and the `ldap3` dependency it pulls in
+/// isn't worth carrying by default.
+#[cfg(feature = "ldap")]
+pub struct LdapConfig {
+    /// e.g. `"ldap://directory.internal:389"`.
+    pub url: String,
+    /// Bind DN with a `{username}` placeholder, e.g.
+    /// `"uid={username},ou=people,dc=example,dc=com"`.
+    pub bind_dn_template: String,
+    /// Base DN to search under for the bound user's group memberships, e.g.
+    /// `"ou=groups,dc=example,dc=com"`.
+    pub search_base: String,
+    /// Directory group CN to granted scope, e.g. `{"cassandra-admins":
+    /// "admin"}`.
+    pub group_scope_map: HashMap<String, String>,
+}
+
+#[cfg(feature = "ldap")]
+pub struct LdapAuthProvider {
+    config: LdapConfig,
+}
+
+#[cfg(feature = "ldap")]
+impl LdapAuthProvider {
+    pub fn new(config: LdapConfig) -> Self {
+        Self { config }
+    }
+
+    fn bind_dn(&self, username: &str) -> String {
+        self.config.bind_dn_template.replace("{username}", username)
+    }
+
+    /// Binds as `username`/`password` and, on success, looks up the groups
+    /// `username` belongs to under `search_base`. Re-binds rather than
+    /// caching a connection across calls — this runs once per request, not
+    /// on a hot path, so the extra round trip isn't worth the complexity of
+    /// pooling directory connections here.
+    async fn bind_and_lookup_groups(&self, username: &str, password: &str) -> Option<Vec<String>> {
+        let (conn, mut ldap) = ldap3::LdapConnAsync::new(&self.config.url).await.ok()?;
+        ldap3::drive!(conn);
+        let dn = self.bind_dn(username);
+        let bind = ldap.simple_bind(&dn, password).await.ok()?;
+        if bind.success().is_err() {
+            return None;
+        }
+        let (results, _) = ldap
+            .search(
+                &self.config.search_base,
+                ldap3::Scope::Subtree,
+                &format!("(&(objectClass=groupOfNames)(member={dn}))"),
+                vec!["cn"],
+            )
+            .await
+            .ok()?
+            .success()
+            .ok()?;
+        let groups = results
+            .into_iter()
+            .filter_map(|entry| {
+                ldap3::SearchEntry::construct(entry)
+                    .attrs
+                    .get("cn")
+                    .and_then(|values| values.first())
+                    .cloned()
+            })
+            .collect();
+        let _ = ldap.unbind().await;
+        Some(groups)
+    }
+}
+
+#[cfg(feature = "ldap")]
+#[async_trait]
+impl AuthProvider for LdapAuthProvider {
+    fn name(&self) -> &str {
+        "ldap"
+    }
+
+    async fn authenticate(&self, headers: &HeaderMap) -> AuthStatus {
+        let Some((username, password)) = basic_auth_credentials(headers) else {
+            return AuthStatus::Deny;
+        };
+        match self.bind_and_lookup_groups(&username, &password).await {
+            Some(_) => AuthStatus::Allow,
+            None => AuthStatus::Deny,
+        }
+    }
+
+    async fn resolve_scopes(&self, headers: &HeaderMap) -> Vec<String> {
+        let Some((username, password)) = basic_auth_credentials(headers) else {
+            return Vec::new();
+        };
+        let Some(groups) = self.bind_and_lookup_groups(&username, &password).await else {
+            return Vec::new();
+        };
+        groups
+            .into_iter()
+            .filter_map(|group| self.config.group_scope_map.get(&group).cloned())
+            .collect()
+    }
+}
+
+/// Decodes `Authorization: Basic base64(username:password)`, used by both
+/// [`StaticDemoAuthProvider`] and [`LdapAuthProvider`] since they authenticate
+/// the same way from the caller's perspective.
+fn basic_auth_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    let value = headers.get("authorization")?;
+    let header = value.to_str().ok()?;
+    let encoded = header.strip_prefix("Basic ")?;
+    let decoded = STANDARD.decode(encoded).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("authorization")?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+fn scopes_from_payload(payload: &Value) -> Vec<String> {
+    if let Some(scopes) = payload.get("scopes").and_then(|v| v.as_array()) {
+        return scopes
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+    }
+    payload
+        .get("scope")
+        .and_then(Value::as_str)
+        .map(|s| s.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Default provider chain: static API key, then HS256 JWT, then the demo
+/// table (empty, and therefore a no-op, unless `CASS_DEMO_USERS` is set).
+/// Deployments that need LDAP construct their own chain with
+/// [`LdapAuthProvider`] added — directory connection details aren't
+/// something this crate can default sensibly.
+pub fn default_providers() -> Vec<std::sync::Arc<dyn AuthProvider>> {
+    vec![
+        std::sync::Arc::new(ApiKeyAuthProvider),
+        std::sync::Arc::new(JwtAuthProvider),
+        std::sync::Arc::new(StaticDemoAuthProvider::from_env()),
+    ]
+}
@@ -21,4 +21,27 @@ pub enum CliCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Inspect or apply database schema migrations without starting the
+    /// HTTP/gRPC servers
+    #[cfg(feature = "db")]
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+}
+
+#[cfg(feature = "db")]
+#[derive(Subcommand, Debug)]
+pub enum MigrateAction {
+    /// List every migration and whether it's been applied
+    Status,
+    /// Apply all pending migrations
+    Up,
+    /// Revert the most recently applied migrations
+    Down {
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+    /// Revert the last migration, then reapply it
+    Redo,
 }
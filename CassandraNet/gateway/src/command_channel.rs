@@ -0,0 +1,50 @@
+//! Server-push command delivery for `AgentControl::OpenCommandStream`.
+//!
+//! `RegisterAgent`/`Heartbeat` are purely reactive — the server can only
+//! piggyback a boolean on `HeartbeatResponse` and wait for the agent's next
+//! poll. `CommandChannelRegistry` tracks one open sender per connected agent
+//! (keyed by `assigned_id`) so a scheduled `AgentCommand` can be pushed the
+//! moment it's ready instead of waiting; `send` drops the sender and reports
+//! failure the moment delivery fails, so callers fall back to
+//! heartbeat-delivered work without a dead entry lingering forever.
+
+use cnproto::AgentCommand;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tokio::sync::mpsc;
+use tonic::Status as GrpcStatus;
+
+pub type CommandResult = Result<AgentCommand, GrpcStatus>;
+
+#[derive(Default, Clone)]
+pub struct CommandChannelRegistry(Arc<RwLock<HashMap<String, mpsc::Sender<CommandResult>>>>);
+
+impl CommandChannelRegistry {
+    pub fn connect(&self, assigned_id: String, sender: mpsc::Sender<CommandResult>) {
+        self.0.write().unwrap().insert(assigned_id, sender);
+    }
+
+    pub fn disconnect(&self, assigned_id: &str) {
+        self.0.write().unwrap().remove(assigned_id);
+    }
+
+    /// Best-effort push. Returns `false` (and drops the stale entry) if the
+    /// agent has no open stream or the stream's buffer is gone.
+    pub fn send(&self, assigned_id: &str, command: AgentCommand) -> bool {
+        let sender = self.0.read().unwrap().get(assigned_id).cloned();
+        let Some(sender) = sender else {
+            return false;
+        };
+        match sender.try_send(Ok(command)) {
+            Ok(()) => true,
+            Err(_) => {
+                self.disconnect(assigned_id);
+                false
+            }
+        }
+    }
+
+    pub fn is_connected(&self, assigned_id: &str) -> bool {
+        self.0.read().unwrap().contains_key(assigned_id)
+    }
+}
@@ -0,0 +1,237 @@
+//! Negotiated response compression for JSON list/metadata endpoints.
+//!
+//! Applied globally like [`crate::metrics::MetricsLayer`]; the size and
+//! content-type checks mean small or non-JSON responses (health, version,
+//! upload session creation, ...) pass through untouched in practice, while
+//! the large `list_agents`/`list_content_metadata`/`list_recent_logs`
+//! bodies this was built for get compressed. Disable entirely with
+//! `CASS_HTTP_COMPRESSION_DISABLED=1` for debugging; tune the minimum body
+//! size with `CASS_HTTP_COMPRESSION_MIN_BYTES` (default 512).
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{
+        header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, VARY},
+        HeaderValue, Request,
+    },
+    response::Response,
+};
+use std::io::Write;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Cap on how much of a response body we'll buffer to compress; larger
+/// bodies pass through uncompressed rather than risk unbounded memory use.
+const MAX_COMPRESSIBLE_BODY_BYTES: usize = 16 * 1024 * 1024;
+const DEFAULT_MIN_COMPRESSIBLE_BYTES: usize = 512;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+}
+
+fn compression_disabled() -> bool {
+    matches!(
+        std::env::var("CASS_HTTP_COMPRESSION_DISABLED").as_deref(),
+        Ok("1") | Ok("true")
+    )
+}
+
+fn min_compressible_bytes() -> usize {
+    std::env::var("CASS_HTTP_COMPRESSION_MIN_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MIN_COMPRESSIBLE_BYTES)
+}
+
+/// Parse an `Accept-Encoding` header, preferring brotli over gzip when the
+/// client offers both with a nonzero quality value.
+fn negotiate_encoding(header: &str) -> Option<Encoding> {
+    let mut accepts_gzip = false;
+    let mut accepts_brotli = false;
+    for entry in header.split(',') {
+        let mut parts = entry.split(';');
+        let name = parts.next()?.trim().to_ascii_lowercase();
+        let q: f32 = parts
+            .next()
+            .and_then(|p| p.trim().strip_prefix("q="))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1.0);
+        if q <= 0.0 {
+            continue;
+        }
+        match name.as_str() {
+            "gzip" => accepts_gzip = true,
+            "br" => accepts_brotli = true,
+            "*" => {
+                accepts_gzip = true;
+                accepts_brotli = true;
+            }
+            _ => {}
+        }
+    }
+    if accepts_brotli {
+        Some(Encoding::Brotli)
+    } else if accepts_gzip {
+        Some(Encoding::Gzip)
+    } else {
+        None
+    }
+}
+
+fn is_compressible_content_type(content_type: &str) -> bool {
+    let content_type = content_type.split(';').next().unwrap_or("").trim();
+    content_type == "application/json" || content_type.starts_with("text/")
+}
+
+fn compress_gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    use flate2::{write::GzEncoder, Compression};
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+fn compress_brotli(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let params = brotli::enc::BrotliEncoderParams::default();
+    brotli::BrotliCompress(&mut std::io::Cursor::new(bytes), &mut out, &params)?;
+    Ok(out)
+}
+
+async fn maybe_compress(response: Response, encoding: Option<Encoding>) -> Response {
+    let Some(encoding) = encoding else {
+        return response;
+    };
+    if response.headers().contains_key(CONTENT_ENCODING) {
+        return response;
+    }
+    let is_compressible = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(is_compressible_content_type)
+        .unwrap_or(false);
+    if !is_compressible {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_COMPRESSIBLE_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    parts
+        .headers
+        .insert(VARY, HeaderValue::from_static("accept-encoding"));
+    if bytes.len() < min_compressible_bytes() {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    let compressed = match encoding {
+        Encoding::Brotli => compress_brotli(&bytes),
+        Encoding::Gzip => compress_gzip(&bytes),
+    };
+    let Ok(compressed) = compressed else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    parts.headers.insert(
+        CONTENT_ENCODING,
+        HeaderValue::from_static(encoding.as_str()),
+    );
+    parts.headers.insert(
+        CONTENT_LENGTH,
+        HeaderValue::from_str(&compressed.len().to_string()).expect("ascii content-length value"),
+    );
+    Response::from_parts(parts, Body::from(compressed))
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct CompressionLayer;
+
+impl<S> Layer<S> for CompressionLayer {
+    type Service = CompressionService<S>;
+    fn layer(&self, inner: S) -> Self::Service {
+        CompressionService { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct CompressionService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for CompressionService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let encoding = if compression_disabled() {
+            None
+        } else {
+            req.headers()
+                .get(ACCEPT_ENCODING)
+                .and_then(|v| v.to_str().ok())
+                .and_then(negotiate_encoding)
+        };
+        let mut inner = self.inner.clone();
+        let fut = inner.call(req);
+        Box::pin(async move {
+            let response = fut.await?;
+            Ok(maybe_compress(response, encoding).await)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_brotli_when_both_offered() {
+        assert_eq!(
+            negotiate_encoding("gzip, br"),
+            Some(Encoding::Brotli)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_gzip_alone() {
+        assert_eq!(negotiate_encoding("gzip"), Some(Encoding::Gzip));
+    }
+
+    #[test]
+    fn ignores_zero_quality_encodings() {
+        assert_eq!(negotiate_encoding("br;q=0, gzip"), Some(Encoding::Gzip));
+    }
+
+    #[test]
+    fn returns_none_for_unsupported_encodings_only() {
+        assert_eq!(negotiate_encoding("identity"), None);
+    }
+
+    #[test]
+    fn gzip_round_trips_through_flate2() {
+        let compressed = compress_gzip(b"hello world").unwrap();
+        assert_ne!(compressed, b"hello world");
+        assert!(!compressed.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! Per-tenant CORS handling, layered outermost in `main.rs` so a preflight
+//! is answered before it reaches rate limiting, metrics, or the router.
+//!
+//! Unlike the blanket `tower_http::cors::CorsLayer` this replaces
+//! (allow-any-origin, fixed method list), this layer looks up the request's
+//! tenant from the path (`/tenants/:tenant_id/...`, the shape every routed
+//! endpoint uses) and matches `Origin`/`Access-Control-Request-Method`
+//! against that tenant's
+//! `TenantSettings.cors_rules`. A request for a tenant with no matching rule
+//! gets no `Access-Control-*` headers at all, so the browser enforces the
+//! same-origin default rather than us emitting a permissive fallback.
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderValue, Method, Request, StatusCode},
+};
+use cncore::platform::persistence::TenantStore;
+use cncore::platform::{CorsRule, TenantId};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct CorsLayer {
+    tenant_store: Arc<dyn TenantStore>,
+}
+
+impl CorsLayer {
+    pub fn new(tenant_store: Arc<dyn TenantStore>) -> Self {
+        Self { tenant_store }
+    }
+}
+
+impl<S> Layer<S> for CorsLayer {
+    type Service = CorsService<S>;
+    fn layer(&self, inner: S) -> Self::Service {
+        CorsService {
+            inner,
+            tenant_store: self.tenant_store.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CorsService<S> {
+    inner: S,
+    tenant_store: Arc<dyn TenantStore>,
+}
+
+impl<S> CorsService<S> {
+    /// The tenant a request is for, parsed from the leading
+    /// `/tenants/:tenant_id/` path segment every routed endpoint shares.
+    /// `None` for routes with no tenant in scope (`/health`, `/version`,
+    /// swagger, ...), which never carry a CORS policy to enforce.
+    fn tenant_id_from_path(path: &str) -> Option<TenantId> {
+        let mut segments = path.trim_start_matches('/').split('/');
+        if segments.next() != Some("tenants") {
+            return None;
+        }
+        segments.next().and_then(|id| Uuid::parse_str(id).ok())
+    }
+
+    /// First rule in the tenant's `cors_rules` whose `allowed_origins`
+    /// matches `origin`, optionally also requiring `method` be allowed.
+    fn matching_rule(&self, tenant_id: TenantId, origin: &str, method: Option<&str>) -> Option<CorsRule> {
+        let settings = self.tenant_store.get_tenant(tenant_id).ok().flatten()?.settings;
+        settings
+            .cors_rules
+            .into_iter()
+            .find(|rule| rule.matches_origin(origin) && method.map_or(true, |m| rule.matches_method(m)))
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: &str) -> Option<&str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+impl<S> Service<Request<Body>> for CorsService<S>
+where
+    S: Service<Request<Body>, Response = axum::response::Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let tenant_id = Self::tenant_id_from_path(req.uri().path());
+        let origin = header_str(req.headers(), "origin").map(str::to_string);
+
+        let Some((tenant_id, origin)) = tenant_id.zip(origin) else {
+            let mut inner = self.inner.clone();
+            return Box::pin(async move { inner.call(req).await });
+        };
+
+        if req.method() == Method::OPTIONS {
+            if let Some(requested_method) = header_str(req.headers(), "access-control-request-method")
+                .map(str::to_string)
+            {
+                return match self.matching_rule(tenant_id, &origin, Some(&requested_method)) {
+                    Some(rule) => Box::pin(async move { Ok(preflight_response(&origin, &rule)) }),
+                    None => {
+                        let mut inner = self.inner.clone();
+                        Box::pin(async move { inner.call(req).await })
+                    }
+                };
+            }
+            let mut inner = self.inner.clone();
+            return Box::pin(async move { inner.call(req).await });
+        }
+
+        let rule = self.matching_rule(tenant_id, &origin, Some(req.method().as_str()));
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            let mut response = inner.call(req).await?;
+            if let Some(rule) = rule {
+                apply_actual_request_headers(response.headers_mut(), &origin, &rule);
+            }
+            Ok(response)
+        })
+    }
+}
+
+fn preflight_response(origin: &str, rule: &CorsRule) -> axum::response::Response {
+    let mut response = axum::response::Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .unwrap();
+    apply_actual_request_headers(response.headers_mut(), origin, rule);
+    let headers = response.headers_mut();
+    if !rule.allowed_methods.is_empty() {
+        if let Ok(value) = HeaderValue::from_str(&rule.allowed_methods.join(", ")) {
+            headers.insert("access-control-allow-methods", value);
+        }
+    }
+    if !rule.allowed_headers.is_empty() {
+        if let Ok(value) = HeaderValue::from_str(&rule.allowed_headers.join(", ")) {
+            headers.insert("access-control-allow-headers", value);
+        }
+    }
+    if let Some(max_age) = rule.max_age_seconds {
+        if let Ok(value) = HeaderValue::from_str(&max_age.to_string()) {
+            headers.insert("access-control-max-age", value);
+        }
+    }
+    response
+}
+
+/// Headers shared by preflight and actual-request responses: the allowed
+/// origin (echoed rather than `*` since a credentialed request can't use a
+/// wildcard) and whatever the rule exposes to browser JS.
+fn apply_actual_request_headers(headers: &mut HeaderMap, origin: &str, rule: &CorsRule) {
+    if let Ok(value) = HeaderValue::from_str(origin) {
+        headers.insert("access-control-allow-origin", value);
+    }
+    headers.insert("vary", HeaderValue::from_static("origin"));
+    if !rule.expose_headers.is_empty() {
+        if let Ok(value) = HeaderValue::from_str(&rule.expose_headers.join(", ")) {
+            headers.insert("access-control-expose-headers", value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tenant_id_from_path_parses_the_leading_segment() {
+        let id = Uuid::new_v4();
+        let path = format!("/tenants/{id}/projects/x/content");
+        assert_eq!(CorsService::<()>::tenant_id_from_path(&path), Some(id));
+    }
+
+    #[test]
+    fn tenant_id_from_path_rejects_non_tenant_routes() {
+        assert_eq!(CorsService::<()>::tenant_id_from_path("/health"), None);
+    }
+}
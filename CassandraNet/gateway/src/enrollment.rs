@@ -0,0 +1,282 @@
+//! Short Authentication String (SAS) handshake for agent enrollment.
+//!
+//! `AgentRegistry::upsert` historically trusted whatever `id`/`hostname` a
+//! caller reported, so a rogue process could impersonate or overwrite
+//! another agent's summary. When a `RegisterAgent` call carries an ephemeral
+//! X25519 public key, `SasEnrollmentStore::begin` starts a handshake: the
+//! control plane generates its own ephemeral keypair, derives a shared
+//! secret via ECDH, and runs it through HKDF-SHA256 over a transcript that
+//! commits to both public keys plus the agent id, producing a short decimal
+//! code (and an emoji rendering of the same bytes) for a human to compare
+//! out of band. Only after an operator confirms the code via `confirm` does
+//! the agent's `lifecycle_status` move to `"verified"`; until then (or once
+//! the pending handshake expires ungranted), [`gate`] rejects further
+//! heartbeats/re-registration for that agent id.
+//!
+//! Re-enrolling an id that's already verified under a *different* key is
+//! handled as its own case ([`EnrollmentState::VerifiedPendingReverify`]):
+//! `begin` never overwrites an existing [`EnrollmentState::Verified`] entry
+//! outright — a syntactically-valid key is not proof of anything, and
+//! trusting it unconditionally is exactly what let an attacker who merely
+//! knew a victim's `node_id` take it over. Instead the new key's challenge
+//! sits alongside the still-verified one, so the verified identity's
+//! heartbeats (and its existing session token) keep working untouched until
+//! an operator confirms the new key out of band. Only once `verified_key`
+//! reports the *caller's presented* key as the verified one — which can only
+//! become true after that confirmation — does `register_agent` apply the
+//! registry overwrite and mint a session token.
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tonic::Status as GrpcStatus;
+use x25519_dalek::{EphemeralSecret, PublicKey};
+
+/// Per-agent enrollment state. Kept separate from `AgentSummary::lifecycle_status`
+/// (which heartbeats freely overwrite with `"active"`) so verification,
+/// once granted, survives every later heartbeat.
+#[derive(Clone, Debug)]
+enum EnrollmentState {
+    Pending(PendingEnrollment),
+    Verified {
+        public_key: [u8; 32],
+    },
+    /// An id verified under `public_key` that has since presented a
+    /// *different* key and is waiting on operator confirmation of it. The
+    /// original `public_key` stays authoritative (so `gate` keeps accepting
+    /// its holder's heartbeats) until `confirm` promotes `reverify`.
+    VerifiedPendingReverify {
+        public_key: [u8; 32],
+        reverify: PendingEnrollment,
+    },
+}
+
+/// How long a pending handshake stays valid without operator confirmation.
+/// Heartbeats for the agent id keep being accepted while a handshake is
+/// pending (the agent needs time to reach an operator), but once this
+/// elapses without a `confirm`, the id is rejected until it re-enrolls.
+const ENROLLMENT_GRACE: Duration = Duration::from_secs(10 * 60);
+
+/// Emoji alphabet the SAS code is also rendered against, mirroring the
+/// decimal/emoji dual display used by interactive device-verification
+/// flows. 64 entries so each maps to a clean 6-bit slice of the HKDF output.
+const SAS_EMOJI: [&str; 64] = [
+    "🐶", "🐱", "🐭", "🐹", "🐰", "🦊", "🐻", "🐼", "🐨", "🐯", "🦁", "🐮", "🐷", "🐸", "🐵",
+    "🐔", "🐧", "🐦", "🐤", "🦆", "🦉", "🦇", "🐺", "🐗", "🐴", "🦄", "🐝", "🐛", "🦋", "🐌",
+    "🐞", "🐢", "🐍", "🦎", "🐙", "🦑", "🦀", "🐡", "🐠", "🐟", "🐬", "🐳", "🐋", "🦈", "🐊",
+    "🐅", "🐆", "🦓", "🦍", "🐘", "🦏", "🐪", "🐫", "🦒", "🐃", "🐂", "🐄", "🐎", "🐖", "🐑",
+    "🐐", "🦌", "🐕", "🐩",
+];
+
+#[derive(Clone, Debug)]
+pub struct PendingEnrollment {
+    pub agent_id: String,
+    /// The key presented by the caller trying to (re-)enroll. Compared
+    /// against a `Verified`/`VerifiedPendingReverify` entry's `public_key` to
+    /// decide whether a later `register_agent` call may apply without
+    /// needing a fresh `confirm`.
+    pub agent_public_key: [u8; 32],
+    pub control_public_key: [u8; 32],
+    pub sas_code: String,
+    pub sas_emoji: Vec<&'static str>,
+    pub created_at: Instant,
+    pub expires_at: Instant,
+}
+
+#[derive(Default, Clone)]
+pub struct SasEnrollmentStore(Arc<RwLock<HashMap<String, EnrollmentState>>>);
+
+impl SasEnrollmentStore {
+    /// Derives a fresh control-plane keypair and SAS code against
+    /// `agent_public_key` for `agent_id`. Returns `None` if
+    /// `agent_public_key` isn't a valid X25519 point.
+    ///
+    /// How the result is stored depends on what's already there: an id with
+    /// no entry, or one still mid-handshake, gets this pending challenge
+    /// directly (clearing any unconfirmed prior attempt — re-enrolling means
+    /// proving identity again). An id that's already `Verified` under a
+    /// *different* key does **not** get overwritten; the challenge is
+    /// parked in `VerifiedPendingReverify` alongside the still-verified key
+    /// instead, so the existing identity's heartbeats are unaffected unless
+    /// and until an operator calls `confirm`. An id presenting the *same*
+    /// key it's already verified under is left untouched entirely — no new
+    /// challenge is needed since `verified_key` already reports that key as
+    /// current.
+    pub fn begin(&self, agent_id: &str, agent_public_key: &[u8]) -> Option<PendingEnrollment> {
+        let agent_public_key: [u8; 32] = agent_public_key.try_into().ok()?;
+        let agent_public = PublicKey::from(agent_public_key);
+        let control_secret = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let control_public = PublicKey::from(&control_secret);
+        let shared = control_secret.diffie_hellman(&agent_public);
+
+        let mut transcript = Vec::with_capacity(32 + 32 + agent_id.len());
+        transcript.extend_from_slice(agent_public.as_bytes());
+        transcript.extend_from_slice(control_public.as_bytes());
+        transcript.extend_from_slice(agent_id.as_bytes());
+        let hk = Hkdf::<Sha256>::new(None, shared.as_bytes());
+        let mut okm = [0u8; 8];
+        hk.expand(&transcript, &mut okm)
+            .expect("8 bytes is a valid HKDF-SHA256 output length");
+
+        let sas_code = sas_decimal_code(&okm);
+        let sas_emoji = sas_emoji_code(&okm);
+        let now = Instant::now();
+        let pending = PendingEnrollment {
+            agent_id: agent_id.to_string(),
+            agent_public_key,
+            control_public_key: *control_public.as_bytes(),
+            sas_code,
+            sas_emoji,
+            created_at: now,
+            expires_at: now + ENROLLMENT_GRACE,
+        };
+
+        let mut guard = self.0.write().expect("enrollment store lock poisoned");
+        match guard.get(agent_id) {
+            Some(EnrollmentState::Verified { public_key }) if *public_key == agent_public_key => {
+                // Already verified under exactly this key — nothing to
+                // challenge; leave the entry as-is.
+            }
+            Some(EnrollmentState::Verified { public_key }) => {
+                let public_key = *public_key;
+                guard.insert(
+                    agent_id.to_string(),
+                    EnrollmentState::VerifiedPendingReverify {
+                        public_key,
+                        reverify: pending.clone(),
+                    },
+                );
+            }
+            Some(EnrollmentState::VerifiedPendingReverify { public_key, .. }) => {
+                let public_key = *public_key;
+                guard.insert(
+                    agent_id.to_string(),
+                    EnrollmentState::VerifiedPendingReverify {
+                        public_key,
+                        reverify: pending.clone(),
+                    },
+                );
+            }
+            _ => {
+                guard.insert(agent_id.to_string(), EnrollmentState::Pending(pending.clone()));
+            }
+        }
+        Some(pending)
+    }
+
+    /// Pending handshake for `agent_id` awaiting operator confirmation, if
+    /// one exists and hasn't expired — whether this is a first-time
+    /// handshake or a reverification of an already-verified id.
+    pub fn get(&self, agent_id: &str) -> Option<PendingEnrollment> {
+        match self.0.read().expect("enrollment store lock poisoned").get(agent_id) {
+            Some(EnrollmentState::Pending(pending)) if Instant::now() < pending.expires_at => {
+                Some(pending.clone())
+            }
+            Some(EnrollmentState::VerifiedPendingReverify { reverify, .. })
+                if Instant::now() < reverify.expires_at =>
+            {
+                Some(reverify.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Operator confirms the displayed code matches; promotes the pending
+    /// handshake (first-time or reverification) to verified under its
+    /// `agent_public_key` so it survives every later heartbeat and
+    /// `register_agent` will apply for a caller presenting that key. Returns
+    /// `false` if there was no (unexpired) handshake to confirm.
+    pub fn confirm(&self, agent_id: &str) -> bool {
+        let mut guard = self.0.write().expect("enrollment store lock poisoned");
+        match guard.get(agent_id) {
+            Some(EnrollmentState::Pending(pending)) if Instant::now() < pending.expires_at => {
+                let public_key = pending.agent_public_key;
+                guard.insert(agent_id.to_string(), EnrollmentState::Verified { public_key });
+                true
+            }
+            Some(EnrollmentState::VerifiedPendingReverify { reverify, .. })
+                if Instant::now() < reverify.expires_at =>
+            {
+                let public_key = reverify.agent_public_key;
+                guard.insert(agent_id.to_string(), EnrollmentState::Verified { public_key });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The X25519 public key `agent_id` is currently verified under, if any.
+    /// `register_agent` only applies a registry overwrite and mints a
+    /// session token when the caller's presented key matches this — i.e.
+    /// once an operator has actually confirmed that key via `confirm`, not
+    /// merely because the caller supplied *some* syntactically-valid key.
+    pub fn verified_key(&self, agent_id: &str) -> Option<[u8; 32]> {
+        match self.0.read().expect("enrollment store lock poisoned").get(agent_id) {
+            Some(EnrollmentState::Verified { public_key })
+            | Some(EnrollmentState::VerifiedPendingReverify { public_key, .. }) => Some(*public_key),
+            _ => None,
+        }
+    }
+
+    /// Whether `agent_id` currently holds a confirmed handshake under any
+    /// key. Re-registering a verified id without proving a fresh handshake
+    /// would let an attacker who merely knows the id overwrite its
+    /// `AgentSummary` and mint themselves a session token for it, so
+    /// callers that persist identity across registrations (see
+    /// `register_agent`) must check this before accepting a re-registration
+    /// that carries no `x25519_public_key`.
+    pub fn is_verified(&self, agent_id: &str) -> bool {
+        self.verified_key(agent_id).is_some()
+    }
+
+    /// Whether `agent_id` should be let through to `AgentRegistry::upsert`.
+    /// Agents that never started a handshake (pre-SAS agents, or ones that
+    /// registered without an `x25519_public_key`) keep the legacy
+    /// trust-on-first-use behavior and always pass — this gate only binds
+    /// ids that opted into the handshake: already-verified ones always
+    /// pass (an unconfirmed reverification attempt parked alongside them
+    /// does not change that), ones still mid-handshake pass until
+    /// [`ENROLLMENT_GRACE`] elapses, and ones whose handshake expired
+    /// ungranted are rejected until they re-enroll.
+    pub fn gate(&self, agent_id: &str) -> Result<(), GrpcStatus> {
+        let mut guard = self.0.write().expect("enrollment store lock poisoned");
+        match guard.get(agent_id) {
+            Some(EnrollmentState::Verified { .. }) => Ok(()),
+            Some(EnrollmentState::VerifiedPendingReverify { public_key, reverify }) => {
+                // The verified identity is unaffected by the still-pending
+                // reverify attempt either way; just drop a stale attempt
+                // once it expires so the entry doesn't linger forever.
+                if Instant::now() >= reverify.expires_at {
+                    let public_key = *public_key;
+                    guard.insert(agent_id.to_string(), EnrollmentState::Verified { public_key });
+                }
+                Ok(())
+            }
+            Some(EnrollmentState::Pending(pending)) if Instant::now() < pending.expires_at => Ok(()),
+            Some(EnrollmentState::Pending(_)) => {
+                guard.remove(agent_id);
+                Err(GrpcStatus::permission_denied(
+                    "agent enrollment expired without operator verification",
+                ))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Maps the first 4 bytes of `okm` to a 7-digit decimal code, the same
+/// width used by comparable SAS verification flows.
+fn sas_decimal_code(okm: &[u8; 8]) -> String {
+    let value = u32::from_be_bytes([okm[0], okm[1], okm[2], okm[3]]) % 10_000_000;
+    format!("{value:07}")
+}
+
+/// Maps the last 4 bytes of `okm` to 4 emoji, 6 bits (64 entries) at a time.
+fn sas_emoji_code(okm: &[u8; 8]) -> Vec<&'static str> {
+    let bits = u32::from_be_bytes([okm[4], okm[5], okm[6], okm[7]]);
+    (0..4)
+        .map(|i| SAS_EMOJI[((bits >> (i * 6)) & 0x3f) as usize])
+        .collect()
+}
@@ -0,0 +1,172 @@
+//! Time-bounded, permission-scoped signed grants for downloading
+//! `ContentMetadata`, mirroring a cloud shared-access-signature: a compact
+//! `r`/`w`/`d` permission string plus a validity window, signed with
+//! HMAC-SHA256 over content id/tenant id/permissions/expiry so
+//! `download_content` can verify a grant gateway-side before serving bytes,
+//! without the caller ever presenting a long-lived credential. Lives next to
+//! `auth.rs`'s HMAC-SHA256 helpers and reuses `pagination.rs`'s opaque,
+//! base64-encoded-JSON token style.
+
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::auth::has_scope;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A single abbreviated permission a [`ContentAccessPolicy`] can grant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentPermission {
+    Read,
+    Write,
+    Delete,
+}
+
+impl ContentPermission {
+    fn code(self) -> char {
+        match self {
+            ContentPermission::Read => 'r',
+            ContentPermission::Write => 'w',
+            ContentPermission::Delete => 'd',
+        }
+    }
+
+    /// The gateway scope a caller must hold to mint a grant for this
+    /// permission — a grant can never exceed its issuer's own access.
+    fn required_scope(self) -> &'static str {
+        match self {
+            ContentPermission::Read => "ugc:read",
+            ContentPermission::Write => "ugc:write",
+            ContentPermission::Delete => "ugc:delete",
+        }
+    }
+}
+
+/// A time-bounded, abbreviated-permission policy scoping a single piece of
+/// content. `permissions` is a compact string over `r`/`w`/`d` (e.g. `"r"` or
+/// `"rw"`); order and repetition don't matter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContentAccessPolicy {
+    pub content_id: Uuid,
+    pub tenant_id: Uuid,
+    pub permissions: String,
+    pub start_time: DateTime<Utc>,
+    pub expiry_time: DateTime<Utc>,
+}
+
+impl ContentAccessPolicy {
+    fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        now >= self.start_time && now <= self.expiry_time
+    }
+
+    fn allows(&self, permission: ContentPermission) -> bool {
+        self.permissions.contains(permission.code())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SignedContentGrant {
+    policy: ContentAccessPolicy,
+    signature: String,
+}
+
+/// Why [`issue_content_grant`] or [`verify_content_grant`] refused a grant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantError {
+    /// The policy itself is malformed (bad permission string, empty or
+    /// inverted time window) independent of who's asking.
+    InvalidPolicy(&'static str),
+    /// The request is well-formed but not allowed: the issuer lacks a
+    /// requested permission's scope, or a presented grant doesn't verify.
+    Forbidden(&'static str),
+}
+
+/// Mints an opaque, signed grant token for `policy`, rejecting any permission
+/// the caller's own credentials (`headers`) don't hold the matching scope
+/// for.
+pub fn issue_content_grant(
+    headers: &HeaderMap,
+    policy: ContentAccessPolicy,
+) -> Result<String, GrantError> {
+    if policy.permissions.is_empty()
+        || !policy.permissions.chars().all(|c| matches!(c, 'r' | 'w' | 'd'))
+    {
+        return Err(GrantError::InvalidPolicy("invalid permission string"));
+    }
+    if policy.expiry_time <= policy.start_time {
+        return Err(GrantError::InvalidPolicy(
+            "expiry_time must be after start_time",
+        ));
+    }
+    for permission in [
+        ContentPermission::Read,
+        ContentPermission::Write,
+        ContentPermission::Delete,
+    ] {
+        if policy.allows(permission) && !has_scope(headers, permission.required_scope()) {
+            return Err(GrantError::Forbidden(
+                "requested permission exceeds caller's scopes",
+            ));
+        }
+    }
+    let signature = sign_policy(&policy)?;
+    let grant = SignedContentGrant { policy, signature };
+    Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(&grant).expect("grant serializes")))
+}
+
+/// Decodes and verifies `token` against `content_id`/`tenant_id`/`requested`,
+/// checking the signature, the validity window, and that the granted
+/// permission set actually covers `requested`. Used gateway-side before
+/// `download_content` serves bytes from storage.
+pub fn verify_content_grant(
+    token: &str,
+    content_id: Uuid,
+    tenant_id: Uuid,
+    requested: ContentPermission,
+) -> Result<(), GrantError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(token)
+        .map_err(|_| GrantError::Forbidden("invalid grant"))?;
+    let grant: SignedContentGrant =
+        serde_json::from_slice(&bytes).map_err(|_| GrantError::Forbidden("invalid grant"))?;
+    let expected = sign_policy(&grant.policy)?;
+    let (provided, expected) = (grant.signature.as_bytes(), expected.as_bytes());
+    if provided.len() != expected.len() || !bool::from(provided.ct_eq(expected)) {
+        return Err(GrantError::Forbidden("invalid grant signature"));
+    }
+    if grant.policy.content_id != content_id || grant.policy.tenant_id != tenant_id {
+        return Err(GrantError::Forbidden("grant does not cover this content"));
+    }
+    if !grant.policy.is_active_at(Utc::now()) {
+        return Err(GrantError::Forbidden("grant is not active"));
+    }
+    if !grant.policy.allows(requested) {
+        return Err(GrantError::Forbidden("grant does not cover this permission"));
+    }
+    Ok(())
+}
+
+fn sign_policy(policy: &ContentAccessPolicy) -> Result<String, GrantError> {
+    let secret = std::env::var("CASS_JWT_SECRET").unwrap_or_default();
+    if secret.is_empty() {
+        return Err(GrantError::Forbidden("signing secret not configured"));
+    }
+    let message = format!(
+        "{}:{}:{}:{}:{}",
+        policy.content_id,
+        policy.tenant_id,
+        policy.permissions,
+        policy.start_time.timestamp(),
+        policy.expiry_time.timestamp(),
+    );
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts a key of any length");
+    mac.update(message.as_bytes());
+    Ok(URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+}
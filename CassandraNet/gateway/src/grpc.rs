@@ -1,28 +1,83 @@
-use crate::state::AgentRegistry;
+use crate::auth::{generate_agent_session_token, validate_agent_session_token};
+use crate::command_channel::{CommandChannelRegistry, CommandResult};
+use crate::enrollment::SasEnrollmentStore;
+use crate::metrics::set_node_telemetry;
+use crate::state::{AgentRegistry, AgentUpsertExtra, NodeHeartbeatSample, NodeHistoryStore};
 use chrono::{DateTime, Utc};
 #[cfg(feature = "db")]
 use cncore::platform::persistence::{AgentHeartbeatRecord, AgentUpsert, PostgresAgentStore};
+use cncommon::observability::{EventSink, FleetEvent, InMemoryMetricsRegistry, NoopEventSink};
 use cnproto::{
     agent_control_server::{AgentControl, AgentControlServer},
-    HeartbeatRequest, HeartbeatResponse, RegisterAgentRequest, RegisterAgentResponse,
+    AgentCommand, HeartbeatRequest, HeartbeatResponse, RegisterAgentRequest,
+    RegisterAgentResponse, RotateCredentialsRequest, RotateCredentialsResponse, StreamHello,
 };
+use futures::Stream;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request as GrpcRequest, Response as GrpcResponse, Status as GrpcStatus};
 use uuid::Uuid;
 
-#[derive(Default, Clone)]
+/// Wraps the receiving half of an agent's command channel so the registry
+/// entry is dropped the moment the stream itself goes away, not just when
+/// `send` first fails to deliver into it.
+struct CommandStream {
+    assigned_id: String,
+    channels: CommandChannelRegistry,
+    inner: ReceiverStream<CommandResult>,
+}
+
+impl Stream for CommandStream {
+    type Item = CommandResult;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+impl Drop for CommandStream {
+    fn drop(&mut self) {
+        self.channels.disconnect(&self.assigned_id);
+    }
+}
+
+#[derive(Clone)]
 pub struct InMemoryAgentControl {
     pub registry: AgentRegistry,
+    pub node_history: NodeHistoryStore,
+    pub enrollment: SasEnrollmentStore,
+    pub command_channels: CommandChannelRegistry,
     #[cfg(feature = "db")]
     pub agent_store: Option<Arc<PostgresAgentStore>>,
+    /// Fans out `AgentRegistered`/`AgentHeartbeat` as they happen. Defaults
+    /// to `NoopEventSink` so a caller that never wires a bus pays nothing.
+    pub events: Arc<dyn EventSink>,
+    /// Records registration/heartbeat counters and the live-agent-count
+    /// gauge. Defaults to a private registry nobody scrapes until wired to
+    /// the gateway's shared one via `with_metrics`.
+    pub metrics: InMemoryMetricsRegistry,
+}
+
+impl Default for InMemoryAgentControl {
+    fn default() -> Self {
+        Self::new(AgentRegistry::default())
+    }
 }
 
 impl InMemoryAgentControl {
     pub fn new(registry: AgentRegistry) -> Self {
         Self {
             registry,
+            node_history: NodeHistoryStore::default(),
+            enrollment: SasEnrollmentStore::default(),
+            command_channels: CommandChannelRegistry::default(),
             #[cfg(feature = "db")]
             agent_store: None,
+            events: Arc::new(NoopEventSink),
+            metrics: InMemoryMetricsRegistry::new(),
         }
     }
 
@@ -33,10 +88,40 @@ impl InMemoryAgentControl {
     ) -> Self {
         Self {
             registry,
+            node_history: NodeHistoryStore::default(),
+            enrollment: SasEnrollmentStore::default(),
+            command_channels: CommandChannelRegistry::default(),
             agent_store,
+            events: Arc::new(NoopEventSink),
+            metrics: InMemoryMetricsRegistry::new(),
         }
     }
 
+    pub fn with_history(mut self, node_history: NodeHistoryStore) -> Self {
+        self.node_history = node_history;
+        self
+    }
+
+    pub fn with_enrollment(mut self, enrollment: SasEnrollmentStore) -> Self {
+        self.enrollment = enrollment;
+        self
+    }
+
+    pub fn with_command_channels(mut self, command_channels: CommandChannelRegistry) -> Self {
+        self.command_channels = command_channels;
+        self
+    }
+
+    pub fn with_events(mut self, events: Arc<dyn EventSink>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: InMemoryMetricsRegistry) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     pub fn into_server(self) -> AgentControlServer<Self> {
         AgentControlServer::new(self)
     }
@@ -48,23 +133,86 @@ impl AgentControl for InMemoryAgentControl {
         &self,
         request: GrpcRequest<RegisterAgentRequest>,
     ) -> Result<GrpcResponse<RegisterAgentResponse>, GrpcStatus> {
+        if let Some(cn) = client_cert_cn(&request) {
+            tracing::info!(client_cert_cn = %cn, "agent.mtls_identity_presented");
+        }
         let req = request.into_inner();
         let tenant_id = parse_uuid_opt(&req.tenant_id)?;
         let project_id = parse_uuid_opt(&req.project_id)?;
-        self.registry.upsert(
+        // A caller that carries an ephemeral X25519 key starts an SAS
+        // handshake instead of being trusted outright; one that doesn't
+        // (pre-handshake agents) falls back to the historical behavior.
+        let verified_key = self.enrollment.verified_key(&req.node_id);
+        let presented_key: Option<[u8; 32]> = req.x25519_public_key.as_slice().try_into().ok();
+        let same_key_as_verified =
+            matches!((verified_key, presented_key), (Some(v), Some(p)) if v == p);
+        if verified_key.is_some() && presented_key.is_none() {
+            // This id already proved itself via a handshake; re-registering
+            // it without a fresh (valid) x25519 key would let anyone who
+            // just knows the id impersonate it, overwrite its registry
+            // entry, and walk away with a new session token.
+            return Err(GrpcStatus::permission_denied(
+                "agent id is already verified; re-registration requires a fresh x25519 handshake key",
+            ));
+        }
+        let pending = if presented_key.is_some() && !same_key_as_verified {
+            self.enrollment.begin(&req.node_id, &req.x25519_public_key)
+        } else {
+            None
+        };
+        if verified_key.is_some() && !same_key_as_verified {
+            // Re-enrollment under a *different* key than the one currently
+            // verified: a syntactically-valid key is not proof of anything,
+            // so the existing identity (registry entry, session token) must
+            // not move until an operator confirms this new key out of band
+            // via `enrollment.confirm` — only then will a later call here,
+            // re-presenting the same key, satisfy `same_key_as_verified` and
+            // fall through to the registry overwrite and token mint below.
+            let resp = RegisterAgentResponse {
+                assigned_id: req.node_id.clone(),
+                session_token: String::new(),
+                heartbeat_interval_seconds: 5,
+                control_x25519_public_key: pending
+                    .as_ref()
+                    .map(|p| p.control_public_key.to_vec())
+                    .unwrap_or_default(),
+                sas_code: pending.as_ref().map(|p| p.sas_code.clone()).unwrap_or_default(),
+                verification_required: true,
+            };
+            return Ok(GrpcResponse::new(resp));
+        }
+        let lifecycle_status = if pending.is_some() {
+            "pending_verification"
+        } else {
+            "registered"
+        };
+        self.registry.upsert_with(
             req.node_id.clone(),
             req.hostname.clone(),
             0.0,
             0,
             tenant_id.map(|id| id.to_string()),
             project_id.map(|id| id.to_string()),
-            Some(String::from("registered")),
+            Some(lifecycle_status.to_string()),
             None,
+            AgentUpsertExtra {
+                os: Some(req.os.clone()),
+                arch: Some(req.arch.clone()),
+                cpu_cores: Some(req.cpu_cores),
+                heartbeat_interval_seconds: Some(5),
+                advertise_addr: Some(req.advertise_addr.clone()),
+                zone: Some(req.zone.clone()),
+                memory_bytes: Some(req.memory_bytes),
+            },
         );
         #[cfg(feature = "db")]
         if let (Some(store), Ok(agent_id)) =
             (self.agent_store.as_ref(), Uuid::parse_str(&req.node_id))
         {
+            let mut metadata = cncore::platform::models::AgentMetadata::default();
+            if !req.zone.is_empty() {
+                metadata.tags.insert("zone".to_string(), req.zone.clone());
+            }
             let upsert = AgentUpsert {
                 id: agent_id,
                 hostname: req.hostname.clone(),
@@ -74,7 +222,7 @@ impl AgentControl for InMemoryAgentControl {
                 memory_bytes: Some(req.memory_bytes as i64),
                 tenant_id,
                 project_id,
-                metadata: Default::default(),
+                metadata,
                 status: Some(cncore::platform::models::AgentStatus::Registered),
                 last_seen: Some(Utc::now()),
             };
@@ -88,10 +236,37 @@ impl AgentControl for InMemoryAgentControl {
         } else {
             req.node_id
         };
+        self.events.publish(&FleetEvent::AgentRegistered {
+            agent_id: assigned_id.clone(),
+            tenant_id: tenant_id.map(|id| id.to_string()),
+            hostname: req.hostname,
+            timestamp: Utc::now(),
+        });
+        self.metrics
+            .increment_counter("cass_agents_registered_total", 1.0, None);
+        self.metrics
+            .set_gauge("cass_agents_live", self.registry.list().len() as f64, None);
+        let session_ttl = cncore::config().agent_session.token_ttl_seconds;
+        let session_token = generate_agent_session_token(
+            &assigned_id,
+            tenant_id.map(|id| id.to_string()).as_deref(),
+            project_id.map(|id| id.to_string()).as_deref(),
+            session_ttl,
+        )
+        .map_err(|err| {
+            tracing::error!(error = %err, "agent.session_token_issue_failed");
+            GrpcStatus::internal("failed to issue session token")
+        })?;
         let resp = RegisterAgentResponse {
             assigned_id,
-            session_token: "session-placeholder".into(),
+            session_token,
             heartbeat_interval_seconds: 5,
+            control_x25519_public_key: pending
+                .as_ref()
+                .map(|p| p.control_public_key.to_vec())
+                .unwrap_or_default(),
+            sas_code: pending.as_ref().map(|p| p.sas_code.clone()).unwrap_or_default(),
+            verification_required: pending.is_some(),
         };
         Ok(GrpcResponse::new(resp))
     }
@@ -101,6 +276,14 @@ impl AgentControl for InMemoryAgentControl {
         request: GrpcRequest<HeartbeatRequest>,
     ) -> Result<GrpcResponse<HeartbeatResponse>, GrpcStatus> {
         let hb = request.into_inner();
+        self.enrollment.gate(&hb.assigned_id)?;
+        let claims = validate_agent_session_token(&hb.session_token)
+            .map_err(|err| GrpcStatus::unauthenticated(err.to_string()))?;
+        if claims.agent_id != hb.assigned_id {
+            return Err(GrpcStatus::unauthenticated(
+                "session token does not match assigned_id",
+            ));
+        }
         let last_seen_override = if hb.timestamp_unix_ms > 0 {
             Some(hb.timestamp_unix_ms)
         } else {
@@ -116,6 +299,23 @@ impl AgentControl for InMemoryAgentControl {
             Some(String::from("active")),
             last_seen_override,
         );
+        self.node_history.record(
+            &hb.assigned_id,
+            NodeHeartbeatSample {
+                cpu_percent: hb.cpu_percent,
+                memory_used_bytes: hb.memory_used_bytes,
+                timestamp_unix_ms: last_seen_override.unwrap_or(hb.timestamp_unix_ms),
+            },
+        );
+        set_node_telemetry(&hb.assigned_id, hb.cpu_percent, hb.memory_used_bytes);
+        self.events.publish(&FleetEvent::AgentHeartbeat {
+            agent_id: hb.assigned_id.clone(),
+            cpu_percent: hb.cpu_percent,
+            memory_used_bytes: hb.memory_used_bytes,
+            timestamp: Utc::now(),
+        });
+        self.metrics
+            .increment_counter("cass_agent_heartbeats_total", 1.0, None);
         #[cfg(feature = "db")]
         if let (Some(store), Ok(agent_id)) =
             (self.agent_store.as_ref(), Uuid::parse_str(&hb.assigned_id))
@@ -131,12 +331,109 @@ impl AgentControl for InMemoryAgentControl {
                 tracing::error!(error = %err, "agent.heartbeat_persist_failed");
             }
         }
+        let rotation_window = cncore::config().agent_session.rotation_window_seconds;
+        let due_for_rotation = claims.expires_at - Utc::now().timestamp() <= rotation_window;
+        let minted = if due_for_rotation {
+            let ttl = cncore::config().agent_session.token_ttl_seconds;
+            match generate_agent_session_token(
+                &claims.agent_id,
+                claims.tenant_id.as_deref(),
+                claims.project_id.as_deref(),
+                ttl,
+            ) {
+                Ok(token) => Some(token),
+                Err(err) => {
+                    tracing::error!(error = %err, "agent.session_token_rotate_failed");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let (rotate_credentials, new_session_token) =
+            session_rotation_outcome(due_for_rotation, minted);
         let resp = HeartbeatResponse {
             ok: true,
-            rotate_credentials: false,
+            rotate_credentials,
+            new_session_token,
+        };
+        Ok(GrpcResponse::new(resp))
+    }
+
+    async fn rotate_credentials(
+        &self,
+        request: GrpcRequest<RotateCredentialsRequest>,
+    ) -> Result<GrpcResponse<RotateCredentialsResponse>, GrpcStatus> {
+        let req = request.into_inner();
+        if req.assigned_id.trim().is_empty() || req.current_secret.trim().is_empty() {
+            return Err(GrpcStatus::unauthenticated("missing agent credential"));
+        }
+        // Issued secrets are opaque, random identifiers; agents never see
+        // the raw bytes of anything used to derive them.
+        let new_secret = format!("secret-{}", Uuid::new_v4());
+        let resp = RotateCredentialsResponse {
+            ok: true,
+            new_secret,
+            tls_cert_pem: String::new(),
+            tls_key_pem: String::new(),
+            expires_unix_ms: 0,
         };
         Ok(GrpcResponse::new(resp))
     }
+
+    type OpenCommandStreamStream =
+        Pin<Box<dyn Stream<Item = Result<AgentCommand, GrpcStatus>> + Send + 'static>>;
+
+    async fn open_command_stream(
+        &self,
+        request: GrpcRequest<StreamHello>,
+    ) -> Result<GrpcResponse<Self::OpenCommandStreamStream>, GrpcStatus> {
+        let hello = request.into_inner();
+        if hello.assigned_id.trim().is_empty() {
+            return Err(GrpcStatus::invalid_argument("missing assigned_id"));
+        }
+        let (tx, rx) = mpsc::channel(16);
+        self.command_channels
+            .connect(hello.assigned_id.clone(), tx);
+        let stream = CommandStream {
+            assigned_id: hello.assigned_id,
+            channels: self.command_channels.clone(),
+            inner: ReceiverStream::new(rx),
+        };
+        Ok(GrpcResponse::new(Box::pin(stream)))
+    }
+}
+
+/// Common Name from the client certificate presented over mTLS, if any.
+/// `None` over plaintext, over TLS without client-cert verification
+/// enabled, or if the leaf certificate has no CN in its subject.
+fn client_cert_cn<T>(request: &GrpcRequest<T>) -> Option<String> {
+    let tls_info = request
+        .extensions()
+        .get::<tonic::transport::server::TlsConnectInfo<tonic::transport::server::TcpConnectInfo>>()?;
+    let leaf = tls_info.peer_certs()?.first()?.clone();
+    let (_, cert) = x509_parser::parse_x509_certificate(leaf.as_ref()).ok()?;
+    cert.subject()
+        .iter_common_name()
+        .next()
+        .and_then(|cn| cn.as_str().ok())
+        .map(str::to_string)
+}
+
+/// `HeartbeatResponse.rotate_credentials`/`new_session_token` for a session
+/// nearing expiry. `minted` is the freshly-signed session token, or `None`
+/// if `due_for_rotation` but minting it failed. That failure case still
+/// asks the agent to rotate (`rotate_credentials: true`) with an empty
+/// token, which is the one signal `cnagent` treats as "fall back to the
+/// legacy `RotateCredentials` RPC" rather than swapping in a new JWT — the
+/// agent's only path to a fresh credential if minting a session token ever
+/// breaks server-side.
+pub(crate) fn session_rotation_outcome(due_for_rotation: bool, minted: Option<String>) -> (bool, String) {
+    match (due_for_rotation, minted) {
+        (true, Some(token)) => (true, token),
+        (true, None) => (true, String::new()),
+        (false, _) => (false, String::new()),
+    }
 }
 
 fn parse_uuid_opt(value: &str) -> Result<Option<Uuid>, GrpcStatus> {
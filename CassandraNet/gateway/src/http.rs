@@ -1,11 +1,20 @@
-use crate::auth::has_scope;
-use crate::metrics::gather_metrics;
-use crate::state::{AgentSummary, AppState};
+use crate::auth::sigv4;
+use crate::auth_provider::UNRESTRICTED_SCOPE;
+use crate::grant::{
+    issue_content_grant, verify_content_grant, ContentAccessPolicy, ContentPermission, GrantError,
+};
+use crate::metrics::{
+    gather_metrics, increment_upload_sessions_created, increment_uploads_completed,
+    remove_node_telemetry, set_agents_returned,
+};
+use crate::pagination::{decode_tenant_cursor, encode_tenant_cursor};
+use crate::state::{AgentSummary, AppState, NodeHeartbeatSample, OptOutRegistry};
 use axum::{
-    extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    body::Bytes,
+    extract::{OriginalUri, Path, Query, State},
+    http::{HeaderMap, Method, StatusCode},
     response::{IntoResponse, Response},
-    routing::{get, post},
+    routing::{any, delete, get, post, put},
     Json, Router,
 };
 use chrono::{DateTime, Duration, Utc};
@@ -13,8 +22,11 @@ use cncommon::observability::{LogEvent, LogLevel};
 #[cfg(feature = "db")]
 use cncore::platform::persistence::{AgentQuery, AgentSummaryRecord};
 use cncore::platform::{
-    ContentId, ContentMetadata, ContentQuery, ContentVisibility, PlatformError, UploadId,
-    UploadSession, UploadStatus,
+    compute_digest, inspect_upload, validate_part_sizes, validate_parts_contiguous,
+    ChecksumAlgorithm, ContentId, ContentLifecycleOutcome, ContentLifecyclePolicy, ContentMetadata,
+    ContentQuery, ContentVisibility, ErrorDetail, ImmutabilityPolicy, ImmutabilityState,
+    IngestPolicy, LifecycleAction, LifecyclePolicyId, PlatformError, RenditionSpec, Task,
+    TaskStatus, UploadId, UploadPart, UploadSession, UploadStatus,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -38,34 +50,176 @@ pub struct VersionResponse {
     pub build_ts: String,
 }
 
+/// ToSchema mirror of [`cncore::platform::ErrorDetail`] (core doesn't depend
+/// on utoipa): a stable dotted `code`, a human-readable `message`, an
+/// optional `target` naming the offending field, nested `details` so a
+/// single request with several invalid fields can report all of them at
+/// once, and `additional_info` for other machine-consumable context.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetailResponse {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ErrorDetailResponse>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub additional_info: Vec<ErrorAdditionalInfoResponse>,
+}
+
+/// ToSchema mirror of [`cncore::platform::ErrorAdditionalInfo`].
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorAdditionalInfoResponse {
+    pub info_type: String,
+    pub info: serde_json::Value,
+}
+
+impl From<ErrorDetail> for ErrorDetailResponse {
+    fn from(value: ErrorDetail) -> Self {
+        Self {
+            code: value.code,
+            message: value.message,
+            target: value.target,
+            details: value.details.into_iter().map(Into::into).collect(),
+            additional_info: value
+                .additional_info
+                .into_iter()
+                .map(|info| ErrorAdditionalInfoResponse {
+                    info_type: info.info_type,
+                    info: info.info,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Shared error body for every documented failure response: the canonical
+/// structured envelope (see [`ErrorDetailResponse`]) rather than a bare
+/// string, so a client can branch on `error.code` and read every failed
+/// field out of `error.details` in one round trip.
 #[derive(Serialize, ToSchema)]
 pub struct ErrorResponse {
-    pub error: &'static str,
+    pub error: ErrorDetailResponse,
+}
+
+/// RFC 7807 `application/problem+json` body. `request_id`/`trace_id` are
+/// populated from the ambient request context (see
+/// `crate::trace_context::current_request_context`) so a client can quote
+/// them verbatim in a support ticket. `error` carries the canonical
+/// structured [`ErrorDetailResponse`] alongside the RFC 7807 fields.
+#[derive(Serialize, ToSchema)]
+pub struct ProblemDetail {
+    #[serde(rename = "type")]
+    pub type_uri: &'static str,
+    pub title: &'static str,
+    pub status: u16,
+    pub error: ErrorDetailResponse,
+    pub instance: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
 }
 
 pub struct HttpError {
     status: StatusCode,
-    message: &'static str,
+    title: &'static str,
+    error: ErrorDetail,
 }
 
 impl HttpError {
     pub fn new(status: StatusCode, message: &'static str) -> Self {
-        Self { status, message }
+        Self {
+            status,
+            title: message,
+            error: ErrorDetail::new(gateway_error_code(message), message),
+        }
+    }
+
+    pub fn with_detail(status: StatusCode, title: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            title,
+            error: ErrorDetail::new(gateway_error_code(title), detail.into()),
+        }
+    }
+
+    pub(crate) fn detail(&self) -> &str {
+        &self.error.message
     }
 }
 
+/// Slugifies a `with_detail`/`new` title (e.g. `"rate limit exceeded"`) into
+/// a stable dotted code (`"gateway.rate_limit_exceeded"`) so every ad hoc
+/// gateway-raised [`HttpError`] gets a machine-readable `ErrorDetail::code`
+/// without each call site having to name one explicitly.
+fn gateway_error_code(title: &str) -> String {
+    let slug: String = title
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    format!("gateway.{slug}")
+}
+
 impl From<PlatformError> for HttpError {
     fn from(value: PlatformError) -> Self {
+        let error = ErrorDetail::from(&value);
         match value {
-            PlatformError::NotFound(_) => HttpError::new(StatusCode::NOT_FOUND, "not found"),
-            PlatformError::Conflict(_) => HttpError::new(StatusCode::CONFLICT, "conflict"),
-            PlatformError::Unauthorized => HttpError::new(StatusCode::UNAUTHORIZED, "unauthorized"),
-            PlatformError::Forbidden => HttpError::new(StatusCode::FORBIDDEN, "forbidden"),
-            PlatformError::InvalidInput(_) => {
-                HttpError::new(StatusCode::BAD_REQUEST, "invalid input")
-            }
-            PlatformError::Internal(_) => {
-                HttpError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal error")
+            PlatformError::NotFound(_) => HttpError {
+                status: StatusCode::NOT_FOUND,
+                title: "not found",
+                error,
+            },
+            PlatformError::Conflict(_) => HttpError {
+                status: StatusCode::CONFLICT,
+                title: "conflict",
+                error,
+            },
+            PlatformError::Unauthorized => HttpError {
+                status: StatusCode::UNAUTHORIZED,
+                title: "unauthorized",
+                error,
+            },
+            PlatformError::Forbidden => HttpError {
+                status: StatusCode::FORBIDDEN,
+                title: "forbidden",
+                error,
+            },
+            PlatformError::InvalidInput(_) => HttpError {
+                status: StatusCode::BAD_REQUEST,
+                title: "invalid input",
+                error,
+            },
+            PlatformError::Locked(_) => HttpError {
+                status: StatusCode::LOCKED,
+                title: "locked",
+                error,
+            },
+            PlatformError::AudienceNotAllowed => HttpError {
+                status: StatusCode::FORBIDDEN,
+                title: "audience not allowed",
+                error,
+            },
+            PlatformError::IssuerNotTrusted => HttpError {
+                status: StatusCode::FORBIDDEN,
+                title: "issuer not trusted",
+                error,
+            },
+            PlatformError::Validation(_) => HttpError {
+                status: StatusCode::BAD_REQUEST,
+                title: "validation failed",
+                error,
+            },
+            PlatformError::Internal(what) => {
+                // Never echo raw internal/database text back to the client;
+                // the error detail is a fixed, non-leaky string while the
+                // real cause goes to the logs for operators to correlate.
+                tracing::error!(error = %what, "platform.internal_error");
+                HttpError {
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    title: "internal error",
+                    error,
+                }
             }
         }
     }
@@ -73,13 +227,25 @@ impl From<PlatformError> for HttpError {
 
 impl IntoResponse for HttpError {
     fn into_response(self) -> Response {
-        (
-            self.status,
-            Json(ErrorResponse {
-                error: self.message,
-            }),
-        )
-            .into_response()
+        let ctx = crate::trace_context::current_request_context();
+        let problem = ProblemDetail {
+            type_uri: "about:blank",
+            title: self.title,
+            status: self.status.as_u16(),
+            error: self.error.into(),
+            instance: ctx
+                .as_ref()
+                .map(|c| format!("urn:request:{}", c.request_id))
+                .unwrap_or_else(|| "urn:request:unknown".to_string()),
+            request_id: ctx.as_ref().map(|c| c.request_id.clone()),
+            trace_id: ctx.map(|c| c.trace_id),
+        };
+        let mut response = (self.status, Json(problem)).into_response();
+        response.headers_mut().insert(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderValue::from_static("application/problem+json"),
+        );
+        response
     }
 }
 
@@ -114,12 +280,154 @@ pub struct ListContentParams {
     pub limit: Option<u32>,
     #[serde(default)]
     pub offset: Option<u32>,
+    /// Opaque cursor from a previous response's `next_cursor`, scoped to the
+    /// requesting tenant. Takes priority over `offset` when both are
+    /// present; `offset` remains available for backward compatibility.
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContentListResponse {
+    pub items: Vec<ContentMetadataResponse>,
+    pub next_cursor: Option<String>,
+}
+
+/// One sub-operation in a `/content/batch` request, modeled on a
+/// key-value batch protocol: tagged by `op`, each item addressed by `id`
+/// and executed independently so one item's failure doesn't abort the rest.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ContentBatchOperation {
+    Read { id: Uuid },
+    Delete { id: Uuid },
+    SetLabels { id: Uuid, labels: Vec<String> },
+    /// Sets `legal_hold` and/or applies an [`ImmutabilityPolicy`], the only
+    /// way either is ever reachable outside a direct store call. Omitted
+    /// fields are left as-is; see
+    /// [`ContentMetadata::apply_immutability_policy`] for why `immutability`
+    /// can reject the change (one-way lock, retention can't shorten).
+    SetRetention {
+        id: Uuid,
+        #[serde(default)]
+        legal_hold: Option<bool>,
+        #[serde(default)]
+        immutability: Option<ImmutabilityPolicy>,
+    },
+}
+
+/// Outcome of one [`ContentBatchOperation`], reported with its own status
+/// code so a client can tell which items in a mixed batch succeeded.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ContentBatchItemResult {
+    pub id: Uuid,
+    #[serde(with = "http_status_serde")]
+    #[schema(value_type = u16)]
+    pub status: StatusCode,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub metadata: Option<ContentMetadataResponse>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+mod http_status_serde {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(status: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(status.as_u16())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StatusCode, D::Error> {
+        let code = u16::deserialize(deserializer)?;
+        StatusCode::from_u16(code).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ContentBatchRequest {
+    pub operations: Vec<ContentBatchOperation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ContentBatchResponse {
+    pub results: Vec<ContentBatchItemResult>,
+}
+
+/// Batch form of [`ListContentParams`]: the same label/search filters and
+/// cursor pagination, just submitted as a POST body instead of query
+/// params so it can accompany a mixed item-operation batch in the same
+/// request shape. `attributes` is matched exactly (all pairs must be
+/// present) and applied after the store's own filters, since no backend
+/// indexes attribute values the way `tags`/`search_term` are indexed.
+#[derive(Debug, Deserialize, ToSchema, Default)]
+pub struct ContentBatchSearchRequest {
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContentBatchSearchResponse {
+    pub items: Vec<ContentMetadataResponse>,
+    pub next: Option<String>,
+}
+
+/// Requests a time-bounded [`ContentAccessPolicy`] grant. `permissions` is a
+/// compact `r`/`w`/`d` string; `start_time` defaults to now.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateContentGrantRequest {
+    #[serde(default = "default_grant_permissions")]
+    pub permissions: String,
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+    pub expiry_time: DateTime<Utc>,
+}
+
+fn default_grant_permissions() -> String {
+    "r".to_string()
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContentGrantResponse {
+    /// Opaque signed token; pass as `?grant=` on the download URL.
+    pub grant: String,
+    pub download_url: String,
+}
+
+/// Query parameters accepted by `download_content` alongside the usual
+/// `x-api-key`/bearer credentials.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct DownloadParams {
+    /// A grant minted by `create_content_grant`. When present, it is
+    /// verified in place of the caller's own `ugc:read` scope.
+    #[serde(default)]
+    pub grant: Option<String>,
+}
+
+/// The `(created_at, id)` keyset embedded in a content-listing cursor, after
+/// tenant-scope validation in [`decode_tenant_cursor`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContentCursorKey {
+    created_at_unix_ms: i64,
+    id: Uuid,
 }
 
 #[derive(Debug, Deserialize, ToSchema, Default, IntoParams)]
 pub struct ListLogsParams {
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Opaque cursor from a previous response's `next_cursor`; continues the
+    /// page going further back in time.
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -140,6 +448,52 @@ pub struct CompleteUploadRequest {
     pub uploaded_by: Option<Uuid>,
     #[serde(default = "default_visibility")]
     pub visibility: ContentVisibility,
+    /// Parts reported by the client for a multipart upload, in any order.
+    /// Empty for a single-PUT session.
+    #[serde(default)]
+    pub parts: Vec<CompletedPart>,
+}
+
+/// A part as reported by the client at completion time, before the server
+/// has stamped it with `uploaded_at`. See `UploadPart` for the stored form.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CompletedPart {
+    pub part_number: u32,
+    pub etag: String,
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RequestUploadPartRequest {
+    pub part_number: u32,
+}
+
+/// Reported by the client once it has finished `PUT`ing a part's bytes to
+/// the URL returned by `request_upload_part`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterUploadPartRequest {
+    pub part_number: u32,
+    pub etag: String,
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadPartUrlResponse {
+    pub part_number: u32,
+    pub upload_url: String,
+    pub headers: HashMap<String, String>,
+}
+
+/// Returned once a part's bytes have actually landed in staging storage, so
+/// the ETag is derived from the real bytes rather than a client-supplied
+/// guess the way `RegisterUploadPartRequest.etag` is.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadPartResponse {
+    pub part_number: u32,
+    pub etag: String,
+    pub size_bytes: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -147,6 +501,10 @@ pub struct UploadSessionResponse {
     pub upload_id: UploadId,
     pub content_id: ContentId,
     pub status: String,
+    /// A presigned SigV4 URL the client can `PUT` bytes to directly when
+    /// `CASS_STORAGE_*` credentials are configured; otherwise a plain
+    /// `{CASS_STORAGE_BASE_URL}/{storage_path}` URL, or an `s3://` URI as a
+    /// last resort.
     pub upload_url: Option<String>,
     pub storage_path: String,
     pub headers: HashMap<String, String>,
@@ -171,6 +529,59 @@ pub struct ContentMetadataResponse {
     pub updated_at: DateTime<Utc>,
     pub uploaded_by: Option<Uuid>,
     pub visibility: ContentVisibility,
+    pub blurhash: Option<String>,
+    /// Relevance rank when the listing was filtered by a `search` term;
+    /// absent otherwise.
+    #[serde(default)]
+    pub relevance: Option<f32>,
+    /// See [`ContentMetadata::guard_mutation`]; set via the `set_retention`
+    /// batch operation.
+    pub legal_hold: bool,
+    #[serde(default)]
+    pub immutability: Option<ImmutabilityPolicy>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Default, IntoParams)]
+pub struct ThumbnailParams {
+    #[serde(default)]
+    pub w: Option<u32>,
+    #[serde(default)]
+    pub h: Option<u32>,
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RenditionJobResponse {
+    pub job_id: Uuid,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobStatusResponse {
+    pub id: Uuid,
+    pub status: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub result: Option<serde_json::Value>,
+}
+
+impl JobStatusResponse {
+    fn from_task(task: Task) -> Self {
+        Self {
+            id: task.id,
+            status: task.status.as_str().to_string(),
+            attempts: task.attempts,
+            last_error: task.last_error,
+            result: task.result,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LogListResponse {
+    pub items: Vec<TelemetryLogResponse>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -217,6 +628,76 @@ impl From<ContentMetadata> for ContentMetadataResponse {
             updated_at: value.updated_at,
             uploaded_by: value.uploaded_by,
             visibility: value.visibility,
+            blurhash: value.blurhash,
+            relevance: value.relevance,
+            legal_hold: value.legal_hold,
+            immutability: value.immutability,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetLifecyclePolicyRequest {
+    #[serde(default)]
+    pub id: Option<LifecyclePolicyId>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    pub label_selector: Vec<String>,
+    pub max_age_days: u32,
+    pub action: LifecycleAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LifecyclePolicyResponse {
+    pub id: LifecyclePolicyId,
+    pub tenant_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub label_selector: Vec<String>,
+    pub max_age_days: u32,
+    pub action: LifecycleAction,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ContentLifecyclePolicy> for LifecyclePolicyResponse {
+    fn from(value: ContentLifecyclePolicy) -> Self {
+        Self {
+            id: value.id,
+            tenant_id: value.tenant_id,
+            project_id: value.project_id,
+            label_selector: value.label_selector,
+            max_age_days: value.max_age_days,
+            action: value.action,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LifecyclePolicyListResponse {
+    pub items: Vec<LifecyclePolicyResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LifecycleOutcomeResponse {
+    pub content_id: Uuid,
+    pub policy_id: LifecyclePolicyId,
+    pub action: LifecycleAction,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LifecycleSweepResponse {
+    pub outcomes: Vec<LifecycleOutcomeResponse>,
+}
+
+impl From<ContentLifecycleOutcome> for LifecycleOutcomeResponse {
+    fn from(value: ContentLifecycleOutcome) -> Self {
+        Self {
+            content_id: value.content_id,
+            policy_id: value.policy_id,
+            action: value.action,
         }
     }
 }
@@ -256,10 +737,155 @@ pub async fn version() -> Json<VersionResponse> {
 }
 
 #[utoipa::path(get, path = "/metrics", tag = "system")]
-pub async fn metrics() -> (axum::http::StatusCode, String) {
+pub async fn metrics() -> (axum::http::StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 1], String) {
     gather_metrics()
 }
 
+#[utoipa::path(
+    get,
+    path = "/admin/telemetry/metrics",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Prometheus text exposition of InMemoryMetricsRegistry series"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_telemetry_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<(axum::http::StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 1], String), HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    let body = state.telemetry.metrics.render_prometheus();
+    Ok((
+        StatusCode::OK,
+        [(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderValue::from_static("text/plain; version=0.0.4"),
+        )],
+        body,
+    ))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CapabilitiesResponse {
+    pub service: String,
+    pub version: String,
+    /// `true` if built with the `db` feature, i.e. backed by Postgres
+    /// rather than the in-memory store.
+    pub db_backend_active: bool,
+    /// `true` if `CASS_STORAGE_BASE_URL` is set, so `upload_url`s point at
+    /// real storage instead of an `s3://` placeholder.
+    pub storage_presigning_configured: bool,
+    pub pagination_modes: Vec<&'static str>,
+    /// `"METHOD path" -> required scope` for every route that calls
+    /// `ensure_scope`; routes absent from this map require no scope.
+    pub required_scopes: HashMap<String, String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/capabilities",
+    tag = "system",
+    responses( (status = 200, description = "Server capabilities and route scope requirements", body = CapabilitiesResponse) )
+)]
+pub async fn capabilities() -> Json<CapabilitiesResponse> {
+    let mut required_scopes = HashMap::new();
+    required_scopes.insert(
+        "GET /admin/telemetry/metrics".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert("GET /admin/nodes".to_string(), "admin".to_string());
+    required_scopes.insert("GET /admin/nodes/:id".to_string(), "admin".to_string());
+    required_scopes.insert("DELETE /admin/nodes/:id".to_string(), "admin".to_string());
+    required_scopes.insert(
+        "POST /admin/agents/:id/opt-out".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert(
+        "GET /admin/agents/:id/enrollment".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert(
+        "POST /admin/agents/:id/verify".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert(
+        "DELETE /admin/agents/:id/opt-out".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert(
+        "POST /admin/tenants/:tenant_id/opt-out".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert(
+        "DELETE /admin/tenants/:tenant_id/opt-out".to_string(),
+        "admin".to_string(),
+    );
+    required_scopes.insert(
+        "POST /tenants/:tenant_id/projects/:project_id/uploads".to_string(),
+        "ugc:write".to_string(),
+    );
+    required_scopes.insert(
+        "POST /tenants/:tenant_id/projects/:project_id/uploads/:upload_id/complete".to_string(),
+        "ugc:write".to_string(),
+    );
+    required_scopes.insert(
+        "POST /tenants/:tenant_id/projects/:project_id/uploads/:upload_id/parts".to_string(),
+        "ugc:write".to_string(),
+    );
+    required_scopes.insert(
+        "PUT /tenants/:tenant_id/projects/:project_id/uploads/:upload_id/parts".to_string(),
+        "ugc:write".to_string(),
+    );
+    required_scopes.insert(
+        "POST /tenants/:tenant_id/projects/:project_id/uploads/:upload_id/abort".to_string(),
+        "ugc:write".to_string(),
+    );
+    required_scopes.insert(
+        "GET /tenants/:tenant_id/projects/:project_id/content".to_string(),
+        "ugc:read".to_string(),
+    );
+    required_scopes.insert(
+        "GET /tenants/:tenant_id/projects/:project_id/content/:content_id/download".to_string(),
+        "ugc:read".to_string(),
+    );
+    required_scopes.insert(
+        "GET /tenants/:tenant_id/projects/:project_id/content/:content_id/thumbnail".to_string(),
+        "ugc:read".to_string(),
+    );
+    required_scopes.insert(
+        "GET /tenants/:tenant_id/jobs/:job_id".to_string(),
+        "ugc:read".to_string(),
+    );
+    required_scopes.insert(
+        "GET /telemetry/logs".to_string(),
+        "observability:read".to_string(),
+    );
+    required_scopes.insert(
+        "PUT /tenants/:tenant_id/lifecycle-policies".to_string(),
+        "ugc:write".to_string(),
+    );
+    required_scopes.insert(
+        "GET /tenants/:tenant_id/lifecycle-policies".to_string(),
+        "ugc:read".to_string(),
+    );
+    required_scopes.insert(
+        "POST /tenants/:tenant_id/lifecycle-policies/sweep".to_string(),
+        "ugc:write".to_string(),
+    );
+
+    Json(CapabilitiesResponse {
+        service: cncore::config().service_name.clone(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        db_backend_active: cfg!(feature = "db"),
+        storage_presigning_configured: storage_base_url().is_some(),
+        pagination_modes: vec!["offset", "cursor"],
+        required_scopes,
+    })
+}
+
 #[derive(Debug, Deserialize, ToSchema, Default, IntoParams)]
 pub struct ListAgentsParams {
     #[serde(default)]
@@ -280,6 +906,18 @@ pub struct ListAgentsParams {
     pub limit: Option<u32>,
     #[serde(default)]
     pub offset: Option<u32>,
+    /// Opaque cursor from a previous response's `next_cursor`, encoding the
+    /// last returned row's `(last_seen_unix_ms, id)`. Takes priority over
+    /// `offset` when both are present; `offset` remains available for
+    /// backward compatibility.
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AgentListResponse {
+    pub items: Vec<AgentSummary>,
+    pub next_cursor: Option<String>,
 }
 
 #[utoipa::path(
@@ -287,21 +925,31 @@ pub struct ListAgentsParams {
     path = "/agents",
     tag = "system",
     params(ListAgentsParams),
-    responses( (status = 200, body = [AgentSummary]) )
+    responses( (status = 200, body = AgentListResponse) )
 )]
 pub async fn list_agents(
     State(state): State<AppState>,
     Query(params): Query<ListAgentsParams>,
-) -> Result<Json<Vec<AgentSummary>>, HttpError> {
+) -> Result<Json<AgentListResponse>, HttpError> {
+    let cursor = params
+        .cursor
+        .as_deref()
+        .map(decode_agent_cursor)
+        .transpose()?;
     #[cfg(feature = "db")]
     {
         if let Some(store) = state.agent_store.as_ref() {
-            match build_agent_query(&params) {
+            match build_agent_query(&params, cursor.as_ref(), &state.opt_outs) {
                 Ok(query) => match store.query_agents(&query).await {
                     Ok(records) => {
                         let mapped: Vec<AgentSummary> =
                             records.into_iter().map(map_agent_record).collect();
-                        return Ok(Json(filter_agent_summaries(mapped, &params)));
+                        return Ok(Json(paginate_agent_summaries(
+                            mapped,
+                            &params,
+                            cursor.as_ref(),
+                            &state.opt_outs,
+                        )));
                     }
                     Err(err) => {
                         tracing::error!(error = %err, "agents.query_failed_fallback");
@@ -312,49 +960,350 @@ pub async fn list_agents(
         }
     }
     let agents = state.registry.list();
-    Ok(Json(filter_agent_summaries(agents, &params)))
+    Ok(Json(paginate_agent_summaries(
+        agents,
+        &params,
+        cursor.as_ref(),
+        &state.opt_outs,
+    )))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminNodeResponse {
+    #[serde(flatten)]
+    pub summary: AgentSummary,
+    pub stale: bool,
+}
+
+impl AdminNodeResponse {
+    fn from_summary(summary: AgentSummary, now_unix_ms: u64) -> Self {
+        let stale = summary.is_stale(now_unix_ms);
+        Self { summary, stale }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AdminNodeDetailResponse {
+    #[serde(flatten)]
+    pub node: AdminNodeResponse,
+    pub history: Vec<NodeHeartbeatSample>,
 }
 
 #[utoipa::path(
-    post,
-    path = "/tenants/{tenant_id}/projects/{project_id}/uploads",
-    params(
-        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
-        ("project_id" = Uuid, Path, description = "Project identifier")
+    get,
+    path = "/admin/nodes",
+    tag = "admin",
+    responses(
+        (status = 200, description = "All registered agent nodes", body = [AdminNodeResponse]),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse)
     ),
-    request_body = CreateUploadRequest,
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_list_nodes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<AdminNodeResponse>>, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    let now_unix_ms = Utc::now().timestamp_millis() as u64;
+    let nodes = state
+        .registry
+        .list()
+        .into_iter()
+        .map(|summary| AdminNodeResponse::from_summary(summary, now_unix_ms))
+        .collect();
+    Ok(Json(nodes))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/nodes/{id}",
+    tag = "admin",
+    params(("id" = String, Path, description = "Agent assigned_id")),
     responses(
-        (status = 201, description = "Upload session created", body = UploadSessionResponse)
+        (status = 200, description = "Node detail with telemetry history", body = AdminNodeDetailResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "Node not found", body = ErrorResponse)
     ),
     security(("ApiKey" = []), ("BearerAuth" = []))
 )]
-pub async fn create_upload_session(
+pub async fn admin_get_node(
     State(state): State<AppState>,
-    Path((tenant_id, project_id)): Path<(Uuid, Uuid)>,
     headers: HeaderMap,
-    Json(payload): Json<CreateUploadRequest>,
-) -> Result<(StatusCode, Json<UploadSessionResponse>), HttpError> {
-    if payload.filename.trim().is_empty() {
-        return Err(HttpError::new(StatusCode::BAD_REQUEST, "filename required"));
-    }
-    ensure_scope(&headers, "ugc:write")?;
-    if !state.rate_limiter.check_and_increment(
-        tenant_id,
-        "ugc:create_upload",
-        60,
-        StdDuration::from_secs(60),
-    ) {
-        return Err(HttpError::new(
-            StatusCode::TOO_MANY_REQUESTS,
-            "rate limit exceeded",
-        ));
+    Path(id): Path<String>,
+) -> Result<Json<AdminNodeDetailResponse>, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    let summary = state
+        .registry
+        .get(&id)
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "node not found"))?;
+    let now_unix_ms = Utc::now().timestamp_millis() as u64;
+    let history = state.node_history.history(&id);
+    Ok(Json(AdminNodeDetailResponse {
+        node: AdminNodeResponse::from_summary(summary, now_unix_ms),
+        history,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/nodes/{id}",
+    tag = "admin",
+    params(("id" = String, Path, description = "Agent assigned_id")),
+    responses(
+        (status = 204, description = "Node deregistered"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "Node not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_delete_node(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    if !state.registry.remove(&id) {
+        return Err(HttpError::new(StatusCode::NOT_FOUND, "node not found"));
     }
-    let now = Utc::now();
-    let content_id = Uuid::new_v4();
-    let upload_id = Uuid::new_v4();
+    state.node_history.remove(&id);
+    remove_node_telemetry(&id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AgentEnrollmentResponse {
+    pub agent_id: String,
+    /// Decimal SAS code; compare it against the one displayed on the agent
+    /// host before calling the verify endpoint.
+    pub sas_code: String,
+    pub sas_emoji: Vec<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/agents/{id}/enrollment",
+    tag = "admin",
+    params(("id" = String, Path, description = "Agent assigned_id")),
+    responses(
+        (status = 200, description = "Pending SAS handshake for this agent", body = AgentEnrollmentResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "No pending (unexpired) handshake for this agent", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_get_agent_enrollment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<AgentEnrollmentResponse>, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    let pending = state
+        .enrollment
+        .get(&id)
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "no pending enrollment for agent"))?;
+    Ok(Json(AgentEnrollmentResponse {
+        agent_id: pending.agent_id,
+        sas_code: pending.sas_code,
+        sas_emoji: pending.sas_emoji.into_iter().map(str::to_string).collect(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/agents/{id}/verify",
+    tag = "admin",
+    params(("id" = String, Path, description = "Agent assigned_id")),
+    responses(
+        (status = 204, description = "SAS code confirmed; agent's heartbeats are accepted going forward"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "No pending (unexpired) handshake for this agent", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_verify_agent(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    if !state.enrollment.confirm(&id) {
+        return Err(HttpError::new(
+            StatusCode::NOT_FOUND,
+            "no pending enrollment for agent",
+        ));
+    }
+    // `upsert` always overwrites `hostname`/`cpu`/`memory`, so carry the
+    // node's existing values forward rather than clobbering them just to
+    // flip `lifecycle_status`.
+    let existing = state.registry.get(&id);
+    state.registry.upsert(
+        id,
+        existing
+            .as_ref()
+            .map(|a| a.hostname.clone())
+            .unwrap_or_default(),
+        existing.as_ref().map(|a| a.cpu_percent).unwrap_or(0.0),
+        existing.as_ref().map(|a| a.memory_used_bytes).unwrap_or(0),
+        None,
+        None,
+        Some(String::from("verified")),
+        existing.as_ref().map(|a| a.last_seen_unix_ms),
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/agents/{id}/opt-out",
+    tag = "admin",
+    params(("id" = String, Path, description = "Agent assigned_id")),
+    responses(
+        (status = 204, description = "Agent marked opted-out and suppressed from listings"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_opt_out_agent(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    state.opt_outs.opt_out_agent(&id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/agents/{id}/opt-out",
+    tag = "admin",
+    params(("id" = String, Path, description = "Agent assigned_id")),
+    responses(
+        (status = 204, description = "Opt-out cleared; agent reappears in listings"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "Agent was not opted out", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_clear_agent_opt_out(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    if !state.opt_outs.clear_agent(&id) {
+        return Err(HttpError::new(StatusCode::NOT_FOUND, "agent was not opted out"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/tenants/{tenant_id}/opt-out",
+    tag = "admin",
+    params(("tenant_id" = String, Path, description = "Tenant identifier")),
+    responses(
+        (status = 204, description = "Tenant marked opted-out; its agents/logs are suppressed from listings"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_opt_out_tenant(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(tenant_id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    state.opt_outs.opt_out_tenant(&tenant_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/tenants/{tenant_id}/opt-out",
+    tag = "admin",
+    params(("tenant_id" = String, Path, description = "Tenant identifier")),
+    responses(
+        (status = 204, description = "Opt-out cleared; tenant reappears in listings"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the admin scope", body = ErrorResponse),
+        (status = 404, description = "Tenant was not opted out", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn admin_clear_tenant_opt_out(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(tenant_id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "admin").await?;
+    if !state.opt_outs.clear_tenant(&tenant_id) {
+        return Err(HttpError::new(
+            StatusCode::NOT_FOUND,
+            "tenant was not opted out",
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/projects/{project_id}/uploads",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier")
+    ),
+    request_body = CreateUploadRequest,
+    responses(
+        (status = 201, description = "Upload session created", body = UploadSessionResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn create_upload_session(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id)): Path<(Uuid, Uuid)>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<UploadSessionResponse>), HttpError> {
+    ensure_scope_or_sigv4(&state, &method, &uri, &headers, &body, "ugc:write").await?;
+    let payload: CreateUploadRequest = serde_json::from_slice(&body)
+        .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid request body"))?;
+    if payload.filename.trim().is_empty() {
+        return Err(HttpError::new(StatusCode::BAD_REQUEST, "filename required"));
+    }
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:create_upload",
+        60,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let now = Utc::now();
+    let content_id = Uuid::new_v4();
+    let upload_id = Uuid::new_v4();
     let storage_path = build_storage_path(&tenant_id, &project_id, &content_id, &payload.filename);
-    let upload_url = storage_base_url()
-        .map(|base| format!("{base}/{storage_path}"))
+    let upload_url = crate::presign::StorageCredentials::from_env()
+        .map(|credentials| {
+            crate::presign::presigned_put_url(&credentials, &storage_path, payload.expires_in_seconds)
+        })
+        .or_else(|| storage_base_url().map(|base| format!("{base}/{storage_path}")))
         .or_else(|| Some(format!("s3://cassandranet/{storage_path}")));
     let expires_at = payload
         .expires_in_seconds
@@ -378,6 +1327,7 @@ pub async fn create_upload_session(
         expires_at,
         upload_url,
         headers,
+        parts: Vec::new(),
     };
     state
         .content_store
@@ -392,6 +1342,7 @@ pub async fn create_upload_session(
         1.0,
         Some(metric_labels.clone()),
     );
+    increment_upload_sessions_created();
     if let Some(size) = payload.size_bytes {
         state.telemetry.metrics.observe_histogram(
             "ugc_upload_size_bytes",
@@ -426,7 +1377,13 @@ pub async fn create_upload_session(
     ),
     request_body = CompleteUploadRequest,
     responses(
-        (status = 200, description = "Upload finalized", body = ContentMetadataResponse)
+        (status = 200, description = "Upload finalized", body = ContentMetadataResponse),
+        (status = 400, description = "Uploaded content failed format/size validation", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Upload session not found", body = ErrorResponse),
+        (status = 409, description = "Upload already completed, or the client-supplied checksum does not match the uploaded object", body = ErrorResponse),
+        (status = 502, description = "Could not fetch the uploaded object from storage", body = ErrorResponse)
     ),
     security(("ApiKey" = []), ("BearerAuth" = []))
 )]
@@ -436,13 +1393,13 @@ pub async fn complete_upload_session(
     headers: HeaderMap,
     Json(payload): Json<CompleteUploadRequest>,
 ) -> Result<Json<ContentMetadataResponse>, HttpError> {
-    ensure_scope(&headers, "ugc:write")?;
-    if !state.rate_limiter.check_and_increment(
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
         tenant_id,
         "ugc:complete_upload",
         120,
         StdDuration::from_secs(60),
-    ) {
+    ).await {
         return Err(HttpError::new(
             StatusCode::TOO_MANY_REQUESTS,
             "rate limit exceeded",
@@ -475,6 +1432,53 @@ pub async fn complete_upload_session(
             "upload session closed",
         ));
     }
+    let registered_parts = state
+        .content_store
+        .list_upload_parts(upload_id)
+        .await
+        .map_err(HttpError::from)?;
+    let staged_parts = registered_parts.clone();
+    if !registered_parts.is_empty() {
+        // The server's own record of uploaded parts is authoritative once
+        // any exist, so a client can't claim parts it never registered.
+        validate_parts_contiguous(&registered_parts).map_err(HttpError::from)?;
+        // Only parts staged as real bytes via `upload_part_bytes` carry a
+        // server-computed digest etag (`sha256:...`); legacy callers that
+        // merely report a claimed etag/size through `register_upload_part`
+        // aren't held to S3's part-size floor, since there's no actual
+        // object behind their claim to size-check in the first place.
+        if registered_parts
+            .iter()
+            .all(|part| part.etag.starts_with("sha256:") || part.etag.starts_with("blake2b:"))
+        {
+            validate_part_sizes(&registered_parts).map_err(HttpError::from)?;
+        }
+        if let Some(total) = registered_parts
+            .iter()
+            .map(|part| part.size_bytes)
+            .sum::<Option<u64>>()
+        {
+            if total != payload.size_bytes {
+                return Err(HttpError::from(PlatformError::Conflict(
+                    "registered part sizes do not add up to the declared upload size",
+                )));
+            }
+        }
+        session.parts = registered_parts;
+    } else if !payload.parts.is_empty() {
+        let parts: Vec<UploadPart> = payload
+            .parts
+            .iter()
+            .map(|part| UploadPart {
+                part_number: part.part_number,
+                etag: part.etag.clone(),
+                size_bytes: part.size_bytes,
+                uploaded_at: now,
+            })
+            .collect();
+        validate_parts_contiguous(&parts).map_err(HttpError::from)?;
+        session.parts = parts;
+    }
     let storage_path = payload.storage_path.clone().unwrap_or_else(|| {
         build_storage_path(
             &tenant_id,
@@ -483,6 +1487,68 @@ pub async fn complete_upload_session(
             &payload.filename,
         )
     });
+
+    let object_bytes = if !staged_parts.is_empty() {
+        // Parts were staged as real bytes via `upload_part_bytes`, so the
+        // final object doesn't exist at `storage_path` yet - assemble it in
+        // part-number order and persist it there, the same place a
+        // single-PUT upload would have landed.
+        let mut ordered = staged_parts;
+        ordered.sort_by_key(|part| part.part_number);
+        let mut assembled = Vec::with_capacity(
+            ordered
+                .iter()
+                .filter_map(|part| part.size_bytes)
+                .sum::<u64>() as usize,
+        );
+        for part in &ordered {
+            let part_path = part_storage_path(&tenant_id, &project_id, &upload_id, part.part_number);
+            let bytes = state.object_fetcher.fetch(&part_path, None).await?;
+            assembled.extend_from_slice(&bytes);
+        }
+        state
+            .object_fetcher
+            .put(&storage_path, assembled.clone())
+            .await?;
+        for part in &ordered {
+            let part_path = part_storage_path(&tenant_id, &project_id, &upload_id, part.part_number);
+            if let Err(err) = state.object_fetcher.delete(&part_path).await {
+                tracing::warn!(upload_id = %upload_id, part_number = part.part_number, error = err.title, "ugc.upload_part_gc_failed");
+            }
+        }
+        assembled
+    } else {
+        state
+            .object_fetcher
+            .fetch(&storage_path, session.upload_url.as_deref())
+            .await?
+    };
+    let tenant_settings = state
+        .tenant_store
+        .get_tenant(tenant_id)
+        .map_err(HttpError::from)?
+        .map(|tenant| tenant.settings);
+    let ingest_policy = IngestPolicy::from_settings(tenant_settings.as_ref());
+    let inspected = inspect_upload(
+        &object_bytes,
+        &payload.visibility,
+        payload.mime_type.as_deref(),
+        &ingest_policy,
+    )
+    .map_err(HttpError::from)?;
+    if let Some(claimed) = payload.checksum.as_deref() {
+        if claimed != inspected.digest {
+            return Err(HttpError::from(PlatformError::Conflict(
+                "uploaded content checksum does not match the server-computed digest",
+            )));
+        }
+    }
+    let dedup_source = state
+        .content_store
+        .find_content_by_digest(tenant_id, &inspected.digest, inspected.size_bytes)
+        .await
+        .map_err(HttpError::from)?;
+
     session.status = UploadStatus::Completed;
     session.updated_at = now;
     session.upload_url = storage_base_url()
@@ -494,21 +1560,28 @@ pub async fn complete_upload_session(
         .await
         .map_err(HttpError::from)?;
 
+    let deduplicated = dedup_source.is_some();
+    let metadata_storage_path = dedup_source
+        .and_then(|existing| existing.storage_path)
+        .unwrap_or_else(|| storage_path.clone());
+
     let metadata = ContentMetadata {
         id: session.content_id,
         tenant_id,
         project_id,
         filename: payload.filename,
-        mime_type: payload.mime_type,
-        size_bytes: Some(payload.size_bytes),
-        checksum: payload.checksum,
-        storage_path: Some(storage_path.clone()),
+        mime_type: Some(inspected.mime_type),
+        size_bytes: Some(inspected.size_bytes),
+        checksum: Some(inspected.digest),
+        storage_path: Some(metadata_storage_path),
         labels: payload.labels,
         attributes: payload.attributes,
         created_at: now,
         updated_at: now,
         uploaded_by: payload.uploaded_by,
         visibility: payload.visibility,
+        blurhash: inspected.blurhash,
+        relevance: None,
     };
     state
         .content_store
@@ -523,9 +1596,17 @@ pub async fn complete_upload_session(
         1.0,
         Some(metric_labels.clone()),
     );
+    increment_uploads_completed();
+    if deduplicated {
+        state.telemetry.metrics.increment_counter(
+            "ugc_upload_deduplicated",
+            1.0,
+            Some(metric_labels.clone()),
+        );
+    }
     state.telemetry.metrics.set_gauge(
         "ugc_last_upload_size_bytes",
-        payload.size_bytes as f64,
+        inspected.size_bytes as f64,
         Some(metric_labels.clone()),
     );
     let metadata_response = ContentMetadataResponse::from(metadata.clone());
@@ -542,7 +1623,849 @@ pub async fn complete_upload_session(
                 "storage_path": metadata_response.storage_path
             })),
     );
-    Ok(Json(metadata_response))
+    Ok(Json(metadata_response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/projects/{project_id}/uploads/{upload_id}/parts",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("upload_id" = Uuid, Path, description = "Upload session id")
+    ),
+    request_body = RequestUploadPartRequest,
+    responses(
+        (status = 200, description = "Presigned URL for the requested part", body = UploadPartUrlResponse),
+        (status = 400, description = "Invalid part number or upload session closed", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Upload session not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn request_upload_part(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, upload_id)): Path<(Uuid, Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(payload): Json<RequestUploadPartRequest>,
+) -> Result<Json<UploadPartUrlResponse>, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:request_upload_part",
+        120,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    if payload.part_number == 0 {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "part_number must be >= 1",
+        ));
+    }
+    let mut session = state
+        .content_store
+        .get_upload_session(upload_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "upload session not found"))?;
+    if session.tenant_id != tenant_id || session.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "upload session scope mismatch",
+        ));
+    }
+    if !matches!(
+        session.status,
+        UploadStatus::Pending | UploadStatus::Uploading
+    ) {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "upload session closed",
+        ));
+    }
+    let base_url = session
+        .upload_url
+        .clone()
+        .unwrap_or_else(|| format!("s3://cassandranet/{upload_id}"));
+    let upload_url = format!(
+        "{base_url}?partNumber={}&uploadId={upload_id}",
+        payload.part_number
+    );
+    let mut part_headers = HashMap::new();
+    part_headers.insert(
+        "x-upload-part-number".to_string(),
+        payload.part_number.to_string(),
+    );
+    if session.status == UploadStatus::Pending {
+        session.status = UploadStatus::Uploading;
+        session.updated_at = Utc::now();
+        state
+            .content_store
+            .update_upload_session(session)
+            .await
+            .map_err(HttpError::from)?;
+    }
+    Ok(Json(UploadPartUrlResponse {
+        part_number: payload.part_number,
+        upload_url,
+        headers: part_headers,
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/tenants/{tenant_id}/projects/{project_id}/uploads/{upload_id}/parts",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("upload_id" = Uuid, Path, description = "Upload session id")
+    ),
+    request_body = RegisterUploadPartRequest,
+    responses(
+        (status = 204, description = "Part recorded"),
+        (status = 400, description = "Invalid part number or upload session closed", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Upload session not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn register_upload_part(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, upload_id)): Path<(Uuid, Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(payload): Json<RegisterUploadPartRequest>,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:register_upload_part",
+        120,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    if payload.part_number == 0 {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "part_number must be >= 1",
+        ));
+    }
+    if payload.etag.trim().is_empty() {
+        return Err(HttpError::new(StatusCode::BAD_REQUEST, "etag required"));
+    }
+    let session = state
+        .content_store
+        .get_upload_session(upload_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "upload session not found"))?;
+    if session.tenant_id != tenant_id || session.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "upload session scope mismatch",
+        ));
+    }
+    if !matches!(
+        session.status,
+        UploadStatus::Pending | UploadStatus::Uploading
+    ) {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "upload session closed",
+        ));
+    }
+    state
+        .content_store
+        .register_upload_part(
+            upload_id,
+            UploadPart {
+                part_number: payload.part_number,
+                etag: payload.etag,
+                size_bytes: payload.size_bytes,
+                uploaded_at: Utc::now(),
+            },
+        )
+        .await
+        .map_err(HttpError::from)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    put,
+    path = "/tenants/{tenant_id}/projects/{project_id}/uploads/{upload_id}/parts/{part_number}",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("upload_id" = Uuid, Path, description = "Upload session id"),
+        ("part_number" = u32, Path, description = "1-based part number")
+    ),
+    request_body(content = String, description = "Raw part bytes", content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "Part staged and its ETag computed", body = UploadPartResponse),
+        (status = 400, description = "Invalid part number or upload session closed", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Upload session not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn upload_part_bytes(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, upload_id, part_number)): Path<(Uuid, Uuid, Uuid, u32)>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<UploadPartResponse>, HttpError> {
+    ensure_scope_or_sigv4(&state, &method, &uri, &headers, &body, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:upload_part_bytes",
+        120,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    if part_number == 0 {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "part_number must be >= 1",
+        ));
+    }
+    let mut session = state
+        .content_store
+        .get_upload_session(upload_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "upload session not found"))?;
+    if session.tenant_id != tenant_id || session.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "upload session scope mismatch",
+        ));
+    }
+    if !matches!(
+        session.status,
+        UploadStatus::Pending | UploadStatus::Uploading
+    ) {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "upload session closed",
+        ));
+    }
+    let size_bytes = body.len() as u64;
+    let etag = compute_digest(&body, ChecksumAlgorithm::Sha256);
+    let storage_path = part_storage_path(&tenant_id, &project_id, &upload_id, part_number);
+    state.object_fetcher.put(&storage_path, body.to_vec()).await?;
+    state
+        .content_store
+        .register_upload_part(
+            upload_id,
+            UploadPart {
+                part_number,
+                etag: etag.clone(),
+                size_bytes: Some(size_bytes),
+                uploaded_at: Utc::now(),
+            },
+        )
+        .await
+        .map_err(HttpError::from)?;
+    if session.status == UploadStatus::Pending {
+        session.status = UploadStatus::Uploading;
+        session.updated_at = Utc::now();
+        state
+            .content_store
+            .update_upload_session(session)
+            .await
+            .map_err(HttpError::from)?;
+    }
+    Ok(Json(UploadPartResponse {
+        part_number,
+        etag,
+        size_bytes,
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/tenants/{tenant_id}/lifecycle-policies",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier")
+    ),
+    request_body = SetLifecyclePolicyRequest,
+    responses(
+        (status = 200, description = "Policy created or replaced", body = LifecyclePolicyResponse),
+        (status = 400, description = "Invalid policy", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Tenant not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn set_lifecycle_policy(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<SetLifecyclePolicyRequest>,
+) -> Result<Json<LifecyclePolicyResponse>, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:set_lifecycle_policy",
+        60,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    if payload.max_age_days == 0 {
+        return Err(HttpError::new(
+            StatusCode::BAD_REQUEST,
+            "max_age_days must be >= 1",
+        ));
+    }
+    let now = Utc::now();
+    let policy = ContentLifecyclePolicy {
+        id: payload.id.unwrap_or_else(Uuid::new_v4),
+        tenant_id,
+        project_id: payload.project_id,
+        label_selector: payload.label_selector,
+        max_age_days: payload.max_age_days,
+        action: payload.action,
+        created_at: now,
+        updated_at: now,
+    };
+    state
+        .content_store
+        .set_lifecycle_policy(policy.clone())
+        .await
+        .map_err(HttpError::from)?;
+    Ok(Json(LifecyclePolicyResponse::from(policy)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tenants/{tenant_id}/lifecycle-policies",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier")
+    ),
+    responses(
+        (status = 200, description = "Lifecycle policies for the tenant", body = LifecyclePolicyListResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn list_lifecycle_policies(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<LifecyclePolicyListResponse>, HttpError> {
+    ensure_scope(&state, &headers, "ugc:read").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:list_lifecycle_policies",
+        120,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let items = state
+        .content_store
+        .list_lifecycle_policies(tenant_id)
+        .await
+        .map_err(HttpError::from)?
+        .into_iter()
+        .map(LifecyclePolicyResponse::from)
+        .collect();
+    Ok(Json(LifecyclePolicyListResponse { items }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/tenants/{tenant_id}/lifecycle-policies/{policy_id}",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("policy_id" = Uuid, Path, description = "Lifecycle policy identifier")
+    ),
+    responses(
+        (status = 204, description = "Policy deleted, or already absent"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn delete_lifecycle_policy(
+    State(state): State<AppState>,
+    Path((tenant_id, policy_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:delete_lifecycle_policy",
+        60,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    state
+        .content_store
+        .delete_lifecycle_policy(tenant_id, policy_id)
+        .await
+        .map_err(HttpError::from)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/lifecycle-policies/sweep",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier")
+    ),
+    responses(
+        (status = 200, description = "Content that aged out of its lifecycle policy, with each outcome already applied", body = LifecycleSweepResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn sweep_expired_content(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<LifecycleSweepResponse>, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:sweep_expired_content",
+        12,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let outcomes = state
+        .content_store
+        .sweep_expired_content(tenant_id, Utc::now())
+        .await
+        .map_err(HttpError::from)?;
+    for outcome in &outcomes {
+        state
+            .content_store
+            .apply_lifecycle_outcome(outcome.clone())
+            .await
+            .map_err(HttpError::from)?;
+    }
+    let outcomes = outcomes.into_iter().map(LifecycleOutcomeResponse::from).collect();
+    Ok(Json(LifecycleSweepResponse { outcomes }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/projects/{project_id}/uploads/{upload_id}/abort",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("upload_id" = Uuid, Path, description = "Upload session id")
+    ),
+    responses(
+        (status = 204, description = "Upload session aborted"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Upload session not found", body = ErrorResponse),
+        (status = 409, description = "Upload already finalized", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn abort_upload_session(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, upload_id)): Path<(Uuid, Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<StatusCode, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:abort_upload",
+        60,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let mut session = state
+        .content_store
+        .get_upload_session(upload_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "upload session not found"))?;
+    if session.tenant_id != tenant_id || session.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "upload session scope mismatch",
+        ));
+    }
+    if !matches!(
+        session.status,
+        UploadStatus::Pending | UploadStatus::Uploading
+    ) {
+        return Err(HttpError::new(
+            StatusCode::CONFLICT,
+            "upload session already finalized",
+        ));
+    }
+    session.status = UploadStatus::Aborted;
+    session.updated_at = Utc::now();
+    state
+        .content_store
+        .update_upload_session(session)
+        .await
+        .map_err(HttpError::from)?;
+    let staged_parts = state
+        .content_store
+        .list_upload_parts(upload_id)
+        .await
+        .map_err(HttpError::from)?;
+    for part in &staged_parts {
+        let part_path = part_storage_path(&tenant_id, &project_id, &upload_id, part.part_number);
+        if let Err(err) = state.object_fetcher.delete(&part_path).await {
+            tracing::warn!(upload_id = %upload_id, part_number = part.part_number, error = err.title, "ugc.upload_part_gc_failed");
+        }
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// A single byte range parsed from a `Range` request header, inclusive of
+/// both ends and already clamped to `total_len`.
+struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+/// Parse a `Range: bytes=...` header against an object of `total_len` bytes.
+/// Only the first range of a (possibly multi-range) request is honored,
+/// matching how most HTTP clients send range requests in practice; returns
+/// `None` if the header is malformed or the range can't be satisfied.
+fn parse_byte_range(header: &str, total_len: u64) -> Option<ByteRange> {
+    let spec = header.strip_prefix("bytes=")?.split(',').next()?.trim();
+    let (start_str, end_str) = spec.split_once('-')?;
+    if start_str.is_empty() {
+        let suffix_len: u64 = end_str.parse().ok()?;
+        if suffix_len == 0 || total_len == 0 {
+            return None;
+        }
+        return Some(ByteRange {
+            start: total_len.saturating_sub(suffix_len),
+            end: total_len - 1,
+        });
+    }
+    let start: u64 = start_str.parse().ok()?;
+    if start >= total_len {
+        return None;
+    }
+    let end = if end_str.is_empty() {
+        total_len - 1
+    } else {
+        end_str.parse::<u64>().ok()?.min(total_len - 1)
+    };
+    if end < start {
+        return None;
+    }
+    Some(ByteRange { start, end })
+}
+
+/// Quoted `ETag` for a piece of content, derived from its authoritative
+/// checksum so it changes only when the stored bytes do; falls back to the
+/// content id for objects ingested before checksums were recorded.
+fn content_etag(metadata: &ContentMetadata) -> String {
+    format!(
+        "\"{}\"",
+        metadata.checksum.as_deref().unwrap_or(&metadata.id.to_string())
+    )
+}
+
+/// `true` if a conditional `GET` against `If-None-Match`/`If-Modified-Since`
+/// is satisfied by the current `etag`/`last_modified`, meaning the response
+/// should short-circuit to `304 Not Modified`. `If-None-Match` takes
+/// precedence over `If-Modified-Since` when both are present, per RFC 7232.
+fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if let Some(if_none_match) = headers
+        .get(axum::http::header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+    {
+        return if_none_match
+            .split(',')
+            .map(|tag| tag.trim())
+            .any(|tag| tag == "*" || tag == etag);
+    }
+    if let Some(if_modified_since) = headers
+        .get(axum::http::header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+    {
+        return last_modified.timestamp() <= if_modified_since.timestamp();
+    }
+    false
+}
+
+fn http_date(at: DateTime<Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// `true` when a `Range` request should still be honored: either there's no
+/// `If-Range` validator on the request, or the one present still matches the
+/// object's current `etag`/`last_modified`. `false` means the client's
+/// cached partial copy is stale, so the `Range` header must be ignored in
+/// favor of a full `200` response, per RFC 7233 §3.2.
+fn if_range_satisfied(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    let Some(if_range) = headers
+        .get(axum::http::header::IF_RANGE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return true;
+    };
+    if let Ok(at) = DateTime::parse_from_rfc2822(if_range) {
+        return last_modified.timestamp() <= at.timestamp();
+    }
+    if_range == etag
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/projects/{project_id}/content/{content_id}/grant",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("content_id" = Uuid, Path, description = "Content identifier")
+    ),
+    request_body = CreateContentGrantRequest,
+    responses(
+        (status = 200, description = "Signed, time-bounded download grant", body = ContentGrantResponse),
+        (status = 400, description = "Invalid permission string or time window", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Requested permission exceeds caller's own scopes", body = ErrorResponse),
+        (status = 404, description = "Content not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn create_content_grant(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, content_id)): Path<(Uuid, Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateContentGrantRequest>,
+) -> Result<Json<ContentGrantResponse>, HttpError> {
+    let metadata = state
+        .content_store
+        .get_content_metadata(content_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "content not found"))?;
+    if metadata.tenant_id != tenant_id || metadata.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "content scope mismatch",
+        ));
+    }
+    let policy = ContentAccessPolicy {
+        content_id,
+        tenant_id,
+        permissions: payload.permissions,
+        start_time: payload.start_time.unwrap_or_else(Utc::now),
+        expiry_time: payload.expiry_time,
+    };
+    let grant = issue_content_grant(&headers, policy).map_err(|err| match err {
+        GrantError::InvalidPolicy(detail) => {
+            HttpError::with_detail(StatusCode::BAD_REQUEST, "invalid grant request", detail)
+        }
+        GrantError::Forbidden(detail) => {
+            HttpError::with_detail(StatusCode::FORBIDDEN, "grant request denied", detail)
+        }
+    })?;
+    let download_url = format!(
+        "/tenants/{tenant_id}/projects/{project_id}/content/{content_id}/download?grant={grant}"
+    );
+    Ok(Json(ContentGrantResponse { grant, download_url }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tenants/{tenant_id}/projects/{project_id}/content/{content_id}/download",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("content_id" = Uuid, Path, description = "Content identifier"),
+        DownloadParams
+    ),
+    responses(
+        (status = 200, description = "Full object body"),
+        (status = 206, description = "Partial object body for a satisfiable Range request"),
+        (status = 304, description = "Content unchanged since the conditional request's validator"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope, or the grant doesn't cover this request", body = ErrorResponse),
+        (status = 404, description = "Content not found", body = ErrorResponse),
+        (status = 416, description = "Range not satisfiable", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn download_content(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, content_id)): Path<(Uuid, Uuid, Uuid)>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    Query(params): Query<DownloadParams>,
+    headers: HeaderMap,
+) -> Result<Response, HttpError> {
+    match &params.grant {
+        Some(token) => {
+            verify_content_grant(token, content_id, tenant_id, ContentPermission::Read).map_err(
+                |err| {
+                    let (GrantError::Forbidden(detail) | GrantError::InvalidPolicy(detail)) = err;
+                    HttpError::with_detail(StatusCode::FORBIDDEN, "invalid grant", detail)
+                },
+            )?
+        }
+        None => ensure_scope_or_sigv4(&state, &method, &uri, &headers, b"", "ugc:read").await?,
+    }
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:download_content",
+        120,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let metadata = state
+        .content_store
+        .get_content_metadata(content_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "content not found"))?;
+    if metadata.tenant_id != tenant_id || metadata.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "content scope mismatch",
+        ));
+    }
+    let etag = content_etag(&metadata);
+    if is_not_modified(&headers, &etag, metadata.updated_at) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        response.headers_mut().insert(
+            axum::http::header::ETAG,
+            axum::http::HeaderValue::from_str(&etag).expect("ascii etag value"),
+        );
+        response.headers_mut().insert(
+            axum::http::header::LAST_MODIFIED,
+            axum::http::HeaderValue::from_str(&http_date(metadata.updated_at))
+                .expect("ascii last-modified value"),
+        );
+        response.headers_mut().insert(
+            axum::http::header::ACCEPT_RANGES,
+            axum::http::HeaderValue::from_static("bytes"),
+        );
+        return Ok(response);
+    }
+    let storage_path = metadata
+        .storage_path
+        .clone()
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "content has no stored object"))?;
+    let upload_url = storage_base_url().map(|base| format!("{base}/{storage_path}"));
+    let object_bytes = state
+        .object_fetcher
+        .fetch(&storage_path, upload_url.as_deref())
+        .await?;
+    let total_len = object_bytes.len() as u64;
+
+    let range_header = headers
+        .get(axum::http::header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .filter(|_| if_range_satisfied(&headers, &etag, metadata.updated_at));
+    let mut response = match range_header {
+        Some(raw) => match parse_byte_range(raw, total_len) {
+            Some(range) => {
+                let body = object_bytes[range.start as usize..=range.end as usize].to_vec();
+                let mut response = (StatusCode::PARTIAL_CONTENT, body).into_response();
+                response.headers_mut().insert(
+                    axum::http::header::CONTENT_RANGE,
+                    axum::http::HeaderValue::from_str(&format!(
+                        "bytes {}-{}/{}",
+                        range.start, range.end, total_len
+                    ))
+                    .expect("ascii content-range value"),
+                );
+                response
+            }
+            None => {
+                let mut response =
+                    HttpError::new(StatusCode::RANGE_NOT_SATISFIABLE, "range not satisfiable")
+                        .into_response();
+                response.headers_mut().insert(
+                    axum::http::header::CONTENT_RANGE,
+                    axum::http::HeaderValue::from_str(&format!("bytes */{total_len}"))
+                        .expect("ascii content-range value"),
+                );
+                return Ok(response);
+            }
+        },
+        None => (StatusCode::OK, object_bytes).into_response(),
+    };
+    response.headers_mut().insert(
+        axum::http::header::CONTENT_TYPE,
+        metadata
+            .mime_type
+            .as_deref()
+            .and_then(|mime| axum::http::HeaderValue::from_str(mime).ok())
+            .unwrap_or_else(|| axum::http::HeaderValue::from_static("application/octet-stream")),
+    );
+    response.headers_mut().insert(
+        axum::http::header::ACCEPT_RANGES,
+        axum::http::HeaderValue::from_static("bytes"),
+    );
+    response.headers_mut().insert(
+        axum::http::header::ETAG,
+        axum::http::HeaderValue::from_str(&etag).expect("ascii etag value"),
+    );
+    response.headers_mut().insert(
+        axum::http::header::LAST_MODIFIED,
+        axum::http::HeaderValue::from_str(&http_date(metadata.updated_at))
+            .expect("ascii last-modified value"),
+    );
+    let mut metric_labels = HashMap::new();
+    metric_labels.insert("tenant_id".to_string(), tenant_id.to_string());
+    metric_labels.insert("project_id".to_string(), project_id.to_string());
+    state.telemetry.metrics.increment_counter(
+        "ugc_content_downloads",
+        1.0,
+        Some(metric_labels),
+    );
+    Ok(response)
 }
 
 #[utoipa::path(
@@ -554,41 +2477,78 @@ pub async fn complete_upload_session(
         ListContentParams
     ),
     responses(
-        (status = 200, description = "Content metadata list", body = [ContentMetadataResponse])
+        (status = 200, description = "Content metadata page", body = ContentListResponse),
+        (status = 400, description = "Malformed or cross-tenant cursor", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
     ),
     security(("ApiKey" = []), ("BearerAuth" = []))
 )]
 pub async fn list_content_metadata(
     State(state): State<AppState>,
     Path((tenant_id, project_id)): Path<(Uuid, Uuid)>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
     headers: HeaderMap,
     Query(params): Query<ListContentParams>,
-) -> Result<Json<Vec<ContentMetadataResponse>>, HttpError> {
-    ensure_scope(&headers, "ugc:read")?;
-    if !state.rate_limiter.check_and_increment(
+) -> Result<Json<ContentListResponse>, HttpError> {
+    ensure_scope_or_sigv4(&state, &method, &uri, &headers, b"", "ugc:read").await?;
+    if !state.check_rate_limit(
         tenant_id,
         "ugc:list_content",
         120,
         StdDuration::from_secs(60),
-    ) {
+    ).await {
         return Err(HttpError::new(
             StatusCode::TOO_MANY_REQUESTS,
             "rate limit exceeded",
         ));
     }
+    let cursor_key = params
+        .cursor
+        .as_deref()
+        .map(|raw| {
+            decode_tenant_cursor::<ContentCursorKey>(raw, tenant_id)
+                .map_err(|msg| HttpError::new(StatusCode::BAD_REQUEST, msg))
+        })
+        .transpose()?;
+    let limit = params.limit.unwrap_or(50);
     let query = ContentQuery {
         tenant_id,
         project_id: Some(project_id),
         search_term: params.search,
         tags: params.tags,
-        limit: params.limit,
-        offset: params.offset,
+        limit: Some(limit + 1),
+        offset: if cursor_key.is_some() {
+            None
+        } else {
+            params.offset
+        },
+        cursor_created_at: cursor_key
+            .as_ref()
+            .and_then(|key| datetime_from_millis(key.created_at_unix_ms)),
+        cursor_id: cursor_key.as_ref().map(|key| key.id),
     };
-    let items = state
+    let mut items = state
         .content_store
         .list_content_metadata(&query)
         .await
         .map_err(HttpError::from)?;
+    let has_more = items.len() > limit as usize;
+    items.truncate(limit as usize);
+    let next_cursor = if has_more {
+        items.last().map(|item| {
+            encode_tenant_cursor(
+                tenant_id,
+                ContentCursorKey {
+                    created_at_unix_ms: item.created_at.timestamp_millis(),
+                    id: item.id,
+                },
+            )
+        })
+    } else {
+        None
+    };
     let mut metric_labels = HashMap::new();
     metric_labels.insert("tenant_id".to_string(), tenant_id.to_string());
     metric_labels.insert("project_id".to_string(), project_id.to_string());
@@ -606,16 +2566,415 @@ pub async fn list_content_metadata(
                 "count": items.len(),
                 "search_term": query.search_term,
                 "tags": query.tags,
-                "limit": query.limit,
+                "limit": limit,
                 "offset": query.offset
             })),
     );
-    Ok(Json(
-        items
-            .into_iter()
-            .map(ContentMetadataResponse::from)
-            .collect(),
-    ))
+    Ok(Json(ContentListResponse {
+        items: items.into_iter().map(ContentMetadataResponse::from).collect(),
+        next_cursor,
+    }))
+}
+
+/// `true` if `op` would loosen an existing retention guarantee: releasing
+/// `legal_hold` or handing back an `Unlocked` immutability policy. Those
+/// directions need [`RETENTION_RELEASE_SCOPE`] on top of the batch
+/// endpoint's ordinary `ugc:write` gate — tightening retention (setting
+/// `legal_hold` or locking a policy) doesn't.
+fn is_retention_release(legal_hold: Option<bool>, immutability: &Option<ImmutabilityPolicy>) -> bool {
+    legal_hold == Some(false)
+        || matches!(immutability, Some(policy) if policy.state == ImmutabilityState::Unlocked)
+}
+
+/// Scope required, in addition to `ugc:write`, to release a legal hold or
+/// hand back an `Unlocked` immutability policy via [`ContentBatchOperation::SetRetention`].
+/// Without this, any caller authorized for ordinary content writes could
+/// flip `legal_hold` off and immediately delete or overwrite the content
+/// through the normal write path, defeating the WORM/legal-hold guarantee.
+const RETENTION_RELEASE_SCOPE: &str = "ugc:release_retention";
+
+/// Runs one [`ContentBatchOperation`] and reports its outcome with its own
+/// status code, independent of how the rest of the batch fared.
+async fn apply_content_batch_operation(
+    state: &AppState,
+    headers: &HeaderMap,
+    op: ContentBatchOperation,
+) -> ContentBatchItemResult {
+    match op {
+        ContentBatchOperation::Read { id } => match state.content_store.get_content_metadata(id).await {
+            Ok(Some(metadata)) => ContentBatchItemResult {
+                id,
+                status: StatusCode::OK,
+                metadata: Some(ContentMetadataResponse::from(metadata)),
+                error: None,
+            },
+            Ok(None) => ContentBatchItemResult {
+                id,
+                status: StatusCode::NOT_FOUND,
+                metadata: None,
+                error: Some("content not found".to_string()),
+            },
+            Err(err) => {
+                let err = HttpError::from(err);
+                ContentBatchItemResult {
+                    id,
+                    status: err.status,
+                    metadata: None,
+                    error: Some(err.title.to_string()),
+                }
+            }
+        },
+        ContentBatchOperation::Delete { id } => match state.content_store.delete_content_metadata(id).await {
+            Ok(()) => ContentBatchItemResult {
+                id,
+                status: StatusCode::NO_CONTENT,
+                metadata: None,
+                error: None,
+            },
+            Err(err) => {
+                let err = HttpError::from(err);
+                ContentBatchItemResult {
+                    id,
+                    status: err.status,
+                    metadata: None,
+                    error: Some(err.title.to_string()),
+                }
+            }
+        },
+        ContentBatchOperation::SetLabels { id, labels } => {
+            match state.content_store.set_content_labels(id, labels).await {
+                Ok(()) => match state.content_store.get_content_metadata(id).await {
+                    Ok(metadata) => ContentBatchItemResult {
+                        id,
+                        status: StatusCode::OK,
+                        metadata: metadata.map(ContentMetadataResponse::from),
+                        error: None,
+                    },
+                    Err(err) => {
+                        let err = HttpError::from(err);
+                        ContentBatchItemResult {
+                            id,
+                            status: err.status,
+                            metadata: None,
+                            error: Some(err.title.to_string()),
+                        }
+                    }
+                },
+                Err(err) => {
+                    let err = HttpError::from(err);
+                    ContentBatchItemResult {
+                        id,
+                        status: err.status,
+                        metadata: None,
+                        error: Some(err.title.to_string()),
+                    }
+                }
+            }
+        }
+        ContentBatchOperation::SetRetention {
+            id,
+            legal_hold,
+            immutability,
+        } => {
+            if is_retention_release(legal_hold, &immutability)
+                && ensure_scope(state, headers, RETENTION_RELEASE_SCOPE).await.is_err()
+            {
+                return ContentBatchItemResult {
+                    id,
+                    status: StatusCode::FORBIDDEN,
+                    metadata: None,
+                    error: Some("releasing retention requires the ugc:release_retention scope".to_string()),
+                };
+            }
+            match state
+                .content_store
+                .set_content_retention(id, legal_hold, immutability)
+                .await
+            {
+                Ok(()) => match state.content_store.get_content_metadata(id).await {
+                    Ok(metadata) => ContentBatchItemResult {
+                        id,
+                        status: StatusCode::OK,
+                        metadata: metadata.map(ContentMetadataResponse::from),
+                        error: None,
+                    },
+                    Err(err) => {
+                        let err = HttpError::from(err);
+                        ContentBatchItemResult {
+                            id,
+                            status: err.status,
+                            metadata: None,
+                            error: Some(err.title.to_string()),
+                        }
+                    }
+                },
+                Err(err) => {
+                    let err = HttpError::from(err);
+                    ContentBatchItemResult {
+                        id,
+                        status: err.status,
+                        metadata: None,
+                        error: Some(err.title.to_string()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/projects/{project_id}/content/batch",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier")
+    ),
+    request_body = ContentBatchRequest,
+    responses(
+        (status = 200, description = "Per-item outcomes, one per submitted operation", body = ContentBatchResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn batch_content_operations(
+    State(state): State<AppState>,
+    Path((tenant_id, _project_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(payload): Json<ContentBatchRequest>,
+) -> Result<Json<ContentBatchResponse>, HttpError> {
+    ensure_scope(&state, &headers, "ugc:write").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:batch_content",
+        60,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let mut results = Vec::with_capacity(payload.operations.len());
+    for op in payload.operations {
+        results.push(apply_content_batch_operation(&state, &headers, op).await);
+    }
+    Ok(Json(ContentBatchResponse { results }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/projects/{project_id}/content/batch/search",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier")
+    ),
+    request_body = ContentBatchSearchRequest,
+    responses(
+        (status = 200, description = "Matched content metadata page", body = ContentBatchSearchResponse),
+        (status = 400, description = "Malformed or cross-tenant cursor", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn batch_search_content(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id)): Path<(Uuid, Uuid)>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ContentBatchSearchResponse>, HttpError> {
+    ensure_scope_or_sigv4(&state, &method, &uri, &headers, &body, "ugc:read").await?;
+    let payload: ContentBatchSearchRequest = serde_json::from_slice(&body)
+        .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid request body"))?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:batch_search_content",
+        60,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let cursor_key = payload
+        .cursor
+        .as_deref()
+        .map(|raw| {
+            decode_tenant_cursor::<ContentCursorKey>(raw, tenant_id)
+                .map_err(|msg| HttpError::new(StatusCode::BAD_REQUEST, msg))
+        })
+        .transpose()?;
+    let limit = payload.limit.unwrap_or(50);
+    let query = ContentQuery {
+        tenant_id,
+        project_id: Some(project_id),
+        search_term: payload.search,
+        tags: payload.tags,
+        limit: Some(limit + 1),
+        offset: None,
+        cursor_created_at: cursor_key
+            .as_ref()
+            .and_then(|key| datetime_from_millis(key.created_at_unix_ms)),
+        cursor_id: cursor_key.as_ref().map(|key| key.id),
+    };
+    let mut items = state
+        .content_store
+        .list_content_metadata(&query)
+        .await
+        .map_err(HttpError::from)?;
+    if !payload.attributes.is_empty() {
+        items.retain(|item| {
+            payload
+                .attributes
+                .iter()
+                .all(|(k, v)| item.attributes.get(k) == Some(v))
+        });
+    }
+    let has_more = items.len() > limit as usize;
+    items.truncate(limit as usize);
+    let next = if has_more {
+        items.last().map(|item| {
+            encode_tenant_cursor(
+                tenant_id,
+                ContentCursorKey {
+                    created_at_unix_ms: item.created_at.timestamp_millis(),
+                    id: item.id,
+                },
+            )
+        })
+    } else {
+        None
+    };
+    Ok(Json(ContentBatchSearchResponse {
+        items: items.into_iter().map(ContentMetadataResponse::from).collect(),
+        next,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tenants/{tenant_id}/projects/{project_id}/content/{content_id}/thumbnail",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("project_id" = Uuid, Path, description = "Project identifier"),
+        ("content_id" = Uuid, Path, description = "Content identifier"),
+        ThumbnailParams
+    ),
+    responses(
+        (status = 200, description = "Cached derivative body"),
+        (status = 202, description = "Derivative is generating", body = RenditionJobResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Content not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn get_content_thumbnail(
+    State(state): State<AppState>,
+    Path((tenant_id, project_id, content_id)): Path<(Uuid, Uuid, Uuid)>,
+    headers: HeaderMap,
+    Query(params): Query<ThumbnailParams>,
+) -> Result<Response, HttpError> {
+    ensure_scope(&state, &headers, "ugc:read").await?;
+    if !state.check_rate_limit(
+        tenant_id,
+        "ugc:thumbnail",
+        120,
+        StdDuration::from_secs(60),
+    ).await {
+        return Err(HttpError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded",
+        ));
+    }
+    let parent = state
+        .content_store
+        .get_content_metadata(content_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "content not found"))?;
+    if parent.tenant_id != tenant_id || parent.project_id != project_id {
+        return Err(HttpError::new(
+            StatusCode::FORBIDDEN,
+            "content scope mismatch",
+        ));
+    }
+    let spec = RenditionSpec::normalize(params.w, params.h, params.format).map_err(HttpError::from)?;
+    let source_checksum = parent.checksum.clone().unwrap_or_else(|| parent.id.to_string());
+    let cache_key = spec.cache_key(&source_checksum);
+
+    let outcome = crate::rendition::schedule_or_reuse(
+        &state,
+        tenant_id,
+        project_id,
+        content_id,
+        spec,
+        cache_key,
+    )
+    .await?;
+
+    match outcome {
+        crate::rendition::ThumbnailLookup::Ready(child) => {
+            let storage_path = child.storage_path.clone().ok_or_else(|| {
+                HttpError::new(StatusCode::INTERNAL_SERVER_ERROR, "rendition missing storage path")
+            })?;
+            let bytes = state.object_fetcher.fetch(&storage_path, None).await?;
+            let mut response = (StatusCode::OK, bytes).into_response();
+            response.headers_mut().insert(
+                axum::http::header::CONTENT_TYPE,
+                child
+                    .mime_type
+                    .as_deref()
+                    .and_then(|mime| axum::http::HeaderValue::from_str(mime).ok())
+                    .unwrap_or_else(|| axum::http::HeaderValue::from_static("application/octet-stream")),
+            );
+            Ok(response)
+        }
+        crate::rendition::ThumbnailLookup::Job(task) => Ok((
+            StatusCode::ACCEPTED,
+            Json(RenditionJobResponse {
+                job_id: task.id,
+                status: task.status.as_str().to_string(),
+            }),
+        )
+            .into_response()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/tenants/{tenant_id}/jobs/{job_id}",
+    params(
+        ("tenant_id" = Uuid, Path, description = "Tenant identifier"),
+        ("job_id" = Uuid, Path, description = "Job identifier")
+    ),
+    responses(
+        (status = 200, description = "Job status", body = JobStatusResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 404, description = "Job not found", body = ErrorResponse)
+    ),
+    security(("ApiKey" = []), ("BearerAuth" = []))
+)]
+pub async fn get_job_status(
+    State(state): State<AppState>,
+    Path((tenant_id, job_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<Json<JobStatusResponse>, HttpError> {
+    ensure_scope(&state, &headers, "ugc:read").await?;
+    let task = state
+        .rendition_engine
+        .get_task(job_id)
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "job not found"))?;
+    if task.tenant_id != tenant_id {
+        return Err(HttpError::new(StatusCode::FORBIDDEN, "job scope mismatch"));
+    }
+    Ok(Json(JobStatusResponse::from_task(task)))
 }
 
 #[utoipa::path(
@@ -623,7 +2982,11 @@ pub async fn list_content_metadata(
     path = "/telemetry/logs",
     params(ListLogsParams),
     responses(
-        (status = 200, description = "Recent structured log events", body = [TelemetryLogResponse])
+        (status = 200, description = "Recent structured log events", body = LogListResponse),
+        (status = 400, description = "Malformed cursor", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Credential lacks the required scope", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = ErrorResponse)
     ),
     security(("ApiKey" = []), ("BearerAuth" = []))
 )]
@@ -631,30 +2994,75 @@ pub async fn list_recent_logs(
     State(state): State<AppState>,
     headers: HeaderMap,
     Query(params): Query<ListLogsParams>,
-) -> Result<Json<Vec<TelemetryLogResponse>>, HttpError> {
-    ensure_scope(&headers, "observability:read")?;
-    if !state.rate_limiter.check_and_increment(
+) -> Result<Json<LogListResponse>, HttpError> {
+    ensure_scope(&state, &headers, "observability:read").await?;
+    if !state.check_rate_limit(
         Uuid::nil(),
         "observability:list_logs",
         30,
         StdDuration::from_secs(60),
-    ) {
+    ).await {
         return Err(HttpError::new(
             StatusCode::TOO_MANY_REQUESTS,
             "rate limit exceeded",
         ));
     }
     let limit = params.limit.unwrap_or(100).min(500);
-    let events = state.telemetry.log_sink.snapshot();
-    let start = events.len().saturating_sub(limit);
-    let slice = events.into_iter().skip(start).collect::<Vec<_>>();
+    let events: Vec<LogEvent> = state
+        .telemetry
+        .log_sink
+        .snapshot()
+        .into_iter()
+        .filter(|event| {
+            event
+                .tenant_id
+                .as_deref()
+                .map(|t| !state.opt_outs.is_tenant_suppressed(t))
+                .unwrap_or(true)
+        })
+        .collect();
+    let upper_bound = match params.cursor.as_deref().map(decode_log_cursor).transpose()? {
+        Some(boundary) => boundary.min(events.len()),
+        None => events.len(),
+    };
+    let start = upper_bound.saturating_sub(limit);
+    let slice = events[start..upper_bound].to_vec();
+    let next_cursor = if start > 0 {
+        Some(encode_log_cursor(start))
+    } else {
+        None
+    };
     state
         .telemetry
         .metrics
         .set_gauge("gateway_log_buffer_size", slice.len() as f64, None);
-    Ok(Json(
-        slice.into_iter().map(TelemetryLogResponse::from).collect(),
-    ))
+    Ok(Json(LogListResponse {
+        items: slice.into_iter().map(TelemetryLogResponse::from).collect(),
+        next_cursor,
+    }))
+}
+
+/// An opaque, base64-encoded cursor for `/telemetry/logs`: the index into
+/// the in-memory log buffer just past the oldest event already returned, so
+/// the next page can continue further back in time in O(limit).
+#[derive(Debug, Serialize, Deserialize)]
+struct LogCursor {
+    boundary: usize,
+}
+
+fn encode_log_cursor(boundary: usize) -> String {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&LogCursor { boundary }).expect("cursor serializes"))
+}
+
+fn decode_log_cursor(raw: &str) -> Result<usize, HttpError> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    let bytes = URL_SAFE_NO_PAD
+        .decode(raw)
+        .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid cursor"))?;
+    let cursor: LogCursor = serde_json::from_slice(&bytes)
+        .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid cursor"))?;
+    Ok(cursor.boundary)
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -689,6 +3097,80 @@ impl AgentPresenceFilter {
     }
 }
 
+/// An opaque, base64-encoded `(last_seen_unix_ms, id)` keyset cursor for
+/// `/agents`, matching the list's `(last_seen DESC, id ASC)` sort order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AgentCursor {
+    last_seen_unix_ms: u64,
+    id: String,
+}
+
+fn encode_agent_cursor(last_seen_unix_ms: u64, id: &str) -> String {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    let cursor = AgentCursor {
+        last_seen_unix_ms,
+        id: id.to_string(),
+    };
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&cursor).expect("cursor serializes"))
+}
+
+fn decode_agent_cursor(raw: &str) -> Result<AgentCursor, HttpError> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    let bytes = URL_SAFE_NO_PAD
+        .decode(raw)
+        .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid cursor"))?;
+    serde_json::from_slice(&bytes).map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid cursor"))
+}
+
+/// `true` if `agent` sorts strictly after `cursor` in the list's
+/// `(last_seen DESC, id ASC)` order, i.e. belongs on the next page.
+fn agent_is_after_cursor(agent: &AgentSummary, cursor: &AgentCursor) -> bool {
+    match agent.last_seen_unix_ms.cmp(&cursor.last_seen_unix_ms) {
+        std::cmp::Ordering::Less => true,
+        std::cmp::Ordering::Greater => false,
+        std::cmp::Ordering::Equal => agent.id.as_str() > cursor.id.as_str(),
+    }
+}
+
+fn paginate_agent_summaries(
+    agents: Vec<AgentSummary>,
+    params: &ListAgentsParams,
+    cursor: Option<&AgentCursor>,
+    opt_outs: &OptOutRegistry,
+) -> AgentListResponse {
+    let mut agents = filter_agent_summaries(agents, params);
+    agents.retain(|agent| {
+        !opt_outs.is_agent_suppressed(&agent.id, agent.tenant_id.as_deref())
+    });
+    agents.sort_by(|a, b| {
+        b.last_seen_unix_ms
+            .cmp(&a.last_seen_unix_ms)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    if let Some(cursor) = cursor {
+        agents.retain(|agent| agent_is_after_cursor(agent, cursor));
+    }
+
+    let offset = if cursor.is_some() {
+        0
+    } else {
+        params.offset.unwrap_or(0) as usize
+    };
+    let limit = params.limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    let remaining: Vec<AgentSummary> = agents.into_iter().skip(offset).collect();
+    let has_more = remaining.len() > limit;
+    let items: Vec<AgentSummary> = remaining.into_iter().take(limit).collect();
+    let next_cursor = if has_more {
+        items
+            .last()
+            .map(|agent| encode_agent_cursor(agent.last_seen_unix_ms, &agent.id))
+    } else {
+        None
+    };
+    set_agents_returned(items.len());
+    AgentListResponse { items, next_cursor }
+}
+
 fn filter_agent_summaries(
     mut agents: Vec<AgentSummary>,
     params: &ListAgentsParams,
@@ -760,19 +3242,15 @@ fn filter_agent_summaries(
         true
     });
 
-    agents.sort_by(|a, b| {
-        b.last_seen_unix_ms
-            .cmp(&a.last_seen_unix_ms)
-            .then_with(|| a.id.cmp(&b.id))
-    });
-
-    let offset = params.offset.unwrap_or(0) as usize;
-    let limit = params.limit.map(|v| v as usize).unwrap_or(usize::MAX);
-    agents.into_iter().skip(offset).take(limit).collect()
+    agents
 }
 
 #[cfg(feature = "db")]
-fn build_agent_query(params: &ListAgentsParams) -> Result<AgentQuery, HttpError> {
+fn build_agent_query(
+    params: &ListAgentsParams,
+    cursor: Option<&AgentCursor>,
+    opt_outs: &OptOutRegistry,
+) -> Result<AgentQuery, HttpError> {
     use uuid::Uuid;
 
     let tenant_id = params
@@ -800,6 +3278,26 @@ fn build_agent_query(params: &ListAgentsParams) -> Result<AgentQuery, HttpError>
         .lifecycle_status
         .as_ref()
         .map(|s| s.to_ascii_lowercase());
+    let (cursor_last_seen, cursor_id) = match cursor {
+        Some(cursor) => {
+            let id = Uuid::parse_str(&cursor.id)
+                .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid cursor"))?;
+            let ts = datetime_from_millis(cursor.last_seen_unix_ms as i64)
+                .ok_or_else(|| HttpError::new(StatusCode::BAD_REQUEST, "invalid cursor"))?;
+            (Some(ts), Some(id))
+        }
+        None => (None, None),
+    };
+    let excluded_agent_ids = opt_outs
+        .opted_out_agent_ids()
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect();
+    let excluded_tenant_ids = opt_outs
+        .opted_out_tenant_ids()
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect();
 
     Ok(AgentQuery {
         tenant_id,
@@ -810,6 +3308,10 @@ fn build_agent_query(params: &ListAgentsParams) -> Result<AgentQuery, HttpError>
         last_seen_before,
         limit: params.limit.map(|v| v as i64),
         offset: params.offset.map(|v| v as i64),
+        cursor_last_seen,
+        cursor_id,
+        excluded_agent_ids,
+        excluded_tenant_ids,
     })
 }
 
@@ -830,6 +3332,13 @@ fn map_agent_record(record: AgentSummaryRecord) -> AgentSummary {
         tenant_id: record.tenant_id.map(|id| id.to_string()),
         project_id: record.project_id.map(|id| id.to_string()),
         lifecycle_status: record.lifecycle_status,
+        os: None,
+        arch: None,
+        cpu_cores: None,
+        heartbeat_interval_seconds: None,
+        advertise_addr: None,
+        zone: None,
+        memory_bytes: None,
     }
 }
 
@@ -851,29 +3360,90 @@ fn datetime_from_millis(value: i64) -> Option<DateTime<Utc>> {
         health,
         version,
         metrics,
+        admin_telemetry_metrics,
+        capabilities,
         list_agents,
+        admin_list_nodes,
+        admin_get_node,
+        admin_delete_node,
+        admin_opt_out_agent,
+        admin_clear_agent_opt_out,
+        admin_get_agent_enrollment,
+        admin_verify_agent,
+        admin_opt_out_tenant,
+        admin_clear_tenant_opt_out,
         create_upload_session,
         complete_upload_session,
+        request_upload_part,
+        register_upload_part,
+        upload_part_bytes,
+        abort_upload_session,
         list_content_metadata,
-        list_recent_logs
+        batch_content_operations,
+        batch_search_content,
+        download_content,
+        create_content_grant,
+        get_content_thumbnail,
+        get_job_status,
+        list_recent_logs,
+        set_lifecycle_policy,
+        list_lifecycle_policies,
+        delete_lifecycle_policy,
+        sweep_expired_content
     ),
     components(
         schemas(
             HealthResponse,
             VersionResponse,
+            CapabilitiesResponse,
             AgentSummary,
             ListAgentsParams,
+            AgentListResponse,
             ErrorResponse,
+            ErrorDetailResponse,
+            ErrorAdditionalInfoResponse,
+            ProblemDetail,
+            AdminNodeResponse,
+            AdminNodeDetailResponse,
+            AgentEnrollmentResponse,
+            NodeHeartbeatSample,
             CreateUploadRequest,
             CompleteUploadRequest,
+            CompletedPart,
+            RequestUploadPartRequest,
+            RegisterUploadPartRequest,
+            UploadPartUrlResponse,
+            UploadPartResponse,
             ListContentParams,
             ListLogsParams,
             UploadSessionResponse,
             ContentMetadataResponse,
-            TelemetryLogResponse
+            ContentListResponse,
+            ContentBatchOperation,
+            ContentBatchItemResult,
+            ContentBatchRequest,
+            ContentBatchResponse,
+            ContentBatchSearchRequest,
+            ContentBatchSearchResponse,
+            CreateContentGrantRequest,
+            ContentGrantResponse,
+            DownloadParams,
+            ThumbnailParams,
+            RenditionJobResponse,
+            JobStatusResponse,
+            TelemetryLogResponse,
+            LogListResponse,
+            SetLifecyclePolicyRequest,
+            LifecyclePolicyResponse,
+            LifecyclePolicyListResponse,
+            LifecycleOutcomeResponse,
+            LifecycleSweepResponse
         )
     ),
-    tags( (name = "system", description = "System & meta endpoints") )
+    tags(
+        (name = "system", description = "System & meta endpoints"),
+        (name = "admin", description = "Operator-facing fleet administration endpoints")
+    )
 )]
 pub struct ApiDoc;
 
@@ -882,7 +3452,27 @@ pub fn router() -> Router<AppState> {
         .route("/health", get(health))
         .route("/version", get(version))
         .route("/metrics", get(metrics))
+        .route("/admin/telemetry/metrics", get(admin_telemetry_metrics))
+        .route("/capabilities", get(capabilities))
         .route("/agents", get(list_agents))
+        .route("/admin/nodes", get(admin_list_nodes))
+        .route(
+            "/admin/nodes/:id",
+            get(admin_get_node).delete(admin_delete_node),
+        )
+        .route(
+            "/admin/agents/:id/opt-out",
+            post(admin_opt_out_agent).delete(admin_clear_agent_opt_out),
+        )
+        .route(
+            "/admin/agents/:id/enrollment",
+            get(admin_get_agent_enrollment),
+        )
+        .route("/admin/agents/:id/verify", post(admin_verify_agent))
+        .route(
+            "/admin/tenants/:tenant_id/opt-out",
+            post(admin_opt_out_tenant).delete(admin_clear_tenant_opt_out),
+        )
         .route(
             "/tenants/:tenant_id/projects/:project_id/uploads",
             post(create_upload_session),
@@ -891,21 +3481,102 @@ pub fn router() -> Router<AppState> {
             "/tenants/:tenant_id/projects/:project_id/uploads/:upload_id/complete",
             post(complete_upload_session),
         )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/uploads/:upload_id/parts",
+            post(request_upload_part).put(register_upload_part),
+        )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/uploads/:upload_id/parts/:part_number",
+            put(upload_part_bytes),
+        )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/uploads/:upload_id/abort",
+            post(abort_upload_session),
+        )
         .route(
             "/tenants/:tenant_id/projects/:project_id/content",
             get(list_content_metadata),
         )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/content/batch",
+            post(batch_content_operations),
+        )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/content/batch/search",
+            post(batch_search_content),
+        )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/content/:content_id/download",
+            get(download_content),
+        )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/content/:content_id/grant",
+            post(create_content_grant),
+        )
+        .route(
+            "/tenants/:tenant_id/projects/:project_id/content/:content_id/thumbnail",
+            get(get_content_thumbnail),
+        )
+        .route("/tenants/:tenant_id/jobs/:job_id", get(get_job_status))
+        .route(
+            "/tenants/:tenant_id/lifecycle-policies",
+            put(set_lifecycle_policy).get(list_lifecycle_policies),
+        )
+        .route(
+            "/tenants/:tenant_id/lifecycle-policies/:policy_id",
+            delete(delete_lifecycle_policy),
+        )
+        .route(
+            "/tenants/:tenant_id/lifecycle-policies/sweep",
+            post(sweep_expired_content),
+        )
         .route("/telemetry/logs", get(list_recent_logs))
+        .route("/node/:id/*rest", any(crate::proxy::proxy_to_node))
 }
 
-fn ensure_scope(headers: &HeaderMap, scope: &str) -> Result<(), HttpError> {
-    if has_scope(headers, scope) {
+/// Scope gate for every route below that requires one. Goes through
+/// `state.auth_providers` (`AppState::authenticate`/`resolve_scopes`)
+/// rather than the free `has_scope`/`validate_api_key`/`validate_jwt`
+/// functions, so a credential only the provider chain recognizes -- a
+/// directory bind via `LdapAuthProvider`, or an operator-configured
+/// `StaticDemoAuthProvider` entry -- actually grants access here, not just
+/// on `/admin/telemetry/metrics`. `UNRESTRICTED_SCOPE` mirrors
+/// `has_scope`'s treatment of a valid `x-api-key` as bypassing scope checks
+/// entirely.
+async fn ensure_scope(state: &AppState, headers: &HeaderMap, scope: &str) -> Result<(), HttpError> {
+    if !matches!(state.authenticate(headers).await, crate::auth::AuthStatus::Allow) {
+        return Err(HttpError::new(StatusCode::UNAUTHORIZED, "authentication required"));
+    }
+    let scopes = state.resolve_scopes(headers).await;
+    if scopes.iter().any(|s| s == scope || s == UNRESTRICTED_SCOPE) {
         Ok(())
     } else {
         Err(HttpError::new(StatusCode::FORBIDDEN, "scope required"))
     }
 }
 
+/// Same gate as `ensure_scope`, but first lets a valid SigV4 `Authorization`
+/// header through unconditionally — an S3-compatible SDK signing requests
+/// with an API key's access-key-id/secret pair has no `x-api-key` or bearer
+/// token to present, so it can't satisfy `ensure_scope` at all. Used on the
+/// upload/content routes AWS SDKs and presigned-URL clients actually hit.
+async fn ensure_scope_or_sigv4(
+    state: &AppState,
+    method: &Method,
+    uri: &axum::http::Uri,
+    headers: &HeaderMap,
+    body: &[u8],
+    scope: &str,
+) -> Result<(), HttpError> {
+    let skew = cncore::config().sigv4.clock_skew_seconds;
+    if sigv4::status(method, uri, headers, body, state.api_key_store.as_ref(), Utc::now(), skew)
+        == crate::auth::AuthStatus::Allow
+    {
+        return Ok(());
+    }
+    ensure_scope(state, headers, scope).await
+}
+
 fn build_storage_path(
     tenant_id: &Uuid,
     project_id: &Uuid,
@@ -921,7 +3592,16 @@ fn build_storage_path(
     )
 }
 
-fn storage_base_url() -> Option<String> {
+/// Where a single multipart part's raw bytes are staged until
+/// `complete_upload_session` assembles them (or `abort_upload_session`
+/// garbage-collects them). Deliberately separate from `build_storage_path`,
+/// which is keyed by `content_id`/`filename` and only ever names the
+/// finished object.
+fn part_storage_path(tenant_id: &Uuid, project_id: &Uuid, upload_id: &Uuid, part_number: u32) -> String {
+    format!("tenants/{tenant_id}/projects/{project_id}/uploads/{upload_id}/parts/{part_number:05}")
+}
+
+pub(crate) fn storage_base_url() -> Option<String> {
     match std::env::var("CASS_STORAGE_BASE_URL") {
         Ok(value) if !value.trim().is_empty() => Some(value),
         _ => None,
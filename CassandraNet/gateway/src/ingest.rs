@@ -0,0 +1,151 @@
+//! Fetches the bytes a client uploaded to presigned storage so
+//! `complete_upload_session` can run `cncore::platform::ingest` against the
+//! real object instead of trusting the request body. One implementation per
+//! backend, same shape as the persistence `*Store` traits: a real HTTP
+//! fetcher for production, an in-memory one for tests and local/dev runs
+//! where nothing is actually reachable over the network.
+
+use crate::http::HttpError;
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+#[async_trait]
+pub trait ObjectFetcher: Send + Sync {
+    /// Fetch the object stored at `storage_path`, preferring `upload_url`
+    /// when the backend needs a full URL rather than a bare key.
+    async fn fetch(&self, storage_path: &str, upload_url: Option<&str>) -> Result<Vec<u8>, HttpError>;
+
+    /// Write `bytes` to `storage_path`, used by the rendition worker to
+    /// persist a generated derivative next to its parent object.
+    async fn put(&self, storage_path: &str, bytes: Vec<u8>) -> Result<(), HttpError>;
+
+    /// Remove whatever is at `storage_path`, used to garbage-collect staged
+    /// multipart parts once a session is aborted or completed. Best-effort:
+    /// a missing object isn't an error, since GC may race a session that was
+    /// never actually staged.
+    async fn delete(&self, storage_path: &str) -> Result<(), HttpError>;
+}
+
+pub struct HttpObjectFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpObjectFetcher {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Default for HttpObjectFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ObjectFetcher for HttpObjectFetcher {
+    async fn fetch(&self, storage_path: &str, upload_url: Option<&str>) -> Result<Vec<u8>, HttpError> {
+        let url = match upload_url {
+            Some(url) if url.starts_with("http://") || url.starts_with("https://") => url.to_string(),
+            _ => {
+                return Err(HttpError::new(
+                    StatusCode::BAD_GATEWAY,
+                    "uploaded object is not reachable over http",
+                ))
+            }
+        };
+        let response = self.client.get(&url).send().await.map_err(|_| {
+            tracing::error!(storage_path, "ingest.fetch_failed");
+            HttpError::new(StatusCode::BAD_GATEWAY, "failed to fetch uploaded object")
+        })?;
+        if !response.status().is_success() {
+            return Err(HttpError::new(
+                StatusCode::BAD_GATEWAY,
+                "failed to fetch uploaded object",
+            ));
+        }
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|_| HttpError::new(StatusCode::BAD_GATEWAY, "failed to read uploaded object"))
+    }
+
+    async fn put(&self, storage_path: &str, bytes: Vec<u8>) -> Result<(), HttpError> {
+        let base = crate::http::storage_base_url().ok_or_else(|| {
+            HttpError::new(StatusCode::BAD_GATEWAY, "no storage backend configured")
+        })?;
+        let url = format!("{base}/{storage_path}");
+        let response = self.client.put(&url).body(bytes).send().await.map_err(|_| {
+            tracing::error!(storage_path, "ingest.put_failed");
+            HttpError::new(StatusCode::BAD_GATEWAY, "failed to store generated object")
+        })?;
+        if !response.status().is_success() {
+            return Err(HttpError::new(
+                StatusCode::BAD_GATEWAY,
+                "failed to store generated object",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, storage_path: &str) -> Result<(), HttpError> {
+        let base = crate::http::storage_base_url().ok_or_else(|| {
+            HttpError::new(StatusCode::BAD_GATEWAY, "no storage backend configured")
+        })?;
+        let url = format!("{base}/{storage_path}");
+        let response = self.client.delete(&url).send().await.map_err(|_| {
+            tracing::error!(storage_path, "ingest.delete_failed");
+            HttpError::new(StatusCode::BAD_GATEWAY, "failed to delete staged object")
+        })?;
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(HttpError::new(
+                StatusCode::BAD_GATEWAY,
+                "failed to delete staged object",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Test/local-dev backing store: holds bytes an in-process test "uploaded"
+/// directly, since nothing in this process ever receives a real presigned
+/// PUT.
+#[derive(Default, Clone)]
+pub struct InMemoryObjectFetcher(Arc<RwLock<HashMap<String, Vec<u8>>>>);
+
+impl InMemoryObjectFetcher {
+    pub fn put(&self, storage_path: impl Into<String>, bytes: Vec<u8>) {
+        self.0.write().unwrap().insert(storage_path.into(), bytes);
+    }
+}
+
+#[async_trait]
+impl ObjectFetcher for InMemoryObjectFetcher {
+    async fn fetch(&self, storage_path: &str, _upload_url: Option<&str>) -> Result<Vec<u8>, HttpError> {
+        self.0
+            .read()
+            .unwrap()
+            .get(storage_path)
+            .cloned()
+            .ok_or_else(|| HttpError::new(StatusCode::NOT_FOUND, "uploaded object not found in storage"))
+    }
+
+    async fn put(&self, storage_path: &str, bytes: Vec<u8>) -> Result<(), HttpError> {
+        self.put(storage_path.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn delete(&self, storage_path: &str) -> Result<(), HttpError> {
+        self.0.write().unwrap().remove(storage_path);
+        Ok(())
+    }
+}
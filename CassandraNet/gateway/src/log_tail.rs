@@ -0,0 +1,92 @@
+//! gRPC-facing counterpart to `LogPipeline::subscribe` — lets an external
+//! collector tail live log events instead of polling `/admin/logs`. Wired up
+//! in `main.rs` as a second service on the same `Server::builder()` as
+//! `grpc::InMemoryAgentControl`.
+
+use cncommon::observability::{LogEvent, LogLevel, LogPipeline, LogSubscriptionFilter};
+use cnproto::observability::{
+    log_tail_server::{LogTail, LogTailServer},
+    LogEventMessage, LogLevel as ProtoLogLevel, TailLogsRequest,
+};
+use futures::Stream;
+use std::pin::Pin;
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tonic::{codec::CompressionEncoding, Request as GrpcRequest, Response as GrpcResponse, Status as GrpcStatus};
+
+#[derive(Clone)]
+pub struct GrpcLogSink {
+    pipeline: LogPipeline,
+}
+
+impl GrpcLogSink {
+    pub fn new(pipeline: LogPipeline) -> Self {
+        Self { pipeline }
+    }
+
+    /// Wraps this sink in its tonic server, compressing stream bodies with
+    /// gzip in both directions — log volume can be substantial under load,
+    /// and collectors are rarely on the same host as the gateway.
+    pub fn into_server(self) -> LogTailServer<Self> {
+        LogTailServer::new(self)
+            .send_compressed(CompressionEncoding::Gzip)
+            .accept_compressed(CompressionEncoding::Gzip)
+    }
+}
+
+#[tonic::async_trait]
+impl LogTail for GrpcLogSink {
+    type TailLogsStream = Pin<Box<dyn Stream<Item = Result<LogEventMessage, GrpcStatus>> + Send + 'static>>;
+
+    async fn tail_logs(
+        &self,
+        request: GrpcRequest<TailLogsRequest>,
+    ) -> Result<GrpcResponse<Self::TailLogsStream>, GrpcStatus> {
+        let req = request.into_inner();
+        let filter = LogSubscriptionFilter {
+            min_level: req.min_level.and_then(proto_level_to_domain),
+            tenant_id: req.tenant_id,
+            component: req.component,
+        };
+        let receiver = self.pipeline.subscribe();
+        let stream = BroadcastStream::new(receiver).filter_map(move |result| match result {
+            Ok(event) if filter.matches(&event) => Some(Ok(to_proto(event))),
+            // A lagged receiver skipped some events; keep tailing from where
+            // it can, same as `emit`'s drop-oldest overflow policy intends.
+            Ok(_) | Err(_) => None,
+        });
+        Ok(GrpcResponse::new(Box::pin(stream)))
+    }
+}
+
+fn proto_level_to_domain(level: i32) -> Option<LogLevel> {
+    match ProtoLogLevel::try_from(level).ok()? {
+        ProtoLogLevel::Unspecified => None,
+        ProtoLogLevel::Trace => Some(LogLevel::Trace),
+        ProtoLogLevel::Debug => Some(LogLevel::Debug),
+        ProtoLogLevel::Info => Some(LogLevel::Info),
+        ProtoLogLevel::Warn => Some(LogLevel::Warn),
+        ProtoLogLevel::Error => Some(LogLevel::Error),
+    }
+}
+
+fn domain_level_to_proto(level: &LogLevel) -> ProtoLogLevel {
+    match level {
+        LogLevel::Trace => ProtoLogLevel::Trace,
+        LogLevel::Debug => ProtoLogLevel::Debug,
+        LogLevel::Info => ProtoLogLevel::Info,
+        LogLevel::Warn => ProtoLogLevel::Warn,
+        LogLevel::Error => ProtoLogLevel::Error,
+    }
+}
+
+fn to_proto(event: LogEvent) -> LogEventMessage {
+    LogEventMessage {
+        level: domain_level_to_proto(&event.level) as i32,
+        message: event.message,
+        timestamp_unix_ms: event.timestamp.timestamp_millis().max(0) as u64,
+        component: event.component,
+        tenant_id: event.tenant_id,
+        project_id: event.project_id,
+        metadata_json: event.metadata.to_string(),
+    }
+}
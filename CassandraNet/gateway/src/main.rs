@@ -1,22 +1,40 @@
 mod auth;
+mod auth_provider;
 mod cli;
+mod command_channel;
+mod compression;
+mod cors;
+mod enrollment;
+mod grant;
 mod grpc;
 mod http;
+mod ingest;
+mod log_tail;
 mod metrics;
+mod pagination;
+mod presign;
+mod proxy;
+mod rate_limit;
+mod rendition;
 mod state;
+mod tls;
+mod trace_context;
 
 #[cfg(test)]
 mod tests;
 
+use crate::compression::CompressionLayer;
+use crate::cors::CorsLayer;
 use crate::metrics::MetricsLayer;
+use crate::rate_limit::RateLimitLayer;
 use crate::state::AppState;
 use clap::Parser;
-use cncore::{config, init_tracing, shutdown_signal};
+use cncommon::observability::EventSink;
+use cncore::{config, init_tracing, shutdown_signal, EventsConfig};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tonic::transport::Server;
-use tower_http::cors::{Any, CorsLayer};
 use utoipa::OpenApi;
 
 #[tokio::main]
@@ -28,7 +46,7 @@ async fn main() -> anyhow::Result<()> {
     } = cli::CliArgs::parse();
 
     if print_config {
-        println!("{}", serde_json::to_string_pretty(config())?);
+        println!("{}", serde_json::to_string_pretty(&*config())?);
         return Ok(());
     }
 
@@ -61,10 +79,49 @@ async fn main() -> anyhow::Result<()> {
                 }
                 return Ok(());
             }
+            #[cfg(feature = "db")]
+            cli::CliCommand::Migrate { action } => {
+                match action {
+                    cli::MigrateAction::Status => {
+                        let statuses = cncore::migration_status().await?;
+                        let pending = statuses.iter().filter(|m| !m.applied).count();
+                        for m in &statuses {
+                            println!(
+                                "{:>14} {} {}",
+                                m.version,
+                                if m.applied { "[applied]" } else { "[pending]" },
+                                m.description
+                            );
+                        }
+                        println!("{pending} pending");
+                    }
+                    cli::MigrateAction::Up => {
+                        cncore::run_migrations().await?;
+                        println!("migrations up to date");
+                    }
+                    cli::MigrateAction::Down { steps } => {
+                        let reverted = cncore::migrate_down(steps).await?;
+                        if reverted.is_empty() {
+                            println!("nothing to revert");
+                        } else {
+                            for version in reverted {
+                                println!("reverted {version}");
+                            }
+                        }
+                    }
+                    cli::MigrateAction::Redo => {
+                        cncore::migrate_redo().await?;
+                        println!("redone last migration");
+                    }
+                }
+                return Ok(());
+            }
         }
     }
 
+    cncore::spawn_config_file_watcher();
     let cfg = config().clone();
+    let event_sink = build_event_sink(&cfg.events);
 
     #[cfg(feature = "db")]
     {
@@ -98,8 +155,9 @@ async fn main() -> anyhow::Result<()> {
         {
             use cncore::platform::persistence::{
                 PostgresAgentStore, PostgresContentStore, PostgresMessagingStore,
-                PostgresModerationStore, PostgresOrchestrationStore,
+                PostgresModerationStore, PostgresOrchestrationStore, PostgresRateLimitStore,
             };
+            use crate::state::{RateLimiter, StoreRateLimitBackend};
             let pool = cncore::db().await?.clone();
             let content_store: Arc<dyn cncore::platform::persistence::ContentStore> =
                 Arc::new(PostgresContentStore::new(pool.clone()));
@@ -109,12 +167,24 @@ async fn main() -> anyhow::Result<()> {
                 Arc::new(PostgresModerationStore::new(pool.clone()));
             let messaging_store: Arc<dyn cncore::platform::persistence::MessagingStore> =
                 Arc::new(PostgresMessagingStore::new(pool.clone()));
-            let agent_store = Arc::new(PostgresAgentStore::new(pool));
+            let agent_store = Arc::new(PostgresAgentStore::new(pool.clone()));
+            spawn_agent_reaper(agent_store.clone());
+            spawn_assignment_reaper(orchestration_store.clone());
+            spawn_message_retention_sweeper(messaging_store.clone());
+            spawn_moderation_expiry_sweeper(moderation_store.clone());
             let mut state = AppState::with_content_store(content_store);
+            spawn_lifecycle_sweeper(state.content_store.clone(), state.tenant_store.clone());
             state.orchestration_store = orchestration_store;
             state.moderation_store = moderation_store;
             state.messaging_store = messaging_store;
             state.agent_store = Some(agent_store);
+            // Multiple gateway replicas share this pool, so route through
+            // the store-backed limiter instead of each replica counting in
+            // isolation.
+            let rate_limit_store: Arc<dyn cncore::platform::persistence::RateLimitStore> =
+                Arc::new(PostgresRateLimitStore::new(pool));
+            state.rate_limiter =
+                RateLimiter::with_backend(Arc::new(StoreRateLimitBackend::new(rate_limit_store)));
             state
         }
         #[cfg(not(feature = "db"))]
@@ -126,25 +196,26 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods([
-            axum::http::Method::GET,
-            axum::http::Method::POST,
-            axum::http::Method::PUT,
-        ])
-        .allow_headers(Any);
+    let cors = CorsLayer::new(state.tenant_store.clone());
 
     let app = http::router()
         .with_state(state.clone())
+        .layer(RateLimitLayer::new())
         .layer(MetricsLayer)
+        .layer(CompressionLayer)
         .layer(cors)
         .merge(swagger);
     let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
 
+    let tls_settings = tls::TlsSettings::from_env()?;
+
     let addr: SocketAddr = cfg.http.bind_addr.parse()?;
     let listener = TcpListener::bind(addr).await?;
-    tracing::info!(%addr, "gateway listening (http + grpc on same port via hyper)");
+    tracing::info!(
+        %addr,
+        tls = tls_settings.is_some(),
+        "gateway listening (http + grpc on same port via hyper)"
+    );
 
     let grpc_service = {
         #[cfg(feature = "db")]
@@ -153,25 +224,367 @@ async fn main() -> anyhow::Result<()> {
                 state.registry.clone(),
                 state.agent_store.clone(),
             )
+            .with_history(state.node_history.clone())
+            .with_enrollment(state.enrollment.clone())
+            .with_command_channels(state.command_channels.clone())
         }
         #[cfg(not(feature = "db"))]
         {
             grpc::InMemoryAgentControl::new(state.registry.clone())
+                .with_history(state.node_history.clone())
+                .with_enrollment(state.enrollment.clone())
+                .with_command_channels(state.command_channels.clone())
         }
     }
+    .with_events(event_sink.clone())
+    .with_metrics(state.telemetry.metrics.clone())
     .into_server();
+    #[cfg(feature = "db")]
+    spawn_agent_liveness_reaper(
+        state.registry.clone(),
+        event_sink.clone(),
+        state.telemetry.metrics.clone(),
+        state.agent_store.clone(),
+    );
+    #[cfg(not(feature = "db"))]
+    spawn_agent_liveness_reaper(
+        state.registry.clone(),
+        event_sink.clone(),
+        state.telemetry.metrics.clone(),
+    );
+    state.rendition_engine.set_events(event_sink);
+    let log_tail_service = log_tail::GrpcLogSink::new(state.telemetry.logs.clone()).into_server();
     let mut grpc_addr = addr;
     grpc_addr.set_port(grpc_addr.port() + 1);
-    let grpc = Server::builder().add_service(grpc_service).serve(grpc_addr);
-    tracing::info!(%grpc_addr, "grpc listening");
+    let mut grpc_builder = Server::builder();
+    if let Some(settings) = &tls_settings {
+        grpc_builder = grpc_builder.tls_config(tls::tonic_tls_config(settings)?)?;
+    }
+    let grpc = grpc_builder
+        .add_service(grpc_service)
+        .add_service(log_tail_service)
+        .serve(grpc_addr);
+    tracing::info!(
+        %grpc_addr,
+        tls = tls_settings.is_some(),
+        mtls = tls_settings.as_ref().is_some_and(|s| s.require_client_cert),
+        "grpc listening"
+    );
     tokio::spawn(async move {
         if let Err(e) = grpc.await {
             tracing::error!(error = %e, "grpc server error");
         }
     });
 
-    axum::serve(listener, make_service)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    match tls_settings {
+        Some(settings) => {
+            let tls_listener = tls::TlsListener::new(listener, tls::server_config(&settings)?);
+            axum::serve(tls_listener, make_service)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?;
+        }
+        None => {
+            axum::serve(listener, make_service)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?;
+        }
+    }
     Ok(())
 }
+
+/// Builds the `EventSink` fleet events fan out to, per `cfg.backend`. Falls
+/// back to `NoopEventSink` for `"none"` and for any backend whose feature
+/// isn't compiled into this binary, rather than failing startup over an
+/// optional subsystem.
+fn build_event_sink(cfg: &EventsConfig) -> Arc<dyn EventSink> {
+    match cfg.backend.as_str() {
+        #[cfg(feature = "redis")]
+        "redis" => {
+            let Some(url) = cfg.redis_url.as_deref() else {
+                tracing::warn!("events.backend=redis but events.redis_url is unset; using no-op sink");
+                return Arc::new(cncommon::observability::NoopEventSink);
+            };
+            let channel = cfg.redis_channel.clone().unwrap_or_else(|| "cassandra.fleet".into());
+            match cncommon::observability::RedisEventSink::new(url, channel) {
+                Ok(sink) => Arc::new(sink),
+                Err(err) => {
+                    tracing::error!(error = %err, "events.redis.connect_failed; using no-op sink");
+                    Arc::new(cncommon::observability::NoopEventSink)
+                }
+            }
+        }
+        #[cfg(not(feature = "redis"))]
+        "redis" => {
+            tracing::warn!("events.backend=redis but this binary was built without the redis feature; using no-op sink");
+            Arc::new(cncommon::observability::NoopEventSink)
+        }
+        "mqtt" => {
+            // `MqttEventSink` needs a live, polled `rumqttc` connection
+            // handed in (see its doc comment); that requires its own
+            // driver task, which isn't wired up yet.
+            tracing::warn!("events.backend=mqtt is not wired up in this binary yet; using no-op sink");
+            Arc::new(cncommon::observability::NoopEventSink)
+        }
+        _ => Arc::new(cncommon::observability::NoopEventSink),
+    }
+}
+
+/// Runs `PostgresAgentStore::reap_stale_agents` on a timer for the lifetime
+/// of the process, logging every agent whose status changed so an operator
+/// tailing logs can see nodes go degraded/unreachable without polling.
+#[cfg(feature = "db")]
+fn spawn_agent_reaper(agent_store: Arc<cncore::platform::persistence::PostgresAgentStore>) {
+    use cncore::platform::persistence::AgentLifecycleConfig;
+
+    let config = AgentLifecycleConfig::default();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(30));
+        loop {
+            ticker.tick().await;
+            match agent_store.reap_stale_agents(chrono::Utc::now(), &config).await {
+                Ok(changed) => {
+                    for agent in changed {
+                        tracing::info!(
+                            agent_id = %agent.id,
+                            hostname = %agent.hostname,
+                            status = ?agent.lifecycle_status,
+                            "agent.lifecycle_transitioned"
+                        );
+                    }
+                }
+                Err(e) => tracing::error!(error = %e, "agent lifecycle reap failed"),
+            }
+        }
+    });
+}
+
+/// Periodically marks in-memory `AgentRegistry` entries `"offline"` once
+/// they've missed `agent_liveness.missed_heartbeat_threshold` heartbeat
+/// intervals, fanning the transition out as a `FleetEvent::AgentOffline` and
+/// a `cass_agents_marked_offline_total` counter bump. Runs regardless of
+/// which persistence backend the gateway is using, since the in-memory
+/// registry (not `PostgresAgentStore`) is what the `/agents` endpoint and the
+/// rendition scheduler actually read from; this is deliberately a separate
+/// mechanism from `spawn_agent_reaper`, which only ever touches the `nodes`
+/// table. Re-reads `agent_liveness` from `cncore::subscribe_config` before
+/// every tick, so a reload changes `scan_interval_seconds`/
+/// `missed_heartbeat_threshold` on the loop's next iteration instead of only
+/// at process startup.
+#[cfg(feature = "db")]
+fn spawn_agent_liveness_reaper(
+    registry: crate::state::AgentRegistry,
+    events: Arc<dyn EventSink>,
+    metrics: cncommon::observability::InMemoryMetricsRegistry,
+    agent_store: Option<Arc<cncore::platform::persistence::PostgresAgentStore>>,
+) {
+    let mut config_rx = cncore::subscribe_config();
+    tokio::spawn(async move {
+        loop {
+            let liveness_config = config_rx.borrow().agent_liveness.clone();
+            tokio::time::sleep(std::time::Duration::from_secs(
+                liveness_config.scan_interval_seconds.max(1),
+            ))
+            .await;
+            let changed = registry.mark_stale_offline(
+                liveness_config.missed_heartbeat_threshold.max(1),
+                liveness_config.max_agents_per_tick(),
+            );
+            for agent in &changed {
+                tracing::info!(agent_id = %agent.id, hostname = %agent.hostname, "agent.liveness_offline");
+                events.publish(&cncommon::observability::FleetEvent::AgentOffline {
+                    agent_id: agent.id.clone(),
+                    timestamp: chrono::Utc::now(),
+                });
+                metrics.increment_counter("cass_agents_marked_offline_total", 1.0, None);
+                if let Some(store) = agent_store.as_ref() {
+                    match uuid::Uuid::parse_str(&agent.id) {
+                        Ok(id) => {
+                            if let Err(e) = store.mark_offline(id).await {
+                                tracing::error!(error = %e, agent_id = %agent.id, "agent.liveness_offline_persist_failed");
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!(error = %e, agent_id = %agent.id, "agent.liveness_offline_invalid_id");
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "db"))]
+fn spawn_agent_liveness_reaper(
+    registry: crate::state::AgentRegistry,
+    events: Arc<dyn EventSink>,
+    metrics: cncommon::observability::InMemoryMetricsRegistry,
+) {
+    let mut config_rx = cncore::subscribe_config();
+    tokio::spawn(async move {
+        loop {
+            let liveness_config = config_rx.borrow().agent_liveness.clone();
+            tokio::time::sleep(std::time::Duration::from_secs(
+                liveness_config.scan_interval_seconds.max(1),
+            ))
+            .await;
+            let changed = registry.mark_stale_offline(
+                liveness_config.missed_heartbeat_threshold.max(1),
+                liveness_config.max_agents_per_tick(),
+            );
+            for agent in &changed {
+                tracing::info!(agent_id = %agent.id, hostname = %agent.hostname, "agent.liveness_offline");
+                events.publish(&cncommon::observability::FleetEvent::AgentOffline {
+                    agent_id: agent.id.clone(),
+                    timestamp: chrono::Utc::now(),
+                });
+                metrics.increment_counter("cass_agents_marked_offline_total", 1.0, None);
+            }
+        }
+    });
+}
+
+/// Runs `OrchestrationStore::requeue_stale` on a timer so an assignment
+/// whose scheduler died mid-lease (no heartbeat, never transitioned out of
+/// `Running`) gets put back on the queue instead of sitting there forever.
+#[cfg(feature = "db")]
+fn spawn_assignment_reaper(
+    orchestration_store: Arc<dyn cncore::platform::persistence::OrchestrationStore>,
+) {
+    use cncore::platform::persistence::AssignmentLifecycleConfig;
+
+    let config = AssignmentLifecycleConfig::default();
+    let ttl = chrono::Duration::minutes(5);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            ticker.tick().await;
+            match orchestration_store
+                .requeue_stale(chrono::Utc::now(), ttl, &config)
+                .await
+            {
+                Ok(changed) => {
+                    for assignment in changed {
+                        tracing::info!(
+                            assignment_id = %assignment.id,
+                            agent_id = %assignment.agent_id,
+                            status = ?assignment.status,
+                            attempt = assignment.attempt,
+                            "orchestration.assignment_requeued"
+                        );
+                    }
+                }
+                Err(e) => tracing::error!(error = %e, "stale assignment requeue failed"),
+            }
+        }
+    });
+}
+
+/// Periodically reclaims content and upload sessions that nothing else ever
+/// revisits once a client stops polling them: evaluates and applies every
+/// tenant's `ContentLifecyclePolicy` outcomes, then deletes expired
+/// `UploadSession`s in one set-based sweep. Logs a single summary line per
+/// tick rather than one per affected row, since a backlog of expired content
+/// can run into the thousands after the sweeper has been down for a while.
+#[cfg(feature = "db")]
+fn spawn_lifecycle_sweeper(
+    content_store: Arc<dyn cncore::platform::persistence::ContentStore>,
+    tenant_store: Arc<dyn cncore::platform::persistence::TenantStore>,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(300));
+        loop {
+            ticker.tick().await;
+            let now = chrono::Utc::now();
+            let tenants = match tenant_store.list_tenants() {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    tracing::error!(error = %e, "lifecycle sweep: failed to list tenants");
+                    continue;
+                }
+            };
+            let mut applied = 0usize;
+            for tenant in tenants {
+                let outcomes = match content_store.sweep_expired_content(tenant.id, now).await {
+                    Ok(outcomes) => outcomes,
+                    Err(e) => {
+                        tracing::error!(error = %e, tenant_id = %tenant.id, "lifecycle sweep failed");
+                        continue;
+                    }
+                };
+                for outcome in outcomes {
+                    if let Err(e) = content_store.apply_lifecycle_outcome(outcome).await {
+                        tracing::error!(error = %e, tenant_id = %tenant.id, "lifecycle outcome apply failed");
+                        continue;
+                    }
+                    applied += 1;
+                }
+            }
+            match content_store.reap_expired_upload_sessions(now).await {
+                Ok(reaped) => tracing::info!(
+                    content_outcomes_applied = applied,
+                    upload_sessions_reaped = reaped,
+                    "lifecycle.sweep_completed"
+                ),
+                Err(e) => tracing::error!(error = %e, "expired upload session reap failed"),
+            }
+        }
+    });
+}
+
+/// Runs `MessagingStore::evict_expired_messages` on a timer so a topic
+/// nobody ever consumes doesn't grow without bound: anything older than
+/// `MESSAGE_TTL`, claimed or not, is discarded for good rather than sitting
+/// in storage forever.
+#[cfg(feature = "db")]
+fn spawn_message_retention_sweeper(
+    messaging_store: Arc<dyn cncore::platform::persistence::MessagingStore>,
+) {
+    let ttl = chrono::Duration::hours(24);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(600));
+        loop {
+            ticker.tick().await;
+            match messaging_store
+                .evict_expired_messages(chrono::Utc::now(), ttl)
+                .await
+            {
+                Ok(evicted) => tracing::info!(evicted, "messaging.retention_swept"),
+                Err(e) => tracing::error!(error = %e, "message retention sweep failed"),
+            }
+        }
+    });
+}
+
+/// Runs `ModerationStore::expire_pending_moderation` on a timer so content a
+/// human reviewer never got to doesn't sit `Pending` indefinitely: anything
+/// still unreviewed after `REVIEW_DEADLINE` is flipped to `Rejected`, the
+/// same as if a moderator had declined it.
+#[cfg(feature = "db")]
+fn spawn_moderation_expiry_sweeper(
+    moderation_store: Arc<dyn cncore::platform::persistence::ModerationStore>,
+) {
+    use cncore::platform::models::ModerationState;
+
+    let deadline = chrono::Duration::hours(72);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(900));
+        loop {
+            ticker.tick().await;
+            match moderation_store
+                .expire_pending_moderation(chrono::Utc::now(), deadline, ModerationState::Rejected)
+                .await
+            {
+                Ok(expired) => {
+                    for content in expired {
+                        tracing::info!(
+                            content_id = %content.id,
+                            tenant_id = %content.tenant_id,
+                            "moderation.content_expired"
+                        );
+                    }
+                }
+                Err(e) => tracing::error!(error = %e, "moderation expiry sweep failed"),
+            }
+        }
+    });
+}
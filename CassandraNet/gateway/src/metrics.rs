@@ -1,16 +1,19 @@
 use crate::auth::{validate_api_key, validate_jwt, AuthStatus};
+use crate::trace_context::{RequestContext, TraceContext, REQUEST_CONTEXT};
 use axum::{
     body::Body,
-    http::{HeaderValue, Request},
+    http::{header::CONTENT_TYPE, HeaderValue, Request},
 };
 use once_cell::sync::Lazy;
 use prometheus::{
-    register_histogram_vec, register_int_counter_vec, register_int_gauge, Encoder, HistogramVec,
-    IntCounterVec, IntGauge, TextEncoder,
+    register_gauge_vec, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, Encoder, GaugeVec, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    TextEncoder,
 };
 use std::task::{Context, Poll};
 use std::time::Instant;
 use tower::{Layer, Service};
+use tracing::Instrument;
 use uuid::Uuid;
 
 static REQ_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -50,6 +53,85 @@ static PROCESS_CPU: Lazy<IntGauge> =
     Lazy::new(|| register_int_gauge!("process_cpu_percent", "Process CPU percent * 100").unwrap());
 static PROCESS_MEM: Lazy<IntGauge> =
     Lazy::new(|| register_int_gauge!("process_memory_bytes", "Resident memory bytes").unwrap());
+static NODE_CPU_PERCENT: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "node_cpu_percent",
+        "Latest reported CPU percent for a registered agent node",
+        &["node_id"]
+    )
+    .unwrap()
+});
+static NODE_MEMORY_USED_BYTES: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "node_memory_used_bytes",
+        "Latest reported memory usage for a registered agent node",
+        &["node_id"]
+    )
+    .unwrap()
+});
+static IN_FLIGHT_REQUESTS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "gateway_http_requests_in_flight",
+        "HTTP requests currently being handled"
+    )
+    .unwrap()
+});
+static AGENTS_RETURNED: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "ugc_agents_returned",
+        "Number of agents returned by the most recent list_agents call"
+    )
+    .unwrap()
+});
+static UPLOAD_SESSIONS_CREATED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "ugc_upload_sessions_created_total",
+        "Upload sessions created"
+    )
+    .unwrap()
+});
+static UPLOADS_COMPLETED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!("ugc_uploads_completed_total", "Uploads completed").unwrap()
+});
+
+/// Record the number of agents returned by a `list_agents` call so fleet
+/// size is visible as a Prometheus gauge alongside the custom telemetry
+/// registry's own counters.
+pub fn set_agents_returned(count: usize) {
+    AGENTS_RETURNED.set(count as i64);
+}
+
+/// Increment the Prometheus-backed counterpart of the
+/// `ugc_upload_sessions_created` domain metric already recorded in
+/// `state.telemetry.metrics`, so it shows up on `/metrics` too.
+pub fn increment_upload_sessions_created() {
+    UPLOAD_SESSIONS_CREATED.inc();
+}
+
+/// Increment the Prometheus-backed counterpart of the
+/// `ugc_uploads_completed` domain metric already recorded in
+/// `state.telemetry.metrics`, so it shows up on `/metrics` too.
+pub fn increment_uploads_completed() {
+    UPLOADS_COMPLETED.inc();
+}
+
+/// Record the latest heartbeat telemetry for `node_id` so fleet state shows
+/// up in `/metrics` alongside the gateway's own process stats.
+pub fn set_node_telemetry(node_id: &str, cpu_percent: f64, memory_used_bytes: u64) {
+    NODE_CPU_PERCENT
+        .with_label_values(&[node_id])
+        .set(cpu_percent);
+    NODE_MEMORY_USED_BYTES
+        .with_label_values(&[node_id])
+        .set(memory_used_bytes as f64);
+}
+
+/// Drop a deregistered node's gauges so `/metrics` doesn't keep reporting
+/// stale series for nodes that no longer exist.
+pub fn remove_node_telemetry(node_id: &str) {
+    let _ = NODE_CPU_PERCENT.remove_label_values(&[node_id]);
+    let _ = NODE_MEMORY_USED_BYTES.remove_label_values(&[node_id]);
+}
 
 pub struct MetricsLayer;
 
@@ -124,60 +206,114 @@ where
             "x-request-id",
             HeaderValue::from_str(&rid).unwrap_or(HeaderValue::from_static("invalid")),
         );
+
+        let incoming_traceparent = req
+            .headers()
+            .get("traceparent")
+            .and_then(|v| v.to_str().ok());
+        let trace_ctx = TraceContext::from_header_or_new(incoming_traceparent);
+        let outbound_span_id = TraceContext::new_span_id();
+        let outbound_traceparent = trace_ctx.child_header(outbound_span_id);
+        req.headers_mut().insert(
+            "traceparent",
+            HeaderValue::from_str(&outbound_traceparent)
+                .unwrap_or(HeaderValue::from_static("invalid")),
+        );
+        let trace_id_hex = trace_ctx.trace_id_hex();
+        let span = tracing::info_span!(
+            "http.request",
+            http.method = %method,
+            http.route = %path,
+            http.status_code = tracing::field::Empty,
+            trace_id = %trace_id_hex,
+            span_id = %hex_encode(&outbound_span_id),
+        );
+
         let start = Instant::now();
         let mut inner = self.inner.clone();
+        IN_FLIGHT_REQUESTS.inc();
         let fut = inner.call(req);
-        Box::pin(async move {
-            match fut.await {
-                Ok(resp) => {
-                    let status = resp.status().as_u16().to_string();
-                    REQ_COUNTER
-                        .with_label_values(&[&method, &path, &status])
-                        .inc();
-                    let dur = start.elapsed().as_secs_f64();
-                    REQ_LATENCY
-                        .with_label_values(&[&method, &path, &status])
-                        .observe(dur);
-                    if status.starts_with('5') {
-                        ERROR_5XX_COUNTER
+        let trace_id_hex_for_response = trace_id_hex.clone();
+        let request_ctx = RequestContext {
+            request_id: rid.clone(),
+            trace_id: trace_id_hex.clone(),
+        };
+        Box::pin(
+            REQUEST_CONTEXT.scope(request_ctx, async move {
+                let result = fut.await;
+                IN_FLIGHT_REQUESTS.dec();
+                match result {
+                    Ok(resp) => {
+                        let status = resp.status().as_u16().to_string();
+                        tracing::Span::current().record("http.status_code", status.as_str());
+                        if status.starts_with('5') {
+                            tracing::Span::current()
+                                .record("otel.status_code", "ERROR");
+                        }
+                        REQ_COUNTER
                             .with_label_values(&[&method, &path, &status])
                             .inc();
+                        let dur = start.elapsed().as_secs_f64();
+                        REQ_LATENCY
+                            .with_label_values(&[&method, &path, &status])
+                            .observe(dur);
+                        if status.starts_with('5') {
+                            ERROR_5XX_COUNTER
+                                .with_label_values(&[&method, &path, &status])
+                                .inc();
+                        }
+                        let mut resp = resp;
+                        resp.headers_mut()
+                            .insert("x-request-id", HeaderValue::from_str(&rid).unwrap());
+                        resp.headers_mut().insert(
+                            "x-trace-id",
+                            HeaderValue::from_str(&trace_id_hex_for_response)
+                                .unwrap_or(HeaderValue::from_static("invalid")),
+                        );
+                        Ok(resp)
+                    }
+                    Err(e) => {
+                        REQ_COUNTER
+                            .with_label_values(&[&method, &path, "error"])
+                            .inc();
+                        let dur = start.elapsed().as_secs_f64();
+                        REQ_LATENCY
+                            .with_label_values(&[&method, &path, "error"])
+                            .observe(dur);
+                        ERROR_5XX_COUNTER
+                            .with_label_values(&[&method, &path, "error"])
+                            .inc();
+                        Err(e)
                     }
-                    let mut resp = resp;
-                    resp.headers_mut()
-                        .insert("x-request-id", HeaderValue::from_str(&rid).unwrap());
-                    Ok(resp)
-                }
-                Err(e) => {
-                    REQ_COUNTER
-                        .with_label_values(&[&method, &path, "error"])
-                        .inc();
-                    let dur = start.elapsed().as_secs_f64();
-                    REQ_LATENCY
-                        .with_label_values(&[&method, &path, "error"])
-                        .observe(dur);
-                    ERROR_5XX_COUNTER
-                        .with_label_values(&[&method, &path, "error"])
-                        .inc();
-                    Err(e)
                 }
-            }
-        })
+            })
+            .instrument(span),
+        )
     }
 }
 
-pub fn gather_metrics() -> (axum::http::StatusCode, String) {
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn gather_metrics() -> (axum::http::StatusCode, [(axum::http::HeaderName, HeaderValue); 1], String) {
     update_process_metrics();
     let encoder = TextEncoder::new();
     let metric_families = prometheus::gather();
     let mut buf = Vec::new();
+    let content_type = [(
+        CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; version=0.0.4"),
+    )];
     match encoder.encode(&metric_families, &mut buf) {
         Ok(_) => (
             axum::http::StatusCode::OK,
+            content_type,
             String::from_utf8_lossy(&buf).into_owned(),
         ),
         Err(e) => (
             axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            content_type,
             format!("encode error: {e}"),
         ),
     }
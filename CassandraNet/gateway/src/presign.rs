@@ -0,0 +1,213 @@
+//! AWS SigV4 query-string presigning for direct-to-storage uploads.
+//! `create_upload_session` calls [`presigned_put_url`] so an agent can `PUT`
+//! bytes straight to S3-compatible storage instead of proxying them through
+//! Cassandra; `complete_upload_session` then fetches the same object back to
+//! run the ingest pipeline. Lives next to `auth.rs`'s HMAC-SHA256 helpers,
+//! just composed four levels deep per the SigV4 spec
+//! (https://docs.aws.amazon.com/general/latest/gr/sigv4-query-string-auth.html).
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const SERVICE: &str = "s3";
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const DEFAULT_EXPIRES_SECONDS: i64 = 900;
+
+/// Credentials and endpoint needed to presign a request, read from env so a
+/// deployment without them configured falls back to the non-presigned
+/// `CASS_STORAGE_BASE_URL` behavior.
+pub struct StorageCredentials {
+    pub endpoint: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+impl StorageCredentials {
+    /// Read `CASS_STORAGE_ENDPOINT`/`CASS_STORAGE_REGION`/`CASS_STORAGE_ACCESS_KEY`/
+    /// `CASS_STORAGE_SECRET_KEY` from the environment. `None` if any is unset
+    /// or blank, since presigning needs all four.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = non_empty_env("CASS_STORAGE_ENDPOINT")?;
+        let region = non_empty_env("CASS_STORAGE_REGION")?;
+        let access_key = non_empty_env("CASS_STORAGE_ACCESS_KEY")?;
+        let secret_key = non_empty_env("CASS_STORAGE_SECRET_KEY")?;
+        Some(Self {
+            endpoint,
+            region,
+            access_key,
+            secret_key,
+        })
+    }
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    match std::env::var(key) {
+        Ok(value) if !value.trim().is_empty() => Some(value),
+        _ => None,
+    }
+}
+
+/// Presign a `PUT {endpoint}/{storage_path}` request, valid for
+/// `expires_seconds` (defaults to 15 minutes), using `UNSIGNED-PAYLOAD` as
+/// the body hash so the caller can stream arbitrary bytes without buffering
+/// them to compute a content hash up front.
+pub fn presigned_put_url(
+    credentials: &StorageCredentials,
+    storage_path: &str,
+    expires_seconds: Option<i64>,
+) -> String {
+    presigned_put_url_at(credentials, storage_path, expires_seconds, Utc::now())
+}
+
+fn presigned_put_url_at(
+    credentials: &StorageCredentials,
+    storage_path: &str,
+    expires_seconds: Option<i64>,
+    now: DateTime<Utc>,
+) -> String {
+    let expires = expires_seconds.unwrap_or(DEFAULT_EXPIRES_SECONDS).max(1);
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date_stamp = now.format("%Y%m%d").to_string();
+    let credential_scope = format!(
+        "{date_stamp}/{region}/{service}/aws4_request",
+        region = credentials.region,
+        service = SERVICE,
+    );
+    let credential = format!("{}/{credential_scope}", credentials.access_key);
+
+    let canonical_uri = format!("/{}", storage_path.trim_start_matches('/'));
+    let host = host_from_endpoint(&credentials.endpoint);
+
+    let mut query_params = vec![
+        ("X-Amz-Algorithm".to_string(), ALGORITHM.to_string()),
+        ("X-Amz-Credential".to_string(), credential),
+        ("X-Amz-Date".to_string(), amz_date.clone()),
+        ("X-Amz-Expires".to_string(), expires.to_string()),
+        ("X-Amz-SignedHeaders".to_string(), "host".to_string()),
+    ];
+    query_params.sort_by(|a, b| a.0.cmp(&b.0));
+    let canonical_query_string = query_params
+        .iter()
+        .map(|(k, v)| format!("{}={}", uri_encode(k), uri_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let canonical_headers = format!("host:{host}\n");
+    let signed_headers = "host";
+    let canonical_request = format!(
+        "PUT\n{canonical_uri}\n{canonical_query_string}\n{canonical_headers}\n{signed_headers}\nUNSIGNED-PAYLOAD"
+    );
+
+    let hashed_request = sha256_hex(canonical_request.as_bytes());
+    let string_to_sign =
+        format!("{ALGORITHM}\n{amz_date}\n{credential_scope}\n{hashed_request}");
+
+    let signing_key = signing_key(&credentials.secret_key, &date_stamp, &credentials.region);
+    let signature = to_hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    format!(
+        "{endpoint}{canonical_uri}?{canonical_query_string}&X-Amz-Signature={signature}",
+        endpoint = credentials.endpoint.trim_end_matches('/'),
+    )
+}
+
+fn signing_key(secret_key: &str, date_stamp: &str, region: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{secret_key}").as_bytes(), date_stamp.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, SERVICE.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts a key of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::Digest;
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn host_from_endpoint(endpoint: &str) -> String {
+    endpoint
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// URI-encode per SigV4's rules: RFC 3986 unreserved characters pass
+/// through, everything else (including `/`) is percent-encoded.
+fn uri_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixture_credentials() -> StorageCredentials {
+        StorageCredentials {
+            endpoint: "https://s3.example.com".to_string(),
+            region: "us-east-1".to_string(),
+            access_key: "AKIDEXAMPLE".to_string(),
+            secret_key: "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY".to_string(),
+        }
+    }
+
+    #[test]
+    fn presigned_url_contains_required_query_params() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let url = presigned_put_url_at(
+            &fixture_credentials(),
+            "tenants/t/projects/p/content/file.png",
+            None,
+            now,
+        );
+        assert!(url.starts_with("https://s3.example.com/tenants/t/projects/p/content/file.png?"));
+        assert!(url.contains("X-Amz-Algorithm=AWS4-HMAC-SHA256"));
+        assert!(url.contains("X-Amz-Credential=AKIDEXAMPLE%2F20240301%2Fus-east-1%2Fs3%2Faws4_request"));
+        assert!(url.contains("X-Amz-Date=20240301T120000Z"));
+        assert!(url.contains("X-Amz-Expires=900"));
+        assert!(url.contains("X-Amz-SignedHeaders=host"));
+        assert!(url.contains("X-Amz-Signature="));
+    }
+
+    #[test]
+    fn presigned_url_is_deterministic_for_the_same_instant() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let a = presigned_put_url_at(&fixture_credentials(), "tenants/t/p/c/f.png", Some(60), now);
+        let b = presigned_put_url_at(&fixture_credentials(), "tenants/t/p/c/f.png", Some(60), now);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn presigned_url_signature_changes_with_storage_path() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let a = presigned_put_url_at(&fixture_credentials(), "tenants/t/p/c/a.png", None, now);
+        let b = presigned_put_url_at(&fixture_credentials(), "tenants/t/p/c/b.png", None, now);
+        assert_ne!(a, b);
+    }
+
+}
@@ -0,0 +1,160 @@
+//! Reverse-proxy routing from `/node/:id/*rest` to a registered agent,
+//! modeled on the same relay-to-backend pattern the gRPC control plane uses
+//! to track agents, just over plain HTTP.
+
+use crate::http::HttpError;
+use crate::state::{AgentSummary, AppState};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, Request, StatusCode, Uri},
+    response::Response,
+};
+use once_cell::sync::Lazy;
+use prometheus::{register_int_counter_vec, IntCounterVec};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static PROXY_REQUESTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "gateway_proxy_requests_total",
+        "Requests forwarded to a registered agent node",
+        &["path", "upstream_node", "status"]
+    )
+    .unwrap()
+});
+static UPSTREAM_ERRORS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "gateway_upstream_errors_total",
+        "Connect/timeout failures proxying to an agent node",
+        &["path", "upstream_node"]
+    )
+    .unwrap()
+});
+static ROUND_ROBIN_CURSOR: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionStrategy {
+    RoundRobin,
+    LeastCpu,
+}
+
+impl SelectionStrategy {
+    fn from_query(uri: &Uri) -> Self {
+        let strategy = uri.query().and_then(|q| {
+            q.split('&')
+                .find_map(|pair| pair.strip_prefix("strategy="))
+        });
+        match strategy {
+            Some("least_cpu") => SelectionStrategy::LeastCpu,
+            _ => SelectionStrategy::RoundRobin,
+        }
+    }
+}
+
+/// Pick a non-stale agent with a reachable `advertise_addr` using `strategy`.
+fn select_node(nodes: &[AgentSummary], strategy: SelectionStrategy) -> Option<&AgentSummary> {
+    let now_unix_ms = chrono::Utc::now().timestamp_millis() as u64;
+    let eligible: Vec<&AgentSummary> = nodes
+        .iter()
+        .filter(|n| !n.is_stale(now_unix_ms))
+        .filter(|n| n.advertise_addr.as_deref().is_some_and(|a| !a.is_empty()))
+        .collect();
+    if eligible.is_empty() {
+        return None;
+    }
+    match strategy {
+        SelectionStrategy::LeastCpu => eligible
+            .into_iter()
+            .min_by(|a, b| a.cpu_percent.total_cmp(&b.cpu_percent)),
+        SelectionStrategy::RoundRobin => {
+            let idx = ROUND_ROBIN_CURSOR.fetch_add(1, Ordering::Relaxed) % eligible.len();
+            Some(eligible[idx])
+        }
+    }
+}
+
+/// `GET/POST/... /node/:id/*rest` — forward to the named node, or to one
+/// chosen by `strategy` when `id` is `auto`.
+pub async fn proxy_to_node(
+    State(state): State<AppState>,
+    Path((id, rest)): Path<(String, String)>,
+    headers: HeaderMap,
+    req: Request<Body>,
+) -> Result<Response, HttpError> {
+    let path = format!("/node/{id}/{rest}");
+    let nodes = state.registry.list();
+    let now_unix_ms = chrono::Utc::now().timestamp_millis() as u64;
+    let target = if id == "auto" {
+        select_node(&nodes, SelectionStrategy::from_query(req.uri())).cloned()
+    } else {
+        nodes
+            .into_iter()
+            .find(|n| n.id == id && !n.is_stale(now_unix_ms))
+    };
+    let Some(target) = target else {
+        UPSTREAM_ERRORS_TOTAL.with_label_values(&[&path, &id]).inc();
+        return Err(HttpError::new(
+            StatusCode::BAD_GATEWAY,
+            "no healthy agent available",
+        ));
+    };
+    let Some(advertise_addr) = target.advertise_addr.clone().filter(|a| !a.is_empty()) else {
+        UPSTREAM_ERRORS_TOTAL
+            .with_label_values(&[&path, &target.id])
+            .inc();
+        return Err(HttpError::new(
+            StatusCode::BAD_GATEWAY,
+            "agent has no reachable address",
+        ));
+    };
+
+    let method = req.method().clone();
+    let body_bytes = axum::body::to_bytes(req.into_body(), usize::MAX)
+        .await
+        .map_err(|_| HttpError::new(StatusCode::BAD_REQUEST, "invalid request body"))?;
+    let upstream_url = format!("http://{advertise_addr}/{rest}");
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|_| HttpError::new(StatusCode::INTERNAL_SERVER_ERROR, "proxy client error"))?;
+    let mut upstream_req = client.request(method.clone(), &upstream_url).body(body_bytes);
+    if let Some(req_id) = headers.get("x-request-id") {
+        upstream_req = upstream_req.header("x-request-id", req_id);
+    }
+    if let Some(traceparent) = headers.get("traceparent") {
+        upstream_req = upstream_req.header("traceparent", traceparent);
+    }
+
+    match upstream_req.send().await {
+        Ok(upstream_resp) => {
+            let status = upstream_resp.status();
+            PROXY_REQUESTS_TOTAL
+                .with_label_values(&[&path, &target.id, status.as_str()])
+                .inc();
+            let mut builder = Response::builder().status(status.as_u16());
+            for (name, value) in upstream_resp.headers() {
+                if let Ok(v) = HeaderValue::from_bytes(value.as_bytes()) {
+                    builder = builder.header(name.as_str(), v);
+                }
+            }
+            let body = upstream_resp
+                .bytes()
+                .await
+                .unwrap_or_default();
+            builder
+                .body(Body::from(body))
+                .map_err(|_| HttpError::new(StatusCode::BAD_GATEWAY, "invalid upstream response"))
+        }
+        Err(_) => {
+            UPSTREAM_ERRORS_TOTAL
+                .with_label_values(&[&path, &target.id])
+                .inc();
+            Err(HttpError::new(
+                StatusCode::BAD_GATEWAY,
+                "upstream request failed",
+            ))
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Per-credential token-bucket rate limiting, applied before requests reach
+//! `MetricsService`'s inner call.
+//!
+//! Keys are the resolved caller identity (API key or JWT subject from
+//! [`crate::auth::identity_from_headers`]), falling back to client IP for
+//! routes that don't carry credentials. Buckets live in a sharded concurrent
+//! map so refill/debit doesn't serialize unrelated keys behind one lock.
+
+use crate::auth::identity_from_headers;
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{HeaderValue, Request},
+};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use prometheus::{register_int_counter_vec, IntCounterVec};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+static RATE_LIMITED_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "gateway_rate_limited_total",
+        "Requests rejected by the per-credential rate limiter",
+        &["path", "key_tier"]
+    )
+    .unwrap()
+});
+
+/// Capacity and refill rate for a token bucket. `capacity` is the maximum
+/// (and starting) number of tokens; `refill_per_sec` tokens are added back
+/// per elapsed second, up to `capacity`.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitTier {
+    pub capacity: f64,
+    pub refill_per_sec: f64,
+}
+
+impl RateLimitTier {
+    pub const fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+        }
+    }
+}
+
+/// Tier applied to requests keyed by a resolved API key or JWT subject.
+pub const AUTHENTICATED_TIER: RateLimitTier = RateLimitTier::new(60.0, 10.0);
+/// Tier applied to requests falling back to client IP (no credential).
+pub const ANONYMOUS_TIER: RateLimitTier = RateLimitTier::new(20.0, 2.0);
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    buckets: Arc<DashMap<String, Bucket>>,
+}
+
+impl RateLimitLayer {
+    pub fn new() -> Self {
+        Self {
+            buckets: Arc::new(DashMap::new()),
+        }
+    }
+}
+
+impl Default for RateLimitLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            buckets: self.buckets.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    buckets: Arc<DashMap<String, Bucket>>,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = axum::response::Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let path = crate::metrics::normalize_path(req.uri().path()).into_owned();
+        let (key, tier_name, tier) = match identity_from_headers(req.headers()) {
+            Some(identity) => (identity, "authenticated", AUTHENTICATED_TIER),
+            None => {
+                let ip = req
+                    .extensions()
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip().to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                (format!("ip:{ip}"), "anonymous", ANONYMOUS_TIER)
+            }
+        };
+
+        if !self.take_token(&key, tier) {
+            RATE_LIMITED_COUNTER
+                .with_label_values(&[&path, tier_name])
+                .inc();
+            let resp = axum::response::Response::builder()
+                .status(axum::http::StatusCode::TOO_MANY_REQUESTS)
+                .header("retry-after", "1")
+                .body(Body::from("rate limit exceeded"))
+                .unwrap();
+            return Box::pin(async move { Ok(resp) });
+        }
+
+        let mut inner = self.inner.clone();
+        Box::pin(async move { inner.call(req).await })
+    }
+}
+
+impl<S> RateLimitService<S> {
+    /// Lazily refill then debit one token for `key`; returns `false` (and
+    /// leaves the bucket untouched) when fewer than one token is available.
+    fn take_token(&self, key: &str, tier: RateLimitTier) -> bool {
+        let now = Instant::now();
+        let mut bucket = self.buckets.entry(key.to_string()).or_insert_with(|| Bucket {
+            tokens: tier.capacity,
+            last_refill: now,
+        });
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * tier.refill_per_sec).min(tier.capacity);
+        bucket.last_refill = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depletes_and_refills_bucket() {
+        let layer = RateLimitLayer::new();
+        let svc = RateLimitService {
+            inner: (),
+            buckets: layer.buckets.clone(),
+        };
+        let tier = RateLimitTier::new(2.0, 1.0);
+        assert!(svc.take_token("k", tier));
+        assert!(svc.take_token("k", tier));
+        assert!(!svc.take_token("k", tier));
+    }
+}
@@ -0,0 +1,198 @@
+//! Gateway side of the `ugc.rendition` background job queue: schedules (or
+//! reuses) a job for a thumbnail/transcode request, and runs the generation
+//! itself once a job is leased. Mirrors pict-rs's `queue`/`processor`/
+//! `generate` split — `schedule_or_reuse` is the queue, `run_job` is the
+//! processor, and `cncore::platform::generate_rendition` is `generate`.
+
+use std::collections::HashMap;
+
+use chrono::{Duration as ChronoDuration, Utc};
+use cncore::platform::{
+    generate_rendition, ContentId, ContentMetadata, ContentQuery, ProjectId, RenditionJobPayload,
+    RenditionSpec, Task, TaskRequest, TaskStatus, TenantId, RENDITION_TASK_KIND,
+};
+use uuid::Uuid;
+
+use crate::http::HttpError;
+use crate::state::AppState;
+
+/// Result of asking the queue for a rendition: either it already exists
+/// (cache hit) or a job is pending/in-flight for it (freshly scheduled or
+/// reused from a concurrent request).
+pub enum ThumbnailLookup {
+    Ready(ContentMetadata),
+    Job(Task),
+}
+
+fn rendition_label(cache_key: &str) -> String {
+    format!("rendition:{cache_key}")
+}
+
+/// Look up a cached derivative for `cache_key`; if none exists, reuse an
+/// in-flight job for the same cache key or enqueue a new one and spawn a
+/// worker to process it.
+pub async fn schedule_or_reuse(
+    state: &AppState,
+    tenant_id: TenantId,
+    project_id: ProjectId,
+    content_id: ContentId,
+    spec: RenditionSpec,
+    cache_key: String,
+) -> Result<ThumbnailLookup, HttpError> {
+    let existing = state
+        .content_store
+        .list_content_metadata(&ContentQuery {
+            tenant_id,
+            project_id: Some(project_id),
+            search_term: None,
+            tags: vec![rendition_label(&cache_key)],
+            limit: Some(1),
+            offset: None,
+            cursor_created_at: None,
+            cursor_id: None,
+        })
+        .await
+        .map_err(HttpError::from)?;
+    if let Some(child) = existing.into_iter().next() {
+        return Ok(ThumbnailLookup::Ready(child));
+    }
+
+    let in_flight = state
+        .rendition_engine
+        .list_tasks(tenant_id, RENDITION_TASK_KIND)
+        .map_err(HttpError::from)?
+        .into_iter()
+        .find(|task| {
+            matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress)
+                && task.payload.get("cache_key").and_then(|v| v.as_str()) == Some(cache_key.as_str())
+        });
+    if let Some(task) = in_flight {
+        return Ok(ThumbnailLookup::Job(task));
+    }
+
+    let payload = RenditionJobPayload {
+        tenant_id,
+        project_id,
+        parent_content_id: content_id,
+        spec,
+        cache_key,
+    };
+    let payload = serde_json::to_value(payload)
+        .map_err(|_| HttpError::new(axum::http::StatusCode::INTERNAL_SERVER_ERROR, "invalid job payload"))?;
+    let task = state
+        .rendition_engine
+        .schedule_task(TaskRequest {
+            tenant_id,
+            kind: RENDITION_TASK_KIND.to_string(),
+            payload,
+            replicas: 1,
+        })
+        .map_err(HttpError::from)?;
+    spawn_worker(state.clone(), task.id);
+    Ok(ThumbnailLookup::Job(task))
+}
+
+fn spawn_worker(state: AppState, task_id: Uuid) {
+    tokio::spawn(async move {
+        if let Err(err) = run_job(&state, task_id).await {
+            tracing::error!(%task_id, error = %err.detail(), "rendition.job_failed");
+        }
+    });
+}
+
+async fn run_job(state: &AppState, task_id: Uuid) -> Result<(), HttpError> {
+    let worker_id = Uuid::new_v4();
+    let lease = state
+        .rendition_engine
+        .lease_task(task_id, worker_id, ChronoDuration::minutes(5))
+        .map_err(HttpError::from)?;
+    let payload: RenditionJobPayload = serde_json::from_value(lease.task.payload.clone()).map_err(|_| {
+        HttpError::new(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            "invalid rendition job payload",
+        )
+    })?;
+
+    match render_and_store(state, &payload).await {
+        Ok(content_id) => {
+            state
+                .rendition_engine
+                .complete_task(task_id, Some(serde_json::json!({ "content_id": content_id })))
+                .map_err(HttpError::from)?;
+        }
+        Err(err) => {
+            state
+                .rendition_engine
+                .fail_task(task_id, err.detail().to_string(), false)
+                .map_err(HttpError::from)?;
+        }
+    }
+    Ok(())
+}
+
+async fn render_and_store(state: &AppState, payload: &RenditionJobPayload) -> Result<Uuid, HttpError> {
+    let parent = state
+        .content_store
+        .get_content_metadata(payload.parent_content_id)
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::new(axum::http::StatusCode::NOT_FOUND, "source content not found"))?;
+    let storage_path = parent.storage_path.clone().ok_or_else(|| {
+        HttpError::new(
+            axum::http::StatusCode::NOT_FOUND,
+            "source content has no stored object",
+        )
+    })?;
+    let upload_url = crate::http::storage_base_url().map(|base| format!("{base}/{storage_path}"));
+    let source_bytes = state.object_fetcher.fetch(&storage_path, upload_url.as_deref()).await?;
+
+    let (rendition_bytes, mime_type) = generate_rendition(&source_bytes, &payload.spec).map_err(HttpError::from)?;
+
+    let child_id = Uuid::new_v4();
+    let child_storage_path = format!("{storage_path}.renditions/{}", payload.cache_key);
+    state.object_fetcher.put(&child_storage_path, rendition_bytes.clone()).await?;
+
+    let now = Utc::now();
+    let mut attributes = HashMap::new();
+    attributes.insert(
+        "parent_content_id".to_string(),
+        payload.parent_content_id.to_string(),
+    );
+    let metadata = ContentMetadata {
+        id: child_id,
+        tenant_id: payload.tenant_id,
+        project_id: payload.project_id,
+        filename: format!(
+            "{}-{}.{}",
+            payload.parent_content_id,
+            payload.cache_key,
+            extension_for(&payload.spec.format)
+        ),
+        mime_type: Some(mime_type),
+        size_bytes: Some(rendition_bytes.len() as u64),
+        checksum: None,
+        storage_path: Some(child_storage_path),
+        labels: vec![rendition_label(&payload.cache_key)],
+        attributes,
+        created_at: now,
+        updated_at: now,
+        uploaded_by: None,
+        visibility: parent.visibility,
+        blurhash: None,
+        relevance: None,
+    };
+    state
+        .content_store
+        .record_content_metadata(metadata)
+        .await
+        .map_err(HttpError::from)?;
+    Ok(child_id)
+}
+
+fn extension_for(format: &str) -> &'static str {
+    match format {
+        "png" => "png",
+        "jpeg" => "jpg",
+        _ => "webp",
+    }
+}
@@ -1,13 +1,22 @@
+use crate::auth_provider::{self, AuthProvider};
+use crate::command_channel::CommandChannelRegistry;
+use crate::enrollment::SasEnrollmentStore;
+use crate::ingest::{HttpObjectFetcher, ObjectFetcher};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use cncommon::observability::{InMemoryLogSink, InMemoryMetricsRegistry, LogPipeline};
 #[cfg(feature = "db")]
 use cncore::platform::persistence::PostgresAgentStore;
 #[cfg(feature = "db")]
 use cncore::platform::persistence::PostgresContentStore;
 use cncore::platform::persistence::{
-    ContentStore, InMemoryPersistence, MessagingStore, ModerationStore, OrchestrationStore,
+    ApiKeyStore, ContentStore, InMemoryPersistence, MessagingStore, ModerationStore,
+    OrchestrationStore, RateLimitStore, TaskStore, TenantStore, WorkflowStore,
 };
+use cncore::platform::{OrchestrationEngine, PlatformResult, Task, TaskRequest};
+use cnproto::{AgentCommand, TaskAssignment};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration as StdDuration, Instant};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -27,11 +36,53 @@ pub struct AgentSummary {
     pub project_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lifecycle_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_cores: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval_seconds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advertise_addr: Option<String>,
+    /// Failure domain this agent reported at registration, e.g. a
+    /// datacenter or availability zone. Feeds `placement::select_agents` via
+    /// `AgentRegistry`'s `AgentCandidateSource` impl; agents that never
+    /// reported one are each treated as their own single-agent zone (see
+    /// `AgentRegistry::candidates`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+    /// Memory capacity reported at registration, used alongside `cpu_cores`
+    /// to compute this agent's placement weight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+}
+
+impl AgentSummary {
+    /// A node is considered stale once it's gone quiet for more than three
+    /// heartbeat intervals; falls back to a 30s default interval when the
+    /// node hasn't told us its cadence yet.
+    pub fn is_stale(&self, now_unix_ms: u64) -> bool {
+        let interval_ms = self.heartbeat_interval_seconds.unwrap_or(10) as u64 * 1000;
+        now_unix_ms.saturating_sub(self.last_seen_unix_ms) > 3 * interval_ms
+    }
 }
 
 #[derive(Default, Clone)]
 pub struct AgentRegistry(pub(crate) Arc<RwLock<HashMap<String, AgentSummary>>>);
 
+#[derive(Default, Clone)]
+pub struct AgentUpsertExtra {
+    pub os: Option<String>,
+    pub arch: Option<String>,
+    pub cpu_cores: Option<u32>,
+    pub heartbeat_interval_seconds: Option<u32>,
+    pub advertise_addr: Option<String>,
+    pub zone: Option<String>,
+    pub memory_bytes: Option<u64>,
+}
+
 impl AgentRegistry {
     pub fn upsert(
         &self,
@@ -43,6 +94,31 @@ impl AgentRegistry {
         project_id: Option<String>,
         lifecycle_status: Option<String>,
         last_seen_override: Option<u64>,
+    ) {
+        self.upsert_with(
+            id,
+            hostname,
+            cpu,
+            mem,
+            tenant_id,
+            project_id,
+            lifecycle_status,
+            last_seen_override,
+            AgentUpsertExtra::default(),
+        );
+    }
+
+    pub fn upsert_with(
+        &self,
+        id: String,
+        hostname: String,
+        cpu: f64,
+        mem: u64,
+        tenant_id: Option<String>,
+        project_id: Option<String>,
+        lifecycle_status: Option<String>,
+        last_seen_override: Option<u64>,
+        extra: AgentUpsertExtra,
     ) {
         let now_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -65,6 +141,27 @@ impl AgentRegistry {
                 if lifecycle_status.is_some() {
                     a.lifecycle_status = lifecycle_status.clone();
                 }
+                if extra.os.is_some() {
+                    a.os = extra.os.clone();
+                }
+                if extra.arch.is_some() {
+                    a.arch = extra.arch.clone();
+                }
+                if extra.cpu_cores.is_some() {
+                    a.cpu_cores = extra.cpu_cores;
+                }
+                if extra.heartbeat_interval_seconds.is_some() {
+                    a.heartbeat_interval_seconds = extra.heartbeat_interval_seconds;
+                }
+                if extra.advertise_addr.is_some() {
+                    a.advertise_addr = extra.advertise_addr.clone();
+                }
+                if extra.zone.is_some() {
+                    a.zone = extra.zone.clone();
+                }
+                if extra.memory_bytes.is_some() {
+                    a.memory_bytes = extra.memory_bytes;
+                }
             })
             .or_insert(AgentSummary {
                 id,
@@ -75,6 +172,13 @@ impl AgentRegistry {
                 tenant_id,
                 project_id,
                 lifecycle_status,
+                os: extra.os,
+                arch: extra.arch,
+                cpu_cores: extra.cpu_cores,
+                heartbeat_interval_seconds: extra.heartbeat_interval_seconds,
+                advertise_addr: extra.advertise_addr,
+                zone: extra.zone,
+                memory_bytes: extra.memory_bytes,
             });
     }
 
@@ -84,38 +188,253 @@ impl AgentRegistry {
         v.sort_by(|a, b| a.id.cmp(&b.id));
         v
     }
+
+    pub fn get(&self, id: &str) -> Option<AgentSummary> {
+        self.0.read().unwrap().get(id).cloned()
+    }
+
+    /// Deregister a node; returns `false` if it wasn't known.
+    pub fn remove(&self, id: &str) -> bool {
+        self.0.write().unwrap().remove(id).is_some()
+    }
+
+    /// Transitions agents that have missed `heartbeat_interval_seconds *
+    /// missed_threshold` worth of heartbeats to `"offline"`, touching at
+    /// most `max_to_touch` of them (the liveness reaper's per-tick
+    /// tranquility cap) so one scan of a large fleet can't block the
+    /// registry's lock for long. Returns the agents that changed.
+    pub fn mark_stale_offline(&self, missed_threshold: u32, max_to_touch: usize) -> Vec<AgentSummary> {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let mut map = self.0.write().unwrap();
+        let mut changed = Vec::new();
+        for agent in map.values_mut() {
+            if changed.len() >= max_to_touch {
+                break;
+            }
+            if agent.lifecycle_status.as_deref() == Some("offline") {
+                continue;
+            }
+            let interval_ms = agent.heartbeat_interval_seconds.unwrap_or(10) as u64 * 1000;
+            let threshold_ms = interval_ms * missed_threshold as u64;
+            if now_ms.saturating_sub(agent.last_seen_unix_ms) > threshold_ms {
+                agent.lifecycle_status = Some("offline".to_string());
+                changed.push(agent.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// How much headroom [`AgentRegistry::candidates`] credits an agent with:
+/// `cpu_cores`/`memory_bytes` (its advertised ceiling) minus its current
+/// load, approximated from the most recent heartbeat's `cpu_percent`.
+/// Agents that haven't reported `cpu_cores` yet (e.g. between registration
+/// and their first heartbeat) get a nominal single-core weight rather than
+/// zero, so a freshly registered agent isn't immediately treated as having
+/// no capacity.
+fn placement_weight(agent: &AgentSummary) -> f64 {
+    let cpu_cores = agent.cpu_cores.unwrap_or(1) as f64;
+    let memory_gib = agent.memory_bytes.unwrap_or(0) as f64 / (1024.0 * 1024.0 * 1024.0);
+    let load_factor = (1.0 - (agent.cpu_percent / 100.0).clamp(0.0, 1.0)).max(0.0);
+    (cpu_cores + memory_gib) * load_factor
+}
+
+impl cncore::platform::AgentCandidateSource for AgentRegistry {
+    fn candidates(&self) -> Vec<cncore::platform::AgentCandidate> {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        self.list()
+            .into_iter()
+            .filter(|agent| !agent.is_stale(now_ms))
+            .map(|agent| cncore::platform::AgentCandidate {
+                zone: agent.zone.clone().unwrap_or_else(|| agent.id.clone()),
+                remaining_capacity: placement_weight(&agent),
+                id: agent.id,
+            })
+            .collect()
+    }
+}
+
+/// Bounded per-node heartbeat history, kept for the admin telemetry-history
+/// endpoint. Each node keeps only its most recent `HISTORY_CAPACITY` samples.
+const HISTORY_CAPACITY: usize = 50;
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct NodeHeartbeatSample {
+    pub cpu_percent: f64,
+    pub memory_used_bytes: u64,
+    pub timestamp_unix_ms: u64,
+}
+
+#[derive(Default, Clone)]
+pub struct NodeHistoryStore(pub(crate) Arc<RwLock<HashMap<String, std::collections::VecDeque<NodeHeartbeatSample>>>>);
+
+impl NodeHistoryStore {
+    pub fn record(&self, id: &str, sample: NodeHeartbeatSample) {
+        let mut map = self.0.write().unwrap();
+        let history = map.entry(id.to_string()).or_default();
+        if history.len() == HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    pub fn history(&self, id: &str) -> Vec<NodeHeartbeatSample> {
+        self.0
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|h| h.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.0.write().unwrap().remove(id);
+    }
+}
+
+/// Data-minimization/retention registry: agents or whole tenants marked
+/// opted-out here are excluded from `list_agents`/`list_recent_logs`
+/// results without their underlying rows being deleted, preserving audit
+/// integrity while honoring the suppression request.
+#[derive(Default, Clone)]
+pub struct OptOutRegistry {
+    agents: Arc<RwLock<HashSet<String>>>,
+    tenants: Arc<RwLock<HashSet<String>>>,
+}
+
+impl OptOutRegistry {
+    pub fn opt_out_agent(&self, id: &str) {
+        self.agents.write().unwrap().insert(id.to_string());
+    }
+
+    /// Returns `false` if the agent wasn't opted out.
+    pub fn clear_agent(&self, id: &str) -> bool {
+        self.agents.write().unwrap().remove(id)
+    }
+
+    pub fn opt_out_tenant(&self, tenant_id: &str) {
+        self.tenants.write().unwrap().insert(tenant_id.to_string());
+    }
+
+    /// Returns `false` if the tenant wasn't opted out.
+    pub fn clear_tenant(&self, tenant_id: &str) -> bool {
+        self.tenants.write().unwrap().remove(tenant_id)
+    }
+
+    pub fn is_tenant_suppressed(&self, tenant_id: &str) -> bool {
+        self.tenants.read().unwrap().contains(tenant_id)
+    }
+
+    /// `true` if the agent itself, or the tenant it belongs to, is opted out.
+    pub fn is_agent_suppressed(&self, agent_id: &str, tenant_id: Option<&str>) -> bool {
+        if self.agents.read().unwrap().contains(agent_id) {
+            return true;
+        }
+        tenant_id
+            .map(|t| self.is_tenant_suppressed(t))
+            .unwrap_or(false)
+    }
+
+    pub fn opted_out_agent_ids(&self) -> Vec<String> {
+        self.agents.read().unwrap().iter().cloned().collect()
+    }
+
+    pub fn opted_out_tenant_ids(&self) -> Vec<String> {
+        self.tenants.read().unwrap().iter().cloned().collect()
+    }
 }
 
 #[derive(Clone)]
 pub struct AppState {
     pub registry: AgentRegistry,
+    pub node_history: NodeHistoryStore,
     pub content_store: Arc<dyn ContentStore>,
+    pub tenant_store: Arc<dyn TenantStore>,
+    /// Backs `auth::sigv4::verify`'s access-key lookup. Always the in-memory
+    /// store, same as `PlatformServices::in_memory` — there's no
+    /// Postgres-backed `ApiKeyStore` impl yet (see `persistence.rs`).
+    pub api_key_store: Arc<dyn ApiKeyStore>,
     pub orchestration_store: Arc<dyn OrchestrationStore>,
     pub moderation_store: Arc<dyn ModerationStore>,
     pub messaging_store: Arc<dyn MessagingStore>,
     #[cfg(feature = "db")]
     pub agent_store: Option<Arc<PostgresAgentStore>>,
+    /// Fetches an upload's bytes back from storage for ingest validation in
+    /// `complete_upload_session`. Defaults to a real HTTP fetch; tests swap
+    /// in `ingest::InMemoryObjectFetcher` since nothing here receives a real
+    /// presigned PUT.
+    pub object_fetcher: Arc<dyn ObjectFetcher>,
+    /// Background job queue for derived renditions (thumbnails/transcodes).
+    /// Tasks persist through the same store as everything else, so queued
+    /// work survives a restart even though an in-flight worker doesn't.
+    pub rendition_engine: Arc<OrchestrationEngine>,
     pub telemetry: TelemetryState,
     pub rate_limiter: RateLimiter,
+    pub opt_outs: OptOutRegistry,
+    /// Composable authentication chain consulted by `authenticate`/
+    /// `resolve_scopes` below, which back every `http::ensure_scope` call.
+    /// Defaults to [`auth_provider::default_providers`] (static API key,
+    /// HS256 JWT, env-configured demo users); deployments that need LDAP
+    /// replace this with a chain that also includes
+    /// `auth_provider::LdapAuthProvider`.
+    pub auth_providers: Vec<Arc<dyn AuthProvider>>,
+    /// Pending/expired Short Authentication String handshakes for agent
+    /// enrollment; shared with `grpc::InMemoryAgentControl` so the admin
+    /// verify endpoint and the `RegisterAgent`/`Heartbeat` RPCs agree on
+    /// which agent ids are still waiting on operator confirmation.
+    pub enrollment: SasEnrollmentStore,
+    /// Open `OpenCommandStream` senders, keyed by `assigned_id`; lets
+    /// `schedule_agent_task` push work immediately instead of waiting for
+    /// the agent's next heartbeat poll.
+    pub command_channels: CommandChannelRegistry,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         let persistence = Arc::new(InMemoryPersistence::new());
         let content_store: Arc<dyn ContentStore> = persistence.clone();
+        let tenant_store: Arc<dyn TenantStore> = persistence.clone();
+        let api_key_store: Arc<dyn ApiKeyStore> = persistence.clone();
         let orchestration_store: Arc<dyn OrchestrationStore> = persistence.clone();
         let moderation_store: Arc<dyn ModerationStore> = persistence.clone();
         let messaging_store: Arc<dyn MessagingStore> = persistence.clone();
+        let task_store: Arc<dyn TaskStore> = persistence.clone();
+        let workflow_store: Arc<dyn WorkflowStore> = persistence.clone();
+        let registry = AgentRegistry::default();
+        let telemetry = TelemetryState::default();
+        let rendition_engine = Arc::new(OrchestrationEngine::new(task_store, workflow_store));
+        // Lets `rendition_engine.schedule_task` (and anything else scheduled
+        // through it) place a task's replicas across the live fleet instead
+        // of always leaving `Task::assigned_agent_ids` empty.
+        rendition_engine.set_candidate_source(Arc::new(registry.clone()));
+        // Shares the gateway's telemetry registry so `cass_tasks_scheduled_total`
+        // shows up alongside request/agent metrics at `/admin/telemetry/metrics`.
+        rendition_engine.set_metrics(telemetry.metrics.clone());
         Self {
-            registry: AgentRegistry::default(),
+            registry,
+            node_history: NodeHistoryStore::default(),
             content_store,
+            tenant_store,
+            api_key_store,
             orchestration_store,
             moderation_store,
             messaging_store,
             #[cfg(feature = "db")]
             agent_store: None,
-            telemetry: TelemetryState::default(),
+            object_fetcher: Arc::new(HttpObjectFetcher::new()),
+            rendition_engine,
+            telemetry,
             rate_limiter: RateLimiter::new(),
+            opt_outs: OptOutRegistry::default(),
+            auth_providers: auth_provider::default_providers(),
+            enrollment: SasEnrollmentStore::default(),
+            command_channels: CommandChannelRegistry::default(),
         }
     }
 }
@@ -138,6 +457,101 @@ impl AppState {
         state.rate_limiter = rate_limiter;
         state
     }
+
+    /// Schedules a task targeting a specific agent the normal way (through
+    /// `rendition_engine`, so it survives even if the agent is offline) and,
+    /// if that agent currently has a live `OpenCommandStream`, best-effort
+    /// pushes it immediately instead of leaving it for the agent's next
+    /// heartbeat poll to discover.
+    pub fn schedule_agent_task(
+        &self,
+        agent_id: &str,
+        request: TaskRequest,
+    ) -> PlatformResult<Task> {
+        let task = self.rendition_engine.schedule_task(request)?;
+        self.command_channels.send(
+            agent_id,
+            AgentCommand {
+                command: Some(cnproto::agent_command::Command::TaskAssignment(
+                    TaskAssignment {
+                        task_id: task.id.to_string(),
+                        kind: task.kind.clone(),
+                        payload_json: task.payload.to_string(),
+                    },
+                )),
+            },
+        );
+        Ok(task)
+    }
+
+    /// Runs `headers` through `auth_providers` in order, returning `Allow`
+    /// from the first provider that recognizes the credentials. Providers
+    /// that don't see the credential shape they look for are expected to
+    /// return `Deny` so the chain falls through rather than rejecting on
+    /// another provider's behalf.
+    ///
+    /// Called by `http::ensure_scope`, the scope gate every route in
+    /// `http.rs` goes through.
+    pub async fn authenticate(&self, headers: &axum::http::HeaderMap) -> crate::auth::AuthStatus {
+        for provider in &self.auth_providers {
+            if matches!(
+                provider.authenticate(headers).await,
+                crate::auth::AuthStatus::Allow
+            ) {
+                return crate::auth::AuthStatus::Allow;
+            }
+        }
+        crate::auth::AuthStatus::Deny
+    }
+
+    /// Scopes granted by the first provider in `auth_providers` that accepts
+    /// `headers`. Only meaningful once `authenticate` has returned `Allow`
+    /// for the same headers. An empty `Vec` means no scopes were granted; a
+    /// `Vec` containing `auth_provider::UNRESTRICTED_SCOPE` means the
+    /// credential bypasses scope checks entirely (e.g. the static API key).
+    pub async fn resolve_scopes(&self, headers: &axum::http::HeaderMap) -> Vec<String> {
+        for provider in &self.auth_providers {
+            if matches!(
+                provider.authenticate(headers).await,
+                crate::auth::AuthStatus::Allow
+            ) {
+                return provider.resolve_scopes(headers).await;
+            }
+        }
+        Vec::new()
+    }
+
+    /// Thin wrapper over `rate_limiter.check_and_increment` that also feeds
+    /// `telemetry.metrics` with a per-tenant/per-route request counter and,
+    /// on rejection, a rate-limit-rejected counter — real series behind
+    /// `InMemoryMetricsRegistry::render_prometheus`'s `/admin/telemetry`
+    /// output rather than an always-empty registry.
+    pub async fn check_rate_limit(
+        &self,
+        tenant_id: Uuid,
+        route: &str,
+        limit: u32,
+        window: StdDuration,
+    ) -> bool {
+        let mut labels = HashMap::new();
+        labels.insert("tenant_id".to_string(), tenant_id.to_string());
+        labels.insert("route".to_string(), route.to_string());
+        self.telemetry
+            .metrics
+            .increment_counter("gateway_requests_total", 1.0, Some(labels.clone()));
+        let allowed = self
+            .rate_limiter
+            .check_and_increment(tenant_id, route, limit, window)
+            .await;
+        if !allowed {
+            self.telemetry.metrics.increment_counter(
+                "gateway_rate_limit_rejected_total",
+                1.0,
+                Some(labels),
+            );
+        }
+        allowed
+    }
 }
 
 #[derive(Clone)]
@@ -161,9 +575,20 @@ impl Default for TelemetryState {
     }
 }
 
-#[derive(Clone)]
-pub struct RateLimiter {
-    inner: Arc<RwLock<HashMap<(Uuid, String), RateWindow>>>,
+/// Where a [`RateLimiter`] actually keeps its fixed-window counters.
+/// [`InMemoryRateLimitBackend`] is the historical per-process behavior;
+/// [`StoreRateLimitBackend`] routes through a shared [`RateLimitStore`] so
+/// every gateway replica enforces one quota per tenant instead of each
+/// counting in isolation.
+#[async_trait]
+pub trait RateLimitBackend: Send + Sync {
+    async fn check_and_increment(
+        &self,
+        tenant_id: Uuid,
+        route: &str,
+        limit: u32,
+        window: StdDuration,
+    ) -> bool;
 }
 
 #[derive(Clone)]
@@ -172,14 +597,23 @@ struct RateWindow {
     count: u32,
 }
 
-impl RateLimiter {
+/// Per-process fixed-window counters. Resets on restart and isn't shared
+/// across replicas, but needs no round trip to anything — the right choice
+/// for a single-instance deployment or for tests.
+#[derive(Clone, Default)]
+pub struct InMemoryRateLimitBackend {
+    inner: Arc<RwLock<HashMap<(Uuid, String), RateWindow>>>,
+}
+
+impl InMemoryRateLimitBackend {
     pub fn new() -> Self {
-        Self {
-            inner: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self::default()
     }
+}
 
-    pub fn check_and_increment(
+#[async_trait]
+impl RateLimitBackend for InMemoryRateLimitBackend {
+    async fn check_and_increment(
         &self,
         tenant_id: Uuid,
         route: &str,
@@ -204,3 +638,125 @@ impl RateLimiter {
         true
     }
 }
+
+/// A cached window read, good for [`CACHE_TTL`] before it's reconciled
+/// against the shared store again. Only ever used to short-circuit a
+/// rejection (see [`StoreRateLimitBackend::check_and_increment`]) — it never
+/// grants a request on its own, so it never drifts from the store's count by
+/// admitting something the store doesn't know about.
+struct CachedWindow {
+    window_start: DateTime<Utc>,
+    count: u32,
+    cached_at: Instant,
+}
+
+/// How long a cached window count is trusted to still be over `limit` before
+/// the next request for that `(tenant_id, route)` pays for a store round
+/// trip again and re-checks for real.
+const CACHE_TTL: StdDuration = StdDuration::from_secs(2);
+
+/// Fixed-window counters backed by [`RateLimitStore`], so multiple gateway
+/// replicas agree on one quota per tenant instead of each enforcing its own
+/// in-process count. Every request that could still be admitted goes through
+/// [`RateLimitStore::increment_rate_window`] so the shared count stays
+/// authoritative — a replica never admits a request purely off a local
+/// cache. The local cache (see [`CACHE_TTL`]) only short-circuits the
+/// *rejection* path: once a replica has seen a window reported as already at
+/// or over `limit`, it keeps rejecting that `(tenant_id, route)` without a
+/// further store round trip until the cache entry goes stale, since nothing
+/// about that decision can change by admitting more load.
+pub struct StoreRateLimitBackend {
+    store: Arc<dyn RateLimitStore>,
+    cache: Arc<RwLock<HashMap<(Uuid, String), CachedWindow>>>,
+}
+
+impl StoreRateLimitBackend {
+    pub fn new(store: Arc<dyn RateLimitStore>) -> Self {
+        Self {
+            store,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for StoreRateLimitBackend {
+    async fn check_and_increment(
+        &self,
+        tenant_id: Uuid,
+        route: &str,
+        limit: u32,
+        window: StdDuration,
+    ) -> bool {
+        let key = (tenant_id, route.to_string());
+        let now = Instant::now();
+        let window_secs = window.as_secs().max(1) as i64;
+        let epoch = Utc::now().timestamp();
+        let window_start = DateTime::<Utc>::from_timestamp(epoch - epoch % window_secs, 0)
+            .unwrap_or_else(Utc::now);
+
+        {
+            let guard = self.cache.read().unwrap();
+            if let Some(cached) = guard.get(&key) {
+                if cached.window_start == window_start
+                    && now.duration_since(cached.cached_at) < CACHE_TTL
+                    && cached.count >= limit
+                {
+                    return false;
+                }
+            }
+        }
+
+        let count = match self
+            .store
+            .increment_rate_window(tenant_id, route, window_start)
+            .await
+        {
+            Ok(count) => count,
+            Err(err) => {
+                // A store outage shouldn't take down every tenant's traffic;
+                // the next successful reconciliation catches back up.
+                tracing::error!(error = %err, "rate_limit.store_failed");
+                return true;
+            }
+        };
+        self.cache.write().unwrap().insert(
+            key,
+            CachedWindow {
+                window_start,
+                count,
+                cached_at: now,
+            },
+        );
+        count <= limit
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    backend: Arc<dyn RateLimitBackend>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self {
+            backend: Arc::new(InMemoryRateLimitBackend::new()),
+        }
+    }
+
+    pub fn with_backend(backend: Arc<dyn RateLimitBackend>) -> Self {
+        Self { backend }
+    }
+
+    pub async fn check_and_increment(
+        &self,
+        tenant_id: Uuid,
+        route: &str,
+        limit: u32,
+        window: StdDuration,
+    ) -> bool {
+        self.backend
+            .check_and_increment(tenant_id, route, limit, window)
+            .await
+    }
+}
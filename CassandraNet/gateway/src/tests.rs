@@ -1,8 +1,14 @@
 use crate::{
     auth::{hs256_generate, hs256_validate},
-    grpc::InMemoryAgentControl,
+    grant::{
+        issue_content_grant, verify_content_grant, ContentAccessPolicy, ContentPermission,
+        GrantError,
+    },
+    grpc::{session_rotation_outcome, InMemoryAgentControl},
     http::{
-        health, list_agents, metrics as metrics_route, version, ApiDoc, ContentMetadataResponse,
+        capabilities, health, list_agents, metrics as metrics_route, version, ApiDoc,
+        ContentMetadataResponse, JobStatusResponse, LifecyclePolicyListResponse,
+        LifecyclePolicyResponse, LifecycleSweepResponse, RenditionJobResponse,
         UploadSessionResponse,
     },
     metrics::{self, MetricsLayer},
@@ -12,11 +18,12 @@ use axum::{
     body::to_bytes,
     http::{Request, StatusCode},
     routing::get,
-    Router,
+    Json, Router,
 };
 use chrono::Utc;
-use cncore::platform::models::{Project, Tenant, TenantSettings};
+use cncore::platform::models::{ContentMetadata, ContentVisibility, Project, Tenant, TenantSettings};
 use cncore::platform::persistence::{ContentStore, InMemoryPersistence, ProjectStore, TenantStore};
+use std::collections::HashMap;
 use cnproto::{agent_control_client::AgentControlClient, HeartbeatRequest, RegisterAgentRequest};
 use once_cell::sync::Lazy;
 use serde_json::json;
@@ -79,6 +86,35 @@ async fn version_endpoint_has_build_info() {
     }
 }
 
+#[tokio::test]
+async fn capabilities_reports_scopes_and_backend() {
+    cncore::init_tracing();
+    let app = Router::new().route("/capabilities", get(capabilities));
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/capabilities")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), 16 * 1024).await.unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["db_backend_active"], cfg!(feature = "db"));
+    assert_eq!(
+        v["required_scopes"]["GET /telemetry/logs"],
+        "observability:read"
+    );
+    assert_eq!(
+        v["required_scopes"]["POST /tenants/:tenant_id/projects/:project_id/uploads"],
+        "ugc:write"
+    );
+    let modes = v["pagination_modes"].as_array().unwrap();
+    assert!(modes.iter().any(|m| m == "cursor"));
+}
+
 #[tokio::test]
 async fn metrics_exists() {
     cncore::init_tracing();
@@ -106,6 +142,10 @@ async fn metrics_exists() {
         .await
         .unwrap();
     assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers().get("content-type").unwrap(),
+        "text/plain; version=0.0.4"
+    );
     let body = to_bytes(res.into_body(), 64 * 1024).await.unwrap();
     let text = String::from_utf8(body.to_vec()).unwrap();
     assert!(
@@ -117,6 +157,55 @@ async fn metrics_exists() {
         text.contains("gateway_build_info"),
         "missing build info gauge"
     );
+    assert!(
+        text.contains("gateway_http_requests_in_flight"),
+        "missing in-flight request gauge"
+    );
+}
+
+#[tokio::test]
+async fn compression_layer_gzips_large_json_and_leaves_small_bodies_alone() {
+    cncore::init_tracing();
+    async fn big_json() -> Json<serde_json::Value> {
+        Json(json!({ "items": vec!["x".repeat(64); 64] }))
+    }
+    async fn small_json() -> Json<serde_json::Value> {
+        Json(json!({ "ok": true }))
+    }
+
+    let app = Router::new()
+        .route("/big", get(big_json))
+        .route("/small", get(small_json))
+        .layer(crate::compression::CompressionLayer);
+
+    let big_res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/big")
+                .header("accept-encoding", "gzip, br")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        big_res.headers().get("content-encoding").unwrap(),
+        "br"
+    );
+    assert_eq!(big_res.headers().get("vary").unwrap(), "accept-encoding");
+
+    let small_res = app
+        .oneshot(
+            Request::builder()
+                .uri("/small")
+                .header("accept-encoding", "gzip, br")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(small_res.headers().get("content-encoding").is_none());
 }
 
 #[tokio::test]
@@ -167,9 +256,147 @@ async fn openapi_has_security_schemes() {
     );
 }
 
+#[tokio::test]
+async fn register_agent_rejects_re_registration_of_a_verified_id_without_a_handshake_key() {
+    cncore::init_tracing();
+    let state = AppState::default();
+    let agent_svc = InMemoryAgentControl::new(state.registry.clone())
+        .with_enrollment(state.enrollment.clone())
+        .into_server();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let grpc_addr = listener.local_addr().unwrap();
+    let incoming = TcpListenerStream::new(listener);
+    let grpc = Server::builder()
+        .add_service(agent_svc)
+        .serve_with_incoming(incoming);
+    tokio::spawn(async move {
+        let _ = grpc.await;
+    });
+
+    let channel = Channel::from_shared(format!("http://{}", grpc_addr))
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut client = AgentControlClient::new(channel);
+
+    let agent_secret = x25519_dalek::EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+    let agent_public = x25519_dalek::PublicKey::from(&agent_secret);
+    let reg = RegisterAgentRequest {
+        node_id: "verified-node".into(),
+        hostname: "host1".into(),
+        os: "os".into(),
+        arch: "arch".into(),
+        cpu_cores: 4,
+        memory_bytes: 1024,
+        secret: "s".into(),
+        tenant_id: String::new(),
+        project_id: String::new(),
+        x25519_public_key: agent_public.as_bytes().to_vec(),
+        ..Default::default()
+    };
+    client.register_agent(reg).await.unwrap();
+    assert!(state.enrollment.confirm("verified-node"));
+
+    // Re-registering the now-verified id without a fresh handshake key must
+    // not be allowed to silently take over the id.
+    let impersonation = RegisterAgentRequest {
+        node_id: "verified-node".into(),
+        hostname: "attacker-host".into(),
+        os: "os".into(),
+        arch: "arch".into(),
+        cpu_cores: 1,
+        memory_bytes: 1,
+        secret: "s".into(),
+        tenant_id: String::new(),
+        project_id: String::new(),
+        ..Default::default()
+    };
+    let err = client.register_agent(impersonation).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn register_agent_with_a_different_key_does_not_take_over_a_verified_id_before_confirmation() {
+    cncore::init_tracing();
+    let state = AppState::default();
+    let agent_svc = InMemoryAgentControl::new(state.registry.clone())
+        .with_enrollment(state.enrollment.clone())
+        .into_server();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let grpc_addr = listener.local_addr().unwrap();
+    let incoming = TcpListenerStream::new(listener);
+    let grpc = Server::builder()
+        .add_service(agent_svc)
+        .serve_with_incoming(incoming);
+    tokio::spawn(async move {
+        let _ = grpc.await;
+    });
+
+    let channel = Channel::from_shared(format!("http://{}", grpc_addr))
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut client = AgentControlClient::new(channel);
+
+    let agent_secret = x25519_dalek::EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+    let agent_public = x25519_dalek::PublicKey::from(&agent_secret);
+    let reg = RegisterAgentRequest {
+        node_id: "victim-node".into(),
+        hostname: "victim-host".into(),
+        os: "victim-os".into(),
+        arch: "victim-arch".into(),
+        cpu_cores: 4,
+        memory_bytes: 1024,
+        secret: "s".into(),
+        tenant_id: String::new(),
+        project_id: String::new(),
+        x25519_public_key: agent_public.as_bytes().to_vec(),
+        ..Default::default()
+    };
+    client.register_agent(reg).await.unwrap();
+    assert!(state.enrollment.confirm("victim-node"));
+
+    // An attacker who only knows the node id presents their own freshly
+    // generated key. This must not overwrite the registry or mint a token —
+    // it should only park a reverification challenge for an operator.
+    let attacker_secret = x25519_dalek::EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+    let attacker_public = x25519_dalek::PublicKey::from(&attacker_secret);
+    let takeover_attempt = RegisterAgentRequest {
+        node_id: "victim-node".into(),
+        hostname: "attacker-host".into(),
+        os: "attacker-os".into(),
+        arch: "attacker-arch".into(),
+        cpu_cores: 1,
+        memory_bytes: 1,
+        secret: "s".into(),
+        tenant_id: String::new(),
+        project_id: String::new(),
+        x25519_public_key: attacker_public.as_bytes().to_vec(),
+        ..Default::default()
+    };
+    let resp = client
+        .register_agent(takeover_attempt)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(resp.verification_required);
+    assert!(resp.session_token.is_empty());
+
+    let summary = state
+        .registry
+        .list()
+        .into_iter()
+        .find(|a| a.id == "victim-node")
+        .expect("victim-node still present");
+    assert_eq!(summary.hostname, "victim-host");
+}
+
 #[tokio::test]
 async fn agents_list_after_grpc_heartbeat() {
     cncore::init_tracing();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
     let store: Arc<dyn ContentStore> = Arc::new(InMemoryPersistence::new());
     let state = AppState::with_content_store(store);
     let agent_svc = InMemoryAgentControl::new(state.registry.clone()).into_server();
@@ -199,8 +426,9 @@ async fn agents_list_after_grpc_heartbeat() {
         secret: "s".into(),
         tenant_id: String::new(),
         project_id: String::new(),
+        ..Default::default()
     };
-    let _ = client.register_agent(reg).await.unwrap();
+    let reg_resp = client.register_agent(reg).await.unwrap().into_inner();
     let hb = HeartbeatRequest {
         assigned_id: "node1".into(),
         cpu_percent: 10.0,
@@ -208,8 +436,10 @@ async fn agents_list_after_grpc_heartbeat() {
         network_rx_bytes: 0,
         network_tx_bytes: 0,
         timestamp_unix_ms: 0,
+        session_token: reg_resp.session_token,
     };
     let _ = client.heartbeat(hb).await.unwrap();
+    std::env::remove_var("CASS_JWT_SECRET");
 
     let app = Router::new()
         .route("/agents", get(list_agents))
@@ -227,7 +457,32 @@ async fn agents_list_after_grpc_heartbeat() {
     assert_eq!(resp.status(), StatusCode::OK);
     let body = to_bytes(resp.into_body(), 16 * 1024).await.unwrap();
     let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert!(v.as_array().unwrap().iter().any(|a| a["id"] == "node1"));
+    assert!(v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|a| a["id"] == "node1"));
+}
+
+#[test]
+fn session_rotation_outcome_falls_back_to_legacy_rotation_when_minting_fails() {
+    // Not due yet: no rotation signal at all.
+    assert_eq!(
+        session_rotation_outcome(false, None),
+        (false, String::new())
+    );
+    // Due, and minting succeeded: the new JWT goes straight to the agent.
+    assert_eq!(
+        session_rotation_outcome(true, Some("new-token".to_string())),
+        (true, "new-token".to_string())
+    );
+    // Due, but minting failed server-side: still ask the agent to rotate,
+    // with an empty token, so it falls back to the legacy RotateCredentials
+    // RPC instead of being left with no rotation path until expiry.
+    assert_eq!(
+        session_rotation_outcome(true, None),
+        (true, String::new())
+    );
 }
 
 #[tokio::test]
@@ -271,16 +526,170 @@ async fn agents_filtering_by_hostname() {
     assert_eq!(resp.status(), StatusCode::OK);
     let body = to_bytes(resp.into_body(), 16 * 1024).await.unwrap();
     let list: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    let items = list.as_array().unwrap();
+    let items = list["items"].as_array().unwrap();
     assert_eq!(items.len(), 1);
     assert_eq!(items[0]["id"], "alpha");
 }
 
 #[tokio::test]
-async fn ugc_upload_flow_round_trip() {
+async fn agents_keyset_cursor_pages_without_duplicates() {
+    cncore::init_tracing();
+    let state = AppState::default();
+    for name in ["alpha", "beta", "gamma"] {
+        state.registry.upsert(
+            name.into(),
+            format!("host-{name}"),
+            10.0,
+            256,
+            None,
+            None,
+            None,
+            None,
+        );
+        // Ensure each agent gets a distinct last_seen so the sort order is
+        // deterministic instead of depending on id comparisons alone.
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    let app = Router::new()
+        .route("/agents", get(list_agents))
+        .with_state(state.clone());
+
+    let first_resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/agents?limit=2")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(first_resp.status(), StatusCode::OK);
+    let first_body = to_bytes(first_resp.into_body(), 16 * 1024).await.unwrap();
+    let first: serde_json::Value = serde_json::from_slice(&first_body).unwrap();
+    let first_items = first["items"].as_array().unwrap();
+    assert_eq!(first_items.len(), 2);
+    let cursor = first["next_cursor"].as_str().expect("next_cursor present");
+
+    let second_resp = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/agents?limit=2&cursor={cursor}"))
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(second_resp.status(), StatusCode::OK);
+    let second_body = to_bytes(second_resp.into_body(), 16 * 1024).await.unwrap();
+    let second: serde_json::Value = serde_json::from_slice(&second_body).unwrap();
+    let second_items = second["items"].as_array().unwrap();
+    assert_eq!(second_items.len(), 1);
+    assert!(second["next_cursor"].is_null());
+
+    let first_ids: Vec<_> = first_items.iter().map(|a| a["id"].clone()).collect();
+    let second_ids: Vec<_> = second_items.iter().map(|a| a["id"].clone()).collect();
+    assert!(first_ids.iter().all(|id| !second_ids.contains(id)));
+}
+
+#[tokio::test]
+async fn agents_opt_out_suppresses_without_deleting() {
+    cncore::init_tracing();
+    let state = AppState::default();
+    state.registry.upsert(
+        "alpha".into(),
+        "host-alpha".into(),
+        20.0,
+        512,
+        None,
+        None,
+        None,
+        None,
+    );
+    state.registry.upsert(
+        "beta".into(),
+        "host-beta".into(),
+        10.0,
+        256,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let app = crate::http::router().with_state(state.clone());
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/agents/alpha/opt-out")
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let list_resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/agents")
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(list_resp.into_body(), 16 * 1024).await.unwrap();
+    let list: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let items = list["items"].as_array().unwrap();
+    assert!(items.iter().all(|a| a["id"] != "alpha"));
+    assert!(items.iter().any(|a| a["id"] == "beta"));
+
+    // The underlying row survives the opt-out; only the listing is filtered.
+    assert!(state.registry.get("alpha").is_some());
+
+    let clear_resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/admin/agents/alpha/opt-out")
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(clear_resp.status(), StatusCode::NO_CONTENT);
+
+    let relist_resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/agents")
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(relist_resp.into_body(), 16 * 1024).await.unwrap();
+    let list: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let items = list["items"].as_array().unwrap();
+    assert!(items.iter().any(|a| a["id"] == "alpha"));
+}
+
+#[tokio::test]
+async fn content_keyset_cursor_pages_and_rejects_cross_tenant_reuse() {
     cncore::init_tracing();
     let persistence = Arc::new(InMemoryPersistence::new());
     let tenant_id = uuid::Uuid::new_v4();
+    let other_tenant_id = uuid::Uuid::new_v4();
     let project_id = uuid::Uuid::new_v4();
     persistence
         .insert_tenant(Tenant {
@@ -299,26 +708,147 @@ async fn ugc_upload_flow_round_trip() {
         })
         .unwrap();
 
+    for name in ["one", "two", "three"] {
+        persistence
+            .record_content_metadata(cncore::platform::models::ContentMetadata {
+                id: uuid::Uuid::new_v4(),
+                tenant_id,
+                project_id,
+                filename: name.into(),
+                mime_type: Some("image/png".into()),
+                size_bytes: Some(1024),
+                checksum: None,
+                storage_path: None,
+                labels: vec![],
+                attributes: Default::default(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                uploaded_by: None,
+                visibility: cncore::platform::models::ContentVisibility::Tenant,
+                blurhash: None,
+                relevance: None,
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
     let store: Arc<dyn ContentStore> = persistence.clone();
-    let state = AppState::with_content_store(store);
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
     let app = crate::http::router().with_state(state.clone());
 
-    let create_body = json!({
-        "filename": "avatar.png",
-        "mime_type": "image/png",
-        "size_bytes": 1024,
-        "labels": ["avatar", "profile"],
-        "attributes": {"resolution": "512x512"},
-        "visibility": "tenant"
-    });
-    let create_req = axum::http::Request::builder()
-        .method("POST")
-        .uri(format!(
-            "/tenants/{}/projects/{}/uploads",
-            tenant_id, project_id
-        ))
-        .header("content-type", "application/json")
-        .header("x-api-key", "test-key")
+    let first_resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/tenants/{}/projects/{}/content?limit=2",
+                    tenant_id, project_id
+                ))
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(first_resp.status(), StatusCode::OK);
+    let first_body = to_bytes(first_resp.into_body(), 16 * 1024).await.unwrap();
+    let first: serde_json::Value = serde_json::from_slice(&first_body).unwrap();
+    let first_items = first["items"].as_array().unwrap();
+    assert_eq!(first_items.len(), 2);
+    let cursor = first["next_cursor"].as_str().expect("next_cursor present");
+
+    let second_resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/tenants/{}/projects/{}/content?limit=2&cursor={}",
+                    tenant_id, project_id, cursor
+                ))
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(second_resp.status(), StatusCode::OK);
+    let second_body = to_bytes(second_resp.into_body(), 16 * 1024).await.unwrap();
+    let second: serde_json::Value = serde_json::from_slice(&second_body).unwrap();
+    let second_items = second["items"].as_array().unwrap();
+    assert_eq!(second_items.len(), 1);
+    assert!(second["next_cursor"].is_null());
+
+    let first_ids: Vec<_> = first_items.iter().map(|m| m["id"].clone()).collect();
+    let second_ids: Vec<_> = second_items.iter().map(|m| m["id"].clone()).collect();
+    assert!(first_ids.iter().all(|id| !second_ids.contains(id)));
+
+    let cross_tenant_resp = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/tenants/{}/projects/{}/content?limit=2&cursor={}",
+                    other_tenant_id, project_id, cursor
+                ))
+                .header("x-api-key", "test-key")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(cross_tenant_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn ugc_upload_flow_round_trip() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(1024 - png_bytes.len()));
+
+    let create_body = json!({
+        "filename": "avatar.png",
+        "mime_type": "image/png",
+        "size_bytes": 1024,
+        "labels": ["avatar", "profile"],
+        "attributes": {"resolution": "512x512"},
+        "visibility": "tenant"
+    });
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
         .body(axum::body::Body::from(create_body.to_string()))
         .unwrap();
     let create_res = app
@@ -337,12 +867,12 @@ async fn ugc_upload_flow_round_trip() {
         String::from_utf8_lossy(&create_bytes)
     );
     let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+    object_fetcher.put(session.storage_path.clone(), png_bytes.clone());
 
     let complete_body = json!({
         "filename": "avatar.png",
         "mime_type": "image/png",
         "size_bytes": 1024,
-        "checksum": "abc123",
         "labels": ["avatar", "profile"],
         "attributes": {"resolution": "512x512"},
         "visibility": "tenant"
@@ -374,7 +904,15 @@ async fn ugc_upload_flow_round_trip() {
     );
     let metadata: ContentMetadataResponse = serde_json::from_slice(&complete_bytes).unwrap();
     assert_eq!(metadata.filename, "avatar.png");
-    assert_eq!(metadata.size_bytes, Some(1024));
+    assert_eq!(metadata.mime_type, Some("image/png".to_string()));
+    assert_eq!(metadata.size_bytes, Some(png_bytes.len() as u64));
+    assert_eq!(
+        metadata.checksum,
+        Some(cncore::platform::ingest::compute_digest(
+            &png_bytes,
+            cncore::platform::ingest::ChecksumAlgorithm::Sha256
+        ))
+    );
 
     let list_req = axum::http::Request::builder()
         .method("GET")
@@ -396,30 +934,2235 @@ async fn ugc_upload_flow_round_trip() {
         "list failed: {}",
         String::from_utf8_lossy(&list_bytes)
     );
-    let entries: Vec<ContentMetadataResponse> = serde_json::from_slice(&list_bytes).unwrap();
+    let page: serde_json::Value = serde_json::from_slice(&list_bytes).unwrap();
+    let entries: Vec<ContentMetadataResponse> =
+        serde_json::from_value(page["items"].clone()).unwrap();
     assert!(entries.iter().any(|m| m.id == metadata.id));
 }
 
-#[test]
-fn normalize_path_reduces_ids() {
-    assert_eq!(metrics::normalize_path("/agents").as_ref(), "/agents");
-    assert_eq!(
-        metrics::normalize_path("/agents/123").as_ref(),
-        "/agents/:id"
-    );
-    assert_eq!(
-        metrics::normalize_path("/agents/550e8400-e29b-41d4-a716-446655440000").as_ref(),
-        "/agents/:id"
-    );
+#[tokio::test]
+async fn ugc_complete_upload_rejects_spoofed_mime_type() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "payload.bin", "mime_type": "image/png", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    // The client claims image/png, but the bytes actually landed in storage
+    // are an executable's magic header, not a PNG.
+    object_fetcher.put(session.storage_path.clone(), b"MZ\x90\x00\x03\x00\x00\x00".to_vec());
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "payload.bin",
+                "mime_type": "image/png",
+                "size_bytes": 8,
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    assert_eq!(complete_res.status(), StatusCode::BAD_REQUEST);
 }
 
-#[test]
-fn hs256_roundtrip() {
-    let _guard = ENV_GUARD.lock().unwrap();
-    std::env::set_var("CASS_JWT_SECRET", "test-secret");
-    let token = hs256_generate("demo").unwrap();
-    assert!(hs256_validate(&token).unwrap());
-    std::env::set_var("CASS_JWT_SECRET", "other-secret");
-    assert!(!hs256_validate(&token).unwrap());
+#[tokio::test]
+async fn ugc_complete_upload_rejects_checksum_mismatch() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(1024 - png_bytes.len()));
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+    object_fetcher.put(session.storage_path.clone(), png_bytes.clone());
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "size_bytes": 1024,
+                "checksum": "sha256:0000000000000000000000000000000000000000000000000000000000000000",
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    assert_eq!(complete_res.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn ugc_complete_upload_dedups_identical_content() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(1024 - png_bytes.len()));
+
+    async fn upload_once(
+        app: &Router,
+        object_fetcher: &crate::ingest::InMemoryObjectFetcher,
+        tenant_id: uuid::Uuid,
+        project_id: uuid::Uuid,
+        png_bytes: &[u8],
+    ) -> ContentMetadataResponse {
+        let create_req = axum::http::Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/tenants/{}/projects/{}/uploads",
+                tenant_id, project_id
+            ))
+            .header("content-type", "application/json")
+            .header("x-api-key", "test-key")
+            .body(axum::body::Body::from(
+                json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "tenant"})
+                    .to_string(),
+            ))
+            .unwrap();
+        let create_res = app
+            .clone()
+            .oneshot(create_req)
+            .await
+            .expect("create upload response");
+        let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+            .await
+            .unwrap();
+        let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+        object_fetcher.put(session.storage_path.clone(), png_bytes.to_vec());
+
+        let complete_req = axum::http::Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/tenants/{}/projects/{}/uploads/{}/complete",
+                tenant_id, project_id, session.upload_id
+            ))
+            .header("content-type", "application/json")
+            .header("x-api-key", "test-key")
+            .body(axum::body::Body::from(
+                json!({
+                    "filename": "avatar.png",
+                    "mime_type": "image/png",
+                    "size_bytes": 1024,
+                    "visibility": "tenant"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let complete_res = app
+            .clone()
+            .oneshot(complete_req)
+            .await
+            .expect("complete upload response");
+        assert_eq!(complete_res.status(), StatusCode::OK);
+        let complete_bytes = axum::body::to_bytes(complete_res.into_body(), 16 * 1024)
+            .await
+            .unwrap();
+        serde_json::from_slice(&complete_bytes).unwrap()
+    }
+
+    let first = upload_once(&app, &object_fetcher, tenant_id, project_id, &png_bytes).await;
+    let second = upload_once(&app, &object_fetcher, tenant_id, project_id, &png_bytes).await;
+
+    assert_ne!(first.id, second.id);
+    assert_eq!(first.checksum, second.checksum);
+    assert_eq!(first.storage_path, second.storage_path);
+}
+
+#[tokio::test]
+async fn ugc_set_lifecycle_policy_round_trips_through_list() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let app = crate::http::router().with_state(state.clone());
+
+    let set_req = axum::http::Request::builder()
+        .method("PUT")
+        .uri(format!("/tenants/{}/lifecycle-policies", tenant_id))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "label_selector": ["stale"],
+                "max_age_days": 30,
+                "action": "Delete"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let set_res = app.clone().oneshot(set_req).await.expect("set response");
+    assert_eq!(set_res.status(), StatusCode::OK);
+    let set_bytes = axum::body::to_bytes(set_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let policy: LifecyclePolicyResponse = serde_json::from_slice(&set_bytes).unwrap();
+    assert_eq!(policy.max_age_days, 30);
+
+    let list_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!("/tenants/{}/lifecycle-policies", tenant_id))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let list_res = app.oneshot(list_req).await.expect("list response");
+    assert_eq!(list_res.status(), StatusCode::OK);
+    let list_bytes = axum::body::to_bytes(list_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let listed: LifecyclePolicyListResponse = serde_json::from_slice(&list_bytes).unwrap();
+    assert_eq!(listed.items.len(), 1);
+    assert_eq!(listed.items[0].id, policy.id);
+}
+
+#[tokio::test]
+async fn ugc_sweep_expired_content_reports_aged_out_items() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let stale_content_id = uuid::Uuid::new_v4();
+    let stale_timestamp = Utc::now() - chrono::Duration::days(60);
+    persistence
+        .record_content_metadata(ContentMetadata {
+            id: stale_content_id,
+            tenant_id,
+            project_id,
+            filename: "old.bin".into(),
+            mime_type: None,
+            size_bytes: Some(10),
+            checksum: None,
+            storage_path: None,
+            labels: vec!["stale".into()],
+            attributes: HashMap::new(),
+            created_at: stale_timestamp,
+            updated_at: stale_timestamp,
+            uploaded_by: None,
+            visibility: ContentVisibility::Tenant,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        })
+        .await
+        .unwrap();
+    persistence
+        .record_content_metadata(ContentMetadata {
+            id: uuid::Uuid::new_v4(),
+            tenant_id,
+            project_id,
+            filename: "fresh.bin".into(),
+            mime_type: None,
+            size_bytes: Some(10),
+            checksum: None,
+            storage_path: None,
+            labels: vec!["stale".into()],
+            attributes: HashMap::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            uploaded_by: None,
+            visibility: ContentVisibility::Tenant,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        })
+        .await
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let app = crate::http::router().with_state(state.clone());
+
+    let set_req = axum::http::Request::builder()
+        .method("PUT")
+        .uri(format!("/tenants/{}/lifecycle-policies", tenant_id))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "label_selector": ["stale"],
+                "max_age_days": 30,
+                "action": "Delete"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let set_res = app.clone().oneshot(set_req).await.expect("set response");
+    assert_eq!(set_res.status(), StatusCode::OK);
+
+    let sweep_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!("/tenants/{}/lifecycle-policies/sweep", tenant_id))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let sweep_res = app.oneshot(sweep_req).await.expect("sweep response");
+    assert_eq!(sweep_res.status(), StatusCode::OK);
+    let sweep_bytes = axum::body::to_bytes(sweep_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let sweep: LifecycleSweepResponse = serde_json::from_slice(&sweep_bytes).unwrap();
+    assert_eq!(sweep.outcomes.len(), 1);
+    assert_eq!(sweep.outcomes[0].content_id, stale_content_id);
+}
+
+#[tokio::test]
+async fn ugc_sweep_expired_content_deletes_and_drops_from_listing() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let expired_content_id = uuid::Uuid::new_v4();
+    let expired_timestamp = Utc::now() - chrono::Duration::days(90);
+    persistence
+        .record_content_metadata(ContentMetadata {
+            id: expired_content_id,
+            tenant_id,
+            project_id,
+            filename: "expired.bin".into(),
+            mime_type: None,
+            size_bytes: Some(10),
+            checksum: None,
+            storage_path: None,
+            labels: vec!["ephemeral".into()],
+            attributes: HashMap::new(),
+            created_at: expired_timestamp,
+            updated_at: expired_timestamp,
+            uploaded_by: None,
+            visibility: ContentVisibility::Tenant,
+            blurhash: None,
+            immutability: None,
+            legal_hold: false,
+            relevance: None,
+        })
+        .await
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let app = crate::http::router().with_state(state.clone());
+
+    let set_req = axum::http::Request::builder()
+        .method("PUT")
+        .uri(format!("/tenants/{}/lifecycle-policies", tenant_id))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "label_selector": ["ephemeral"],
+                "max_age_days": 30,
+                "action": "Delete"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let set_res = app.clone().oneshot(set_req).await.expect("set response");
+    assert_eq!(set_res.status(), StatusCode::OK);
+
+    let sweep_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!("/tenants/{}/lifecycle-policies/sweep", tenant_id))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let sweep_res = app.clone().oneshot(sweep_req).await.expect("sweep response");
+    assert_eq!(sweep_res.status(), StatusCode::OK);
+
+    let list_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content",
+            tenant_id, project_id
+        ))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let list_res = app.oneshot(list_req).await.expect("list content response");
+    assert_eq!(list_res.status(), StatusCode::OK);
+    let list_bytes = axum::body::to_bytes(list_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let page: crate::http::ContentListResponse = serde_json::from_slice(&list_bytes).unwrap();
+    assert!(page.items.iter().all(|item| item.id != expired_content_id));
+}
+
+#[tokio::test]
+async fn ugc_multipart_upload_round_trip() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    for part_number in 1..=2u32 {
+        let part_req = axum::http::Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/tenants/{}/projects/{}/uploads/{}/parts",
+                tenant_id, project_id, session.upload_id
+            ))
+            .header("content-type", "application/json")
+            .header("x-api-key", "test-key")
+            .body(axum::body::Body::from(
+                json!({"part_number": part_number}).to_string(),
+            ))
+            .unwrap();
+        let part_res = app
+            .clone()
+            .oneshot(part_req)
+            .await
+            .expect("request upload part response");
+        assert_eq!(part_res.status(), StatusCode::OK);
+    }
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(16));
+    object_fetcher.put(session.storage_path.clone(), png_bytes.clone());
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "size_bytes": png_bytes.len(),
+                "visibility": "tenant",
+                "parts": [
+                    {"part_number": 1, "etag": "etag-a"},
+                    {"part_number": 2, "etag": "etag-b"}
+                ]
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    let complete_status = complete_res.status();
+    let complete_bytes = axum::body::to_bytes(complete_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    assert_eq!(
+        complete_status,
+        StatusCode::OK,
+        "complete failed: {}",
+        String::from_utf8_lossy(&complete_bytes)
+    );
+}
+
+#[tokio::test]
+async fn ugc_register_upload_part_is_authoritative_at_completion() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(16));
+    object_fetcher.put(session.storage_path.clone(), png_bytes.clone());
+
+    for (part_number, size) in [(1u32, 16u64), (2u32, png_bytes.len() as u64 - 16)] {
+        let register_req = axum::http::Request::builder()
+            .method("PUT")
+            .uri(format!(
+                "/tenants/{}/projects/{}/uploads/{}/parts",
+                tenant_id, project_id, session.upload_id
+            ))
+            .header("content-type", "application/json")
+            .header("x-api-key", "test-key")
+            .body(axum::body::Body::from(
+                json!({
+                    "part_number": part_number,
+                    "etag": format!("etag-{part_number}"),
+                    "size_bytes": size
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let register_res = app
+            .clone()
+            .oneshot(register_req)
+            .await
+            .expect("register upload part response");
+        assert_eq!(register_res.status(), StatusCode::NO_CONTENT);
+    }
+
+    // The complete request deliberately omits `parts` — the server's own
+    // registered parts should be used instead.
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "size_bytes": png_bytes.len(),
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    let complete_status = complete_res.status();
+    let complete_bytes = axum::body::to_bytes(complete_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    assert_eq!(
+        complete_status,
+        StatusCode::OK,
+        "complete failed: {}",
+        String::from_utf8_lossy(&complete_bytes)
+    );
+}
+
+#[tokio::test]
+async fn ugc_complete_upload_rejects_mismatched_registered_part_sizes() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "movie.mp4", "mime_type": "video/mp4", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    let register_req = axum::http::Request::builder()
+        .method("PUT")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/parts",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"part_number": 1, "etag": "etag-a", "size_bytes": 5}).to_string(),
+        ))
+        .unwrap();
+    let register_res = app
+        .clone()
+        .oneshot(register_req)
+        .await
+        .expect("register upload part response");
+    assert_eq!(register_res.status(), StatusCode::NO_CONTENT);
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "movie.mp4",
+                "mime_type": "video/mp4",
+                "size_bytes": 20,
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    assert_eq!(complete_res.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn ugc_upload_part_bytes_stages_and_assembles_real_content() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    state.object_fetcher = Arc::new(crate::ingest::InMemoryObjectFetcher::default());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app.clone().oneshot(create_req).await.unwrap();
+    let create_bytes = to_bytes(create_res.into_body(), 16 * 1024).await.unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(16));
+
+    let part_req = Request::builder()
+        .method("PUT")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/parts/1",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/octet-stream")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(png_bytes.clone()))
+        .unwrap();
+    let part_res = app.clone().oneshot(part_req).await.unwrap();
+    assert_eq!(part_res.status(), StatusCode::OK);
+    let part_bytes = to_bytes(part_res.into_body(), 16 * 1024).await.unwrap();
+    let part: crate::http::UploadPartResponse = serde_json::from_slice(&part_bytes).unwrap();
+    assert_eq!(part.part_number, 1);
+    assert_eq!(part.size_bytes, png_bytes.len() as u64);
+    assert!(part.etag.starts_with("sha256:"));
+
+    let complete_req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "size_bytes": png_bytes.len(),
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app.oneshot(complete_req).await.unwrap();
+    let complete_status = complete_res.status();
+    let complete_bytes = to_bytes(complete_res.into_body(), 16 * 1024).await.unwrap();
+    assert_eq!(
+        complete_status,
+        StatusCode::OK,
+        "complete failed: {}",
+        String::from_utf8_lossy(&complete_bytes)
+    );
+    let metadata: ContentMetadataResponse = serde_json::from_slice(&complete_bytes).unwrap();
+    assert_eq!(metadata.size_bytes, Some(png_bytes.len() as u64));
+}
+
+#[tokio::test]
+async fn ugc_complete_upload_rejects_undersized_non_final_staged_part() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    state.object_fetcher = Arc::new(crate::ingest::InMemoryObjectFetcher::default());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "movie.mp4", "mime_type": "video/mp4", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app.clone().oneshot(create_req).await.unwrap();
+    let create_bytes = to_bytes(create_res.into_body(), 16 * 1024).await.unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    for (part_number, size) in [(1u32, 10usize), (2u32, 20usize)] {
+        let part_req = Request::builder()
+            .method("PUT")
+            .uri(format!(
+                "/tenants/{}/projects/{}/uploads/{}/parts/{}",
+                tenant_id, project_id, session.upload_id, part_number
+            ))
+            .header("content-type", "application/octet-stream")
+            .header("x-api-key", "test-key")
+            .body(axum::body::Body::from(vec![0u8; size]))
+            .unwrap();
+        let part_res = app.clone().oneshot(part_req).await.unwrap();
+        assert_eq!(part_res.status(), StatusCode::OK);
+    }
+
+    let complete_req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "movie.mp4",
+                "mime_type": "video/mp4",
+                "size_bytes": 30,
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app.oneshot(complete_req).await.unwrap();
+    assert_eq!(complete_res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn ugc_complete_upload_rejects_non_contiguous_parts() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "movie.mp4", "mime_type": "video/mp4", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "movie.mp4",
+                "mime_type": "video/mp4",
+                "size_bytes": 20,
+                "visibility": "tenant",
+                "parts": [
+                    {"part_number": 1, "etag": "etag-a"},
+                    {"part_number": 3, "etag": "etag-b"}
+                ]
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    assert_eq!(complete_res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn ugc_abort_upload_session_blocks_completion() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let state = AppState::with_content_store(store);
+    let app = crate::http::router().with_state(state.clone());
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "draft.bin", "visibility": "tenant"}).to_string(),
+        ))
+        .unwrap();
+    let create_res = app
+        .clone()
+        .oneshot(create_req)
+        .await
+        .expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+
+    let abort_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/abort",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let abort_res = app
+        .clone()
+        .oneshot(abort_req)
+        .await
+        .expect("abort upload response");
+    assert_eq!(abort_res.status(), StatusCode::NO_CONTENT);
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "draft.bin",
+                "size_bytes": 0,
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app
+        .oneshot(complete_req)
+        .await
+        .expect("complete upload response");
+    assert_eq!(complete_res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn ugc_download_content_supports_range_requests() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(1024 - png_bytes.len()));
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "size_bytes": 1024, "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app.clone().oneshot(create_req).await.unwrap();
+    let create_bytes = to_bytes(create_res.into_body(), 16 * 1024).await.unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+    object_fetcher.put(session.storage_path.clone(), png_bytes.clone());
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "size_bytes": 1024,
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app.clone().oneshot(complete_req).await.unwrap();
+    let complete_bytes = to_bytes(complete_res.into_body(), 16 * 1024).await.unwrap();
+    let metadata: ContentMetadataResponse = serde_json::from_slice(&complete_bytes).unwrap();
+
+    let full_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let full_res = app.clone().oneshot(full_req).await.unwrap();
+    assert_eq!(full_res.status(), StatusCode::OK);
+    assert_eq!(
+        full_res.headers().get("accept-ranges").unwrap(),
+        "bytes"
+    );
+    let full_res_etag = full_res
+        .headers()
+        .get("etag")
+        .expect("etag header")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let full_body = to_bytes(full_res.into_body(), 2048).await.unwrap();
+    assert_eq!(full_body.as_ref(), png_bytes.as_slice());
+
+    let range_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .header("range", "bytes=0-9")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let range_res = app.clone().oneshot(range_req).await.unwrap();
+    assert_eq!(range_res.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        range_res.headers().get("content-range").unwrap(),
+        &format!("bytes 0-9/{}", png_bytes.len())
+    );
+    let range_body = to_bytes(range_res.into_body(), 2048).await.unwrap();
+    assert_eq!(range_body.as_ref(), &png_bytes[0..=9]);
+
+    let bad_range_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .header("range", format!("bytes={}-", png_bytes.len() + 10))
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let bad_range_res = app.clone().oneshot(bad_range_req).await.unwrap();
+    assert_eq!(bad_range_res.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+
+    let etag = full_res_etag.clone();
+    let conditional_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .header("if-none-match", etag)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let conditional_res = app.clone().oneshot(conditional_req).await.unwrap();
+    assert_eq!(conditional_res.status(), StatusCode::NOT_MODIFIED);
+
+    let stale_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .header("if-none-match", "\"some-other-etag\"")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let stale_res = app.clone().oneshot(stale_req).await.unwrap();
+    assert_eq!(stale_res.status(), StatusCode::OK);
+
+    let if_range_fresh_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .header("range", "bytes=0-9")
+        .header("if-range", full_res_etag.clone())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let if_range_fresh_res = app.clone().oneshot(if_range_fresh_req).await.unwrap();
+    assert_eq!(if_range_fresh_res.status(), StatusCode::PARTIAL_CONTENT);
+
+    let if_range_stale_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .header("range", "bytes=0-9")
+        .header("if-range", "\"some-other-etag\"")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let if_range_stale_res = app.oneshot(if_range_stale_req).await.unwrap();
+    assert_eq!(if_range_stale_res.status(), StatusCode::OK);
+    let if_range_stale_body = to_bytes(if_range_stale_res.into_body(), 2048).await.unwrap();
+    assert_eq!(if_range_stale_body.as_ref(), png_bytes.as_slice());
+}
+
+#[tokio::test]
+async fn ugc_content_grant_mints_a_signed_time_bounded_download_link() {
+    cncore::init_tracing();
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    let bytes = b"\x89PNG\r\n\x1a\nrest-of-file".to_vec();
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "private"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app.clone().oneshot(create_req).await.unwrap();
+    let create_bytes = to_bytes(create_res.into_body(), 16 * 1024).await.unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+    object_fetcher.put(session.storage_path.clone(), bytes.clone());
+
+    let complete_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "visibility": "private"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app.clone().oneshot(complete_req).await.unwrap();
+    let complete_bytes = to_bytes(complete_res.into_body(), 16 * 1024).await.unwrap();
+    let metadata: ContentMetadataResponse = serde_json::from_slice(&complete_bytes).unwrap();
+
+    // Minting without a credential is refused outright.
+    let unauthenticated_grant_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/grant",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("content-type", "application/json")
+        .body(axum::body::Body::from(
+            json!({"permissions": "r", "expiry_time": Utc::now() + chrono::Duration::minutes(5)})
+                .to_string(),
+        ))
+        .unwrap();
+    let unauthenticated_grant_res = app.clone().oneshot(unauthenticated_grant_req).await.unwrap();
+    assert_eq!(unauthenticated_grant_res.status(), StatusCode::FORBIDDEN);
+
+    let grant_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/grant",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"permissions": "r", "expiry_time": Utc::now() + chrono::Duration::minutes(5)})
+                .to_string(),
+        ))
+        .unwrap();
+    let grant_res = app.clone().oneshot(grant_req).await.unwrap();
+    assert_eq!(grant_res.status(), StatusCode::OK);
+    let grant_bytes = to_bytes(grant_res.into_body(), 16 * 1024).await.unwrap();
+    let grant: crate::http::ContentGrantResponse = serde_json::from_slice(&grant_bytes).unwrap();
+
+    // A bare GET with no credential and no grant is still refused.
+    let bare_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download",
+            tenant_id, project_id, metadata.id
+        ))
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let bare_res = app.clone().oneshot(bare_req).await.unwrap();
+    assert_eq!(bare_res.status(), StatusCode::FORBIDDEN);
+
+    // The signed grant works in place of a credential.
+    let granted_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download?grant={}",
+            tenant_id, project_id, metadata.id, grant.grant
+        ))
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let granted_res = app.clone().oneshot(granted_req).await.unwrap();
+    assert_eq!(granted_res.status(), StatusCode::OK);
+    let granted_body = to_bytes(granted_res.into_body(), 2048).await.unwrap();
+    assert_eq!(granted_body.as_ref(), bytes.as_slice());
+
+    // A grant minted for another content id is refused.
+    let other_content_req = axum::http::Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/download?grant={}",
+            tenant_id,
+            project_id,
+            uuid::Uuid::new_v4(),
+            grant.grant
+        ))
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let other_content_res = app.oneshot(other_content_req).await.unwrap();
+    assert_eq!(other_content_res.status(), StatusCode::FORBIDDEN);
+
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[tokio::test]
+async fn ugc_thumbnail_queues_a_job_and_reports_its_status() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let object_fetcher = crate::ingest::InMemoryObjectFetcher::default();
+    state.object_fetcher = Arc::new(object_fetcher.clone());
+    let app = crate::http::router().with_state(state.clone());
+
+    // Not a real decodable PNG, just magic bytes padded out; the rendition
+    // job is expected to fail at decode time, which is enough to exercise
+    // the queue/status/dedup plumbing without a real image fixture.
+    let mut png_bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_bytes.extend(std::iter::repeat(0u8).take(1024 - png_bytes.len()));
+
+    let create_req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "size_bytes": 1024, "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app.clone().oneshot(create_req).await.unwrap();
+    let create_bytes = to_bytes(create_res.into_body(), 16 * 1024).await.unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+    object_fetcher.put(session.storage_path.clone(), png_bytes.clone());
+
+    let complete_req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads/{}/complete",
+            tenant_id, project_id, session.upload_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "filename": "avatar.png",
+                "mime_type": "image/png",
+                "size_bytes": 1024,
+                "visibility": "tenant"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let complete_res = app.clone().oneshot(complete_req).await.unwrap();
+    let complete_bytes = to_bytes(complete_res.into_body(), 16 * 1024).await.unwrap();
+    let metadata: ContentMetadataResponse = serde_json::from_slice(&complete_bytes).unwrap();
+
+    let thumbnail_req = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/thumbnail?w=64",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let thumbnail_res = app.clone().oneshot(thumbnail_req).await.unwrap();
+    assert_eq!(thumbnail_res.status(), StatusCode::ACCEPTED);
+    let thumbnail_bytes = to_bytes(thumbnail_res.into_body(), 16 * 1024).await.unwrap();
+    let job: RenditionJobResponse = serde_json::from_slice(&thumbnail_bytes).unwrap();
+
+    let mut last_status = JobStatusResponse {
+        id: job.job_id,
+        status: job.status.clone(),
+        attempts: 0,
+        last_error: None,
+        result: None,
+    };
+    for _ in 0..20 {
+        let status_req = Request::builder()
+            .method("GET")
+            .uri(format!("/tenants/{}/jobs/{}", tenant_id, job.job_id))
+            .header("x-api-key", "test-key")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let status_res = app.clone().oneshot(status_req).await.unwrap();
+        assert_eq!(status_res.status(), StatusCode::OK);
+        let status_bytes = to_bytes(status_res.into_body(), 16 * 1024).await.unwrap();
+        last_status = serde_json::from_slice(&status_bytes).unwrap();
+        if last_status.status != "pending" && last_status.status != "in_progress" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(last_status.status, "failed");
+    assert!(last_status.last_error.is_some());
+
+    // The failed job is terminal, so a repeat request schedules a fresh one
+    // rather than reusing it forever.
+    let second_req = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/{}/thumbnail?w=64",
+            tenant_id, project_id, metadata.id
+        ))
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let second_res = app.oneshot(second_req).await.unwrap();
+    assert_eq!(second_res.status(), StatusCode::ACCEPTED);
+    let second_bytes = to_bytes(second_res.into_body(), 16 * 1024).await.unwrap();
+    let second_job: RenditionJobResponse = serde_json::from_slice(&second_bytes).unwrap();
+    assert_ne!(second_job.job_id, job.job_id);
+}
+
+#[test]
+fn normalize_path_reduces_ids() {
+    assert_eq!(metrics::normalize_path("/agents").as_ref(), "/agents");
+    assert_eq!(
+        metrics::normalize_path("/agents/123").as_ref(),
+        "/agents/:id"
+    );
+    assert_eq!(
+        metrics::normalize_path("/agents/550e8400-e29b-41d4-a716-446655440000").as_ref(),
+        "/agents/:id"
+    );
+}
+
+#[test]
+fn hs256_roundtrip() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let token = hs256_generate("demo").unwrap();
+    assert!(hs256_validate(&token).unwrap());
+    std::env::set_var("CASS_JWT_SECRET", "other-secret");
+    assert!(!hs256_validate(&token).unwrap());
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+fn authorized_headers() -> axum::http::HeaderMap {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert("x-api-key", axum::http::HeaderValue::from_static("test-key"));
+    headers
+}
+
+fn fixture_content_policy(permissions: &str, now: chrono::DateTime<Utc>) -> ContentAccessPolicy {
+    ContentAccessPolicy {
+        content_id: uuid::Uuid::new_v4(),
+        tenant_id: uuid::Uuid::new_v4(),
+        permissions: permissions.to_string(),
+        start_time: now - chrono::Duration::minutes(1),
+        expiry_time: now + chrono::Duration::minutes(5),
+    }
+}
+
+#[test]
+fn content_grant_round_trips_and_verifies() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let now = Utc::now();
+    let policy = fixture_content_policy("r", now);
+    let (content_id, tenant_id) = (policy.content_id, policy.tenant_id);
+    let token = issue_content_grant(&authorized_headers(), policy).unwrap();
+    verify_content_grant(&token, content_id, tenant_id, ContentPermission::Read).unwrap();
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[test]
+fn content_grant_rejects_once_expired() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let now = Utc::now();
+    let mut policy = fixture_content_policy("r", now);
+    policy.start_time = now - chrono::Duration::minutes(10);
+    policy.expiry_time = now - chrono::Duration::minutes(1);
+    let (content_id, tenant_id) = (policy.content_id, policy.tenant_id);
+    let token = issue_content_grant(&authorized_headers(), policy).unwrap();
+    let err =
+        verify_content_grant(&token, content_id, tenant_id, ContentPermission::Read).unwrap_err();
+    assert_eq!(err, GrantError::Forbidden("grant is not active"));
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[test]
+fn content_grant_rejects_permission_it_does_not_cover() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let now = Utc::now();
+    let policy = fixture_content_policy("r", now);
+    let (content_id, tenant_id) = (policy.content_id, policy.tenant_id);
+    let token = issue_content_grant(&authorized_headers(), policy).unwrap();
+    let err = verify_content_grant(&token, content_id, tenant_id, ContentPermission::Write)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        GrantError::Forbidden("grant does not cover this permission")
+    );
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[test]
+fn content_grant_rejects_tampered_signature() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let now = Utc::now();
+    let policy = fixture_content_policy("r", now);
+    let (content_id, tenant_id) = (policy.content_id, policy.tenant_id);
+    let token = issue_content_grant(&authorized_headers(), policy).unwrap();
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    let bytes = URL_SAFE_NO_PAD.decode(&token).unwrap();
+    let mut value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    value["signature"] = json!("tampered");
+    let tampered = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&value).unwrap());
+    let err = verify_content_grant(&tampered, content_id, tenant_id, ContentPermission::Read)
+        .unwrap_err();
+    assert_eq!(err, GrantError::Forbidden("invalid grant signature"));
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[test]
+fn content_grant_rejects_invalid_permission_string() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let policy = fixture_content_policy("x", Utc::now());
+    let err = issue_content_grant(&authorized_headers(), policy).unwrap_err();
+    assert_eq!(err, GrantError::InvalidPolicy("invalid permission string"));
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[test]
+fn content_grant_refuses_a_permission_beyond_the_issuers_scope() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    let policy = fixture_content_policy("r", Utc::now());
+    let err = issue_content_grant(&axum::http::HeaderMap::new(), policy).unwrap_err();
+    assert_eq!(
+        err,
+        GrantError::Forbidden("requested permission exceeds caller's scopes")
+    );
+    std::env::remove_var("CASS_JWT_SECRET");
+}
+
+#[tokio::test]
+async fn ugc_create_upload_returns_presigned_url_when_storage_credentials_set() {
+    cncore::init_tracing();
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_STORAGE_ENDPOINT", "https://s3.example.com");
+    std::env::set_var("CASS_STORAGE_REGION", "us-east-1");
+    std::env::set_var("CASS_STORAGE_ACCESS_KEY", "AKIDEXAMPLE");
+    std::env::set_var("CASS_STORAGE_SECRET_KEY", "secret");
+
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let state = AppState::with_content_store(store);
+    let app = crate::http::router().with_state(state);
+
+    let create_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/uploads",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({"filename": "avatar.png", "mime_type": "image/png", "visibility": "tenant"})
+                .to_string(),
+        ))
+        .unwrap();
+    let create_res = app.oneshot(create_req).await.expect("create upload response");
+    let create_bytes = axum::body::to_bytes(create_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let session: UploadSessionResponse = serde_json::from_slice(&create_bytes).unwrap();
+    let upload_url = session.upload_url.expect("presigned url");
+    assert!(upload_url.starts_with("https://s3.example.com/"));
+    assert!(upload_url.contains("X-Amz-Signature="));
+
+    for key in [
+        "CASS_STORAGE_ENDPOINT",
+        "CASS_STORAGE_REGION",
+        "CASS_STORAGE_ACCESS_KEY",
+        "CASS_STORAGE_SECRET_KEY",
+    ] {
+        std::env::remove_var(key);
+    }
+}
+
+fn cors_app(tenant_id: uuid::Uuid, cors_rules: Vec<cncore::platform::CorsRule>) -> Router {
+    let persistence = Arc::new(InMemoryPersistence::new());
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings {
+                cors_rules,
+                ..Default::default()
+            },
+        })
+        .unwrap();
+    let tenant_store: Arc<dyn TenantStore> = persistence;
+    let mut state = AppState::with_content_store(Arc::new(InMemoryPersistence::new()));
+    state.tenant_store = tenant_store.clone();
+    crate::http::router()
+        .with_state(state)
+        .layer(crate::cors::CorsLayer::new(tenant_store))
+}
+
+#[tokio::test]
+async fn cors_preflight_echoes_allowed_origin_and_rule_headers() {
+    cncore::init_tracing();
+    let tenant_id = uuid::Uuid::new_v4();
+    let app = cors_app(
+        tenant_id,
+        vec![cncore::platform::CorsRule {
+            allowed_origins: vec!["https://app.example.com".into()],
+            allowed_methods: vec!["GET".into(), "POST".into()],
+            allowed_headers: vec!["content-type".into()],
+            expose_headers: vec!["x-request-id".into()],
+            max_age_seconds: Some(600),
+        }],
+    );
+
+    let project_id = uuid::Uuid::new_v4();
+    let res = app
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri(format!("/tenants/{}/projects/{}/content", tenant_id, project_id))
+                .header("origin", "https://app.example.com")
+                .header("access-control-request-method", "POST")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        res.headers().get("access-control-allow-origin").unwrap(),
+        "https://app.example.com"
+    );
+    assert_eq!(res.headers().get("access-control-allow-methods").unwrap(), "GET, POST");
+    assert_eq!(res.headers().get("access-control-max-age").unwrap(), "600");
+}
+
+#[tokio::test]
+async fn cors_preflight_rejects_origin_outside_tenant_allowlist() {
+    cncore::init_tracing();
+    let tenant_id = uuid::Uuid::new_v4();
+    let app = cors_app(
+        tenant_id,
+        vec![cncore::platform::CorsRule {
+            allowed_origins: vec!["https://app.example.com".into()],
+            allowed_methods: vec!["GET".into(), "POST".into()],
+            allowed_headers: vec!["content-type".into()],
+            expose_headers: vec![],
+            max_age_seconds: None,
+        }],
+    );
+
+    let project_id = uuid::Uuid::new_v4();
+    let res = app
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri(format!("/tenants/{}/projects/{}/content", tenant_id, project_id))
+                .header("origin", "https://evil.example.com")
+                .header("access-control-request-method", "POST")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(res.headers().get("access-control-allow-origin").is_none());
+}
+
+#[tokio::test]
+async fn ugc_batch_content_operations_reports_per_item_outcomes() {
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let readable_id = uuid::Uuid::new_v4();
+    let relabel_id = uuid::Uuid::new_v4();
+    let deletable_id = uuid::Uuid::new_v4();
+    let missing_id = uuid::Uuid::new_v4();
+    for (id, filename) in [
+        (readable_id, "readable.bin"),
+        (relabel_id, "relabel.bin"),
+        (deletable_id, "deletable.bin"),
+    ] {
+        persistence
+            .record_content_metadata(ContentMetadata {
+                id,
+                tenant_id,
+                project_id,
+                filename: filename.into(),
+                mime_type: None,
+                size_bytes: Some(10),
+                checksum: None,
+                storage_path: None,
+                labels: vec!["original".into()],
+                attributes: HashMap::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                uploaded_by: None,
+                visibility: ContentVisibility::Tenant,
+                blurhash: None,
+                immutability: None,
+                legal_hold: false,
+                relevance: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let app = crate::http::router().with_state(state);
+
+    let batch_req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/batch",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .header("x-api-key", "test-key")
+        .body(axum::body::Body::from(
+            json!({
+                "operations": [
+                    {"op": "read", "id": readable_id},
+                    {"op": "set_labels", "id": relabel_id, "labels": ["relabeled"]},
+                    {"op": "delete", "id": deletable_id},
+                    {"op": "read", "id": missing_id}
+                ]
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let batch_res = app.clone().oneshot(batch_req).await.expect("batch response");
+    assert_eq!(batch_res.status(), StatusCode::OK);
+    let batch_bytes = axum::body::to_bytes(batch_res.into_body(), 16 * 1024)
+        .await
+        .unwrap();
+    let batch: crate::http::ContentBatchResponse = serde_json::from_slice(&batch_bytes).unwrap();
+    assert_eq!(batch.results.len(), 4);
+
+    assert_eq!(batch.results[0].id, readable_id);
+    assert_eq!(batch.results[0].status, StatusCode::OK);
+    assert_eq!(
+        batch.results[0].metadata.as_ref().unwrap().filename,
+        "readable.bin"
+    );
+
+    assert_eq!(batch.results[1].id, relabel_id);
+    assert_eq!(batch.results[1].status, StatusCode::OK);
+    assert_eq!(
+        batch.results[1].metadata.as_ref().unwrap().labels,
+        vec!["relabeled".to_string()]
+    );
+
+    assert_eq!(batch.results[2].id, deletable_id);
+    assert_eq!(batch.results[2].status, StatusCode::NO_CONTENT);
+
+    assert_eq!(batch.results[3].id, missing_id);
+    assert_eq!(batch.results[3].status, StatusCode::NOT_FOUND);
+
+    assert!(persistence
+        .get_content_metadata(deletable_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// Signs a bearer token carrying an explicit `scopes` claim, so tests can
+/// exercise scope checks that an `x-api-key` header (which `has_scope`
+/// allows unconditionally) can't reach.
+fn scoped_bearer_header(scopes: &[&str]) -> axum::http::HeaderMap {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    type HmacSha256 = Hmac<Sha256>;
+    let secret = std::env::var("CASS_JWT_SECRET").unwrap();
+    let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+    let exp = (Utc::now() + chrono::Duration::hours(1)).timestamp();
+    let payload = URL_SAFE_NO_PAD.encode(
+        serde_json::to_vec(&json!({"sub": "test", "exp": exp, "scopes": scopes})).unwrap(),
+    );
+    let signing_input = format!("{header}.{payload}");
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(signing_input.as_bytes());
+    let sig = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        "authorization",
+        axum::http::HeaderValue::from_str(&format!("Bearer {signing_input}.{sig}")).unwrap(),
+    );
+    headers
+}
+
+#[tokio::test]
+async fn ugc_batch_set_retention_release_requires_release_scope() {
+    let _guard = ENV_GUARD.lock().unwrap();
+    std::env::set_var("CASS_JWT_SECRET", "test-secret");
+    cncore::init_tracing();
+    let persistence = Arc::new(InMemoryPersistence::new());
+    let tenant_id = uuid::Uuid::new_v4();
+    let project_id = uuid::Uuid::new_v4();
+    persistence
+        .insert_tenant(Tenant {
+            id: tenant_id,
+            name: "tenant".into(),
+            created_at: Utc::now(),
+            settings: TenantSettings::default(),
+        })
+        .unwrap();
+    persistence
+        .insert_project(Project {
+            id: project_id,
+            tenant_id,
+            name: "project".into(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    let content_id = uuid::Uuid::new_v4();
+    persistence
+        .record_content_metadata(ContentMetadata {
+            id: content_id,
+            tenant_id,
+            project_id,
+            filename: "held.bin".into(),
+            mime_type: None,
+            size_bytes: Some(10),
+            checksum: None,
+            storage_path: None,
+            labels: vec![],
+            attributes: HashMap::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            uploaded_by: None,
+            visibility: ContentVisibility::Tenant,
+            blurhash: None,
+            immutability: None,
+            legal_hold: true,
+            relevance: None,
+        })
+        .await
+        .unwrap();
+
+    let store: Arc<dyn ContentStore> = persistence.clone();
+    let mut state = AppState::with_content_store(store);
+    state.tenant_store = persistence.clone();
+    let app = crate::http::router().with_state(state);
+
+    let batch_body = json!({
+        "operations": [
+            {"op": "set_retention", "id": content_id, "legal_hold": false}
+        ]
+    })
+    .to_string();
+
+    // A caller scoped for ordinary UGC writes, but not release, is refused.
+    let mut req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/batch",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .body(axum::body::Body::from(batch_body.clone()))
+        .unwrap();
+    req.headers_mut().extend(scoped_bearer_header(&["ugc:write"]));
+    let res = app.clone().oneshot(req).await.expect("batch response");
+    assert_eq!(res.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(res.into_body(), 16 * 1024).await.unwrap();
+    let batch: crate::http::ContentBatchResponse = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(batch.results[0].status, StatusCode::FORBIDDEN);
+    assert!(persistence
+        .get_content_metadata(content_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .legal_hold);
+
+    // A caller holding the release scope can lift the hold.
+    let mut req = axum::http::Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/tenants/{}/projects/{}/content/batch",
+            tenant_id, project_id
+        ))
+        .header("content-type", "application/json")
+        .body(axum::body::Body::from(batch_body))
+        .unwrap();
+    req.headers_mut()
+        .extend(scoped_bearer_header(&["ugc:write", "ugc:release_retention"]));
+    let res = app.clone().oneshot(req).await.expect("batch response");
+    assert_eq!(res.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(res.into_body(), 16 * 1024).await.unwrap();
+    let batch: crate::http::ContentBatchResponse = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(batch.results[0].status, StatusCode::OK);
+    assert!(!persistence
+        .get_content_metadata(content_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .legal_hold);
+
     std::env::remove_var("CASS_JWT_SECRET");
 }
@@ -0,0 +1,207 @@
+//! Optional TLS termination for both the HTTP and agent-control gRPC
+//! servers, so agent registration/heartbeat can be secured without an
+//! external proxy in front of the gateway.
+//!
+//! Set `CASS_TLS_CERT`/`CASS_TLS_KEY` to PEM-encoded cert chain/private key
+//! paths to enable TLS; leaving both unset serves plaintext exactly as
+//! before (this mirrors the `CASS_JWT_SECRET`-style flat env var config
+//! used elsewhere in the gateway rather than the layered `AppConfig`, since
+//! TLS material is file paths, not values you'd want in a config source).
+//! Setting `CASS_TLS_CLIENT_CA` (a PEM bundle of trusted client CA certs)
+//! or `CASS_TLS_REQUIRE_CLIENT_CERT=1` additionally requires and verifies a
+//! client certificate on every connection (mutual TLS); if mTLS is
+//! required but no `CASS_TLS_CLIENT_CA` is given, the trusted root set
+//! falls back to the OS trust store via `rustls-native-certs`.
+
+use anyhow::{bail, Context, Result};
+use rustls_pemfile::Item;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+/// Paths/flags read from the environment; `None` from [`TlsSettings::from_env`]
+/// means "serve plaintext".
+pub struct TlsSettings {
+    pub cert_path: String,
+    pub key_path: String,
+    pub client_ca_path: Option<String>,
+    pub require_client_cert: bool,
+}
+
+impl TlsSettings {
+    pub fn from_env() -> Result<Option<Self>> {
+        let cert_path = std::env::var("CASS_TLS_CERT").ok();
+        let key_path = std::env::var("CASS_TLS_KEY").ok();
+        let (cert_path, key_path) = match (cert_path, key_path) {
+            (None, None) => return Ok(None),
+            (Some(cert_path), Some(key_path)) => (cert_path, key_path),
+            _ => bail!("CASS_TLS_CERT and CASS_TLS_KEY must both be set to enable TLS"),
+        };
+        let client_ca_path = std::env::var("CASS_TLS_CLIENT_CA").ok();
+        let require_client_cert = client_ca_path.is_some()
+            || matches!(
+                std::env::var("CASS_TLS_REQUIRE_CLIENT_CERT").as_deref(),
+                Ok("1") | Ok("true")
+            );
+        Ok(Some(Self {
+            cert_path,
+            key_path,
+            client_ca_path,
+            require_client_cert,
+        }))
+    }
+}
+
+fn load_cert_chain(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let file = std::fs::File::open(path).with_context(|| format!("opening TLS cert {path}"))?;
+    rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("parsing TLS cert chain {path}"))
+}
+
+fn load_private_key(path: &str) -> Result<PrivateKeyDer<'static>> {
+    let file = std::fs::File::open(path).with_context(|| format!("opening TLS key {path}"))?;
+    let mut reader = BufReader::new(file);
+    loop {
+        match rustls_pemfile::read_one(&mut reader)?
+            .with_context(|| format!("no private key found in {path}"))?
+        {
+            Item::Pkcs8Key(key) => return Ok(PrivateKeyDer::Pkcs8(key)),
+            Item::Pkcs1Key(key) => return Ok(PrivateKeyDer::Pkcs1(key)),
+            Item::Sec1Key(key) => return Ok(PrivateKeyDer::Sec1(key)),
+            _ => continue,
+        }
+    }
+}
+
+fn client_cert_root_store(settings: &TlsSettings) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    match &settings.client_ca_path {
+        Some(path) => {
+            for cert in load_cert_chain(path)? {
+                roots.add(cert)?;
+            }
+        }
+        None => {
+            for cert in rustls_native_certs::load_native_certs()
+                .context("loading OS trust store for client cert verification")?
+            {
+                roots.add(cert)?;
+            }
+        }
+    }
+    Ok(roots)
+}
+
+/// Builds the `rustls::ServerConfig` shared by both the HTTP acceptor and
+/// (re-derived from the same PEM inputs) the gRPC server's TLS config.
+pub fn server_config(settings: &TlsSettings) -> Result<ServerConfig> {
+    let certs = load_cert_chain(&settings.cert_path)?;
+    let key = load_private_key(&settings.key_path)?;
+    let builder = ServerConfig::builder();
+    let config = if settings.require_client_cert {
+        let roots = Arc::new(client_cert_root_store(settings)?);
+        let verifier = WebPkiClientVerifier::builder(roots)
+            .build()
+            .context("building client cert verifier")?;
+        builder
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(certs, key)?
+    } else {
+        builder
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?
+    };
+    Ok(config)
+}
+
+/// Builds the gRPC server's TLS config from the same PEM inputs used for
+/// the HTTP acceptor, so both servers present/verify the same identity.
+pub fn tonic_tls_config(settings: &TlsSettings) -> Result<tonic::transport::ServerTlsConfig> {
+    let cert_pem = std::fs::read(&settings.cert_path)
+        .with_context(|| format!("reading TLS cert {}", settings.cert_path))?;
+    let key_pem = std::fs::read(&settings.key_path)
+        .with_context(|| format!("reading TLS key {}", settings.key_path))?;
+    let mut tls = tonic::transport::ServerTlsConfig::new()
+        .identity(tonic::transport::Identity::from_pem(cert_pem, key_pem));
+    if settings.require_client_cert {
+        let ca_pem = match &settings.client_ca_path {
+            Some(path) => {
+                std::fs::read(path).with_context(|| format!("reading client CA {path}"))?
+            }
+            None => native_roots_as_pem()?,
+        };
+        tls = tls.client_ca_root(tonic::transport::Certificate::from_pem(ca_pem));
+    }
+    Ok(tls)
+}
+
+/// Re-encodes the OS trust store as concatenated PEM, since tonic's
+/// `Certificate::from_pem` (unlike the raw `rustls::RootCertStore` path
+/// used for the HTTP acceptor) only accepts PEM, not DER.
+fn native_roots_as_pem() -> Result<Vec<u8>> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    let mut pem = Vec::new();
+    for cert in rustls_native_certs::load_native_certs()
+        .context("loading OS trust store for gRPC client cert verification")?
+    {
+        let encoded = STANDARD.encode(cert.as_ref());
+        pem.extend_from_slice(b"-----BEGIN CERTIFICATE-----\n");
+        for line in encoded.as_bytes().chunks(64) {
+            pem.extend_from_slice(line);
+            pem.push(b'\n');
+        }
+        pem.extend_from_slice(b"-----END CERTIFICATE-----\n");
+    }
+    Ok(pem)
+}
+
+/// A `TcpListener` that completes the TLS handshake on `accept`, so it can
+/// be handed to `axum::serve` exactly like the plaintext listener is
+/// elsewhere in `main.rs`.
+pub struct TlsListener {
+    tcp: TcpListener,
+    acceptor: TlsAcceptor,
+}
+
+impl TlsListener {
+    pub fn new(tcp: TcpListener, config: ServerConfig) -> Self {
+        Self {
+            tcp,
+            acceptor: TlsAcceptor::from(Arc::new(config)),
+        }
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = tokio_rustls::server::TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            let (stream, addr) = match self.tcp.accept().await {
+                Ok(pair) => pair,
+                Err(err) => {
+                    tracing::warn!(error = %err, "tls.tcp_accept_failed");
+                    continue;
+                }
+            };
+            match self.acceptor.accept(stream).await {
+                Ok(tls_stream) => return (tls_stream, addr),
+                Err(err) => {
+                    tracing::warn!(error = %err, %addr, "tls.handshake_failed");
+                    continue;
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        self.tcp.local_addr()
+    }
+}
@@ -0,0 +1,148 @@
+//! Minimal W3C Trace Context (`traceparent` header) support.
+//!
+//! We don't pull in the full `opentelemetry` SDK just to parse/mint this
+//! header; `TraceContext` only knows enough to propagate a trace-id and mint
+//! new span-ids as requests cross the gateway.
+
+use rand::RngCore;
+
+tokio::task_local! {
+    /// Request-id/trace-id for the request currently being handled, set by
+    /// `MetricsService` around the inner call so error bodies built deep in
+    /// handler code (far from the headers) can still quote them.
+    pub static REQUEST_CONTEXT: RequestContext;
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub trace_id: String,
+}
+
+/// Read the ambient request/trace id, if `MetricsService` set one for this
+/// call. `None` outside of a request (e.g. unit tests calling handlers
+/// directly).
+pub fn current_request_context() -> Option<RequestContext> {
+    REQUEST_CONTEXT.try_with(|ctx| ctx.clone()).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub parent_id: [u8; 8],
+    pub flags: u8,
+}
+
+impl TraceContext {
+    /// Mint a fresh root context (no incoming `traceparent`).
+    pub fn new_root() -> Self {
+        let mut rng = rand::thread_rng();
+        let mut trace_id = [0u8; 16];
+        let mut parent_id = [0u8; 8];
+        rng.fill_bytes(&mut trace_id);
+        rng.fill_bytes(&mut parent_id);
+        Self {
+            trace_id,
+            parent_id,
+            flags: 0x01, // sampled by default
+        }
+    }
+
+    /// Parse `00-<32 hex trace-id>-<16 hex parent-id>-<2 hex flags>`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next()?;
+        let trace_id_hex = parts.next()?;
+        let parent_id_hex = parts.next()?;
+        let flags_hex = parts.next()?;
+        if parts.next().is_some() || version != "00" {
+            return None;
+        }
+        if trace_id_hex.len() != 32 || parent_id_hex.len() != 16 || flags_hex.len() != 2 {
+            return None;
+        }
+        let trace_id = decode_hex::<16>(trace_id_hex)?;
+        let parent_id = decode_hex::<8>(parent_id_hex)?;
+        let flags = u8::from_str_radix(flags_hex, 16).ok()?;
+        if trace_id == [0u8; 16] || parent_id == [0u8; 8] {
+            return None;
+        }
+        Some(Self {
+            trace_id,
+            parent_id,
+            flags,
+        })
+    }
+
+    /// Parse an incoming header, falling back to a fresh root context.
+    pub fn from_header_or_new(value: Option<&str>) -> Self {
+        value.and_then(Self::parse).unwrap_or_else(Self::new_root)
+    }
+
+    pub fn trace_id_hex(&self) -> String {
+        encode_hex(&self.trace_id)
+    }
+
+    /// Build the outbound `traceparent`, carrying the same trace-id forward
+    /// with a freshly minted span-id acting as the new parent-id.
+    pub fn child_header(&self, span_id: [u8; 8]) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            encode_hex(&self.trace_id),
+            encode_hex(&span_id),
+            self.flags
+        )
+    }
+
+    pub fn new_span_id() -> [u8; 8] {
+        let mut span_id = [0u8; 8];
+        rand::thread_rng().fill_bytes(&mut span_id);
+        span_id
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, chunk) in s.as_bytes().chunks(2).enumerate() {
+        let byte_str = std::str::from_utf8(chunk).ok()?;
+        out[i] = u8::from_str_radix(byte_str, 16).ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_valid_traceparent() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let ctx = TraceContext::parse(header).expect("should parse");
+        assert_eq!(ctx.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(ctx.flags, 0x01);
+    }
+
+    #[test]
+    fn rejects_malformed_header() {
+        assert!(TraceContext::parse("not-a-traceparent").is_none());
+        assert!(TraceContext::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+            .is_none());
+    }
+
+    #[test]
+    fn child_header_preserves_trace_id() {
+        let ctx = TraceContext::new_root();
+        let span_id = TraceContext::new_span_id();
+        let header = ctx.child_header(span_id);
+        let reparsed = TraceContext::parse(&header).unwrap();
+        assert_eq!(reparsed.trace_id, ctx.trace_id);
+        assert_eq!(reparsed.parent_id, span_id);
+    }
+}